@@ -0,0 +1,126 @@
+use std::error::Error;
+
+use clap::Args;
+use magic_curves::{compare_curves, AnyBondingCurve, CurveComparison};
+
+/// Arguments for the `compare` command.
+#[derive(Args)]
+pub struct CompareArgs {
+    /// The first curve, as a spec such as `linear:10,1000`.
+    a: AnyBondingCurve,
+    /// The second curve, as a spec such as `quadratic:1,0,500`.
+    b: AnyBondingCurve,
+    /// The supplies to compare, as `start..end` with both ends included.
+    #[arg(long, default_value = "0..1000000")]
+    range: String,
+    /// The number of evenly spaced supplies to sample, including both ends.
+    #[arg(long, default_value_t = 11)]
+    samples: usize,
+}
+
+/// Compares two curves over a supply range and prints where they cross, how far apart they
+/// are, and a side-by-side table of prices.
+pub fn run(args: CompareArgs) -> Result<(), Box<dyn Error>> {
+    let (start, end) = parse_range(&args.range)?;
+    let comparison = compare_curves(&args.a, &args.b, start, end, args.samples)?;
+    println!("a: {}", args.a);
+    println!("b: {}", args.b);
+    let intersections: Vec<String> = comparison
+        .intersections
+        .iter()
+        .map(u64::to_string)
+        .collect();
+    println!("intersections: {}", intersections.join(", "));
+    println!("max_divergence: {}", comparison.max_divergence);
+    println!(
+        "max_divergence_supply: {}",
+        comparison.max_divergence_supply
+    );
+    println!("mean_divergence: {}", comparison.mean_divergence);
+    println!(
+        "max_relative_divergence: {}",
+        comparison.max_relative_divergence
+    );
+    println!();
+    print!("{}", format_table(&comparison));
+    Ok(())
+}
+
+/// Parses a `start..end` supply range.
+fn parse_range(range: &str) -> Result<(u64, u64), String> {
+    let invalid = || format!("--range must be `start..end`, found `{}`", range);
+    let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+    match (start.trim().parse(), end.trim().parse()) {
+        (Ok(start), Ok(end)) => Ok((start, end)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Formats the sampled prices as right-aligned `supply`, `a`, `b`, and `a - b` columns.
+fn format_table(comparison: &CurveComparison) -> String {
+    let mut cells = vec![[
+        "supply".to_string(),
+        "a".to_string(),
+        "b".to_string(),
+        "a - b".to_string(),
+    ]];
+    for row in &comparison.rows {
+        cells.push([
+            row.supply.to_string(),
+            row.a.to_string(),
+            row.b.to_string(),
+            (row.a - row.b).to_string(),
+        ]);
+    }
+    let mut widths = [0; 4];
+    for line in &cells {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut table = String::new();
+    for line in &cells {
+        let line: Vec<String> = line
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect();
+        table.push_str(&line.join("  "));
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use magic_curves::compare_curves;
+
+    use super::{format_table, parse_range};
+
+    #[test]
+    pub fn test_parse_range() {
+        assert_eq!(parse_range("0..1000000"), Ok((0, 1_000_000)));
+        assert_eq!(parse_range(" 5 .. 10 "), Ok((5, 10)));
+        assert_eq!(
+            parse_range("0-10"),
+            Err("--range must be `start..end`, found `0-10`".to_string())
+        );
+        assert!(parse_range("0..x").is_err());
+    }
+
+    #[test]
+    pub fn test_format_table() {
+        let a = "linear:10,1000".parse().unwrap();
+        let b = "linear:20,500".parse().unwrap();
+        let comparison = compare_curves(&a, &b, 0, 100, 3).unwrap();
+        assert_eq!(
+            format_table(&comparison),
+            concat!(
+                "supply     a     b  a - b\n",
+                "     0  1000   500    500\n",
+                "    50  1500  1500      0\n",
+                "   100  2000  2500   -500\n",
+            )
+        );
+    }
+}
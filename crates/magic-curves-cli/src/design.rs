@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use clap::Args;
+use magic_curves::{design_exponential, design_linear, design_quadratic, CurveFamily, DesignError};
+
+/// Arguments for the `design` command.
+#[derive(Args)]
+pub struct DesignArgs {
+    /// The price of the first token.
+    #[arg(long)]
+    start_price: String,
+    /// The amount to raise by selling the whole supply.
+    #[arg(long)]
+    target_raise: String,
+    /// The number of tokens that will be sold.
+    #[arg(long)]
+    max_supply: u64,
+    /// The curve family to design.
+    #[arg(long)]
+    family: CurveFamily,
+}
+
+/// Solves the curve parameters that satisfy the constraints and prints them.
+pub fn run(args: DesignArgs) -> Result<(), Box<dyn Error>> {
+    match args.family {
+        CurveFamily::Linear => {
+            let design = design_linear(
+                parse_integer(&args.start_price, "start-price")?,
+                parse_integer(&args.target_raise, "target-raise")?,
+                args.max_supply,
+            )?;
+            println!("family: linear");
+            println!("linear: {}", design.curve.linear);
+            println!("base: {}", design.curve.base);
+            println!("raise: {}", design.raise);
+        }
+        CurveFamily::Quadratic => {
+            let design = design_quadratic(
+                parse_integer(&args.start_price, "start-price")?,
+                parse_integer(&args.target_raise, "target-raise")?,
+                args.max_supply,
+            )?;
+            println!("family: quadratic");
+            println!("quadratic: {}", design.curve.quadratic);
+            println!("linear: {}", design.curve.linear);
+            println!("base: {}", design.curve.base);
+            println!("raise: {}", design.raise);
+        }
+        CurveFamily::Exponential => {
+            let design = design_exponential(
+                parse_float(&args.start_price, "start-price")?,
+                parse_float(&args.target_raise, "target-raise")?,
+                args.max_supply,
+            )?;
+            println!("family: exponential");
+            println!("base: {}", design.curve.base);
+            println!("growth: {}", design.curve.growth);
+            println!("raise: {}", design.raise);
+        }
+        CurveFamily::Logarithmic | CurveFamily::Sigmoid => {
+            return Err(DesignError::UnsupportedFamily(args.family).into())
+        }
+    }
+    Ok(())
+}
+
+fn parse_integer(value: &str, name: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("--{} must be an integer for integer curves", name))
+}
+
+fn parse_float(value: &str, name: &str) -> Result<f64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("--{} must be a number", name))
+}
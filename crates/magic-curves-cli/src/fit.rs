@@ -0,0 +1,111 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use clap::Args;
+use magic_curves::{
+    fit_exponential, fit_linear, fit_logarithmic, fit_quadratic, CurveFamily, FitError, FitMetrics,
+};
+
+/// Arguments for the `fit` command.
+#[derive(Args)]
+pub struct FitArgs {
+    /// CSV file with `supply,price` rows. A header row is allowed.
+    #[arg(long)]
+    data: PathBuf,
+    /// The curve family to fit.
+    #[arg(long)]
+    family: CurveFamily,
+}
+
+/// Fits the requested curve family and prints its parameters and metrics.
+pub fn run(args: FitArgs) -> Result<(), Box<dyn Error>> {
+    let contents = fs::read_to_string(&args.data)
+        .map_err(|e| format!("could not read {}: {}", args.data.display(), e))?;
+    let points = parse_points(&contents)?;
+
+    let metrics = match args.family {
+        CurveFamily::Linear => {
+            let fit = fit_linear(&points)?;
+            println!("family: linear");
+            println!("linear: {}", fit.curve.linear);
+            println!("base: {}", fit.curve.base);
+            fit.metrics
+        }
+        CurveFamily::Quadratic => {
+            let fit = fit_quadratic(&points)?;
+            println!("family: quadratic");
+            println!("quadratic: {}", fit.curve.quadratic);
+            println!("linear: {}", fit.curve.linear);
+            println!("base: {}", fit.curve.base);
+            fit.metrics
+        }
+        CurveFamily::Exponential => {
+            let fit = fit_exponential(&points)?;
+            println!("family: exponential");
+            println!("base: {}", fit.curve.base);
+            println!("growth: {}", fit.curve.growth);
+            fit.metrics
+        }
+        CurveFamily::Logarithmic => {
+            let fit = fit_logarithmic(&points)?;
+            println!("family: logarithmic");
+            println!("base: {}", fit.curve.base);
+            println!("growth: {}", fit.curve.growth);
+            fit.metrics
+        }
+        CurveFamily::Sigmoid => return Err(FitError::UnsupportedFamily(args.family).into()),
+    };
+    print_metrics(&metrics, points.len());
+    Ok(())
+}
+
+fn print_metrics(metrics: &FitMetrics, points: usize) {
+    println!("points: {}", points);
+    println!("r_squared: {}", metrics.r_squared);
+    println!("rmse: {}", metrics.rmse);
+    println!("max_abs_error: {}", metrics.max_abs_error);
+}
+
+/// Parses `supply,price` rows, skipping blank lines and a leading header row.
+fn parse_points(contents: &str) -> Result<Vec<(u64, f64)>, String> {
+    let mut points = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split(',').map(str::trim);
+        let (supply, price) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(supply), Some(price), None) => (supply, price),
+            _ => return Err(format!("line {}: expected `supply,price`", index + 1)),
+        };
+        match (supply.parse::<u64>(), price.parse::<f64>()) {
+            (Ok(supply), Ok(price)) => points.push((supply, price)),
+            _ if points.is_empty() && index == 0 => continue,
+            _ => return Err(format!("line {}: invalid number in `{}`", index + 1, line)),
+        }
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_points;
+
+    #[test]
+    pub fn test_parse_points() {
+        let points = parse_points("supply,price\n0,1.5\n\n 10 , 2.5 \n").unwrap();
+        assert_eq!(points, vec![(0, 1.5), (10, 2.5)]);
+    }
+
+    #[test]
+    pub fn test_parse_points_errors() {
+        assert_eq!(
+            parse_points("0,1.5\n1;2.5\n"),
+            Err("line 2: expected `supply,price`".to_string())
+        );
+        assert_eq!(
+            parse_points("0,1.5\nx,2.5\n"),
+            Err("line 2: invalid number in `x,2.5`".to_string())
+        );
+    }
+}
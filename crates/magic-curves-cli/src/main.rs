@@ -0,0 +1,49 @@
+mod compare;
+mod design;
+mod fit;
+mod repl;
+mod vectors;
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+/// Command line tools for working with bonding curves.
+#[derive(Parser)]
+#[command(name = "magic-curves", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compares two curves: where they cross, how far apart they are, and their prices.
+    Compare(compare::CompareArgs),
+    /// Fits a curve to observed (supply, price) data.
+    Fit(fit::FitArgs),
+    /// Solves curve parameters from a start price, target raise, and max supply.
+    Design(design::DesignArgs),
+    /// Loads a curve and answers price, quote, invert, and sample commands interactively.
+    Repl(repl::ReplArgs),
+    /// Emits canonical JSON test vectors for checking ports of this library.
+    Vectors(vectors::VectorsArgs),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Compare(args) => compare::run(args),
+        Command::Fit(args) => fit::run(args),
+        Command::Design(args) => design::run(args),
+        Command::Repl(args) => repl::run(args),
+        Command::Vectors(args) => vectors::run(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use clap::Args;
+use magic_curves::{AnyBondingCurve, OperationSide};
+
+const HELP: &str = "\
+commands:
+  price <supply>                    the price of the next token at <supply>
+  quote <supply> <amount> [side]    the total price of <amount> tokens; side is buy or sell
+  invert <supply> <budget>          the most tokens <budget> buys at <supply>
+  sample <start> <end> [count]      prices at <count> evenly spaced supplies (default 11)
+  curve [spec]                      shows the loaded curve, or loads a new one
+  help                              shows this message
+  quit                              exits";
+
+/// Arguments for the `repl` command.
+#[derive(Args)]
+pub struct ReplArgs {
+    /// The curve to load, as a spec such as `linear:10,1000`.
+    curve: AnyBondingCurve,
+}
+
+/// Loads a curve and answers `price`, `quote`, `invert`, and `sample` commands read from
+/// standard input until `quit` or the end of input.
+pub fn run(args: ReplArgs) -> Result<(), Box<dyn Error>> {
+    let mut session = Session { curve: args.curve };
+    println!("loaded {}. Type `help` for commands.", session.curve);
+    let mut stdout = io::stdout();
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("> ");
+        stdout.flush()?;
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        match session.execute(&line?) {
+            Ok(Some(output)) if output.is_empty() => {}
+            Ok(Some(output)) => println!("{}", output),
+            Ok(None) => return Ok(()),
+            Err(error) => println!("error: {}", error),
+        }
+    }
+}
+
+/// The state of an interactive session: the curve commands are run against.
+struct Session {
+    curve: AnyBondingCurve,
+}
+
+impl Session {
+    /// Runs one command line and returns its output, or `None` if the session should end.
+    fn execute(&mut self, line: &str) -> Result<Option<String>, String> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Ok(Some(String::new()));
+        };
+        let args: Vec<&str> = words.collect();
+        let output = match (command, args.as_slice()) {
+            ("price", [supply]) => {
+                let price = self
+                    .curve
+                    .calculate_price_as_f64(parse(supply, "supply")?)
+                    .map_err(|e| e.to_string())?;
+                price.to_string()
+            }
+            ("quote", [supply, amount, side @ ..]) if side.len() <= 1 => {
+                let side = match side.first() {
+                    None => OperationSide::Add,
+                    Some(side) => parse_side(side)?,
+                };
+                let price = self
+                    .curve
+                    .calculate_price_many_as_f64(
+                        parse(supply, "supply")?,
+                        parse(amount, "amount")?,
+                        side,
+                    )
+                    .map_err(|e| e.to_string())?;
+                price.to_string()
+            }
+            ("invert", [supply, budget]) => {
+                let budget = budget
+                    .parse::<f64>()
+                    .map_err(|_| format!("budget must be a number, found `{}`", budget))?;
+                let (amount, price) = self.invert(parse(supply, "supply")?, budget);
+                format!("tokens: {}\nprice: {}", amount, price)
+            }
+            ("sample", [start, end, count @ ..]) if count.len() <= 1 => {
+                let count = match count.first() {
+                    None => 11,
+                    Some(count) => parse(count, "count")?,
+                };
+                self.sample(parse(start, "start")?, parse(end, "end")?, count)?
+            }
+            ("curve", []) => self.curve.to_string(),
+            ("curve", [spec]) => {
+                self.curve = spec.parse().map_err(|e| format!("{}", e))?;
+                format!("loaded {}", self.curve)
+            }
+            ("help", []) => HELP.to_string(),
+            ("quit" | "exit", []) => return Ok(None),
+            ("price" | "quote" | "invert" | "sample" | "curve" | "help" | "quit" | "exit", _) => {
+                return Err(format!("wrong arguments for `{}`; type `help`", command))
+            }
+            _ => return Err(format!("unknown command `{}`; type `help`", command)),
+        };
+        Ok(Some(output))
+    }
+
+    /// Finds the most tokens that can be bought at `supply` for at most `budget`, and their
+    /// price.
+    fn invert(&self, supply: u64, budget: f64) -> (u64, f64) {
+        let price = |amount| {
+            self.curve
+                .calculate_price_many_as_f64(supply, amount, OperationSide::Add)
+                .ok()
+                .filter(|price| *price <= budget)
+        };
+        let limit = u64::MAX - supply;
+        // Double until the price exceeds the budget, then bisect between the last two amounts.
+        let (mut low, mut high) = (0, 1);
+        while high <= limit && price(high).is_some() {
+            low = high;
+            if high == limit {
+                break;
+            }
+            high = high.saturating_mul(2).min(limit);
+        }
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if price(mid).is_some() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low, price(low).unwrap_or(0.0))
+    }
+
+    /// Formats the prices at `count` evenly spaced supplies of `start..=end`, one per line.
+    fn sample(&self, start: u64, end: u64, count: u64) -> Result<String, String> {
+        if start > end || count < 2 {
+            return Err("sample needs start <= end and a count of at least 2".to_string());
+        }
+        let span = (end - start) as u128;
+        let mut lines = Vec::new();
+        let mut last = None;
+        for i in 0..count {
+            let supply = start + (span * i as u128 / (count - 1) as u128) as u64;
+            if last == Some(supply) {
+                continue;
+            }
+            last = Some(supply);
+            let price = self
+                .curve
+                .calculate_price_as_f64(supply)
+                .map_err(|e| e.to_string())?;
+            lines.push(format!("{} {}", supply, price));
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+fn parse(value: &str, name: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} must be an integer, found `{}`", name, value))
+}
+
+fn parse_side(value: &str) -> Result<OperationSide, String> {
+    match value {
+        "buy" | "add" => Ok(OperationSide::Add),
+        "sell" | "remove" => Ok(OperationSide::Remove),
+        _ => Err(format!("side must be buy or sell, found `{}`", value)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Session;
+
+    fn run(session: &mut Session, line: &str) -> String {
+        match session.execute(line) {
+            Ok(Some(output)) => output,
+            Ok(None) => "<quit>".to_string(),
+            Err(error) => format!("error: {}", error),
+        }
+    }
+
+    #[test]
+    pub fn test_session_commands() {
+        let mut session = Session {
+            curve: "linear:10,1000".parse().unwrap(),
+        };
+        assert_eq!(run(&mut session, "price 5"), "1050");
+        assert_eq!(run(&mut session, "quote 0 2"), "2010");
+        assert_eq!(run(&mut session, "  quote 2 2 sell "), "2030");
+        // 1000 + 1010 + 1020 fits in 3100, a fourth token does not.
+        assert_eq!(run(&mut session, "invert 0 3100"), "tokens: 3\nprice: 3030");
+        assert_eq!(run(&mut session, "invert 0 999"), "tokens: 0\nprice: 0");
+        assert_eq!(
+            run(&mut session, "sample 0 10 3"),
+            "0 1000\n5 1050\n10 1100"
+        );
+        assert_eq!(run(&mut session, "sample 0 1 5"), "0 1000\n1 1010");
+        assert_eq!(
+            run(&mut session, "curve quadratic:1,0,500"),
+            "loaded quadratic:1,0,500"
+        );
+        assert_eq!(run(&mut session, "price 10"), "600");
+        assert_eq!(run(&mut session, ""), "");
+        assert_eq!(run(&mut session, "quit"), "<quit>");
+    }
+
+    #[test]
+    pub fn test_session_invert_free_curve() {
+        let mut session = Session {
+            curve: "linear:0,0".parse().unwrap(),
+        };
+        assert_eq!(
+            run(&mut session, "invert 10 0"),
+            format!("tokens: {}\nprice: 0", u64::MAX - 10)
+        );
+        assert_eq!(
+            run(&mut session, "invert 0 0"),
+            format!("tokens: {}\nprice: 0", u64::MAX)
+        );
+    }
+
+    #[test]
+    pub fn test_session_errors() {
+        let mut session = Session {
+            curve: "linear:10,1000".parse().unwrap(),
+        };
+        assert_eq!(
+            run(&mut session, "buy 5"),
+            "error: unknown command `buy`; type `help`"
+        );
+        assert_eq!(
+            run(&mut session, "price"),
+            "error: wrong arguments for `price`; type `help`"
+        );
+        assert_eq!(
+            run(&mut session, "price x"),
+            "error: supply must be an integer, found `x`"
+        );
+        assert_eq!(
+            run(&mut session, "quote 0 1 hold"),
+            "error: side must be buy or sell, found `hold`"
+        );
+        assert!(run(&mut session, "quote 1 2 sell").starts_with("error: "));
+        assert!(run(&mut session, "sample 5 1").starts_with("error: "));
+        assert!(run(&mut session, "curve cubic:1").starts_with("error: "));
+        assert_eq!(
+            run(&mut session, "curve"),
+            "linear:10,1000",
+            "a failed load keeps the curve"
+        );
+    }
+}
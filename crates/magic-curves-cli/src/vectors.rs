@@ -0,0 +1,30 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use clap::Args;
+use magic_curves::{float_vectors_digest, test_vectors_json};
+
+/// Arguments for the `vectors` command.
+#[derive(Args)]
+pub struct VectorsArgs {
+    /// File to write the vectors to. Defaults to standard output.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Prints only the digest of the float vector outputs, for comparing targets.
+    #[arg(long)]
+    digest: bool,
+}
+
+/// Prints or writes the canonical conformance test vectors as JSON.
+pub fn run(args: VectorsArgs) -> Result<(), Box<dyn Error>> {
+    if args.digest {
+        println!("{:016x}", float_vectors_digest());
+        return Ok(());
+    }
+    let json = test_vectors_json();
+    match args.output {
+        Some(path) => fs::write(&path, json)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?,
+        None => print!("{}", json),
+    }
+    Ok(())
+}
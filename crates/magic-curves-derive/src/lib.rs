@@ -0,0 +1,277 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    fold::{self, Fold},
+    parse_macro_input,
+    spanned::Spanned,
+    BinOp, Data, DeriveInput, Expr, Fields, Ident, LitStr, Type, UnOp,
+};
+
+/// Derives `BondingCurve` from a price formula written in terms of the struct's fields.
+///
+/// The formula is given in a `#[bonding_curve(...)]` attribute, together with the price type:
+///
+/// * `price` - The formula as a string. `x` is the supply; any other bare identifier that
+///   names a field refers to that field.
+/// * `output` - The price type, such as `u64` or `f64`. Fields used in the formula must
+///   have this type.
+///
+/// `calculate_price_many` sums `calculate_price` over the supply range. For integer price
+/// types, `BondingCurveWithCheckedOperations` is derived too, with every `+`, `-`, `*`, `/`,
+/// `%`, unary `-`, and `.pow(n)` in the formula replaced by its checked counterpart. When
+/// `magic-curves` is built with its `panic-free` feature, only that checked impl is derived.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::core::{BondingCurve, OperationSide};
+/// use magic_curves_derive::BondingCurve;
+///
+/// #[derive(BondingCurve)]
+/// #[bonding_curve(price = "slope * x + floor", output = u64)]
+/// struct Ramp {
+///     slope: u64,
+///     floor: u64,
+/// }
+///
+/// let ramp = Ramp { slope: 2, floor: 10 };
+/// assert_eq!(ramp.calculate_price(5), 20);
+/// assert_eq!(ramp.calculate_price_many(0, 3, OperationSide::Add), 36);
+/// ```
+#[proc_macro_derive(BondingCurve, attributes(bonding_curve))]
+pub fn derive_bonding_curve(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct CurveAttribute {
+    price: Expr,
+    output: Type,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attribute = parse_attribute(&input)?;
+    let fields = field_names(&input)?;
+    let output = &attribute.output;
+    let price = Substitute {
+        fields: &fields,
+        output,
+    }
+    .fold_expr(attribute.price.clone());
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let mut tokens = quote! {
+        ::magic_curves::__unchecked! {
+            #[automatically_derived]
+            impl #impl_generics ::magic_curves::BondingCurve<#output> for #name #type_generics #where_clause {
+                fn calculate_price(&self, supply: u64) -> #output {
+                    #price
+                }
+
+                fn calculate_price_many(
+                    &self,
+                    starting_supply: u64,
+                    amount: u64,
+                    side: ::magic_curves::OperationSide,
+                ) -> #output {
+                    if amount == 0 {
+                        return 0 as #output;
+                    }
+                    let first = match side {
+                        ::magic_curves::OperationSide::Add => starting_supply,
+                        ::magic_curves::OperationSide::Remove => starting_supply + 1 - amount,
+                    };
+                    let mut total = 0 as #output;
+                    for supply in first..first + amount {
+                        total += ::magic_curves::BondingCurve::<#output>::calculate_price(self, supply);
+                    }
+                    total
+                }
+            }
+        }
+    };
+
+    if is_integer(output) {
+        let checked_price = checked(&price)?;
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::magic_curves::BondingCurveWithCheckedOperations<#output> for #name #type_generics #where_clause {
+                fn calculate_price_checked(
+                    &self,
+                    supply: u64,
+                ) -> Result<#output, ::magic_curves::BondingCurveError> {
+                    let price = #checked_price;
+                    Ok(price)
+                }
+
+                fn calculate_price_many_checked(
+                    &self,
+                    starting_supply: u64,
+                    amount: u64,
+                    side: ::magic_curves::OperationSide,
+                ) -> Result<#output, ::magic_curves::BondingCurveError> {
+                    if amount == 0 {
+                        return Ok(0);
+                    }
+                    let first = match side {
+                        ::magic_curves::OperationSide::Add => starting_supply,
+                        ::magic_curves::OperationSide::Remove if amount > starting_supply => {
+                            return Err(::magic_curves::BondingCurveError::SupplyUnderflow);
+                        }
+                        ::magic_curves::OperationSide::Remove => starting_supply - amount + 1,
+                    };
+                    let end = first
+                        .checked_add(amount)
+                        .ok_or(::magic_curves::BondingCurveError::Overflow)?;
+                    let mut total = 0 as #output;
+                    for supply in first..end {
+                        let price = ::magic_curves::BondingCurveWithCheckedOperations::<#output>::calculate_price_checked(self, supply)?;
+                        total = total
+                            .checked_add(price)
+                            .ok_or(::magic_curves::BondingCurveError::Overflow)?;
+                    }
+                    Ok(total)
+                }
+            }
+        });
+    }
+    Ok(tokens)
+}
+
+fn parse_attribute(input: &DeriveInput) -> syn::Result<CurveAttribute> {
+    let mut price = None;
+    let mut output = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("bonding_curve"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("price") {
+                let formula: LitStr = meta.value()?.parse()?;
+                price = Some(formula.parse::<Expr>()?);
+                Ok(())
+            } else if meta.path.is_ident("output") {
+                output = Some(meta.value()?.parse::<Type>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `price` or `output`"))
+            }
+        })?;
+    }
+    match (price, output) {
+        (Some(price), Some(output)) => Ok(CurveAttribute { price, output }),
+        _ => Err(syn::Error::new(
+            input.ident.span(),
+            "expected #[bonding_curve(price = \"...\", output = T)]",
+        )),
+    }
+}
+
+fn field_names(input: &DeriveInput) -> syn::Result<Vec<Ident>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect()),
+            Fields::Unit => Ok(Vec::new()),
+            Fields::Unnamed(_) => Err(syn::Error::new(
+                input.ident.span(),
+                "BondingCurve can only be derived for structs with named fields",
+            )),
+        },
+        _ => Err(syn::Error::new(
+            input.ident.span(),
+            "BondingCurve can only be derived for structs",
+        )),
+    }
+}
+
+fn is_integer(ty: &Type) -> bool {
+    const INTEGERS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    match ty {
+        Type::Path(path) => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| INTEGERS.iter().any(|name| ident == name)),
+        _ => false,
+    }
+}
+
+/// Replaces `x` with the supply and field names with `self.field`.
+struct Substitute<'a> {
+    fields: &'a [Ident],
+    output: &'a Type,
+}
+
+impl Fold for Substitute<'_> {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        if let Expr::Path(path) = &expr {
+            if let Some(ident) = path.path.get_ident() {
+                let output = self.output;
+                if ident == "x" {
+                    return syn::parse_quote_spanned!(ident.span()=> (supply as #output));
+                }
+                if self.fields.iter().any(|field| field == ident) {
+                    return syn::parse_quote_spanned!(ident.span()=> self.#ident);
+                }
+            }
+        }
+        fold::fold_expr(self, expr)
+    }
+}
+
+/// Rewrites the arithmetic of a substituted formula into checked operations.
+fn checked(expr: &Expr) -> syn::Result<TokenStream2> {
+    let overflow = quote!(::magic_curves::BondingCurveError::Overflow);
+    match expr {
+        Expr::Binary(binary) => {
+            let left = checked(&binary.left)?;
+            let right = checked(&binary.right)?;
+            let span = binary.op.span();
+            let method = match binary.op {
+                BinOp::Add(_) => quote_spanned!(span=> checked_add),
+                BinOp::Sub(_) => quote_spanned!(span=> checked_sub),
+                BinOp::Mul(_) => quote_spanned!(span=> checked_mul),
+                BinOp::Div(_) => quote_spanned!(span=> checked_div),
+                BinOp::Rem(_) => quote_spanned!(span=> checked_rem),
+                _ => {
+                    return Err(syn::Error::new(
+                        span,
+                        "unsupported operator in an integer formula",
+                    ))
+                }
+            };
+            match binary.op {
+                BinOp::Div(_) | BinOp::Rem(_) => Ok(quote! {{
+                    let divisor = #right;
+                    if divisor == 0 {
+                        return Err(::magic_curves::BondingCurveError::DivisionByZero);
+                    }
+                    (#left).#method(divisor).ok_or(#overflow)?
+                }}),
+                _ => Ok(quote!((#left).#method(#right).ok_or(#overflow)?)),
+            }
+        }
+        Expr::Paren(paren) => checked(&paren.expr),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            let inner = checked(&unary.expr)?;
+            Ok(quote!((#inner).checked_neg().ok_or(#overflow)?))
+        }
+        Expr::MethodCall(call) if call.method == "pow" && call.args.len() == 1 => {
+            let receiver = checked(&call.receiver)?;
+            let exponent = &call.args[0];
+            Ok(quote!((#receiver).checked_pow(#exponent).ok_or(#overflow)?))
+        }
+        other => Ok(quote!((#other))),
+    }
+}
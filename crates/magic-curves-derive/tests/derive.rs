@@ -0,0 +1,114 @@
+// The trait is imported from `core` so the test also builds when the `derive` feature
+// re-exports the macro under the same name.
+use magic_curves::core::{
+    BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, LinearBondingCurve,
+    OperationSide, QuadraticBondingCurve,
+};
+use magic_curves_derive::BondingCurve;
+
+#[derive(BondingCurve)]
+#[bonding_curve(price = "linear * x + base", output = u64)]
+struct DerivedLinear {
+    linear: u64,
+    base: u64,
+}
+
+#[derive(BondingCurve)]
+#[bonding_curve(price = "quadratic * x.pow(2) + linear * x + base", output = u64)]
+struct DerivedQuadratic {
+    quadratic: u64,
+    linear: u64,
+    base: u64,
+}
+
+#[derive(BondingCurve)]
+#[bonding_curve(price = "total / (x + offset)", output = u64)]
+struct DerivedHyperbolic {
+    total: u64,
+    offset: u64,
+}
+
+#[derive(BondingCurve)]
+#[bonding_curve(price = "base * (growth * x).exp()", output = f64)]
+struct DerivedExponential {
+    base: f64,
+    growth: f64,
+}
+
+#[test]
+pub fn test_derived_matches_builtin_curves() {
+    let derived = DerivedLinear {
+        linear: 100,
+        base: 1000,
+    };
+    let builtin = LinearBondingCurve::new(100, 1000);
+    let derived_quadratic = DerivedQuadratic {
+        quadratic: 10_000_000,
+        linear: 500_000_000,
+        base: 1_000_000_000,
+    };
+    let builtin_quadratic = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+    for (supply, amount, side) in [
+        (0, 1, OperationSide::Add),
+        (10, 5, OperationSide::Add),
+        (10, 5, OperationSide::Remove),
+        (1_000, 100, OperationSide::Add),
+    ] {
+        assert_eq!(
+            derived.calculate_price_many(supply, amount, side),
+            builtin.calculate_price_many(supply, amount, side)
+        );
+        assert_eq!(
+            derived_quadratic.calculate_price_many_checked(supply, amount, side),
+            builtin_quadratic.calculate_price_many_checked(supply, amount, side)
+        );
+    }
+    assert_eq!(derived.calculate_price(7), builtin.calculate_price(7));
+}
+
+#[test]
+pub fn test_derived_checked_operations() {
+    let derived = DerivedLinear {
+        linear: u64::MAX,
+        base: 1,
+    };
+    assert_eq!(derived.calculate_price_checked(0), Ok(1));
+    assert_eq!(
+        derived.calculate_price_checked(2),
+        Err(BondingCurveError::Overflow)
+    );
+    assert_eq!(
+        derived.calculate_price_many_checked(1, 3, OperationSide::Remove),
+        Err(BondingCurveError::SupplyUnderflow)
+    );
+    assert_eq!(
+        derived.calculate_price_many_checked(u64::MAX, 0, OperationSide::Remove),
+        Ok(0)
+    );
+    assert_eq!(
+        derived.calculate_price_many(10, 0, OperationSide::Remove),
+        0
+    );
+
+    let hyperbolic = DerivedHyperbolic {
+        total: 1_000,
+        offset: 0,
+    };
+    assert_eq!(hyperbolic.calculate_price_checked(10), Ok(100));
+    assert_eq!(
+        hyperbolic.calculate_price_checked(0),
+        Err(BondingCurveError::DivisionByZero)
+    );
+}
+
+#[test]
+pub fn test_derived_float_curve() {
+    let derived = DerivedExponential {
+        base: 0.01,
+        growth: 0.02,
+    };
+    assert_eq!(derived.calculate_price(0), 0.01);
+    let many = derived.calculate_price_many(0, 3, OperationSide::Add);
+    let looped: f64 = (0..3).map(|supply| derived.calculate_price(supply)).sum();
+    assert_eq!(many, looped);
+}
@@ -1,8 +1,11 @@
 mod utils;
 
+use std::convert::TryFrom;
+
 use magic_curves::{
-    BondingCurve, ExponentialBondingCurve, LinearBondingCurve, LogarithmicBondingCurve,
-    OperationSide, QuadraticBondingCurve, SigmoidBondingCurve,
+    BondingCurve, ExponentialBondingCurve, ExponentialConfig, LinearBondingCurve, LinearConfig,
+    LogarithmicBondingCurve, LogarithmicConfig, OperationSide, QuadraticBondingCurve,
+    QuadraticConfig, SigmoidBondingCurve, SigmoidConfig,
 };
 use wasm_bindgen::prelude::*;
 
@@ -36,7 +39,7 @@ impl Side {
 /// The calculated price as a u64.
 #[wasm_bindgen]
 pub fn price_at_current_supply_linear(linear: u64, base: u64, current_supply: u64) -> u64 {
-    LinearBondingCurve::new(linear, base).calculate_price(current_supply)
+    LinearBondingCurve::from(LinearConfig { linear, base }).calculate_price(current_supply)
 }
 
 /// Calculates the total price for multiple tokens in a linear bonding curve.
@@ -60,7 +63,7 @@ pub fn price_many_at_current_supply_linear(
     amount: u64,
     side: Side,
 ) -> u64 {
-    LinearBondingCurve::new(linear, base).calculate_price_many(
+    LinearBondingCurve::from(LinearConfig { linear, base }).calculate_price_many(
         current_supply,
         amount,
         side.to_operation_side(),
@@ -86,7 +89,12 @@ pub fn price_at_current_supply_quadratic(
     base: u64,
     current_supply: u64,
 ) -> u64 {
-    QuadraticBondingCurve::new(quadratic, linear, base).calculate_price(current_supply)
+    QuadraticBondingCurve::from(QuadraticConfig {
+        quadratic,
+        linear,
+        base,
+    })
+    .calculate_price(current_supply)
 }
 
 /// Calculates the total price for multiple tokens in a quadratic bonding curve.
@@ -112,11 +120,12 @@ pub fn price_many_at_current_supply_quadratic(
     amount: u64,
     side: Side,
 ) -> u64 {
-    QuadraticBondingCurve::new(quadratic, linear, base).calculate_price_many(
-        current_supply,
-        amount,
-        side.to_operation_side(),
-    )
+    QuadraticBondingCurve::from(QuadraticConfig {
+        quadratic,
+        linear,
+        base,
+    })
+    .calculate_price_many(current_supply, amount, side.to_operation_side())
 }
 
 /// Calculates the price for an exponential bonding curve at the current supply.
@@ -129,10 +138,17 @@ pub fn price_many_at_current_supply_quadratic(
 ///
 /// # Returns
 ///
-/// The calculated price as an f64.
+/// The calculated price as an f64, or an error if the curve parameters are invalid.
 #[wasm_bindgen]
-pub fn price_at_current_supply_exponential(base: f64, growth: f64, current_supply: u64) -> f64 {
-    ExponentialBondingCurve::new(base, growth).calculate_price(current_supply)
+pub fn price_at_current_supply_exponential(
+    base: f64,
+    growth: f64,
+    current_supply: u64,
+) -> Result<f64, JsError> {
+    Ok(
+        ExponentialBondingCurve::try_from(ExponentialConfig { base, growth })?
+            .calculate_price(current_supply),
+    )
 }
 
 /// Calculates the total price for multiple tokens in an exponential bonding curve.
@@ -147,7 +163,7 @@ pub fn price_at_current_supply_exponential(base: f64, growth: f64, current_suppl
 ///
 /// # Returns
 ///
-/// The calculated total price as an f64.
+/// The calculated total price as an f64, or an error if the curve parameters are invalid.
 #[wasm_bindgen]
 pub fn price_many_at_current_supply_exponential(
     base: f64,
@@ -155,11 +171,10 @@ pub fn price_many_at_current_supply_exponential(
     current_supply: u64,
     amount: u64,
     side: Side,
-) -> f64 {
-    ExponentialBondingCurve::new(base, growth).calculate_price_many(
-        current_supply,
-        amount,
-        side.to_operation_side(),
+) -> Result<f64, JsError> {
+    Ok(
+        ExponentialBondingCurve::try_from(ExponentialConfig { base, growth })?
+            .calculate_price_many(current_supply, amount, side.to_operation_side()),
     )
 }
 
@@ -173,10 +188,17 @@ pub fn price_many_at_current_supply_exponential(
 ///
 /// # Returns
 ///
-/// The calculated price as an f64.
+/// The calculated price as an f64, or an error if the curve parameters are invalid.
 #[wasm_bindgen]
-pub fn price_at_current_supply_logarithmic(base: f64, growth: f64, current_supply: u64) -> f64 {
-    LogarithmicBondingCurve::new(base, growth).calculate_price(current_supply)
+pub fn price_at_current_supply_logarithmic(
+    base: f64,
+    growth: f64,
+    current_supply: u64,
+) -> Result<f64, JsError> {
+    Ok(
+        LogarithmicBondingCurve::try_from(LogarithmicConfig { base, growth })?
+            .calculate_price(current_supply),
+    )
 }
 
 /// Calculates the total price for multiple tokens in a logarithmic bonding curve.
@@ -191,7 +213,7 @@ pub fn price_at_current_supply_logarithmic(base: f64, growth: f64, current_suppl
 ///
 /// # Returns
 ///
-/// The calculated total price as an f64.
+/// The calculated total price as an f64, or an error if the curve parameters are invalid.
 #[wasm_bindgen]
 pub fn price_many_at_current_supply_logarithmic(
     base: f64,
@@ -199,11 +221,10 @@ pub fn price_many_at_current_supply_logarithmic(
     current_supply: u64,
     amount: u64,
     side: Side,
-) -> f64 {
-    LogarithmicBondingCurve::new(base, growth).calculate_price_many(
-        current_supply,
-        amount,
-        side.to_operation_side(),
+) -> Result<f64, JsError> {
+    Ok(
+        LogarithmicBondingCurve::try_from(LogarithmicConfig { base, growth })?
+            .calculate_price_many(current_supply, amount, side.to_operation_side()),
     )
 }
 
@@ -211,29 +232,34 @@ pub fn price_many_at_current_supply_logarithmic(
 ///
 /// # Arguments
 ///
-/// * `base` - The base price of the curve.
+/// * `max_price` - The maximum price the curve approaches.
 /// * `growth` - The growth rate of the curve.
 /// * `mid_supply` - The supply at the midpoint of the sigmoid curve.
 /// * `current_supply` - The current token supply.
 ///
 /// # Returns
 ///
-/// The calculated price as an f64.
+/// The calculated price as an f64, or an error if the curve parameters are invalid.
 #[wasm_bindgen]
 pub fn price_at_current_supply_sigmoid(
-    base: f64,
+    max_price: f64,
     growth: f64,
     mid_supply: u64,
     current_supply: u64,
-) -> f64 {
-    SigmoidBondingCurve::new(base, growth, mid_supply).calculate_price(current_supply)
+) -> Result<f64, JsError> {
+    Ok(SigmoidBondingCurve::try_from(SigmoidConfig {
+        max_price,
+        growth,
+        mid_supply,
+    })?
+    .calculate_price(current_supply))
 }
 
 /// Calculates the total price for multiple tokens in a sigmoid bonding curve.
 ///
 /// # Arguments
 ///
-/// * `base` - The base price of the curve.
+/// * `max_price` - The maximum price the curve approaches.
 /// * `growth` - The growth rate of the curve.
 /// * `mid_supply` - The supply at the midpoint of the sigmoid curve.
 /// * `current_supply` - The current token supply.
@@ -242,19 +268,31 @@ pub fn price_at_current_supply_sigmoid(
 ///
 /// # Returns
 ///
-/// The calculated total price as an f64.
+/// The calculated total price as an f64, or an error if the curve parameters are invalid.
 #[wasm_bindgen]
 pub fn price_many_at_current_supply_sigmoid(
-    base: f64,
+    max_price: f64,
     growth: f64,
     mid_supply: u64,
     current_supply: u64,
     amount: u64,
     side: Side,
-) -> f64 {
-    SigmoidBondingCurve::new(base, growth, mid_supply).calculate_price_many(
-        current_supply,
-        amount,
-        side.to_operation_side(),
-    )
+) -> Result<f64, JsError> {
+    Ok(SigmoidBondingCurve::try_from(SigmoidConfig {
+        max_price,
+        growth,
+        mid_supply,
+    })?
+    .calculate_price_many(current_supply, amount, side.to_operation_side()))
+}
+
+/// Returns the digest of the float test vector outputs, as printed by `magic-curves vectors
+/// --digest`. Build with the `soft-float` feature for a digest that matches native targets.
+///
+/// # Returns
+///
+/// The digest as a 16-digit hexadecimal string.
+#[wasm_bindgen]
+pub fn float_vectors_digest() -> String {
+    format!("{:016x}", magic_curves::float_vectors_digest())
 }
@@ -0,0 +1,254 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::{BondingCurveError, CurveFamily, LinearBondingCurve, QuadraticBondingCurve};
+#[cfg(feature = "float")]
+use super::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+/// Represents a bonding curve stored in on-chain account state.
+///
+/// This struct has a fixed size regardless of the curve it holds, so it can be embedded
+/// in an Anchor `#[account]` struct (or any borsh account) and sized up front. The
+/// parameters are stored in declaration order of the curve's fields, with `f64` fields
+/// stored as their raw bits.
+///
+/// # Fields
+///
+/// * `tag`: The `CurveFamily::tag` of the stored curve.
+/// * `params`: The curve parameters. Unused slots are zero.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{CurveAccount, LinearBondingCurve};
+///
+/// let account = CurveAccount::from(LinearBondingCurve::new(100, 1000));
+/// let curve = LinearBondingCurve::try_from(account).unwrap();
+/// assert_eq!(curve, LinearBondingCurve::new(100, 1000));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CurveAccount {
+    pub tag: u8,
+    pub params: [u64; 3],
+}
+
+impl CurveAccount {
+    /// The serialized size of a `CurveAccount`, for use in Anchor `space` calculations.
+    pub const INIT_SPACE: usize = 1 + 3 * 8;
+
+    /// The size of an account holding only a `CurveAccount`, including Anchor's 8-byte discriminator.
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Returns the family of the stored curve, if the tag is known.
+    pub fn family(&self) -> Option<CurveFamily> {
+        CurveFamily::from_tag(self.tag)
+    }
+
+    fn new(family: CurveFamily, params: [u64; 3]) -> Self {
+        Self {
+            tag: family.tag(),
+            params,
+        }
+    }
+
+    fn params_for(&self, family: CurveFamily) -> Result<[u64; 3], BondingCurveError> {
+        if self.tag != family.tag() {
+            return Err(BondingCurveError::InvalidCurveTag);
+        }
+        Ok(self.params)
+    }
+}
+
+impl From<LinearBondingCurve> for CurveAccount {
+    fn from(curve: LinearBondingCurve) -> Self {
+        Self::new(CurveFamily::Linear, [curve.linear, curve.base, 0])
+    }
+}
+
+impl From<QuadraticBondingCurve> for CurveAccount {
+    fn from(curve: QuadraticBondingCurve) -> Self {
+        Self::new(
+            CurveFamily::Quadratic,
+            [curve.quadratic, curve.linear, curve.base],
+        )
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<ExponentialBondingCurve> for CurveAccount {
+    fn from(curve: ExponentialBondingCurve) -> Self {
+        Self::new(
+            CurveFamily::Exponential,
+            [curve.base.to_bits(), curve.growth.to_bits(), 0],
+        )
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<LogarithmicBondingCurve> for CurveAccount {
+    fn from(curve: LogarithmicBondingCurve) -> Self {
+        Self::new(
+            CurveFamily::Logarithmic,
+            [curve.base.to_bits(), curve.growth.to_bits(), 0],
+        )
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<SigmoidBondingCurve> for CurveAccount {
+    fn from(curve: SigmoidBondingCurve) -> Self {
+        Self::new(
+            CurveFamily::Sigmoid,
+            [
+                curve.max_price.to_bits(),
+                curve.growth.to_bits(),
+                curve.mid_supply,
+            ],
+        )
+    }
+}
+
+impl TryFrom<CurveAccount> for LinearBondingCurve {
+    type Error = BondingCurveError;
+
+    fn try_from(account: CurveAccount) -> Result<Self, Self::Error> {
+        let [linear, base, _] = account.params_for(CurveFamily::Linear)?;
+        Ok(Self::new(linear, base))
+    }
+}
+
+impl TryFrom<CurveAccount> for QuadraticBondingCurve {
+    type Error = BondingCurveError;
+
+    fn try_from(account: CurveAccount) -> Result<Self, Self::Error> {
+        let [quadratic, linear, base] = account.params_for(CurveFamily::Quadratic)?;
+        Ok(Self::new(quadratic, linear, base))
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<CurveAccount> for ExponentialBondingCurve {
+    type Error = BondingCurveError;
+
+    fn try_from(account: CurveAccount) -> Result<Self, Self::Error> {
+        let [base, growth, _] = account.params_for(CurveFamily::Exponential)?;
+        Self::try_new(f64::from_bits(base), f64::from_bits(growth))
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<CurveAccount> for LogarithmicBondingCurve {
+    type Error = BondingCurveError;
+
+    fn try_from(account: CurveAccount) -> Result<Self, Self::Error> {
+        let [base, growth, _] = account.params_for(CurveFamily::Logarithmic)?;
+        Self::try_new(f64::from_bits(base), f64::from_bits(growth))
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<CurveAccount> for SigmoidBondingCurve {
+    type Error = BondingCurveError;
+
+    fn try_from(account: CurveAccount) -> Result<Self, Self::Error> {
+        let [max_price, growth, mid_supply] = account.params_for(CurveFamily::Sigmoid)?;
+        Self::try_new(
+            f64::from_bits(max_price),
+            f64::from_bits(growth),
+            mid_supply,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, CurveAccount, CurveFamily, LinearBondingCurve, QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_curve_account_round_trip() {
+        let quadratic = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let account = CurveAccount::from(quadratic);
+        assert_eq!(account.family(), Some(CurveFamily::Quadratic));
+        assert_eq!(QuadraticBondingCurve::try_from(account).unwrap(), quadratic);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_curve_account_float_round_trip() {
+        use crate::{ExponentialBondingCurve, SigmoidBondingCurve};
+
+        let sigmoid = SigmoidBondingCurve::new(100.0, 0.01, 500);
+        let account = CurveAccount::from(sigmoid);
+        assert_eq!(SigmoidBondingCurve::try_from(account).unwrap(), sigmoid);
+
+        let exponential = ExponentialBondingCurve::new(0.01, 0.02);
+        let account = CurveAccount::from(exponential);
+        assert_eq!(
+            ExponentialBondingCurve::try_from(account).unwrap(),
+            exponential
+        );
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_curve_account_rejects_invalid_float_params() {
+        use crate::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+        let nan_base = CurveAccount {
+            tag: CurveFamily::Exponential.tag(),
+            params: [f64::NAN.to_bits(), 0.02f64.to_bits(), 0],
+        };
+        assert_eq!(
+            ExponentialBondingCurve::try_from(nan_base),
+            Err(BondingCurveError::InvalidParameter)
+        );
+
+        let nan_base = CurveAccount {
+            tag: CurveFamily::Logarithmic.tag(),
+            params: [f64::NAN.to_bits(), 0.02f64.to_bits(), 0],
+        };
+        assert_eq!(
+            LogarithmicBondingCurve::try_from(nan_base),
+            Err(BondingCurveError::InvalidParameter)
+        );
+
+        let nan_max_price = CurveAccount {
+            tag: CurveFamily::Sigmoid.tag(),
+            params: [f64::NAN.to_bits(), 0.01f64.to_bits(), 500],
+        };
+        assert_eq!(
+            SigmoidBondingCurve::try_from(nan_max_price),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[test]
+    pub fn test_curve_account_borsh() {
+        let account = CurveAccount::from(LinearBondingCurve::new(500_000_000, 1_000_000_000));
+        let bytes = borsh::to_vec(&account).unwrap();
+        assert_eq!(bytes.len(), CurveAccount::INIT_SPACE);
+        assert_eq!(CurveAccount::LEN, 33);
+
+        let decoded: CurveAccount = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(
+            LinearBondingCurve::try_from(decoded).unwrap(),
+            LinearBondingCurve::new(500_000_000, 1_000_000_000)
+        );
+    }
+
+    #[test]
+    pub fn test_curve_account_tag_mismatch() {
+        let account = CurveAccount::from(LinearBondingCurve::new(1, 1));
+        assert_eq!(
+            QuadraticBondingCurve::try_from(account),
+            Err(BondingCurveError::InvalidCurveTag)
+        );
+        let unknown = CurveAccount {
+            tag: 200,
+            params: [0; 3],
+        };
+        assert_eq!(unknown.family(), None);
+    }
+}
@@ -0,0 +1,144 @@
+// Checks that the core pricing paths never allocate, so real-time systems can call them
+// where allocation is forbidden. The test build counts every allocation per thread.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Forwards to the system allocator, counting the allocations of every thread.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // The counter is gone while a thread shuts down, and those allocations do not matter.
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and asserts that it did not allocate on this thread.
+fn assert_no_alloc<R>(name: &str, f: impl FnOnce() -> R) -> R {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let allocations = ALLOCATIONS.with(Cell::get) - before;
+    assert_eq!(allocations, 0, "{name} allocated {allocations} times");
+    result
+}
+
+mod test {
+    use super::assert_no_alloc;
+    use crate::{
+        quote_batch_into, quote_with_fees, AnyBondingCurve, BondingCurveError,
+        BondingCurveWithCheckedOperations, CubicBondingCurve, CurveState, GeometricBondingCurve,
+        GeometricLevelCurve, InverseBondingCurve, InverseTable, LinearBondingCurve, OperationSide,
+        PowerLevelCurve, QuadraticBondingCurve, QuoteItem, QuoteRequest, RebaseAdapter,
+        TrancheBalances, TranchePolicy, UncheckedBondingCurve,
+    };
+
+    /// Asserts that pricing with `curve`, including failing, does not allocate.
+    fn assert_pricing_is_allocation_free<C: BondingCurveWithCheckedOperations<u64>>(
+        name: &str,
+        curve: &C,
+    ) {
+        assert_no_alloc(name, || {
+            for side in [OperationSide::Add, OperationSide::Remove] {
+                let _ = curve.calculate_price_checked(100);
+                let _ = curve.calculate_price_many_checked(100, 50, side);
+                let _ = curve.calculate_price_many_checked(10, 50, side);
+                let _ = curve.calculate_price_many_checked(u64::MAX, 2, side);
+                let _ = QuoteRequest::new(100, 50, side).quote(curve);
+            }
+        });
+    }
+
+    #[test]
+    pub fn test_integer_pricing_does_not_allocate() {
+        let linear = LinearBondingCurve::new(100, 1000);
+        let quadratic = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        assert_pricing_is_allocation_free("linear", &linear);
+        assert_pricing_is_allocation_free("quadratic", &quadratic);
+        assert_pricing_is_allocation_free("cubic", &CubicBondingCurve::new(2, 10, 100, 1000));
+        assert_pricing_is_allocation_free("geometric", &GeometricBondingCurve::new(1_000, 10_100));
+        assert_pricing_is_allocation_free("levels", &GeometricLevelCurve::new(100, 15_000));
+        assert_pricing_is_allocation_free("power", &PowerLevelCurve::triangular(100));
+
+        let trades = [
+            (AnyBondingCurve::from(linear), 10, 2, OperationSide::Add),
+            (
+                AnyBondingCurve::from(quadratic),
+                3,
+                2,
+                OperationSide::Remove,
+            ),
+        ];
+        let mut quotes = [Err(BondingCurveError::InvalidParameter); 2];
+        let mut items = [QuoteItem {
+            starting_supply: 0,
+            amount: 0,
+            price: 0,
+        }; 8];
+        let table = InverseTable::<16>::new(&linear, 10).unwrap();
+        let fees = LinearBondingCurve::new(0, 100);
+        let rebase = RebaseAdapter::new(linear, 1_000, 2_000);
+        assert_no_alloc("batches and tools", || {
+            quote_batch_into(&trades, &mut quotes).unwrap();
+            quadratic
+                .quote_itemized_into(5, 15, OperationSide::Add, 2, &mut items)
+                .unwrap();
+            let _ = table.supply_at_price(&linear, 12_345);
+            let _ = linear.amount_for_total(10, 14_500, OperationSide::Add);
+            let _ = quote_with_fees(&linear, &fees, 10, 10, OperationSide::Add);
+            let _ = rebase.quote(20, OperationSide::Remove);
+            let _ = quadratic.price_many_unchecked_unsafe(10, 5, OperationSide::Remove);
+        });
+        assert!(quotes.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    pub fn test_curve_state_does_not_allocate() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 0)
+            .with_tranches(TranchePolicy::new(100, 50), TrancheBalances::default())
+            .unwrap();
+        assert_no_alloc("curve state", || {
+            state.buy(100).unwrap();
+            state.sell(30).unwrap();
+            let _ = state.sell(1_000);
+            state.graduate(true).unwrap();
+            state.sell(10).unwrap();
+            let _ = state.buy(1);
+            state.snapshot()
+        });
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_float_pricing_does_not_allocate() {
+        use crate::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+        let exponential = ExponentialBondingCurve::new(0.01, 0.02);
+        let logarithmic = LogarithmicBondingCurve::new(0.02, 0.01);
+        let sigmoid = SigmoidBondingCurve::new(100.0, 0.01, 500);
+        let any = AnyBondingCurve::from(sigmoid);
+        assert_no_alloc("float curves", || {
+            for side in [OperationSide::Add, OperationSide::Remove] {
+                let _ = exponential.calculate_price_many_checked(100, 50, side);
+                let _ = logarithmic.calculate_price_many_checked(100, 50, side);
+                let _ = sigmoid.calculate_price_many_checked(100, 50, side);
+                let _ = any.quote(&QuoteRequest::new(100, 50, side));
+            }
+        });
+    }
+}
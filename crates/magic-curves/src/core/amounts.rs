@@ -0,0 +1,301 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuoteRequest,
+    QuoteResponse,
+};
+
+/// Represents a number of tokens of a curve, such as a supply or the size of a trade.
+///
+/// Token amounts add to and subtract from each other, but never mix with a `QuoteAmount`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::TokenAmount;
+///
+/// let supply = TokenAmount::new(100);
+/// assert_eq!(supply.checked_add(TokenAmount::new(10)), Some(TokenAmount::new(110)));
+/// assert_eq!(supply.checked_sub(TokenAmount::new(101)), None);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TokenAmount(pub u64);
+
+/// Represents an amount of the reserve currency, such as the price of a trade.
+///
+/// Quote amounts add to and subtract from each other, and a unit price multiplies by a
+/// `TokenAmount`, but the two never add up.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{QuoteAmount, TokenAmount};
+///
+/// let price = QuoteAmount::new(2_000);
+/// assert_eq!(price.checked_mul(TokenAmount::new(3)), Some(QuoteAmount::new(6_000)));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct QuoteAmount(pub u64);
+
+macro_rules! amount {
+    ($name:ident) => {
+        impl $name {
+            #[doc = concat!("Creates a new `", stringify!($name), "`.")]
+            pub const fn new(value: u64) -> Self {
+                Self(value)
+            }
+
+            /// Returns the raw amount.
+            pub const fn get(self) -> u64 {
+                self.0
+            }
+
+            /// Adds two amounts, returning `None` on overflow.
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                self.0.checked_add(other.0).map(Self)
+            }
+
+            /// Subtracts two amounts, returning `None` if `other` is larger.
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.0.checked_sub(other.0).map(Self)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(amount: $name) -> Self {
+                amount.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+amount!(TokenAmount);
+amount!(QuoteAmount);
+
+impl QuoteAmount {
+    /// Values a number of tokens at this unit price, returning `None` on overflow.
+    pub fn checked_mul(self, tokens: TokenAmount) -> Option<Self> {
+        self.0.checked_mul(tokens.0).map(Self)
+    }
+}
+
+/// Represents a `QuoteRequest` whose supplies are `TokenAmount`s.
+///
+/// # Fields
+///
+/// * `starting_supply`: The supply before the operation.
+/// * `amount`: The number of tokens to add or remove.
+/// * `side`: Whether tokens are being added or removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypedQuoteRequest {
+    pub starting_supply: TokenAmount,
+    pub amount: TokenAmount,
+    pub side: OperationSide,
+}
+
+/// Represents a `QuoteResponse` whose price is a `QuoteAmount` and supply a `TokenAmount`.
+///
+/// # Fields
+///
+/// * `price`: The total price of the requested tokens.
+/// * `ending_supply`: The supply after the operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypedQuoteResponse {
+    pub price: QuoteAmount,
+    pub ending_supply: TokenAmount,
+}
+
+impl TypedQuoteRequest {
+    /// Creates a new `TypedQuoteRequest`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `TypedQuoteRequest`.
+    pub fn new(starting_supply: TokenAmount, amount: TokenAmount, side: OperationSide) -> Self {
+        Self {
+            starting_supply,
+            amount,
+            side,
+        }
+    }
+
+    /// Quotes this request against a curve, like `QuoteRequest::quote`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve to price the request with.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TypedQuoteResponse`, or the `BondingCurveError` of
+    /// `QuoteRequest::quote`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{
+    ///     LinearBondingCurve, OperationSide, QuoteAmount, TokenAmount, TypedQuoteRequest,
+    /// };
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let request = TypedQuoteRequest::new(TokenAmount(10), TokenAmount(2), OperationSide::Add);
+    /// let response = request.quote(&curve).unwrap();
+    /// assert_eq!(response.price, QuoteAmount(4100));
+    /// assert_eq!(response.ending_supply, TokenAmount(12));
+    /// ```
+    pub fn quote<C>(&self, curve: &C) -> Result<TypedQuoteResponse, BondingCurveError>
+    where
+        C: BondingCurveWithCheckedOperations<u64>,
+    {
+        QuoteRequest::from(*self).quote(curve).map(Into::into)
+    }
+}
+
+impl From<TypedQuoteRequest> for QuoteRequest {
+    fn from(request: TypedQuoteRequest) -> Self {
+        QuoteRequest::new(request.starting_supply.0, request.amount.0, request.side)
+    }
+}
+
+impl From<QuoteRequest> for TypedQuoteRequest {
+    fn from(request: QuoteRequest) -> Self {
+        TypedQuoteRequest::new(
+            TokenAmount(request.starting_supply),
+            TokenAmount(request.amount),
+            request.side,
+        )
+    }
+}
+
+impl From<QuoteResponse> for TypedQuoteResponse {
+    fn from(response: QuoteResponse) -> Self {
+        TypedQuoteResponse {
+            price: QuoteAmount(response.price),
+            ending_supply: TokenAmount(response.ending_supply),
+        }
+    }
+}
+
+impl From<TypedQuoteResponse> for QuoteResponse {
+    fn from(response: TypedQuoteResponse) -> Self {
+        QuoteResponse {
+            price: response.price.0,
+            ending_supply: response.ending_supply.0,
+        }
+    }
+}
+
+/// The checked pricing operations of an integer curve, taking `TokenAmount`s and returning
+/// `QuoteAmount`s. Implemented for every `BondingCurveWithCheckedOperations<u64>`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{LinearBondingCurve, QuoteAmount, TokenAmount, TypedBondingCurve};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// assert_eq!(curve.calculate_price_typed(TokenAmount(10)), Ok(QuoteAmount(2000)));
+/// ```
+pub trait TypedBondingCurve {
+    /// Calculates the price of the token at `supply`, like `calculate_price_checked`.
+    fn calculate_price_typed(&self, supply: TokenAmount) -> Result<QuoteAmount, BondingCurveError>;
+
+    /// Calculates the total price of `amount` tokens, like `calculate_price_many_checked`.
+    fn calculate_price_many_typed(
+        &self,
+        starting_supply: TokenAmount,
+        amount: TokenAmount,
+        side: OperationSide,
+    ) -> Result<QuoteAmount, BondingCurveError>;
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64> + ?Sized> TypedBondingCurve for C {
+    fn calculate_price_typed(&self, supply: TokenAmount) -> Result<QuoteAmount, BondingCurveError> {
+        self.calculate_price_checked(supply.0).map(QuoteAmount)
+    }
+
+    fn calculate_price_many_typed(
+        &self,
+        starting_supply: TokenAmount,
+        amount: TokenAmount,
+        side: OperationSide,
+    ) -> Result<QuoteAmount, BondingCurveError> {
+        self.calculate_price_many_checked(starting_supply.0, amount.0, side)
+            .map(QuoteAmount)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuadraticBondingCurve,
+        QuoteAmount, QuoteRequest, TokenAmount, TypedBondingCurve, TypedQuoteRequest,
+    };
+
+    #[test]
+    pub fn test_typed_quotes_match_untyped() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let request =
+            TypedQuoteRequest::new(TokenAmount(100), TokenAmount(10), OperationSide::Remove);
+        let response = request.quote(&curve).unwrap();
+        let untyped = QuoteRequest::from(request).quote(&curve).unwrap();
+        assert_eq!(response, untyped.into());
+        assert_eq!(
+            TypedQuoteRequest::from(QuoteRequest::from(request)),
+            request
+        );
+        assert_eq!(
+            curve.calculate_price_many_typed(
+                TokenAmount(100),
+                TokenAmount(10),
+                OperationSide::Remove
+            ),
+            Ok(response.price)
+        );
+        assert_eq!(
+            curve.calculate_price_typed(TokenAmount(7)),
+            Ok(QuoteAmount(curve.calculate_price_checked(7).unwrap()))
+        );
+        assert_eq!(
+            TypedQuoteRequest::new(TokenAmount(5), TokenAmount(6), OperationSide::Remove)
+                .quote(&curve),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(QuoteAmount(u64::MAX).checked_mul(TokenAmount(2)), None);
+        assert_eq!(u64::from(response.ending_supply), 90);
+    }
+}
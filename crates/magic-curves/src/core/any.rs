@@ -0,0 +1,219 @@
+#[cfg(feature = "float")]
+use super::{
+    AsF64, BondingCurveWithCheckedOperations, ExponentialBondingCurve, LogarithmicBondingCurve,
+    OperationSide, SigmoidBondingCurve,
+};
+use super::{BondingCurveError, CurveFamily, LinearBondingCurve, QuadraticBondingCurve};
+
+/// Represents a bonding curve of any family.
+///
+/// This enum is used where the family of a curve is only known at runtime, such as
+/// when decoding stored curves or reading them from configuration.
+///
+/// # Variants
+///
+/// * `Linear` - A `LinearBondingCurve`.
+/// * `Quadratic` - A `QuadraticBondingCurve`.
+/// * `Exponential` - An `ExponentialBondingCurve`.
+/// * `Logarithmic` - A `LogarithmicBondingCurve`.
+/// * `Sigmoid` - A `SigmoidBondingCurve`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AnyBondingCurve {
+    Linear(LinearBondingCurve),
+    Quadratic(QuadraticBondingCurve),
+    #[cfg(feature = "float")]
+    Exponential(ExponentialBondingCurve),
+    #[cfg(feature = "float")]
+    Logarithmic(LogarithmicBondingCurve),
+    #[cfg(feature = "float")]
+    Sigmoid(SigmoidBondingCurve),
+}
+
+impl AnyBondingCurve {
+    /// Returns the family of the curve.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{AnyBondingCurve, CurveFamily, LinearBondingCurve};
+    ///
+    /// let curve = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+    /// assert_eq!(curve.family(), CurveFamily::Linear);
+    /// ```
+    pub fn family(&self) -> CurveFamily {
+        match self {
+            AnyBondingCurve::Linear(_) => CurveFamily::Linear,
+            AnyBondingCurve::Quadratic(_) => CurveFamily::Quadratic,
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Exponential(_) => CurveFamily::Exponential,
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Logarithmic(_) => CurveFamily::Logarithmic,
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Sigmoid(_) => CurveFamily::Sigmoid,
+        }
+    }
+
+    /// Calculates the price at `supply` as an `f64`, whatever the price type of the family,
+    /// so curves of different families can be compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, or the `BondingCurveError` of the checked price.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{AnyBondingCurve, LinearBondingCurve};
+    ///
+    /// let curve = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+    /// assert_eq!(curve.calculate_price_as_f64(5), Ok(1500.0));
+    /// ```
+    #[cfg(feature = "float")]
+    pub fn calculate_price_as_f64(&self, supply: u64) -> Result<f64, BondingCurveError> {
+        match self {
+            AnyBondingCurve::Linear(curve) => {
+                curve.calculate_price_checked(supply).map(AsF64::as_f64)
+            }
+            AnyBondingCurve::Quadratic(curve) => {
+                curve.calculate_price_checked(supply).map(AsF64::as_f64)
+            }
+            AnyBondingCurve::Exponential(curve) => curve.calculate_price_checked(supply),
+            AnyBondingCurve::Logarithmic(curve) => curve.calculate_price_checked(supply),
+            AnyBondingCurve::Sigmoid(curve) => curve.calculate_price_checked(supply),
+        }
+    }
+
+    /// Calculates the total price of `amount` tokens as an `f64`, whatever the price type of
+    /// the family.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, or the `BondingCurveError` of the checked price.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{AnyBondingCurve, LinearBondingCurve, OperationSide};
+    ///
+    /// let curve = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+    /// let price = curve.calculate_price_many_as_f64(0, 2, OperationSide::Add);
+    /// assert_eq!(price, Ok(2100.0));
+    /// ```
+    #[cfg(feature = "float")]
+    pub fn calculate_price_many_as_f64(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<f64, BondingCurveError> {
+        match self {
+            AnyBondingCurve::Linear(curve) => curve
+                .calculate_price_many_checked(starting_supply, amount, side)
+                .map(AsF64::as_f64),
+            AnyBondingCurve::Quadratic(curve) => curve
+                .calculate_price_many_checked(starting_supply, amount, side)
+                .map(AsF64::as_f64),
+            AnyBondingCurve::Exponential(curve) => {
+                curve.calculate_price_many_checked(starting_supply, amount, side)
+            }
+            AnyBondingCurve::Logarithmic(curve) => {
+                curve.calculate_price_many_checked(starting_supply, amount, side)
+            }
+            AnyBondingCurve::Sigmoid(curve) => {
+                curve.calculate_price_many_checked(starting_supply, amount, side)
+            }
+        }
+    }
+
+    /// Returns the curve parameters in declaration order, with `f64` fields as their raw bits.
+    /// Unused slots are zero.
+    pub(crate) fn params(&self) -> [u64; 3] {
+        match self {
+            AnyBondingCurve::Linear(curve) => [curve.linear, curve.base, 0],
+            AnyBondingCurve::Quadratic(curve) => [curve.quadratic, curve.linear, curve.base],
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Exponential(curve) => {
+                [curve.base.to_bits(), curve.growth.to_bits(), 0]
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Logarithmic(curve) => {
+                [curve.base.to_bits(), curve.growth.to_bits(), 0]
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Sigmoid(curve) => [
+                curve.max_price.to_bits(),
+                curve.growth.to_bits(),
+                curve.mid_supply,
+            ],
+        }
+    }
+
+    /// Rebuilds a curve from its `CurveFamily::tag` and the output of `params`.
+    ///
+    /// Fails with `BondingCurveError::InvalidCurveTag` for unknown tags, and for float
+    /// families when the `float` feature is disabled. Float curves are built with their
+    /// `try_new`, so invalid parameters fail with its error.
+    pub(crate) fn from_params(tag: u8, params: [u64; 3]) -> Result<Self, BondingCurveError> {
+        let [a, b, c] = params;
+        match CurveFamily::from_tag(tag) {
+            Some(CurveFamily::Linear) => Ok(LinearBondingCurve::new(a, b).into()),
+            Some(CurveFamily::Quadratic) => Ok(QuadraticBondingCurve::new(a, b, c).into()),
+            #[cfg(feature = "float")]
+            Some(CurveFamily::Exponential) => {
+                Ok(ExponentialBondingCurve::try_new(f64::from_bits(a), f64::from_bits(b))?.into())
+            }
+            #[cfg(feature = "float")]
+            Some(CurveFamily::Logarithmic) => {
+                Ok(LogarithmicBondingCurve::try_new(f64::from_bits(a), f64::from_bits(b))?.into())
+            }
+            #[cfg(feature = "float")]
+            Some(CurveFamily::Sigmoid) => {
+                Ok(SigmoidBondingCurve::try_new(f64::from_bits(a), f64::from_bits(b), c)?.into())
+            }
+            _ => Err(BondingCurveError::InvalidCurveTag),
+        }
+    }
+}
+
+impl From<LinearBondingCurve> for AnyBondingCurve {
+    fn from(curve: LinearBondingCurve) -> Self {
+        AnyBondingCurve::Linear(curve)
+    }
+}
+
+impl From<QuadraticBondingCurve> for AnyBondingCurve {
+    fn from(curve: QuadraticBondingCurve) -> Self {
+        AnyBondingCurve::Quadratic(curve)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<ExponentialBondingCurve> for AnyBondingCurve {
+    fn from(curve: ExponentialBondingCurve) -> Self {
+        AnyBondingCurve::Exponential(curve)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<LogarithmicBondingCurve> for AnyBondingCurve {
+    fn from(curve: LogarithmicBondingCurve) -> Self {
+        AnyBondingCurve::Logarithmic(curve)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<SigmoidBondingCurve> for AnyBondingCurve {
+    fn from(curve: SigmoidBondingCurve) -> Self {
+        AnyBondingCurve::Sigmoid(curve)
+    }
+}
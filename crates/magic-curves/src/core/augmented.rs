@@ -0,0 +1,275 @@
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    bps_of, supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide,
+};
+
+/// Represents the outcome of quoting a trade on an `AugmentedBondingCurve`.
+///
+/// # Fields
+///
+/// * `side`: Whether tokens are being bought or sold.
+/// * `amount`: The number of tokens traded.
+/// * `reserve`: The price of the tokens, paid into the reserve on a buy and out of it on a
+///   sell.
+/// * `tribute`: The part of the trade paid to the funding pool.
+/// * `total`: The amount the buyer pays, `reserve + tribute`, or the amount the seller
+///   receives, `reserve - tribute`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AugmentedQuote {
+    pub side: OperationSide,
+    pub amount: u64,
+    pub reserve: u64,
+    pub tribute: u64,
+    pub total: u64,
+}
+
+/// Represents an augmented bonding curve, which funds a project from the trades on its curve.
+///
+/// The first `hatch_supply` tokens are sold during the hatch at the fixed `hatch_price`, and
+/// every later token at the spot price of `curve`. Buyers pay `entry_tribute` basis points on
+/// top of the price to the funding pool, and sellers leave `exit_tribute` basis points of the
+/// refund to it, so the reserve always holds exactly the price of the supply and can buy back
+/// every token.
+///
+/// As a `BondingCurve` the type prices the reserve side of trades, without tributes; use
+/// `AugmentedBondingCurve::quote` for the full split.
+///
+/// # Fields
+///
+/// * `curve`: The curve that prices tokens after the hatch.
+/// * `hatch_price`: The price of every token sold during the hatch.
+/// * `hatch_supply`: The number of tokens sold during the hatch.
+/// * `entry_tribute`: The tribute on buys, in basis points of the price.
+/// * `exit_tribute`: The tribute on sells, in basis points of the price.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{AugmentedBondingCurve, LinearBondingCurve, OperationSide};
+///
+/// let abc = AugmentedBondingCurve::new(LinearBondingCurve::new(10, 0), 50, 5, 200, 1_000);
+/// // Two hatch tokens at 50 and one curve token at 10 * 5, plus a 2% entry tribute.
+/// let buy = abc.quote(3, 3, OperationSide::Add).unwrap();
+/// assert_eq!((buy.reserve, buy.tribute, buy.total), (150, 3, 153));
+/// let sell = abc.quote(6, 1, OperationSide::Remove).unwrap();
+/// assert_eq!((sell.reserve, sell.tribute, sell.total), (60, 6, 54));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AugmentedBondingCurve<C> {
+    pub curve: C,
+    pub hatch_price: u64,
+    pub hatch_supply: u64,
+    pub entry_tribute: u64,
+    pub exit_tribute: u64,
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> AugmentedBondingCurve<C> {
+    /// Creates a new `AugmentedBondingCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve that prices tokens after the hatch.
+    /// * `hatch_price` - The price of every token sold during the hatch.
+    /// * `hatch_supply` - The number of tokens sold during the hatch.
+    /// * `entry_tribute` - The tribute on buys, in basis points of the price.
+    /// * `exit_tribute` - The tribute on sells, in basis points of the price.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `AugmentedBondingCurve`.
+    pub fn new(
+        curve: C,
+        hatch_price: u64,
+        hatch_supply: u64,
+        entry_tribute: u64,
+        exit_tribute: u64,
+    ) -> Self {
+        Self {
+            curve,
+            hatch_price,
+            hatch_supply,
+            entry_tribute,
+            exit_tribute,
+        }
+    }
+
+    /// Returns whether the hatch is still open at the given supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    pub fn is_hatching(&self, supply: u64) -> bool {
+        supply < self.hatch_supply
+    }
+
+    /// Quotes a buy or a sell, split between the reserve and the funding pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    /// * `amount` - The number of tokens to buy or sell.
+    /// * `side` - Whether tokens are being bought or sold.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `AugmentedQuote`, `BondingCurveError::InvalidParameter` if
+    /// a tribute is above `BASIS_POINTS`, or the first `BondingCurveError` a price fails with.
+    pub fn quote(
+        &self,
+        supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<AugmentedQuote, BondingCurveError> {
+        let reserve = self.calculate_price_many_checked(supply, amount, side)?;
+        let (tribute, total) = match side {
+            OperationSide::Add => {
+                let tribute = bps_of(reserve, self.entry_tribute)?;
+                let total = reserve
+                    .checked_add(tribute)
+                    .ok_or(BondingCurveError::Overflow)?;
+                (tribute, total)
+            }
+            OperationSide::Remove => {
+                let tribute = bps_of(reserve, self.exit_tribute)?;
+                (tribute, reserve - tribute)
+            }
+        };
+        Ok(AugmentedQuote {
+            side,
+            amount,
+            reserve,
+            tribute,
+            total,
+        })
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurve<u64>> BondingCurve<u64> for AugmentedBondingCurve<C> {
+    fn calculate_price(&self, supply: u64) -> u64 {
+        if supply < self.hatch_supply {
+            self.hatch_price
+        } else {
+            self.curve.calculate_price(supply)
+        }
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> BondingCurveWithCheckedOperations<u64>
+    for AugmentedBondingCurve<C>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        if supply < self.hatch_supply {
+            Ok(self.hatch_price)
+        } else {
+            self.curve.calculate_price_checked(supply)
+        }
+    }
+
+    /// Prices the hatch tokens of the range at `hatch_price`, and the rest with the curve's
+    /// own `calculate_price_many_checked`.
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        let hatched = self.hatch_supply.clamp(first, last.saturating_add(1)) - first;
+        let hatch = hatched
+            .checked_mul(self.hatch_price)
+            .ok_or(BondingCurveError::Overflow)?;
+        if hatched == amount {
+            return Ok(hatch);
+        }
+        let curve = self.curve.calculate_price_many_checked(
+            first + hatched,
+            amount - hatched,
+            OperationSide::Add,
+        )?;
+        hatch.checked_add(curve).ok_or(BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        AugmentedBondingCurve, BondingCurveError, BondingCurveWithCheckedOperations,
+        LinearBondingCurve, OperationSide, QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_augmented_bonding_curve_hatch() {
+        let curve = QuadraticBondingCurve::new(1_000, 20_000, 3_000_000);
+        let abc = AugmentedBondingCurve::new(curve, 2_000_000, 100, 0, 0);
+        assert!(abc.is_hatching(99));
+        assert!(!abc.is_hatching(100));
+        assert_eq!(abc.calculate_price_checked(99), Ok(2_000_000));
+        assert_eq!(
+            abc.calculate_price_checked(100),
+            curve.calculate_price_checked(100)
+        );
+
+        let across = abc
+            .calculate_price_many_checked(90, 20, OperationSide::Add)
+            .unwrap();
+        let expected = 10 * 2_000_000
+            + curve
+                .calculate_price_many_checked(100, 10, OperationSide::Add)
+                .unwrap();
+        assert_eq!(across, expected);
+        let summed: u64 = (90..110)
+            .map(|x| abc.calculate_price_checked(x).unwrap())
+            .sum();
+        assert_eq!(across, summed);
+        // Removing from 109 prices the same 20 supplies, 90 through 109.
+        assert_eq!(
+            abc.calculate_price_many_checked(109, 20, OperationSide::Remove),
+            Ok(across)
+        );
+        assert_eq!(
+            abc.calculate_price_many_checked(0, 50, OperationSide::Add),
+            Ok(50 * 2_000_000)
+        );
+        assert_eq!(
+            abc.calculate_price_many_checked(150, 10, OperationSide::Add),
+            curve.calculate_price_many_checked(150, 10, OperationSide::Add)
+        );
+    }
+
+    #[test]
+    pub fn test_augmented_bonding_curve_tributes() {
+        let abc =
+            AugmentedBondingCurve::new(LinearBondingCurve::new(100, 1_000), 500, 10, 250, 500);
+        let buy = abc.quote(5, 10, OperationSide::Add).unwrap();
+        assert_eq!(
+            buy.reserve,
+            5 * 500 + (10..15).map(|x| 100 * x + 1_000).sum::<u64>()
+        );
+        assert_eq!(buy.tribute, buy.reserve * 250 / 10_000);
+        assert_eq!(buy.total, buy.reserve + buy.tribute);
+
+        let sell = abc.quote(15, 10, OperationSide::Remove).unwrap();
+        assert_eq!(sell.tribute, sell.reserve / 20);
+        assert_eq!(sell.total, sell.reserve - sell.tribute);
+        assert_eq!(
+            abc.quote(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+
+        let greedy = AugmentedBondingCurve {
+            entry_tribute: 10_001,
+            ..abc
+        };
+        assert_eq!(
+            greedy.quote(0, 1, OperationSide::Add),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert!(greedy.quote(1, 1, OperationSide::Remove).is_ok());
+    }
+}
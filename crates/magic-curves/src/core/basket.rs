@@ -0,0 +1,191 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents one ingredient of a `CurveBasket`.
+///
+/// # Fields
+///
+/// * `curve`: The curve the ingredient is priced with.
+/// * `weight`: The number of ingredient tokens in one unit of the basket.
+/// * `supply`: The current supply of the ingredient.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BasketComponent<C> {
+    pub curve: C,
+    pub weight: u64,
+    pub supply: u64,
+}
+
+/// Represents an item priced as a weighted combination of several curves, such as a crafting
+/// recipe that costs 2 iron and 1 wood.
+///
+/// Each unit of the basket trades `weight` tokens of every ingredient on its own curve, at its
+/// own supply, so the total is the sum of the ingredients' `calculate_price_many_checked`.
+/// Ingredients of different curve types can be mixed with `&dyn` curves.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     BondingCurveWithCheckedOperations, CurveBasket, LinearBondingCurve, OperationSide,
+///     QuadraticBondingCurve,
+/// };
+///
+/// let iron = LinearBondingCurve::new(10, 100);
+/// let wood = QuadraticBondingCurve::new(1, 0, 50);
+/// let mut sword = CurveBasket::<&dyn BondingCurveWithCheckedOperations<u64>>::new()
+///     .with(&iron, 2, 0)
+///     .with(&wood, 1, 0);
+/// // 2 iron at supplies 0 and 1, plus 1 wood at supply 0.
+/// assert_eq!(sword.quote(1, OperationSide::Add), Ok(100 + 110 + 50));
+/// sword.apply(1, OperationSide::Add).unwrap();
+/// assert_eq!(sword.components[0].supply, 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CurveBasket<C> {
+    pub components: Vec<BasketComponent<C>>,
+}
+
+impl<C> Default for CurveBasket<C> {
+    fn default() -> Self {
+        Self {
+            components: Vec::new(),
+        }
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> CurveBasket<C> {
+    /// Creates a new, empty `CurveBasket`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an ingredient to the basket.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve the ingredient is priced with.
+    /// * `weight` - The number of ingredient tokens in one unit of the basket.
+    /// * `supply` - The current supply of the ingredient.
+    ///
+    /// # Returns
+    ///
+    /// The basket, with the ingredient added.
+    pub fn with(mut self, curve: C, weight: u64, supply: u64) -> Self {
+        self.components.push(BasketComponent {
+            curve,
+            weight,
+            supply,
+        });
+        self
+    }
+
+    /// Prices `amount` units of the basket without trading them.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of basket units to trade.
+    /// * `side` - Whether the ingredients are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price of all ingredients, or the first
+    /// `BondingCurveError` an ingredient fails with.
+    pub fn quote(&self, amount: u64, side: OperationSide) -> Result<u64, BondingCurveError> {
+        self.components.iter().try_fold(0u64, |total, component| {
+            let price = component.quote(amount, side)?;
+            total.checked_add(price).ok_or(BondingCurveError::Overflow)
+        })
+    }
+
+    /// Trades `amount` units of the basket, updating every ingredient's supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of basket units to trade.
+    /// * `side` - Whether the ingredients are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, or a `BondingCurveError` if any ingredient
+    /// cannot be traded, in which case no supply is changed.
+    pub fn apply(&mut self, amount: u64, side: OperationSide) -> Result<u64, BondingCurveError> {
+        let price = self.quote(amount, side)?;
+        for component in &mut self.components {
+            // The quote succeeded, so every ingredient's trade size and supply are valid.
+            let tokens = component.weight * amount;
+            component.supply = match side {
+                OperationSide::Add => component.supply + tokens,
+                OperationSide::Remove => component.supply - tokens,
+            };
+        }
+        Ok(price)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> BasketComponent<C> {
+    fn quote(&self, amount: u64, side: OperationSide) -> Result<u64, BondingCurveError> {
+        let tokens = self
+            .weight
+            .checked_mul(amount)
+            .ok_or(BondingCurveError::Overflow)?;
+        if side == OperationSide::Add && self.supply.checked_add(tokens).is_none() {
+            return Err(BondingCurveError::Overflow);
+        }
+        self.curve
+            .calculate_price_many_checked(self.supply, tokens, side)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, CurveBasket, LinearBondingCurve,
+        OperationSide, QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_basket_quote() {
+        let iron = LinearBondingCurve::new(100, 1000);
+        let wood = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut basket = CurveBasket::<&dyn BondingCurveWithCheckedOperations<u64>>::new()
+            .with(&iron, 2, 10)
+            .with(&wood, 1, 40);
+        let expected = iron
+            .calculate_price_many_checked(10, 6, OperationSide::Add)
+            .unwrap()
+            + wood
+                .calculate_price_many_checked(40, 3, OperationSide::Add)
+                .unwrap();
+        assert_eq!(basket.quote(3, OperationSide::Add), Ok(expected));
+        assert_eq!(basket.quote(0, OperationSide::Add), Ok(0));
+        assert_eq!(basket.apply(3, OperationSide::Add), Ok(expected));
+        assert_eq!(basket.components[0].supply, 16);
+        assert_eq!(basket.components[1].supply, 43);
+        let refund = iron
+            .calculate_price_many_checked(16, 6, OperationSide::Remove)
+            .unwrap()
+            + wood
+                .calculate_price_many_checked(43, 3, OperationSide::Remove)
+                .unwrap();
+        assert_eq!(basket.apply(3, OperationSide::Remove), Ok(refund));
+        assert_eq!(basket.components[0].supply, 10);
+    }
+
+    #[test]
+    pub fn test_basket_failures_leave_supplies_unchanged() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let mut basket = CurveBasket::new().with(curve, 1, 20).with(curve, 5, 20);
+        assert_eq!(
+            basket.apply(5, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(basket.components[0].supply, 20);
+        assert_eq!(
+            basket.quote(u64::MAX, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            CurveBasket::<LinearBondingCurve>::new().quote(1, OperationSide::Add),
+            Ok(0)
+        );
+    }
+}
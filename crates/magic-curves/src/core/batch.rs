@@ -0,0 +1,273 @@
+#[cfg(feature = "float")]
+use super::BondingCurveWithCheckedOperations;
+use super::{AnyBondingCurve, BondingCurveError, OperationSide, QuoteRequest};
+
+/// Represents a price computed by an `AnyBondingCurve`, in the price type of its family.
+///
+/// # Variants
+///
+/// * `Integer` - The price of a linear or quadratic curve.
+/// * `Float` - The price of an exponential, logarithmic, or sigmoid curve.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyPrice {
+    Integer(u64),
+    #[cfg(feature = "float")]
+    Float(f64),
+}
+
+#[cfg(feature = "float")]
+impl AnyPrice {
+    /// Returns the price as an `f64`, whatever its type.
+    pub fn as_f64(self) -> f64 {
+        match self {
+            AnyPrice::Integer(price) => price as f64,
+            AnyPrice::Float(price) => price,
+        }
+    }
+}
+
+/// Represents the answer to a quote against an `AnyBondingCurve`.
+///
+/// # Fields
+///
+/// * `price`: The total price of the requested tokens.
+/// * `ending_supply`: The supply after the operation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnyQuote {
+    pub price: AnyPrice,
+    pub ending_supply: u64,
+}
+
+impl AnyBondingCurve {
+    /// Quotes a request against the curve, whatever its family.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The supply, amount, and side to quote.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `AnyQuote`, or the `BondingCurveError` `QuoteRequest::quote`
+    /// fails with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{AnyBondingCurve, AnyPrice, LinearBondingCurve, OperationSide, QuoteRequest};
+    ///
+    /// let curve = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+    /// let quote = curve.quote(&QuoteRequest::new(10, 2, OperationSide::Add)).unwrap();
+    /// assert_eq!(quote.price, AnyPrice::Integer(4100));
+    /// assert_eq!(quote.ending_supply, 12);
+    /// ```
+    pub fn quote(&self, request: &QuoteRequest) -> Result<AnyQuote, BondingCurveError> {
+        let price = match self {
+            AnyBondingCurve::Linear(curve) => AnyPrice::Integer(request.quote(curve)?.price),
+            AnyBondingCurve::Quadratic(curve) => AnyPrice::Integer(request.quote(curve)?.price),
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Exponential(curve) => {
+                AnyPrice::Float(curve.calculate_price_many_checked(
+                    request.starting_supply,
+                    request.amount,
+                    request.side,
+                )?)
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Logarithmic(curve) => {
+                AnyPrice::Float(curve.calculate_price_many_checked(
+                    request.starting_supply,
+                    request.amount,
+                    request.side,
+                )?)
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Sigmoid(curve) => {
+                AnyPrice::Float(curve.calculate_price_many_checked(
+                    request.starting_supply,
+                    request.amount,
+                    request.side,
+                )?)
+            }
+        };
+        Ok(AnyQuote {
+            price,
+            ending_supply: request.ending_supply()?,
+        })
+    }
+}
+
+/// Quotes many trades, each against its own curve, such as an indexer refreshing the
+/// quotes of thousands of pools every slot.
+///
+/// Curves are dispatched by family with a `match`, so there is no boxing or other per-item
+/// setup, and the results are written into a single allocation.
+///
+/// # Arguments
+///
+/// * `trades` - The curve, starting supply, amount, and side of each trade.
+///
+/// # Returns
+///
+/// The result of `AnyBondingCurve::quote` for each trade, in the order of `trades`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     quote_batch, AnyBondingCurve, AnyPrice, BondingCurveError, LinearBondingCurve,
+///     OperationSide, QuadraticBondingCurve,
+/// };
+///
+/// let linear = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+/// let quadratic = AnyBondingCurve::from(QuadraticBondingCurve::new(1, 0, 500));
+/// let quotes = quote_batch(&[
+///     (linear, 10, 2, OperationSide::Add),
+///     (quadratic, 3, 2, OperationSide::Remove),
+///     (linear, 1, 2, OperationSide::Remove),
+/// ]);
+/// assert_eq!(quotes[0].unwrap().price, AnyPrice::Integer(4100));
+/// assert_eq!(quotes[1].unwrap().price, AnyPrice::Integer(1013));
+/// assert_eq!(quotes[2], Err(BondingCurveError::SupplyUnderflow));
+/// ```
+pub fn quote_batch(
+    trades: &[(AnyBondingCurve, u64, u64, OperationSide)],
+) -> Vec<Result<AnyQuote, BondingCurveError>> {
+    let mut quotes = Vec::with_capacity(trades.len());
+    quotes.extend(trades.iter().map(quote_trade));
+    quotes
+}
+
+/// Quotes many trades like `quote_batch`, but writes the results into a buffer instead of
+/// allocating, for contexts that forbid allocation.
+///
+/// # Arguments
+///
+/// * `trades` - The curve, starting supply, amount, and side of each trade.
+/// * `quotes` - The buffer the results are written to, one per trade from the start.
+///
+/// # Returns
+///
+/// `Ok(())`, or `BondingCurveError::InvalidParameter` if `quotes` is shorter than `trades`,
+/// in which case nothing is written.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     quote_batch_into, AnyBondingCurve, AnyPrice, BondingCurveError, LinearBondingCurve,
+///     OperationSide,
+/// };
+///
+/// let linear = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+/// let mut quotes = [Err(BondingCurveError::InvalidParameter); 2];
+/// quote_batch_into(&[(linear, 10, 2, OperationSide::Add)], &mut quotes).unwrap();
+/// assert_eq!(quotes[0].unwrap().price, AnyPrice::Integer(4100));
+/// ```
+pub fn quote_batch_into(
+    trades: &[(AnyBondingCurve, u64, u64, OperationSide)],
+    quotes: &mut [Result<AnyQuote, BondingCurveError>],
+) -> Result<(), BondingCurveError> {
+    if quotes.len() < trades.len() {
+        return Err(BondingCurveError::InvalidParameter);
+    }
+    for (quote, trade) in quotes.iter_mut().zip(trades) {
+        *quote = quote_trade(trade);
+    }
+    Ok(())
+}
+
+/// Quotes one trade of `quote_batch`.
+fn quote_trade(
+    &(curve, starting_supply, amount, side): &(AnyBondingCurve, u64, u64, OperationSide),
+) -> Result<AnyQuote, BondingCurveError> {
+    curve.quote(&QuoteRequest::new(starting_supply, amount, side))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        quote_batch, quote_batch_into, AnyBondingCurve, AnyPrice, AnyQuote, BondingCurveError,
+        OperationSide,
+    };
+
+    #[test]
+    pub fn test_quote_batch() {
+        let linear: AnyBondingCurve = "linear:100,1000".parse().unwrap();
+        let quadratic: AnyBondingCurve = "quadratic:10,20,30".parse().unwrap();
+        let quotes = quote_batch(&[
+            (linear, 0, 10, OperationSide::Add),
+            (quadratic, 3, 3, OperationSide::Remove),
+            (quadratic, 2, 3, OperationSide::Remove),
+            (linear, u64::MAX, 1, OperationSide::Add),
+        ]);
+        assert_eq!(
+            quotes,
+            vec![
+                Ok(AnyQuote {
+                    price: AnyPrice::Integer(14_500),
+                    ending_supply: 10,
+                }),
+                Ok(AnyQuote {
+                    price: AnyPrice::Integer(350),
+                    ending_supply: 0,
+                }),
+                Err(BondingCurveError::SupplyUnderflow),
+                Err(BondingCurveError::Overflow),
+            ]
+        );
+        assert!(quote_batch(&[]).is_empty());
+        assert_eq!(
+            quote_batch_into(&[(linear, 0, 1, OperationSide::Add)], &mut []),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_quote_batch_all_families() {
+        let specs = [
+            "linear:100,1000",
+            "quadratic:10,20,30",
+            "exponential:1,0.01",
+            "logarithmic:1,2",
+            "sigmoid:100,0.01,500",
+        ];
+        let mut trades = Vec::new();
+        for spec in specs {
+            let curve: AnyBondingCurve = spec.parse().unwrap();
+            for (supply, amount, side) in [
+                (0, 10, OperationSide::Add),
+                (50, 7, OperationSide::Remove),
+                (5, 6, OperationSide::Remove),
+                (u64::MAX, 1, OperationSide::Add),
+            ] {
+                trades.push((curve, supply, amount, side));
+            }
+        }
+        let quotes = quote_batch(&trades);
+        assert_eq!(quotes.len(), trades.len());
+        for (quote, &(curve, supply, amount, side)) in quotes.iter().zip(&trades) {
+            assert_eq!(
+                *quote,
+                curve.quote(&crate::QuoteRequest::new(supply, amount, side))
+            );
+            if let Ok(quote) = quote {
+                assert_eq!(
+                    Ok(quote.price.as_f64()),
+                    curve.calculate_price_many_as_f64(supply, amount, side)
+                );
+            }
+        }
+        for family in 0..specs.len() {
+            assert_eq!(quotes[family * 4 + 1].unwrap().ending_supply, 43);
+            assert_eq!(
+                quotes[family * 4 + 2],
+                Err(BondingCurveError::SupplyUnderflow)
+            );
+            assert!(quotes[family * 4 + 3].is_err());
+        }
+        assert!(matches!(quotes[8].unwrap().price, AnyPrice::Float(_)));
+    }
+}
@@ -0,0 +1,299 @@
+use super::{supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+#[cfg(not(feature = "panic-free"))]
+use super::{BondingCurve, TimeBondingCurve};
+
+/// Represents how much of `BlendedCurve::b` is mixed into a blend.
+///
+/// The weight is a fraction between zero, pricing with `a` alone, and one, pricing with `b`
+/// alone. Transition weights ramp linearly from zero at `start` to one at `end`, and step
+/// straight to one at `start` when `end` is not above it.
+///
+/// # Variants
+///
+/// * `Fixed` - A constant weight of `numerator / denominator`.
+/// * `BySupply` - A weight that ramps with the supply being priced.
+/// * `ByTime` - A weight that ramps with the elapsed time, as passed to `TimeBondingCurve`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendWeight {
+    Fixed { numerator: u64, denominator: u64 },
+    BySupply { start: u64, end: u64 },
+    ByTime { start: u64, end: u64 },
+}
+
+impl BlendWeight {
+    /// Returns the weight at the given supply and time as a `(numerator, denominator)` pair,
+    /// or `BondingCurveError::InvalidParameter` if a fixed weight is not between zero and one.
+    fn fraction(&self, supply: u64, elapsed: u64) -> Result<(u64, u64), BondingCurveError> {
+        let (position, start, end) = match *self {
+            BlendWeight::Fixed {
+                numerator,
+                denominator,
+            } => {
+                if denominator == 0 || numerator > denominator {
+                    return Err(BondingCurveError::InvalidParameter);
+                }
+                return Ok((numerator, denominator));
+            }
+            BlendWeight::BySupply { start, end } => (supply, start, end),
+            BlendWeight::ByTime { start, end } => (elapsed, start, end),
+        };
+        if position < start {
+            Ok((0, 1))
+        } else if position >= end {
+            Ok((1, 1))
+        } else {
+            Ok((position - start, end - start))
+        }
+    }
+}
+
+/// Represents a curve priced as a convex combination of two curves, for migrating a market
+/// from an old curve to a new one without a price jump.
+///
+/// ```ignore
+/// f(x, t) = a(x) * (1 - w(x, t)) + b(x) * w(x, t)
+/// ```
+///
+/// The blend lies between the two prices, so it never overflows where both curves fit, and a
+/// curve with no weight at a supply is not priced there at all. It is rounded down, and the
+/// total of a trade sums the blend at every priced supply, which takes time proportional to
+/// the amount. As a `BondingCurve`, a `ByTime` weight is read at
+/// an elapsed time of zero; use `TimeBondingCurve` to price it at other times.
+///
+/// # Fields
+///
+/// * `a`: The curve being migrated from.
+/// * `b`: The curve being migrated to.
+/// * `weight`: How much of `b` is mixed in.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BlendWeight, BlendedCurve, LinearBondingCurve, TimeBondingCurve};
+///
+/// let old = LinearBondingCurve::new(0, 1_000);
+/// let new = LinearBondingCurve::new(0, 2_000);
+/// let blend = BlendedCurve::new(old, new, BlendWeight::ByTime { start: 100, end: 200 });
+/// assert_eq!(blend.price_at(0, 0), 1_000);
+/// assert_eq!(blend.price_at(0, 150), 1_500);
+/// assert_eq!(blend.price_at(0, 300), 2_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlendedCurve<A, B> {
+    pub a: A,
+    pub b: B,
+    pub weight: BlendWeight,
+}
+
+impl<A, B> BlendedCurve<A, B> {
+    /// Creates a new `BlendedCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The curve being migrated from.
+    /// * `b` - The curve being migrated to.
+    /// * `weight` - How much of `b` is mixed in.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `BlendedCurve`.
+    pub fn new(a: A, b: B, weight: BlendWeight) -> Self {
+        Self { a, b, weight }
+    }
+}
+
+impl<A: BondingCurveWithCheckedOperations<u64>, B: BondingCurveWithCheckedOperations<u64>>
+    BlendedCurve<A, B>
+{
+    /// Calculates the blended price at the given supply and time, with error checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current total supply of tokens.
+    /// * `elapsed` - The time elapsed, read by a `ByTime` weight.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the blended price, `BondingCurveError::InvalidParameter` if the
+    /// weight is not between zero and one, or the first `BondingCurveError` a curve fails with.
+    pub fn price_at_checked(&self, supply: u64, elapsed: u64) -> Result<u64, BondingCurveError> {
+        let (numerator, denominator) = self.weight.fraction(supply, elapsed)?;
+        let a = if numerator == denominator {
+            0
+        } else {
+            self.a.calculate_price_checked(supply)?
+        };
+        let b = if numerator == 0 {
+            0
+        } else {
+            self.b.calculate_price_checked(supply)?
+        };
+        Ok(mix(a, b, numerator, denominator))
+    }
+
+    /// Calculates the blended price of a trade at a single point in time, with error checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    /// * `elapsed` - The time elapsed, read by a `ByTime` weight.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total blended price, or a `BondingCurveError` if the supply
+    /// range, the weight, a curve, or the total is invalid.
+    pub fn price_many_at_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        elapsed: u64,
+    ) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        (first..=last).try_fold(0u64, |total, supply| {
+            total
+                .checked_add(self.price_at_checked(supply, elapsed)?)
+                .ok_or(BondingCurveError::Overflow)
+        })
+    }
+}
+
+/// Mixes `numerator / denominator` of `b` into `a`, rounding down.
+fn mix(a: u64, b: u64, numerator: u64, denominator: u64) -> u64 {
+    let mixed = a as u128 * (denominator - numerator) as u128 + b as u128 * numerator as u128;
+    (mixed / denominator as u128) as u64
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<A: BondingCurve<u64>, B: BondingCurve<u64>> TimeBondingCurve<u64> for BlendedCurve<A, B> {
+    /// Calculates the blended price. An invalid fixed weight is clamped to one.
+    fn price_at(&self, supply: u64, elapsed: u64) -> u64 {
+        let (numerator, denominator) = self.weight.fraction(supply, elapsed).unwrap_or((1, 1));
+        let a = if numerator == denominator {
+            0
+        } else {
+            self.a.calculate_price(supply)
+        };
+        let b = if numerator == 0 {
+            0
+        } else {
+            self.b.calculate_price(supply)
+        };
+        mix(a, b, numerator, denominator)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<A: BondingCurve<u64>, B: BondingCurve<u64>> BondingCurve<u64> for BlendedCurve<A, B> {
+    fn calculate_price(&self, supply: u64) -> u64 {
+        self.price_at(supply, 0)
+    }
+}
+
+impl<A: BondingCurveWithCheckedOperations<u64>, B: BondingCurveWithCheckedOperations<u64>>
+    BondingCurveWithCheckedOperations<u64> for BlendedCurve<A, B>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.price_at_checked(supply, 0)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        self.price_many_at_checked(starting_supply, amount, side, 0)
+    }
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        BlendWeight, BlendedCurve, BondingCurve, BondingCurveError,
+        BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
+        QuadraticBondingCurve, TimeBondingCurve,
+    };
+
+    #[test]
+    pub fn test_blended_curve_by_supply() {
+        let old = LinearBondingCurve::new(100, 1_000);
+        let new = QuadraticBondingCurve::new(1, 50, 2_000);
+        let blend = BlendedCurve::new(old, new, BlendWeight::BySupply { start: 10, end: 20 });
+        assert_eq!(blend.calculate_price(5), old.calculate_price(5));
+        assert_eq!(blend.calculate_price(20), new.calculate_price(20));
+        assert_eq!(
+            blend.calculate_price(15),
+            (old.calculate_price(15) + new.calculate_price(15)) / 2
+        );
+        let expected: u64 = (8..14).map(|x| blend.calculate_price(x)).sum();
+        assert_eq!(
+            blend.calculate_price_many(8, 6, OperationSide::Add),
+            expected
+        );
+        assert_eq!(
+            blend.calculate_price_many_checked(14, 6, OperationSide::Remove),
+            Ok(blend.calculate_price_many(14, 6, OperationSide::Remove))
+        );
+        assert_eq!(
+            blend.calculate_price_many_checked(3, 4, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[test]
+    pub fn test_blended_curve_weights() {
+        let old = LinearBondingCurve::new(0, 999);
+        let new = LinearBondingCurve::new(0, 1);
+        let fixed = |numerator, denominator| {
+            BlendedCurve::new(
+                old,
+                new,
+                BlendWeight::Fixed {
+                    numerator,
+                    denominator,
+                },
+            )
+        };
+        assert_eq!(fixed(1, 3).calculate_price_checked(0), Ok(666));
+        assert_eq!(fixed(3, 3).calculate_price_checked(0), Ok(1));
+        assert_eq!(
+            fixed(4, 3).calculate_price_checked(0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            fixed(0, 0).calculate_price_checked(0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(fixed(4, 3).calculate_price(0), 1);
+
+        let step = BlendedCurve::new(old, new, BlendWeight::ByTime { start: 50, end: 50 });
+        assert_eq!(step.price_at(0, 49), 999);
+        assert_eq!(step.price_at(0, 50), 1);
+        assert_eq!(step.calculate_price(0), 999);
+        assert_eq!(
+            step.price_many_at_checked(0, 3, OperationSide::Add, 50),
+            Ok(3)
+        );
+    }
+
+    #[test]
+    pub fn test_blended_curve_ignores_unused_curve() {
+        // The new curve overflows near `u64::MAX`, but is not mixed in before the migration.
+        let old = LinearBondingCurve::new(0, 7);
+        let new = LinearBondingCurve::new(2, 0);
+        let blend = BlendedCurve::new(old, new, BlendWeight::ByTime { start: 10, end: 20 });
+        assert_eq!(blend.price_at(u64::MAX, 0), 7);
+        assert_eq!(blend.price_at_checked(u64::MAX, 0), Ok(7));
+        assert_eq!(
+            blend.price_at_checked(u64::MAX, 15),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use super::{BondingCurve, BondingCurveError, OperationSide};
+
+/// Represents a curve whose prices are answered from a precomputed lookup table.
+///
+/// Prices are sampled every `stride` supplies up to `max_supply` and `calculate_price`
+/// interpolates linearly between the two nearest samples, so hot paths avoid calling `exp`
+/// or `ln` per request. Supplies on a sample point, and supplies past the table, are priced
+/// exactly by the wrapped curve. `calculate_price_many` always uses the wrapped curve.
+///
+/// # Error
+///
+/// For a curve `f` with a continuous second derivative, the interpolation error is at most:
+///
+/// ```ignore
+/// |error| <= stride^2 / 8 * max |f''(x)|
+/// ```
+///
+/// where the maximum is taken over the sampled range. Halving the stride quarters the error.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, CachedCurve, ExponentialBondingCurve};
+///
+/// let curve = ExponentialBondingCurve::new(0.01, 0.02);
+/// let cached = CachedCurve::new(curve, 1_000, 10).unwrap();
+/// let error = (cached.calculate_price(105) - curve.calculate_price(105)).abs();
+/// assert!(error < 1e-3);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedCurve<C> {
+    curve: C,
+    stride: u64,
+    prices: Vec<f64>,
+}
+
+impl<C: BondingCurve<f64>> CachedCurve<C> {
+    /// Creates a new `CachedCurve`, sampling `curve` every `stride` supplies.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve to cache.
+    /// * `max_supply` - The highest supply the table must cover.
+    /// * `stride` - The distance between samples.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new `CachedCurve`, or `BondingCurveError::InvalidParameter`
+    /// if `stride` is zero.
+    pub fn new(curve: C, max_supply: u64, stride: u64) -> Result<Self, BondingCurveError> {
+        if stride == 0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let samples = max_supply.div_ceil(stride);
+        let prices = (0..=samples)
+            .map(|index| curve.calculate_price(index.saturating_mul(stride)))
+            .collect();
+        Ok(Self {
+            curve,
+            stride,
+            prices,
+        })
+    }
+
+    /// Returns the wrapped curve.
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the distance between samples.
+    pub fn stride(&self) -> u64 {
+        self.stride
+    }
+}
+
+impl<C: BondingCurve<f64>> BondingCurve<f64> for CachedCurve<C> {
+    /// Calculates the price based on the supply, interpolating between cached samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// The price of the token based on the supply.
+    fn calculate_price(&self, supply: u64) -> f64 {
+        let index = (supply / self.stride) as usize;
+        let offset = supply % self.stride;
+        match (self.prices.get(index), self.prices.get(index + 1)) {
+            (Some(&price), _) if offset == 0 => price,
+            (Some(&low), Some(&high)) => low + (high - low) * (offset as f64 / self.stride as f64),
+            _ => self.curve.calculate_price(supply),
+        }
+    }
+
+    /// Calculates the price for a given amount of tokens with the wrapped curve.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price for the given amount of tokens.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
+        self.curve
+            .calculate_price_many(starting_supply, amount, side)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurve, BondingCurveError, CachedCurve, ExponentialBondingCurve, OperationSide,
+        SigmoidBondingCurve,
+    };
+
+    #[test]
+    pub fn test_cached_curve_error_bound() {
+        let curve = ExponentialBondingCurve::new(0.01, 0.02);
+        let stride = 16;
+        let cached = CachedCurve::new(curve, 1_000, stride).unwrap();
+        // f''(x) = base * growth^2 * e^(growth * x), largest at the end of the table.
+        let max_second_derivative = 0.01 * 0.02f64.powi(2) * (0.02 * 1_008.0f64).exp();
+        let bound = (stride * stride) as f64 / 8.0 * max_second_derivative;
+        for supply in 0..=1_000 {
+            let error = (cached.calculate_price(supply) - curve.calculate_price(supply)).abs();
+            assert!(error <= bound, "supply {}: {} > {}", supply, error, bound);
+        }
+        assert_eq!(cached.calculate_price(160), curve.calculate_price(160));
+    }
+
+    #[test]
+    pub fn test_cached_curve_delegates() {
+        let curve = SigmoidBondingCurve::new(1000.0, 0.01, 500);
+        let cached = CachedCurve::new(curve, 100, 7).unwrap();
+        assert_eq!(cached.calculate_price(5_000), curve.calculate_price(5_000));
+        assert_eq!(
+            cached.calculate_price_many(10, 5, OperationSide::Add),
+            curve.calculate_price_many(10, 5, OperationSide::Add)
+        );
+        assert_eq!(cached.stride(), 7);
+        assert_eq!(
+            CachedCurve::new(curve, 100, 0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(cached.curve(), &curve);
+    }
+}
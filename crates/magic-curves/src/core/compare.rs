@@ -0,0 +1,364 @@
+use super::{
+    AnyBondingCurve, AsF64, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide,
+    Tolerance,
+};
+
+/// Represents one curve's row of the comparison returned by `compare_acquisition_cost`.
+///
+/// # Fields
+///
+/// * `cost`: The total price of the first `tokens` tokens.
+/// * `average_price`: `cost` divided by the number of tokens.
+/// * `relative_cost`: `cost` divided by the cheapest curve's cost, so the cheapest curve is
+///   at `1.0` and a curve at `2.0` gives early supporters half as many tokens per budget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AcquisitionCost {
+    pub cost: f64,
+    pub average_price: f64,
+    pub relative_cost: f64,
+}
+
+/// Compares what the first `tokens` tokens cost under several curves, to pick between curve
+/// shapes for the same early-supporter budget.
+///
+/// # Arguments
+///
+/// * `curves` - The curves to compare.
+/// * `tokens` - The number of tokens bought from a supply of zero.
+///
+/// # Returns
+///
+/// A `Result` containing one `AcquisitionCost` per curve, in the order of `curves`, or the
+/// first `BondingCurveError` a curve fails with. When the cheapest cost is zero, free curves
+/// have a `relative_cost` of `1.0` and the others of infinity.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     compare_acquisition_cost, BondingCurveWithCheckedOperations, LinearBondingCurve,
+///     QuadraticBondingCurve,
+/// };
+///
+/// let linear = LinearBondingCurve::new(10, 1_000);
+/// let quadratic = QuadraticBondingCurve::new(1, 0, 1_000);
+/// let curves: [&dyn BondingCurveWithCheckedOperations<u64>; 2] = [&linear, &quadratic];
+/// let rows = compare_acquisition_cost(&curves, 100).unwrap();
+/// assert_eq!(rows[0].cost, 149_500.0);
+/// assert_eq!(rows[1].cost, 428_350.0);
+/// assert_eq!(rows[0].relative_cost, 1.0);
+/// assert!(rows[1].relative_cost > 2.8);
+/// ```
+pub fn compare_acquisition_cost<T: AsF64>(
+    curves: &[&dyn BondingCurveWithCheckedOperations<T>],
+    tokens: u64,
+) -> Result<Vec<AcquisitionCost>, BondingCurveError> {
+    let costs = curves
+        .iter()
+        .map(|curve| {
+            curve
+                .calculate_price_many_checked(0, tokens, OperationSide::Add)
+                .map(AsF64::as_f64)
+        })
+        .collect::<Result<Vec<f64>, _>>()?;
+    let cheapest = costs.iter().copied().fold(f64::INFINITY, f64::min);
+    Ok(costs
+        .into_iter()
+        .map(|cost| AcquisitionCost {
+            cost,
+            average_price: if tokens == 0 {
+                0.0
+            } else {
+                cost / tokens as f64
+            },
+            relative_cost: if cost == cheapest {
+                1.0
+            } else {
+                cost / cheapest
+            },
+        })
+        .collect())
+}
+
+/// Represents one sampled supply of a `CurveComparison`.
+///
+/// # Fields
+///
+/// * `supply`: The sampled supply.
+/// * `a`: The price of the first curve at `supply`.
+/// * `b`: The price of the second curve at `supply`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComparisonRow {
+    pub supply: u64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// Represents how far apart two curves price over a supply range, as returned by
+/// `compare_curves`.
+///
+/// # Fields
+///
+/// * `rows`: The prices of both curves at each sampled supply, in increasing order.
+/// * `intersections`: The supplies where the curves cross: the first supply at which the
+///   sign of `a - b` differs from the supply before it, or at which both prices are equal.
+/// * `max_divergence`: The largest absolute price difference among the rows.
+/// * `max_divergence_supply`: The supply of the row with the largest difference.
+/// * `mean_divergence`: The mean absolute price difference of the rows.
+/// * `max_relative_divergence`: The largest difference relative to the higher price of its
+///   row, from `0.0` (equal) to `1.0` (one curve is free).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveComparison {
+    pub rows: Vec<ComparisonRow>,
+    pub intersections: Vec<u64>,
+    pub max_divergence: f64,
+    pub max_divergence_supply: u64,
+    pub mean_divergence: f64,
+    pub max_relative_divergence: f64,
+}
+
+impl CurveComparison {
+    /// Returns whether both curves price every sampled supply equally within `tolerance`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{compare_curves, AnyBondingCurve, Tolerance};
+    ///
+    /// let a: AnyBondingCurve = "linear:10,1000".parse().unwrap();
+    /// let b: AnyBondingCurve = "linear:10,1001".parse().unwrap();
+    /// let comparison = compare_curves(&a, &b, 0, 100, 11).unwrap();
+    /// assert!(comparison.is_within(Tolerance::absolute(1.0)));
+    /// assert!(!comparison.is_within(Tolerance::relative(1e-4)));
+    /// ```
+    pub fn is_within(&self, tolerance: Tolerance) -> bool {
+        self.rows
+            .iter()
+            .all(|row| tolerance.approx_eq(row.a, row.b))
+    }
+}
+
+/// Compares the prices of two curves of any family over `start..=end`, to see where one is
+/// cheaper than the other and by how much.
+///
+/// The range is sampled at `samples` evenly spaced supplies, including both ends. Crossings
+/// are found between consecutive samples and then located exactly by bisection, so two
+/// crossings closer together than the sampling step can be missed.
+///
+/// # Arguments
+///
+/// * `a` - The first curve.
+/// * `b` - The second curve.
+/// * `start` - The first supply to compare.
+/// * `end` - The last supply to compare.
+/// * `samples` - The number of supplies to sample, at least two.
+///
+/// # Returns
+///
+/// A `Result` containing the `CurveComparison`, `BondingCurveError::InvalidParameter` if
+/// `start` is after `end` or `samples` is below two, or the first error a curve fails with.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{compare_curves, AnyBondingCurve};
+///
+/// let a: AnyBondingCurve = "linear:10,1000".parse().unwrap();
+/// let b: AnyBondingCurve = "linear:20,500".parse().unwrap();
+/// let comparison = compare_curves(&a, &b, 0, 100, 11).unwrap();
+/// assert_eq!(comparison.intersections, vec![50]);
+/// assert_eq!(comparison.max_divergence, 500.0);
+/// assert_eq!(comparison.rows.len(), 11);
+/// ```
+pub fn compare_curves(
+    a: &AnyBondingCurve,
+    b: &AnyBondingCurve,
+    start: u64,
+    end: u64,
+    samples: usize,
+) -> Result<CurveComparison, BondingCurveError> {
+    if start > end || samples < 2 {
+        return Err(BondingCurveError::InvalidParameter);
+    }
+    let row = |supply| -> Result<ComparisonRow, BondingCurveError> {
+        Ok(ComparisonRow {
+            supply,
+            a: a.calculate_price_as_f64(supply)?,
+            b: b.calculate_price_as_f64(supply)?,
+        })
+    };
+    let span = (end - start) as u128;
+    let mut rows: Vec<ComparisonRow> = Vec::with_capacity(samples);
+    for i in 0..samples {
+        let supply = start + (span * i as u128 / (samples - 1) as u128) as u64;
+        if rows.last().map(|last| last.supply) != Some(supply) {
+            rows.push(row(supply)?);
+        }
+    }
+
+    let mut intersections = Vec::new();
+    for (index, current) in rows.iter().enumerate() {
+        let side = sign(current);
+        if side == 0.0 {
+            intersections.push(current.supply);
+            continue;
+        }
+        let Some(previous) = index.checked_sub(1).map(|i| &rows[i]) else {
+            continue;
+        };
+        let before = sign(previous);
+        if before == 0.0 || before == side {
+            continue;
+        }
+        // `low` keeps the sign of the previous sample and `high` does not.
+        let (mut low, mut high) = (previous.supply, current.supply);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if sign(&row(mid)?) == before {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        intersections.push(high);
+    }
+
+    let differences = rows.iter().map(|row| (row.a - row.b).abs());
+    let (max_index, max_divergence) =
+        differences
+            .clone()
+            .enumerate()
+            .fold((0, 0.0), |max, (index, difference)| {
+                if difference > max.1 {
+                    (index, difference)
+                } else {
+                    max
+                }
+            });
+    let mean_divergence = differences.sum::<f64>() / rows.len() as f64;
+    let max_relative_divergence = rows
+        .iter()
+        .map(|row| {
+            let higher = row.a.abs().max(row.b.abs());
+            if higher == 0.0 {
+                0.0
+            } else {
+                (row.a - row.b).abs() / higher
+            }
+        })
+        .fold(0.0, f64::max);
+    Ok(CurveComparison {
+        max_divergence_supply: rows[max_index].supply,
+        rows,
+        intersections,
+        max_divergence,
+        mean_divergence,
+        max_relative_divergence,
+    })
+}
+
+/// Returns the sign of `a - b` as `-1.0`, `0.0`, or `1.0`.
+fn sign(row: &ComparisonRow) -> f64 {
+    let difference = row.a - row.b;
+    if difference == 0.0 {
+        0.0
+    } else {
+        difference.signum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compare_acquisition_cost, compare_curves, AnyBondingCurve, BondingCurveError,
+        BondingCurveWithCheckedOperations, ExponentialBondingCurve, LinearBondingCurve,
+        LogarithmicBondingCurve, OperationSide, Tolerance,
+    };
+
+    #[test]
+    pub fn test_compare_acquisition_cost() {
+        let exponential = ExponentialBondingCurve::new(1.0, 0.01);
+        let logarithmic = LogarithmicBondingCurve::new(1.0, 2.0);
+        let curves: [&dyn BondingCurveWithCheckedOperations<f64>; 2] = [&exponential, &logarithmic];
+        let rows = compare_acquisition_cost(&curves, 1_000).unwrap();
+        assert_eq!(rows.len(), 2);
+        for (row, curve) in rows.iter().zip(curves) {
+            let cost = curve
+                .calculate_price_many_checked(0, 1_000, OperationSide::Add)
+                .unwrap();
+            assert_eq!(row.cost, cost);
+            assert_eq!(row.average_price, cost / 1_000.0);
+        }
+        let cheapest = rows[0].cost.min(rows[1].cost);
+        assert!(rows.iter().any(|row| row.relative_cost == 1.0));
+        assert!(rows
+            .iter()
+            .all(|row| row.relative_cost == row.cost / cheapest));
+    }
+
+    #[test]
+    pub fn test_compare_acquisition_cost_edges() {
+        let free = LinearBondingCurve::new(0, 0);
+        let paid = LinearBondingCurve::new(0, 5);
+        let curves: [&dyn BondingCurveWithCheckedOperations<u64>; 2] = [&free, &paid];
+        let rows = compare_acquisition_cost(&curves, 10).unwrap();
+        assert_eq!(rows[0].relative_cost, 1.0);
+        assert_eq!(rows[1].relative_cost, f64::INFINITY);
+        let rows = compare_acquisition_cost(&curves, 0).unwrap();
+        assert!(rows.iter().all(|row| row.average_price == 0.0));
+        assert!(compare_acquisition_cost::<u64>(&[], 10).unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn test_compare_curves() {
+        let a: AnyBondingCurve = "exponential:1,0.001".parse().unwrap();
+        let b: AnyBondingCurve = "logarithmic:1,1".parse().unwrap();
+        let comparison = compare_curves(&a, &b, 1, 10_000, 7).unwrap();
+        assert_eq!(comparison.rows.first().unwrap().supply, 1);
+        assert_eq!(comparison.rows.last().unwrap().supply, 10_000);
+        for &crossing in &comparison.intersections {
+            let before = a.calculate_price_as_f64(crossing - 1).unwrap()
+                - b.calculate_price_as_f64(crossing - 1).unwrap();
+            let after = a.calculate_price_as_f64(crossing).unwrap()
+                - b.calculate_price_as_f64(crossing).unwrap();
+            assert!(after == 0.0 || before.signum() != after.signum());
+        }
+        // The logarithmic overtakes the exponential at once, and falls behind it again.
+        assert_eq!(comparison.intersections, vec![2, 2_161]);
+        let max = comparison
+            .rows
+            .iter()
+            .map(|row| (row.a - row.b).abs())
+            .fold(0.0, f64::max);
+        assert_eq!(comparison.max_divergence, max);
+        assert!(comparison.max_relative_divergence <= 1.0);
+    }
+
+    #[test]
+    pub fn test_compare_curves_edges() {
+        let a: AnyBondingCurve = "linear:0,5".parse().unwrap();
+        let b: AnyBondingCurve = "quadratic:0,0,5".parse().unwrap();
+        let equal = compare_curves(&a, &b, 0, 3, 10).unwrap();
+        assert_eq!(equal.rows.len(), 4);
+        assert_eq!(equal.intersections, vec![0, 1, 2, 3]);
+        assert_eq!((equal.max_divergence, equal.mean_divergence), (0.0, 0.0));
+        assert_eq!(equal.max_divergence_supply, 0);
+        assert!(equal.is_within(Tolerance::EXACT));
+        assert_eq!(
+            compare_curves(&a, &b, 3, 2, 10),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            compare_curves(&a, &b, 0, 2, 1),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        let huge: AnyBondingCurve = "linear:18446744073709551615,0".parse().unwrap();
+        assert_eq!(
+            compare_curves(&a, &huge, 0, 2, 2),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
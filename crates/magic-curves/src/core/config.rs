@@ -0,0 +1,335 @@
+use super::{LinearBondingCurve, QuadraticBondingCurve};
+
+#[cfg(feature = "float")]
+use super::{
+    BondingCurveError, ExponentialBondingCurve, LogarithmicBondingCurve, PowerBondingCurve,
+    SigmoidBondingCurve,
+};
+
+/// Represents the parameters of a `LinearBondingCurve`.
+///
+/// The config structs are the shared, serializable description of each curve, consumed by
+/// the native constructors and by the WASM and JSON layers alike. With the `serde` feature
+/// they reject unknown fields, so a misnamed parameter fails loudly instead of defaulting.
+///
+/// # Fields
+///
+/// * `linear`: The linear coefficient that determines the rate of price increase.
+/// * `base`: The base price, which is the minimum price for the first token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct LinearConfig {
+    pub linear: u64,
+    pub base: u64,
+}
+
+/// Represents the parameters of a `QuadraticBondingCurve`. See `LinearConfig`.
+///
+/// # Fields
+///
+/// * `quadratic`: The quadratic coefficient that determines the rate of price increase.
+/// * `linear`: The linear coefficient that determines the rate of price increase.
+/// * `base`: The base price, which is the minimum price for the first token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct QuadraticConfig {
+    pub quadratic: u64,
+    pub linear: u64,
+    pub base: u64,
+}
+
+/// Represents the parameters of an `ExponentialBondingCurve`. See `LinearConfig`.
+///
+/// # Fields
+///
+/// * `base`: The base price, which is the initial price for the first token.
+/// * `growth`: The growth rate that determines how quickly the price increases.
+#[cfg(feature = "float")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct ExponentialConfig {
+    pub base: f64,
+    pub growth: f64,
+}
+
+/// Represents the parameters of a `LogarithmicBondingCurve`. See `LinearConfig`.
+///
+/// # Fields
+///
+/// * `base`: The base price, which is the initial price for the first token.
+/// * `growth`: The growth rate that determines how quickly the price increases.
+#[cfg(feature = "float")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct LogarithmicConfig {
+    pub base: f64,
+    pub growth: f64,
+}
+
+/// Represents the parameters of a `PowerBondingCurve`. See `LinearConfig`.
+///
+/// # Fields
+///
+/// * `coefficient`: The coefficient that scales every price.
+/// * `exponent`: The exponent of the supply, zero or more.
+#[cfg(feature = "float")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct PowerConfig {
+    pub coefficient: f64,
+    pub exponent: f64,
+}
+
+/// Represents the parameters of a `SigmoidBondingCurve`. See `LinearConfig`.
+///
+/// # Fields
+///
+/// * `max_price`: The maximum price that the curve approaches asymptotically.
+/// * `growth`: The growth rate that determines how quickly the price increases.
+/// * `mid_supply`: The supply at which the price is half of the maximum price.
+#[cfg(feature = "float")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct SigmoidConfig {
+    pub max_price: f64,
+    pub growth: f64,
+    pub mid_supply: u64,
+}
+
+impl From<LinearConfig> for LinearBondingCurve {
+    /// Builds the curve. Every `u64` coefficient is valid, so this cannot fail.
+    fn from(config: LinearConfig) -> Self {
+        LinearBondingCurve::new(config.linear, config.base)
+    }
+}
+
+impl From<LinearBondingCurve> for LinearConfig {
+    fn from(curve: LinearBondingCurve) -> Self {
+        LinearConfig {
+            linear: curve.linear,
+            base: curve.base,
+        }
+    }
+}
+
+impl From<QuadraticConfig> for QuadraticBondingCurve {
+    /// Builds the curve. Every `u64` coefficient is valid, so this cannot fail.
+    fn from(config: QuadraticConfig) -> Self {
+        QuadraticBondingCurve::new(config.quadratic, config.linear, config.base)
+    }
+}
+
+impl From<QuadraticBondingCurve> for QuadraticConfig {
+    fn from(curve: QuadraticBondingCurve) -> Self {
+        QuadraticConfig {
+            quadratic: curve.quadratic,
+            linear: curve.linear,
+            base: curve.base,
+        }
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<ExponentialConfig> for ExponentialBondingCurve {
+    type Error = BondingCurveError;
+
+    /// Builds the curve with `ExponentialBondingCurve::try_new`.
+    fn try_from(config: ExponentialConfig) -> Result<Self, Self::Error> {
+        ExponentialBondingCurve::try_new(config.base, config.growth)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<ExponentialBondingCurve> for ExponentialConfig {
+    fn from(curve: ExponentialBondingCurve) -> Self {
+        ExponentialConfig {
+            base: curve.base,
+            growth: curve.growth,
+        }
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<LogarithmicConfig> for LogarithmicBondingCurve {
+    type Error = BondingCurveError;
+
+    /// Builds the curve with `LogarithmicBondingCurve::try_new`.
+    fn try_from(config: LogarithmicConfig) -> Result<Self, Self::Error> {
+        LogarithmicBondingCurve::try_new(config.base, config.growth)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<LogarithmicBondingCurve> for LogarithmicConfig {
+    fn from(curve: LogarithmicBondingCurve) -> Self {
+        LogarithmicConfig {
+            base: curve.base,
+            growth: curve.growth,
+        }
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<PowerConfig> for PowerBondingCurve {
+    type Error = BondingCurveError;
+
+    /// Builds the curve with `PowerBondingCurve::try_new`.
+    fn try_from(config: PowerConfig) -> Result<Self, Self::Error> {
+        PowerBondingCurve::try_new(config.coefficient, config.exponent)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<PowerBondingCurve> for PowerConfig {
+    fn from(curve: PowerBondingCurve) -> Self {
+        PowerConfig {
+            coefficient: curve.coefficient,
+            exponent: curve.exponent,
+        }
+    }
+}
+
+#[cfg(feature = "float")]
+impl TryFrom<SigmoidConfig> for SigmoidBondingCurve {
+    type Error = BondingCurveError;
+
+    /// Builds the curve with `SigmoidBondingCurve::try_new`.
+    fn try_from(config: SigmoidConfig) -> Result<Self, Self::Error> {
+        SigmoidBondingCurve::try_new(config.max_price, config.growth, config.mid_supply)
+    }
+}
+
+#[cfg(feature = "float")]
+impl From<SigmoidBondingCurve> for SigmoidConfig {
+    fn from(curve: SigmoidBondingCurve) -> Self {
+        SigmoidConfig {
+            max_price: curve.max_price,
+            growth: curve.growth,
+            mid_supply: curve.mid_supply,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{LinearBondingCurve, LinearConfig, QuadraticBondingCurve, QuadraticConfig};
+
+    #[test]
+    pub fn test_integer_configs() {
+        let curve = LinearBondingCurve::from(LinearConfig {
+            linear: 100,
+            base: 1000,
+        });
+        assert_eq!(curve, LinearBondingCurve::new(100, 1000));
+        assert_eq!(LinearConfig::from(curve).linear, 100);
+        let quadratic = QuadraticBondingCurve::new(10, 20, 30);
+        assert_eq!(
+            QuadraticBondingCurve::from(QuadraticConfig::from(quadratic)),
+            quadratic
+        );
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_float_configs_are_validated() {
+        use crate::{
+            BondingCurveError, ExponentialConfig, LogarithmicConfig, PowerConfig,
+            SigmoidBondingCurve, SigmoidConfig,
+        };
+
+        let config = SigmoidConfig {
+            max_price: 1000.0,
+            growth: 0.01,
+            mid_supply: 500,
+        };
+        assert_eq!(
+            SigmoidBondingCurve::try_from(config),
+            Ok(SigmoidBondingCurve::new(1000.0, 0.01, 500))
+        );
+        assert_eq!(
+            SigmoidBondingCurve::try_from(SigmoidConfig {
+                growth: 0.0,
+                ..config
+            }),
+            Err(BondingCurveError::DivisionByZero)
+        );
+        assert_eq!(
+            crate::ExponentialBondingCurve::try_from(ExponentialConfig {
+                base: f64::NAN,
+                growth: 0.02,
+            }),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            crate::LogarithmicBondingCurve::try_from(LogarithmicConfig {
+                base: 0.02,
+                growth: -1.0,
+            }),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            crate::PowerBondingCurve::try_from(PowerConfig {
+                coefficient: 0.001,
+                exponent: -0.5,
+            }),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            crate::PowerBondingCurve::try_from(PowerConfig {
+                coefficient: -0.001,
+                exponent: 1.5,
+            }),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "float"))]
+    #[test]
+    pub fn test_configs_json() {
+        use crate::SigmoidConfig;
+
+        let config: SigmoidConfig =
+            serde_json::from_str(r#"{"max_price":1000.0,"growth":0.01,"mid_supply":500}"#).unwrap();
+        assert_eq!(config.mid_supply, 500);
+        assert_eq!(
+            serde_json::from_str::<SigmoidConfig>(&serde_json::to_string(&config).unwrap())
+                .unwrap(),
+            config
+        );
+        // The old WASM name for `max_price` is rejected rather than ignored.
+        assert!(serde_json::from_str::<SigmoidConfig>(
+            r#"{"base":1000.0,"growth":0.01,"mid_supply":500}"#
+        )
+        .is_err());
+    }
+}
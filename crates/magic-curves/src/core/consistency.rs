@@ -0,0 +1,257 @@
+#[cfg(not(feature = "panic-free"))]
+use std::iter::Sum;
+
+use super::Tolerance;
+#[cfg(not(feature = "panic-free"))]
+use super::{supply_range, BondingCurve, BondingCurveError, OperationSide};
+
+/// Represents a price type that can be compared as an `f64` by `check_integral`.
+pub trait AsF64: Copy {
+    /// Converts the price to an `f64`, rounding if it has no exact representation.
+    fn as_f64(self) -> f64;
+}
+
+impl AsF64 for u64 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl AsF64 for u128 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl AsF64 for f32 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl AsF64 for f64 {
+    fn as_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Represents how `check_integral` integrates `calculate_price` numerically.
+///
+/// # Variants
+///
+/// * `Sum` - Sums the spot price at every priced supply, the convention of the integer curves
+///   and of the default `calculate_price_many`.
+/// * `Simpson` - Integrates the spot price continuously over the supply range with Simpson's
+///   rule, sampling every supply, the convention of the float curves. A single token is
+///   integrated with the less accurate trapezoidal rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Quadrature {
+    Sum,
+    Simpson,
+}
+
+/// Represents the outcome of `check_integral`.
+///
+/// # Fields
+///
+/// * `numeric`: The price obtained by integrating `calculate_price`.
+/// * `closed_form`: The price returned by `calculate_price_many`.
+/// * `relative_error`: The difference of the two, relative to the larger magnitude, or zero
+///   if both are zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IntegralCheck {
+    pub numeric: f64,
+    pub closed_form: f64,
+    pub relative_error: f64,
+}
+
+impl IntegralCheck {
+    /// Returns whether the numeric and closed-form prices agree within `tolerance`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{check_integral, ExponentialBondingCurve, OperationSide, Quadrature, Tolerance};
+    ///
+    /// let curve = ExponentialBondingCurve::new(0.05, 0.01);
+    /// let check = check_integral(&curve, 1000, 10, OperationSide::Add, Quadrature::Simpson).unwrap();
+    /// assert!(check.is_within(Tolerance::default()));
+    /// assert!(!check.is_within(Tolerance::EXACT));
+    /// ```
+    pub fn is_within(&self, tolerance: Tolerance) -> bool {
+        tolerance.approx_eq(self.numeric, self.closed_form)
+    }
+}
+
+/// Checks that a curve's `calculate_price_many` agrees with its own `calculate_price`, by
+/// integrating the spot price numerically over the same range.
+///
+/// This is a diagnostic for new and custom curves, and for fixed-point ports of a formula;
+/// it evaluates `calculate_price` once per supply in the range, so keep `amount` modest.
+///
+/// # Arguments
+///
+/// * `curve` - The curve to check.
+/// * `starting_supply` - The initial supply before the operation.
+/// * `amount` - The number of tokens to add or remove.
+/// * `side` - Specifies whether tokens are being added or removed.
+/// * `quadrature` - How to integrate the spot price.
+///
+/// # Returns
+///
+/// A `Result` containing the `IntegralCheck`, or a `BondingCurveError` if the supply range
+/// is invalid.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{check_integral, ExponentialBondingCurve, OperationSide, Quadrature};
+///
+/// let curve = ExponentialBondingCurve::new(0.05, 0.01);
+/// let check = check_integral(&curve, 1000, 10, OperationSide::Add, Quadrature::Simpson).unwrap();
+/// assert!(check.relative_error < 1e-9);
+/// ```
+#[cfg(not(feature = "panic-free"))]
+pub fn check_integral<T, C>(
+    curve: &C,
+    starting_supply: u64,
+    amount: u64,
+    side: OperationSide,
+    quadrature: Quadrature,
+) -> Result<IntegralCheck, BondingCurveError>
+where
+    T: AsF64 + Sum<T>,
+    C: BondingCurve<T> + ?Sized,
+{
+    let numeric = match supply_range(starting_supply, amount, side)? {
+        None => 0.0,
+        Some((first, last)) => match quadrature {
+            Quadrature::Sum => (first..=last)
+                .map(|supply| curve.calculate_price(supply).as_f64())
+                .sum(),
+            Quadrature::Simpson => {
+                let low = match side {
+                    OperationSide::Add => starting_supply,
+                    OperationSide::Remove => starting_supply - amount,
+                };
+                let price = |offset: u64| curve.calculate_price(low + offset).as_f64();
+                simpson(&price, amount)
+            }
+        },
+    };
+    let closed_form = curve
+        .calculate_price_many(starting_supply, amount, side)
+        .as_f64();
+    let scale = numeric.abs().max(closed_form.abs());
+    let relative_error = if scale == 0.0 {
+        0.0
+    } else {
+        (numeric - closed_form).abs() / scale
+    };
+    Ok(IntegralCheck {
+        numeric,
+        closed_form,
+        relative_error,
+    })
+}
+
+/// Integrates `price` over `[0, intervals]` with unit steps, using Simpson's 3/8 rule on the
+/// last three intervals when their number is odd, and the trapezoidal rule for one interval.
+#[cfg(not(feature = "panic-free"))]
+fn simpson(price: &dyn Fn(u64) -> f64, intervals: u64) -> f64 {
+    if intervals == 1 {
+        return (price(0) + price(1)) / 2.0;
+    }
+    let (even, tail) = if intervals.is_multiple_of(2) {
+        (intervals, 0.0)
+    } else {
+        let start = intervals - 3;
+        let tail = 3.0 / 8.0
+            * (price(start) + 3.0 * price(start + 1) + 3.0 * price(start + 2) + price(intervals));
+        (start, tail)
+    };
+    let mut body = price(0) + price(even);
+    for offset in 1..even {
+        body += if offset % 2 == 1 { 4.0 } else { 2.0 } * price(offset);
+    }
+    body / 3.0 + tail
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        check_integral, BondingCurve, BondingCurveError, ExponentialBondingCurve,
+        LinearBondingCurve, OperationSide, Quadrature, SigmoidBondingCurve, Tolerance,
+    };
+
+    #[test]
+    pub fn test_check_integral_integer_sum_is_exact() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            let check = check_integral(&curve, 500, 100, side, Quadrature::Sum).unwrap();
+            assert_eq!(check.numeric, check.closed_form);
+            assert_eq!(check.relative_error, 0.0);
+        }
+    }
+
+    #[test]
+    pub fn test_check_integral_float_simpson() {
+        let exponential = ExponentialBondingCurve::new(0.05, 0.01);
+        let sigmoid = SigmoidBondingCurve::new(1000.0, 0.001, 50);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            for amount in [2, 7, 100] {
+                let check =
+                    check_integral(&exponential, 1000, amount, side, Quadrature::Simpson).unwrap();
+                assert!(check.relative_error < 1e-6, "{:?}", check);
+                let check =
+                    check_integral(&sigmoid, 1000, amount, side, Quadrature::Simpson).unwrap();
+                assert!(check.relative_error < 1e-6, "{:?}", check);
+            }
+        }
+        // A single interval falls back to the less accurate trapezoidal rule.
+        let check =
+            check_integral(&sigmoid, 1000, 1, OperationSide::Add, Quadrature::Simpson).unwrap();
+        assert!(check.relative_error < 1e-4);
+        let check =
+            check_integral(&exponential, 1000, 100, OperationSide::Add, Quadrature::Sum).unwrap();
+        assert!(check.relative_error > 1e-3);
+        assert!(!check.is_within(Tolerance::relative(1e-3)));
+        assert!(check.is_within(Tolerance::absolute(check.closed_form)));
+        assert_eq!(
+            check_integral(
+                &exponential,
+                5,
+                6,
+                OperationSide::Remove,
+                Quadrature::Simpson
+            ),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[test]
+    pub fn test_check_integral_detects_inconsistent_curve() {
+        struct OffByOne;
+
+        impl BondingCurve<u64> for OffByOne {
+            fn calculate_price(&self, supply: u64) -> u64 {
+                supply
+            }
+
+            fn calculate_price_many(
+                &self,
+                starting_supply: u64,
+                amount: u64,
+                _: OperationSide,
+            ) -> u64 {
+                // Prices supplies `start + 1..=start + amount` instead of `start..start + amount`.
+                (starting_supply + 1..=starting_supply + amount).sum()
+            }
+        }
+
+        let check = check_integral(&OffByOne, 10, 10, OperationSide::Add, Quadrature::Sum).unwrap();
+        assert_eq!(check.numeric, 145.0);
+        assert_eq!(check.closed_form, 155.0);
+        assert!(check.relative_error > 0.06);
+    }
+}
@@ -0,0 +1,280 @@
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, CubicBondingCurve, GeometricBondingCurve,
+    GeometricLevelCurve, LinearBondingCurve, OperationSide, PegStabilityCurve, PowerLevelCurve,
+    QuadraticBondingCurve,
+};
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+/// Represents the checked operation that failed.
+///
+/// # Variants
+///
+/// * `Price`: `calculate_price_checked` at `supply`.
+/// * `PriceMany`: `calculate_price_many_checked` for a trade.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CurveOperation {
+    Price {
+        supply: u64,
+    },
+    PriceMany {
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    },
+}
+
+impl CurveOperation {
+    /// Runs the operation on a curve.
+    fn run<C: BondingCurveWithCheckedOperations<u64>>(
+        &self,
+        curve: &C,
+    ) -> Result<u64, BondingCurveError> {
+        match *self {
+            CurveOperation::Price { supply } => curve.calculate_price_checked(supply),
+            CurveOperation::PriceMany {
+                starting_supply,
+                amount,
+                side,
+            } => curve.calculate_price_many_checked(starting_supply, amount, side),
+        }
+    }
+}
+
+impl Display for CurveOperation {
+    /// Formats the operation as a call, such as `calculate_price_many_checked(10, 5, Add)`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CurveOperation::Price { supply } => write!(f, "calculate_price_checked({supply})"),
+            CurveOperation::PriceMany {
+                starting_supply,
+                amount,
+                side,
+            } => write!(
+                f,
+                "calculate_price_many_checked({starting_supply}, {amount}, {side:?})"
+            ),
+        }
+    }
+}
+
+/// Represents a `BondingCurveError` together with the operation that raised it, so an
+/// overflow reported from production can be reproduced.
+///
+/// Building it formats the curve, so it lives behind the `rich-errors` feature, and
+/// on-chain programs keep the plain `BondingCurveError`. It converts into the plain error, so
+/// `?` still works in functions that return a `BondingCurveError`.
+///
+/// # Fields
+///
+/// * `error`: The underlying error.
+/// * `curve`: The `Debug` form of the curve, with its type and every parameter.
+/// * `operation`: The operation that failed.
+/// * `term`: The term of the formula that overflowed on its own, `"sum"` if only their sum
+///   overflowed, or `None` if the error is not an overflow or the curve has no terms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub error: BondingCurveError,
+    pub curve: String,
+    pub operation: CurveOperation,
+    pub term: Option<&'static str>,
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} on {}", self.error, self.operation, self.curve)?;
+        if let Some(term) = self.term {
+            write!(f, " (overflowing term: {term})")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ErrorContext {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<ErrorContext> for BondingCurveError {
+    fn from(context: ErrorContext) -> Self {
+        context.error
+    }
+}
+
+/// Checked operations whose errors carry an `ErrorContext`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{CheckedWithContext, OperationSide, QuadraticBondingCurve};
+///
+/// let curve = QuadraticBondingCurve::new(1, 0, 0);
+/// let error = curve
+///     .calculate_price_many_with_context(1 << 40, 2, OperationSide::Add)
+///     .unwrap_err();
+/// assert_eq!(error.term, Some("quadratic"));
+/// assert_eq!(
+///     error.to_string(),
+///     "An overflow occurred during the operation. \
+///      calculate_price_many_checked(1099511627776, 2, Add) on \
+///      QuadraticBondingCurve { quadratic: 1, linear: 0, base: 0 } (overflowing term: quadratic)"
+/// );
+/// ```
+pub trait CheckedWithContext: BondingCurveWithCheckedOperations<u64> + Debug + Sized {
+    /// Returns the terms of the curve's formula, each as the curve with every other
+    /// coefficient set to zero. Curves that are not a sum of terms return none.
+    fn terms(&self) -> Vec<(&'static str, Self)> {
+        Vec::new()
+    }
+
+    /// Calculates the price at `supply`, like `calculate_price_checked`.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, or the `ErrorContext` of the failure.
+    fn calculate_price_with_context(&self, supply: u64) -> Result<u64, ErrorContext> {
+        with_context(self, CurveOperation::Price { supply })
+    }
+
+    /// Calculates the price of a trade, like `calculate_price_many_checked`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, or the `ErrorContext` of the failure.
+    fn calculate_price_many_with_context(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, ErrorContext> {
+        with_context(
+            self,
+            CurveOperation::PriceMany {
+                starting_supply,
+                amount,
+                side,
+            },
+        )
+    }
+}
+
+/// Runs `operation` on `curve`, and on failure finds the term that overflowed.
+fn with_context<C: CheckedWithContext>(
+    curve: &C,
+    operation: CurveOperation,
+) -> Result<u64, ErrorContext> {
+    operation.run(curve).map_err(|error| {
+        let terms = curve.terms();
+        let term = match error {
+            BondingCurveError::Overflow if !terms.is_empty() => Some(
+                terms
+                    .iter()
+                    .find(|(_, term)| operation.run(term).is_err())
+                    .map_or("sum", |(name, _)| *name),
+            ),
+            _ => None,
+        };
+        ErrorContext {
+            error,
+            curve: format!("{curve:?}"),
+            operation,
+            term,
+        }
+    })
+}
+
+impl CheckedWithContext for LinearBondingCurve {
+    fn terms(&self) -> Vec<(&'static str, Self)> {
+        vec![
+            ("linear", Self::new(self.linear, 0)),
+            ("base", Self::new(0, self.base)),
+        ]
+    }
+}
+
+impl CheckedWithContext for QuadraticBondingCurve {
+    fn terms(&self) -> Vec<(&'static str, Self)> {
+        vec![
+            ("quadratic", Self::new(self.quadratic, 0, 0)),
+            ("linear", Self::new(0, self.linear, 0)),
+            ("base", Self::new(0, 0, self.base)),
+        ]
+    }
+}
+
+impl CheckedWithContext for CubicBondingCurve {
+    fn terms(&self) -> Vec<(&'static str, Self)> {
+        vec![
+            ("cubic", Self::new(self.cubic, 0, 0, 0)),
+            ("quadratic", Self::new(0, self.quadratic, 0, 0)),
+            ("linear", Self::new(0, 0, self.linear, 0)),
+            ("base", Self::new(0, 0, 0, self.base)),
+        ]
+    }
+}
+
+impl CheckedWithContext for GeometricBondingCurve {}
+
+impl CheckedWithContext for GeometricLevelCurve {}
+
+impl CheckedWithContext for PowerLevelCurve {}
+
+impl CheckedWithContext for PegStabilityCurve {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, CheckedWithContext, CubicBondingCurve, CurveOperation,
+        GeometricBondingCurve, LinearBondingCurve, OperationSide,
+    };
+
+    #[test]
+    pub fn test_error_context_names_the_overflowing_term() {
+        let curve = CubicBondingCurve::new(1, 1, 1, 1);
+        let error = curve.calculate_price_with_context(1 << 22).unwrap_err();
+        assert_eq!(error.error, BondingCurveError::Overflow);
+        assert_eq!(error.operation, CurveOperation::Price { supply: 1 << 22 });
+        assert_eq!(error.term, Some("cubic"));
+        assert_eq!(
+            error.curve,
+            "CubicBondingCurve { cubic: 1, quadratic: 1, linear: 1, base: 1 }"
+        );
+
+        // Neither term overflows alone, only their sum.
+        let linear = LinearBondingCurve::new(1, u64::MAX);
+        let error = linear.calculate_price_with_context(1).unwrap_err();
+        assert_eq!(error.term, Some("sum"));
+        assert_eq!(BondingCurveError::from(error), BondingCurveError::Overflow);
+    }
+
+    #[test]
+    pub fn test_error_context_without_terms() {
+        let linear = LinearBondingCurve::new(100, 1000);
+        assert_eq!(linear.calculate_price_with_context(10), Ok(2000));
+        let error = linear
+            .calculate_price_many_with_context(5, 6, OperationSide::Remove)
+            .unwrap_err();
+        assert_eq!(error.error, BondingCurveError::SupplyUnderflow);
+        assert_eq!(error.term, None);
+
+        let geometric = GeometricBondingCurve::new(u64::MAX, 20_000);
+        let error = geometric.calculate_price_with_context(1).unwrap_err();
+        assert_eq!(
+            (error.error, error.term),
+            (BondingCurveError::Overflow, None)
+        );
+    }
+}
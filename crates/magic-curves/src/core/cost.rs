@@ -0,0 +1,353 @@
+use super::{
+    AnyBondingCurve, AugmentedBondingCurve, BlendedCurve, GeometricBondingCurve,
+    GeometricLevelCurve, LaunchRamp, LinearBondingCurve, OperationSide, PegStabilityCurve,
+    PhasedCurve, PowerLevelCurve, QuadraticBondingCurve, SellDamping, BASIS_POINTS,
+};
+#[cfg(feature = "float")]
+use super::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+/// The weight of one arithmetic operation, such as a checked multiply, divide, or add, in the
+/// units of `ComputeCost`.
+pub const ARITHMETIC_WEIGHT: u64 = 1;
+
+/// The weight of one transcendental function, an `exp`, `ln`, or `powf`, in the units of
+/// `ComputeCost`.
+///
+/// This is a rough estimate for hardware floats; with the `soft-float` feature, and on
+/// targets that emulate floats such as the Solana runtime, every float operation is heavier.
+pub const TRANSCENDENTAL_WEIGHT: u64 = 20;
+
+/// Estimates how much computation a curve's operations take, relative to each other, so
+/// routers and on-chain planners can budget compute before choosing a path.
+///
+/// Weights are in units of `ARITHMETIC_WEIGHT` and `TRANSCENDENTAL_WEIGHT`. They count the
+/// operations of the formulas the curve evaluates, so they tell a constant-time integer curve
+/// from one that calls `exp` and `ln`, or from one that loops over every token, but they are
+/// not measurements of any particular machine.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{ComputeCost, GeometricBondingCurve, LinearBondingCurve, OperationSide};
+///
+/// let linear = LinearBondingCurve::new(100, 1000);
+/// let geometric = GeometricBondingCurve::new(1_000, 10_100);
+/// assert!(
+///     linear.price_many_cost(0, 1_000, OperationSide::Add)
+///         < geometric.price_many_cost(0, 1_000, OperationSide::Add)
+/// );
+/// ```
+pub trait ComputeCost {
+    /// Returns the relative weight of `calculate_price` at `supply`.
+    fn price_cost(&self, supply: u64) -> u64;
+
+    /// Returns the relative weight of `calculate_price_many` for the same arguments.
+    ///
+    /// The default sums `price_cost` over every token, like the default
+    /// `calculate_price_many`, valued at the range's last token.
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        let (_, last) = range(starting_supply, amount, side);
+        self.price_cost(last).saturating_mul(amount)
+    }
+}
+
+/// Returns the first and last supply priced by a trade, saturating where `supply_range`
+/// would fail.
+fn range(starting_supply: u64, amount: u64, side: OperationSide) -> (u64, u64) {
+    let amount = amount.max(1);
+    match side {
+        OperationSide::Add => (starting_supply, starting_supply.saturating_add(amount - 1)),
+        OperationSide::Remove => (starting_supply.saturating_sub(amount - 1), starting_supply),
+    }
+}
+
+/// Returns the number of bits of `value`, the steps of exponentiation by squaring.
+fn bits(value: u64) -> u64 {
+    (u64::BITS - value.leading_zeros()) as u64
+}
+
+impl ComputeCost for LinearBondingCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        2 * ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        8 * ARITHMETIC_WEIGHT
+    }
+}
+
+impl ComputeCost for QuadraticBondingCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        5 * ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        16 * ARITHMETIC_WEIGHT
+    }
+}
+
+impl ComputeCost for GeometricBondingCurve {
+    /// Every step of the power squares and multiplies through a 256-bit product.
+    fn price_cost(&self, supply: u64) -> u64 {
+        (2 + 8 * bits(supply)) * ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        if self.multiplier == BASIS_POINTS {
+            return ARITHMETIC_WEIGHT;
+        }
+        let (first, last) = range(starting_supply, amount, side);
+        (6 + 8 * (bits(first) + bits(last.saturating_add(1)))) * ARITHMETIC_WEIGHT
+    }
+}
+
+impl ComputeCost for GeometricLevelCurve {
+    fn price_cost(&self, supply: u64) -> u64 {
+        (2 + 2 * bits(supply)) * ARITHMETIC_WEIGHT
+    }
+
+    /// Levels are summed one at a time, unless every level costs the same.
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        if self.ratio == BASIS_POINTS {
+            return ARITHMETIC_WEIGHT;
+        }
+        let (_, last) = range(starting_supply, amount, side);
+        (self.price_cost(last) + ARITHMETIC_WEIGHT).saturating_mul(amount)
+    }
+}
+
+impl ComputeCost for PowerLevelCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        (2 + 2 * bits(self.power as u64)) * ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        12 * ARITHMETIC_WEIGHT
+    }
+}
+
+impl ComputeCost for PegStabilityCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        6 * ARITHMETIC_WEIGHT
+    }
+}
+
+#[cfg(feature = "float")]
+impl ComputeCost for ExponentialBondingCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        TRANSCENDENTAL_WEIGHT + 2 * ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        2 * TRANSCENDENTAL_WEIGHT + 6 * ARITHMETIC_WEIGHT
+    }
+}
+
+#[cfg(feature = "float")]
+impl ComputeCost for LogarithmicBondingCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        TRANSCENDENTAL_WEIGHT + 2 * ARITHMETIC_WEIGHT
+    }
+
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        2 * TRANSCENDENTAL_WEIGHT + 8 * ARITHMETIC_WEIGHT
+    }
+}
+
+#[cfg(feature = "float")]
+impl ComputeCost for SigmoidBondingCurve {
+    fn price_cost(&self, _supply: u64) -> u64 {
+        TRANSCENDENTAL_WEIGHT + 3 * ARITHMETIC_WEIGHT
+    }
+
+    /// The integral evaluates a softplus, an `exp` and an `ln`, at both ends.
+    fn price_many_cost(&self, _starting_supply: u64, _amount: u64, _side: OperationSide) -> u64 {
+        4 * TRANSCENDENTAL_WEIGHT + 8 * ARITHMETIC_WEIGHT
+    }
+}
+
+impl ComputeCost for AnyBondingCurve {
+    fn price_cost(&self, supply: u64) -> u64 {
+        match self {
+            AnyBondingCurve::Linear(curve) => curve.price_cost(supply),
+            AnyBondingCurve::Quadratic(curve) => curve.price_cost(supply),
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Exponential(curve) => curve.price_cost(supply),
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Logarithmic(curve) => curve.price_cost(supply),
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Sigmoid(curve) => curve.price_cost(supply),
+        }
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        match self {
+            AnyBondingCurve::Linear(curve) => curve.price_many_cost(starting_supply, amount, side),
+            AnyBondingCurve::Quadratic(curve) => {
+                curve.price_many_cost(starting_supply, amount, side)
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Exponential(curve) => {
+                curve.price_many_cost(starting_supply, amount, side)
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Logarithmic(curve) => {
+                curve.price_many_cost(starting_supply, amount, side)
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Sigmoid(curve) => curve.price_many_cost(starting_supply, amount, side),
+        }
+    }
+}
+
+impl<C: ComputeCost> ComputeCost for AugmentedBondingCurve<C> {
+    fn price_cost(&self, supply: u64) -> u64 {
+        ARITHMETIC_WEIGHT + self.curve.price_cost(supply)
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        4 * ARITHMETIC_WEIGHT + self.curve.price_many_cost(starting_supply, amount, side)
+    }
+}
+
+impl<C: ComputeCost> ComputeCost for SellDamping<C> {
+    fn price_cost(&self, supply: u64) -> u64 {
+        self.curve.price_cost(supply)
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        4 * ARITHMETIC_WEIGHT + self.curve.price_many_cost(starting_supply, amount, side)
+    }
+}
+
+impl<C: ComputeCost> ComputeCost for LaunchRamp<C> {
+    fn price_cost(&self, supply: u64) -> u64 {
+        6 * ARITHMETIC_WEIGHT + self.curve.price_cost(supply)
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        6 * ARITHMETIC_WEIGHT + self.curve.price_many_cost(starting_supply, amount, side)
+    }
+}
+
+impl<A: ComputeCost, B: ComputeCost> ComputeCost for BlendedCurve<A, B> {
+    fn price_cost(&self, supply: u64) -> u64 {
+        6 * ARITHMETIC_WEIGHT + self.a.price_cost(supply) + self.b.price_cost(supply)
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        6 * ARITHMETIC_WEIGHT
+            + self.a.price_many_cost(starting_supply, amount, side)
+            + self.b.price_many_cost(starting_supply, amount, side)
+    }
+}
+
+impl<C: ComputeCost> ComputeCost for PhasedCurve<C> {
+    /// Finding the phase of a supply walks the phases before it.
+    fn price_cost(&self, supply: u64) -> u64 {
+        match self.phase_at(supply) {
+            Some((index, start)) => {
+                (index as u64 + 1) * ARITHMETIC_WEIGHT
+                    + self.phases[index].curve.price_cost(supply - start)
+            }
+            None => self.phases.len() as u64 * ARITHMETIC_WEIGHT,
+        }
+    }
+
+    fn price_many_cost(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        let (first, last) = range(starting_supply, amount, side);
+        let mut cost = 0u64;
+        let mut start = 0u64;
+        for phase in &self.phases {
+            cost = cost.saturating_add(ARITHMETIC_WEIGHT);
+            let end = start.saturating_add(phase.supply);
+            if phase.supply > 0 && end > first && start <= last {
+                let from = first.max(start);
+                let until = last.min(end - 1);
+                cost = cost.saturating_add(phase.curve.price_many_cost(
+                    from - start,
+                    until - from + 1,
+                    OperationSide::Add,
+                ));
+            }
+            if end > last {
+                break;
+            }
+            start = end;
+        }
+        cost
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ComputeCost, GeometricBondingCurve, GeometricLevelCurve, LinearBondingCurve, OperationSide,
+        PhasedCurve, QuadraticBondingCurve, ARITHMETIC_WEIGHT,
+    };
+
+    #[test]
+    pub fn test_compute_cost_scales_with_the_work() {
+        let linear = LinearBondingCurve::new(100, 1000);
+        assert_eq!(
+            linear.price_many_cost(0, 1, OperationSide::Add),
+            linear.price_many_cost(0, 1_000_000, OperationSide::Add)
+        );
+        // Exponentiation by squaring takes a step per bit of the supply.
+        let geometric = GeometricBondingCurve::new(1_000, 10_100);
+        assert!(geometric.price_cost(1 << 20) > geometric.price_cost(1 << 10));
+        // Levels are summed one by one, unless they are flat.
+        let levels = GeometricLevelCurve::new(100, 15_000);
+        assert!(
+            levels.price_many_cost(0, 100, OperationSide::Add)
+                > 10 * levels.price_many_cost(0, 10, OperationSide::Add)
+        );
+        let flat = GeometricLevelCurve::new(100, 10_000);
+        assert_eq!(
+            flat.price_many_cost(0, 100, OperationSide::Add),
+            ARITHMETIC_WEIGHT
+        );
+    }
+
+    #[test]
+    pub fn test_compute_cost_of_phases() {
+        let seed = QuadraticBondingCurve::new(0, 3, 1_000);
+        let curve = PhasedCurve::new().with("seed", 100, seed).with(
+            "public",
+            250,
+            QuadraticBondingCurve::new(1, 5, 2_000),
+        );
+        let one = seed.price_many_cost(0, 1, OperationSide::Add);
+        assert_eq!(
+            curve.price_many_cost(0, 10, OperationSide::Add),
+            ARITHMETIC_WEIGHT + one
+        );
+        assert_eq!(
+            curve.price_many_cost(90, 20, OperationSide::Add),
+            2 * (ARITHMETIC_WEIGHT + one)
+        );
+        assert_eq!(
+            curve.price_cost(150),
+            2 * ARITHMETIC_WEIGHT + seed.price_cost(0)
+        );
+        assert_eq!(curve.price_cost(1_000), 2 * ARITHMETIC_WEIGHT);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_compute_cost_of_float_curves() {
+        use crate::{
+            AnyBondingCurve, ExponentialBondingCurve, SigmoidBondingCurve, TRANSCENDENTAL_WEIGHT,
+        };
+
+        let sigmoid = SigmoidBondingCurve::new(1_000.0, 0.01, 500);
+        assert!(sigmoid.price_many_cost(0, 10, OperationSide::Add) >= 4 * TRANSCENDENTAL_WEIGHT);
+        let exponential = AnyBondingCurve::Exponential(ExponentialBondingCurve::new(0.01, 0.02));
+        let linear = AnyBondingCurve::Linear(LinearBondingCurve::new(100, 1000));
+        assert!(exponential.price_cost(5) > linear.price_cost(5));
+    }
+}
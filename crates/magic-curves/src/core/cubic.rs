@@ -0,0 +1,381 @@
+#[cfg(not(feature = "panic-free"))]
+use super::{narrow, BondingCurve};
+use super::{supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+use std::fmt::{self, Display, Formatter};
+
+/// Represents a cubic bonding curve.
+///
+/// This struct defines a cubic bonding curve with cubic, quadratic, linear, and base
+/// coefficients, for launches where a quadratic curve grows too slowly and an exponential one
+/// overshoots. It has a fixed `#[repr(C)]` layout, and with the `bytemuck` feature it
+/// implements `Pod` and `Zeroable` so it can live directly in zero-copy accounts.
+///
+/// # Fields
+///
+/// * `cubic`: The cubic coefficient that dominates the price at high supplies.
+/// * `quadratic`: The quadratic coefficient that determines the rate of price increase.
+/// * `linear`: The linear coefficient that affects the price linearly.
+/// * `base`: The base price, which is the minimum price for the first token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(C)]
+pub struct CubicBondingCurve {
+    pub cubic: u64,
+    pub quadratic: u64,
+    pub linear: u64,
+    pub base: u64,
+}
+
+impl CubicBondingCurve {
+    /// Creates a new `CubicBondingCurve` with the specified coefficients.
+    ///
+    /// # Arguments
+    ///
+    /// * `cubic` - The cubic coefficient that dominates the price at high supplies.
+    /// * `quadratic` - The quadratic coefficient that determines the rate of price increase.
+    /// * `linear` - The linear coefficient that affects the price linearly.
+    /// * `base` - The base price, which is the minimum price for the first token.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `CubicBondingCurve`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::CubicBondingCurve;
+    ///
+    /// let curve = CubicBondingCurve::new(2, 10, 100, 1000);
+    /// ```
+    pub fn new(cubic: u64, quadratic: u64, linear: u64, base: u64) -> Self {
+        Self {
+            cubic,
+            quadratic,
+            linear,
+            base,
+        }
+    }
+}
+
+/// Returns the sum of `x` for `x` in `0..n`.
+fn prefix_sum(n: u128) -> u128 {
+    // `n` is at most `2^64`, so the product fits in a `u128`.
+    n * n.saturating_sub(1) / 2
+}
+
+/// Returns the sum of `x^2` for `x` in `0..n`, or `None` if it does not fit in a `u128`.
+fn prefix_sum_of_squares(n: u128) -> Option<u128> {
+    (n * n.saturating_sub(1))
+        .checked_mul((2 * n).saturating_sub(1))
+        .map(|x| x / 6)
+}
+
+/// Returns the sum of `x^3` for `x` in `0..n`, the square of `prefix_sum`, or `None` if it
+/// does not fit in a `u128`.
+fn prefix_sum_of_cubes(n: u128) -> Option<u128> {
+    let sum = prefix_sum(n);
+    sum.checked_mul(sum)
+}
+
+impl Display for CubicBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 2x^3 + 10x^2 + 100x + 1000`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "f(x) = {}x^3 + {}x^2 + {}x + {}",
+            self.cubic, self.quadratic, self.linear, self.base
+        )
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<u64> for CubicBondingCurve {
+    /// Calculates the price based on the supply.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) = cubic * x^3 + quadratic * x^2 + linear * x + base
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// The price of the token based on the supply. Like native arithmetic, a price that does
+    /// not fit in a `u64` panics in debug builds and wraps in release builds.
+    fn calculate_price(&self, supply: u64) -> u64 {
+        // Horner form, as in the checked path, so only a price that does not fit overflows.
+        ((self.cubic * supply + self.quadratic) * supply + self.linear) * supply + self.base
+    }
+
+    /// Calculates the price for a given amount of tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price for the given amount of tokens. Like native arithmetic, a result that
+    /// does not fit in a `u64`, or removing more tokens than exist, panics in debug builds and
+    /// wraps in release builds.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        if amount == 0 {
+            return 0;
+        }
+        // The priced supplies are `low..high`.
+        let (low, high) = match side {
+            OperationSide::Add => (
+                starting_supply as u128,
+                starting_supply as u128 + amount as u128,
+            ),
+            OperationSide::Remove => (
+                (starting_supply - amount) as u128 + 1,
+                starting_supply as u128 + 1,
+            ),
+        };
+
+        // Terms with a zero coefficient are skipped, as in the checked path, so their sums
+        // cannot overflow the intermediates of a price that fits.
+        let term = |coefficient: u64, sum: fn(u128) -> u128| {
+            if coefficient == 0 {
+                return 0;
+            }
+            (sum(high) - sum(low)) * coefficient as u128
+        };
+        let sum_cubic = term(self.cubic, |n| prefix_sum(n) * prefix_sum(n));
+        let sum_quadratic = term(self.quadratic, |n| {
+            n * n.saturating_sub(1) * (2 * n).saturating_sub(1) / 6
+        });
+        let sum_linear = term(self.linear, prefix_sum);
+
+        // Sum of constant terms: base * n
+        let sum_constant = self.base as u128 * amount as u128;
+
+        narrow(sum_cubic + sum_quadratic + sum_linear + sum_constant)
+    }
+}
+
+impl BondingCurveWithCheckedOperations<u64> for CubicBondingCurve {
+    /// Calculates the price based on the supply with overflow checking.
+    ///
+    /// The price is evaluated in Horner form, whose intermediates never exceed the price, so
+    /// it only fails when the price itself does not fit in a `u64`.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price of the token based on the supply,
+    /// or a `BondingCurveError` if the calculation overflows.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.cubic
+            .checked_mul(supply)
+            .and_then(|x| x.checked_add(self.quadratic))
+            .and_then(|x| x.checked_mul(supply))
+            .and_then(|x| x.checked_add(self.linear))
+            .and_then(|x| x.checked_mul(supply))
+            .and_then(|x| x.checked_add(self.base))
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Calculates the price for a given amount of tokens with overflow checking.
+    ///
+    /// # Formula
+    ///
+    /// Every power is summed over the priced supplies in closed form, as the difference of
+    /// two prefix sums of Faulhaber's formulas:
+    ///
+    /// ```ignore
+    /// Σ x   = n * (n - 1) / 2
+    /// Σ x^2 = n * (n - 1) * (2n - 1) / 6
+    /// Σ x^3 = (n * (n - 1) / 2)^2
+    /// ```
+    ///
+    /// # Cost
+    ///
+    /// The sums are computed in `u128`, terms with a zero coefficient are skipped entirely,
+    /// and the result is only narrowed to `u64` at the end. A sum only overflows a `u128`
+    /// when its term alone would not fit in a `u64`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price for the given amount of tokens,
+    /// `BondingCurveError::SupplyUnderflow` if more than `starting_supply` tokens are removed,
+    /// or `BondingCurveError::Overflow` if the calculation overflows.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        // The priced supplies are `low..high`.
+        let (low, high) = (first as u128, last as u128 + 1);
+
+        let term = |coefficient: u64, sum: fn(u128) -> Option<u128>| {
+            if coefficient == 0 {
+                return Some(0);
+            }
+            (sum(high)? - sum(low)?).checked_mul(coefficient as u128)
+        };
+        let sum_cubic = term(self.cubic, prefix_sum_of_cubes);
+        let sum_quadratic = term(self.quadratic, prefix_sum_of_squares);
+        let sum_linear = term(self.linear, |n| Some(prefix_sum(n)));
+
+        // Sum of constant terms: base * n
+        let sum_constant = self.base as u128 * amount as u128;
+
+        let sum = sum_cubic
+            .and_then(|x| x.checked_add(sum_quadratic?))
+            .and_then(|x| x.checked_add(sum_linear?))
+            .and_then(|x| x.checked_add(sum_constant))
+            .ok_or(BondingCurveError::Overflow)?;
+        u64::try_from(sum).map_err(|_| BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, CubicBondingCurve,
+        OperationSide,
+    };
+
+    #[test]
+    pub fn test_cubic_price_calculus() {
+        let curve = CubicBondingCurve::new(2, 10, 100, 1000);
+        assert_eq!(curve.calculate_price(0), 1000);
+        // 2 * 1000 + 10 * 100 + 100 * 10 + 1000
+        assert_eq!(curve.calculate_price(10), 5000);
+        assert_eq!(curve.calculate_price_checked(10), Ok(5000));
+        assert_eq!(curve.to_string(), "f(x) = 2x^3 + 10x^2 + 100x + 1000");
+
+        // The price only has to fit, not every power of the supply.
+        let linear = CubicBondingCurve::new(0, 0, 1, 0);
+        assert_eq!(linear.calculate_price_checked(u64::MAX), Ok(u64::MAX));
+        assert_eq!(
+            CubicBondingCurve::new(1, 0, 0, 0).calculate_price_checked(1 << 22),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[test]
+    pub fn test_cubic_price_many_matches_loop() {
+        let curves = [
+            CubicBondingCurve::new(2, 10, 100, 1000),
+            CubicBondingCurve::new(1, 0, 0, 0),
+            CubicBondingCurve::new(0, 3, 0, 7),
+            CubicBondingCurve::new(5, 0, 1_000, 0),
+        ];
+        for curve in curves {
+            for starting_supply in [0, 1, 2, 17, 500] {
+                for amount in [0, 1, 2, 9, 100] {
+                    let added: u64 = (starting_supply..starting_supply + amount)
+                        .map(|supply| curve.calculate_price(supply))
+                        .sum();
+                    assert_eq!(
+                        curve.calculate_price_many(starting_supply, amount, OperationSide::Add),
+                        added
+                    );
+                    if amount <= starting_supply {
+                        let removed: u64 = (starting_supply + 1 - amount..=starting_supply)
+                            .map(|supply| curve.calculate_price(supply))
+                            .sum();
+                        assert_eq!(
+                            curve.calculate_price_many_checked(
+                                starting_supply,
+                                amount,
+                                OperationSide::Remove
+                            ),
+                            Ok(removed)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_cubic_unchecked_matches_checked() {
+        let curves = [
+            CubicBondingCurve::new(2, 10, 100, 1000),
+            CubicBondingCurve::new(0, 0, 1, 0),
+            CubicBondingCurve::new(1, 0, 0, 0),
+        ];
+        for curve in curves {
+            for (starting_supply, amount) in [(100, 100), (5, 5), (1 << 12, 1 << 10)] {
+                for side in [OperationSide::Add, OperationSide::Remove] {
+                    assert_eq!(
+                        Ok(curve.calculate_price_many(starting_supply, amount, side)),
+                        curve.calculate_price_many_checked(starting_supply, amount, side)
+                    );
+                }
+            }
+            assert_eq!(
+                Ok(curve.calculate_price(1 << 12)),
+                curve.calculate_price_checked(1 << 12)
+            );
+        }
+        let linear = CubicBondingCurve::new(0, 0, 1, 0);
+        assert_eq!(
+            linear.calculate_price_many(u64::MAX, 1, OperationSide::Remove),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic)]
+    pub fn test_cubic_unchecked_overflow() {
+        // 2^66 wraps to 0 in release builds, like native arithmetic.
+        let curve = CubicBondingCurve::new(1, 0, 0, 0);
+        assert_eq!(curve.calculate_price(1 << 22), 0);
+    }
+
+    #[test]
+    pub fn test_cubic_price_many_edges() {
+        let curve = CubicBondingCurve::new(2, 10, 100, 1000);
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(1 << 20, 1 << 20, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+        // Selling the whole supply prices every supply from 1 up.
+        assert_eq!(
+            curve.calculate_price_many_checked(3, 3, OperationSide::Remove),
+            Ok(curve.calculate_price(1) + curve.calculate_price(2) + curve.calculate_price(3))
+        );
+        // The sums reach the top of the supply range when only the linear term grows.
+        let linear = CubicBondingCurve::new(0, 0, 1, 0);
+        assert_eq!(
+            linear.calculate_price_many_checked(u64::MAX, 1, OperationSide::Remove),
+            Ok(u64::MAX)
+        );
+        assert_eq!(
+            linear.calculate_price_many_checked(u64::MAX, 2, OperationSide::Remove),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
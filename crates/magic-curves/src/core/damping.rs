@@ -0,0 +1,274 @@
+#[cfg(not(feature = "panic-free"))]
+use super::{BondingCurve, TimeBondingCurve};
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, BASIS_POINTS};
+
+/// Represents what the haircut of a `SellDamping` fades with.
+///
+/// The full haircut applies up to `start`, fades linearly to zero at `end`, and is gone from
+/// then on; when `end` is not above `start`, it drops straight to zero at `start`.
+///
+/// # Variants
+///
+/// * `BySupply` - A haircut that fades as the supply before the sale grows.
+/// * `ByTime` - A haircut that fades with the elapsed time, as passed to `TimeBondingCurve`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DampingSchedule {
+    BySupply { start: u64, end: u64 },
+    ByTime { start: u64, end: u64 },
+}
+
+/// Represents a curve whose sale proceeds are cut by a haircut that fades away, such as a
+/// steep exit fee right after launch that drops to nothing over the first day.
+///
+/// Purchases and single-token prices are those of the wrapped curve. A sale is priced by the
+/// wrapped curve and then cut by the haircut in effect at the supply before the sale and the
+/// time of the sale, rounded in the curve's favor:
+///
+/// ```ignore
+/// proceeds = curve_proceeds * (1 - haircut(x, t) / 10_000)
+/// ```
+///
+/// Because the haircut only shrinks as the supply grows, selling in several pieces never
+/// pays more than selling at once. As a `BondingCurve`, a `ByTime` schedule is read at an
+/// elapsed time of zero; use `TimeBondingCurve` to price it at other times.
+///
+/// # Fields
+///
+/// * `curve`: The curve that prices trades before the haircut.
+/// * `haircut`: The largest haircut, in basis points of the proceeds.
+/// * `schedule`: What the haircut fades with.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     DampingSchedule, LinearBondingCurve, OperationSide, SellDamping, TimeBondingCurve,
+/// };
+///
+/// let schedule = DampingSchedule::ByTime { start: 0, end: 86_400 };
+/// let damped = SellDamping::new(LinearBondingCurve::new(0, 1_000), 2_000, schedule);
+/// assert_eq!(damped.price_many_at(10, 10, OperationSide::Remove, 0), 8_000);
+/// assert_eq!(damped.price_many_at(10, 10, OperationSide::Remove, 43_200), 9_000);
+/// assert_eq!(damped.price_many_at(10, 10, OperationSide::Remove, 86_400), 10_000);
+/// assert_eq!(damped.price_many_at(0, 10, OperationSide::Add, 0), 10_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SellDamping<C> {
+    pub curve: C,
+    pub haircut: u64,
+    pub schedule: DampingSchedule,
+}
+
+impl<C> SellDamping<C> {
+    /// Creates a new `SellDamping`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve that prices trades before the haircut.
+    /// * `haircut` - The largest haircut, in basis points of the proceeds.
+    /// * `schedule` - What the haircut fades with.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SellDamping`.
+    pub fn new(curve: C, haircut: u64, schedule: DampingSchedule) -> Self {
+        Self {
+            curve,
+            haircut,
+            schedule,
+        }
+    }
+
+    /// Returns the haircut of a sale at the given supply and time.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The supply before the sale.
+    /// * `elapsed` - The time of the sale, read by a `ByTime` schedule.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the haircut in basis points, rounded up, or
+    /// `BondingCurveError::InvalidParameter` if `haircut` is above `BASIS_POINTS`.
+    pub fn haircut_at(&self, supply: u64, elapsed: u64) -> Result<u64, BondingCurveError> {
+        if self.haircut > BASIS_POINTS {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let (position, start, end) = match self.schedule {
+            DampingSchedule::BySupply { start, end } => (supply, start, end),
+            DampingSchedule::ByTime { start, end } => (elapsed, start, end),
+        };
+        if position < start {
+            Ok(self.haircut)
+        } else if position >= end {
+            Ok(0)
+        } else {
+            let remaining = (end - position) as u128;
+            Ok((self.haircut as u128 * remaining).div_ceil((end - start) as u128) as u64)
+        }
+    }
+}
+
+/// Cuts `haircut` basis points off `proceeds`, rounding the cut up.
+fn damp(proceeds: u64, haircut: u64) -> u64 {
+    let cut = (proceeds as u128 * haircut as u128).div_ceil(BASIS_POINTS as u128);
+    proceeds - cut as u64
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> SellDamping<C> {
+    /// Calculates the price of a trade at a single point in time, with error checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    /// * `elapsed` - The time of the trade, read by a `ByTime` schedule.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price after any haircut, or the `BondingCurveError`
+    /// of the haircut or the wrapped curve.
+    pub fn price_many_at_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        elapsed: u64,
+    ) -> Result<u64, BondingCurveError> {
+        let price = self
+            .curve
+            .calculate_price_many_checked(starting_supply, amount, side)?;
+        match side {
+            OperationSide::Add => Ok(price),
+            OperationSide::Remove => Ok(damp(price, self.haircut_at(starting_supply, elapsed)?)),
+        }
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurve<u64>> TimeBondingCurve<u64> for SellDamping<C> {
+    /// Returns the price of the wrapped curve, which the haircut does not change.
+    fn price_at(&self, supply: u64, _elapsed: u64) -> u64 {
+        self.curve.calculate_price(supply)
+    }
+
+    /// Calculates the price of a trade after any haircut. An invalid haircut is clamped to
+    /// `BASIS_POINTS`.
+    fn price_many_at(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        elapsed: u64,
+    ) -> u64 {
+        let price = self
+            .curve
+            .calculate_price_many(starting_supply, amount, side);
+        match side {
+            OperationSide::Add => price,
+            OperationSide::Remove => damp(
+                price,
+                self.haircut_at(starting_supply, elapsed)
+                    .unwrap_or(BASIS_POINTS),
+            ),
+        }
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurve<u64>> BondingCurve<u64> for SellDamping<C> {
+    fn calculate_price(&self, supply: u64) -> u64 {
+        self.curve.calculate_price(supply)
+    }
+
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        self.price_many_at(starting_supply, amount, side, 0)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> BondingCurveWithCheckedOperations<u64>
+    for SellDamping<C>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.curve.calculate_price_checked(supply)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        self.price_many_at_checked(starting_supply, amount, side, 0)
+    }
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, DampingSchedule,
+        OperationSide, QuadraticBondingCurve, SellDamping, TimeBondingCurve,
+    };
+
+    #[test]
+    pub fn test_sell_damping_by_supply() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let schedule = DampingSchedule::BySupply {
+            start: 100,
+            end: 1_100,
+        };
+        let damped = SellDamping::new(curve, 5_000, schedule);
+        assert_eq!(damped.haircut_at(50, 0), Ok(5_000));
+        assert_eq!(damped.haircut_at(600, 0), Ok(2_500));
+        assert_eq!(damped.haircut_at(1_099, 0), Ok(5));
+        assert_eq!(damped.haircut_at(1_100, 0), Ok(0));
+        let gross = curve.calculate_price_many(600, 10, OperationSide::Remove);
+        assert_eq!(
+            damped.calculate_price_many_checked(600, 10, OperationSide::Remove),
+            Ok(gross - gross / 4)
+        );
+        assert_eq!(
+            damped.calculate_price_many(600, 10, OperationSide::Remove),
+            gross - gross / 4
+        );
+        // Purchases and spot prices are not damped.
+        assert_eq!(
+            damped.calculate_price_many_checked(50, 10, OperationSide::Add),
+            Ok(curve.calculate_price_many(50, 10, OperationSide::Add))
+        );
+        assert_eq!(damped.calculate_price(50), curve.calculate_price(50));
+        // Splitting a sale never pays more than selling at once.
+        let whole = damped.calculate_price_many(700, 200, OperationSide::Remove);
+        let split = damped.calculate_price_many(700, 100, OperationSide::Remove)
+            + damped.calculate_price_many(600, 100, OperationSide::Remove);
+        assert!(split <= whole);
+    }
+
+    #[test]
+    pub fn test_sell_damping_by_time() {
+        let curve = QuadraticBondingCurve::new(0, 0, 1_001);
+        let schedule = DampingSchedule::ByTime { start: 10, end: 10 };
+        let damped = SellDamping::new(curve, 10_000, schedule);
+        assert_eq!(damped.price_many_at(1, 1, OperationSide::Remove, 9), 0);
+        assert_eq!(damped.price_many_at(1, 1, OperationSide::Remove, 10), 1_001);
+        // The cut rounds up, so proceeds round down.
+        let damped = SellDamping::new(curve, 1, schedule);
+        assert_eq!(
+            damped.price_many_at_checked(1, 1, OperationSide::Remove, 0),
+            Ok(1_000)
+        );
+        let invalid = SellDamping::new(curve, 10_001, schedule);
+        assert_eq!(
+            invalid.price_many_at_checked(1, 1, OperationSide::Remove, 0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(invalid.price_many_at(1, 1, OperationSide::Remove, 0), 0);
+        assert_eq!(
+            damped.price_many_at_checked(1, 2, OperationSide::Remove, 0),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+}
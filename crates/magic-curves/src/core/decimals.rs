@@ -0,0 +1,548 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents the number of decimals of a token, used to convert between UI amounts
+/// (such as `1.5` SOL) and base units (such as `1_500_000_000` lamports).
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::Decimals;
+///
+/// assert_eq!(Decimals::SOL.to_base_units(2).unwrap(), 2_000_000_000);
+/// assert_eq!(Decimals::USDC.parse_ui_amount("1.5").unwrap(), 1_500_000);
+/// assert_eq!(Decimals::SOL.format_ui_amount(1_500_000_000), "1.5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Decimals(pub u8);
+
+/// The error returned when a UI amount string cannot be converted to base units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAmountError(pub String);
+
+impl Display for ParseAmountError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Invalid token amount `{}`.", self.0)
+    }
+}
+
+impl Error for ParseAmountError {}
+
+impl Decimals {
+    /// The decimals of USDC and most stablecoins on Solana.
+    pub const USDC: Decimals = Decimals(6);
+    /// The decimals of SOL (lamports).
+    pub const SOL: Decimals = Decimals(9);
+
+    /// Returns the number of base units in one whole token (`10^decimals`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the unit, or `BondingCurveError::Overflow` if it does not fit in a `u64`.
+    pub fn unit(self) -> Result<u64, BondingCurveError> {
+        10u64
+            .checked_pow(self.0 as u32)
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Converts a whole number of tokens into base units.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui_amount` - The number of whole tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the amount in base units, or `BondingCurveError::Overflow`.
+    pub fn to_base_units(self, ui_amount: u64) -> Result<u64, BondingCurveError> {
+        ui_amount
+            .checked_mul(self.unit()?)
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Splits an amount in base units into whole tokens and the remaining base units.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_units` - The amount in base units.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `(whole, fraction)`, or `BondingCurveError::Overflow` if the unit
+    /// does not fit in a `u64`.
+    pub fn split_base_units(self, base_units: u64) -> Result<(u64, u64), BondingCurveError> {
+        let unit = self.unit()?;
+        Ok((base_units / unit, base_units % unit))
+    }
+
+    /// Parses a UI amount such as `"1.25"` into base units, without going through floats.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui_amount` - The amount as a decimal string, with at most `decimals` fractional digits.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the amount in base units, or a `ParseAmountError`.
+    pub fn parse_ui_amount(self, ui_amount: &str) -> Result<u64, ParseAmountError> {
+        let invalid = || ParseAmountError(ui_amount.to_string());
+        let (whole, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+        let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty())
+            || !digits(whole)
+            || !digits(fraction)
+            || fraction.len() > self.0 as usize
+        {
+            return Err(invalid());
+        }
+        let whole = if whole.is_empty() {
+            0
+        } else {
+            whole.parse::<u64>().map_err(|_| invalid())?
+        };
+        let fraction = if fraction.is_empty() {
+            0
+        } else {
+            let scale = Decimals(self.0 - fraction.len() as u8)
+                .unit()
+                .map_err(|_| invalid())?;
+            fraction
+                .parse::<u64>()
+                .ok()
+                .and_then(|f| f.checked_mul(scale))
+                .ok_or_else(invalid)?
+        };
+        self.to_base_units(whole)
+            .ok()
+            .and_then(|w| w.checked_add(fraction))
+            .ok_or_else(invalid)
+    }
+
+    /// Formats an amount in base units as a UI amount, dropping trailing zeros.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_units` - The amount in base units.
+    ///
+    /// # Returns
+    ///
+    /// The amount as a decimal string, such as `"1.5"`.
+    pub fn format_ui_amount(self, base_units: u64) -> String {
+        let digits = base_units.to_string();
+        let decimals = self.0 as usize;
+        let (whole, fraction) = if digits.len() > decimals {
+            let (whole, fraction) = digits.split_at(digits.len() - decimals);
+            (whole.to_string(), fraction.to_string())
+        } else {
+            (
+                "0".to_string(),
+                format!("{:0>width$}", digits, width = decimals),
+            )
+        };
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            whole
+        } else {
+            format!("{}.{}", whole, fraction)
+        }
+    }
+
+    /// Converts a floating point UI amount into base units, rounding to the nearest unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui_amount` - The amount in whole tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the amount in base units, or `BondingCurveError::Overflow` if the
+    /// amount is negative, not finite, or too large.
+    #[cfg(feature = "float")]
+    pub fn ui_to_base_units(self, ui_amount: f64) -> Result<u64, BondingCurveError> {
+        let base_units = (ui_amount * 10f64.powi(self.0 as i32)).round();
+        if !(0.0..=u64::MAX as f64).contains(&base_units) || base_units == u64::MAX as f64 {
+            return Err(BondingCurveError::Overflow);
+        }
+        Ok(base_units as u64)
+    }
+
+    /// Converts an amount in base units into a floating point UI amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_units` - The amount in base units.
+    ///
+    /// # Returns
+    ///
+    /// The amount in whole tokens.
+    #[cfg(feature = "float")]
+    pub fn base_units_to_ui(self, base_units: u64) -> f64 {
+        base_units as f64 / 10f64.powi(self.0 as i32)
+    }
+}
+
+/// Represents an amount of base units whose number of decimals is part of its type, so a
+/// 6-decimal price cannot be mixed up with a 9-decimal one without an explicit `rescale`.
+///
+/// Formats and parses as a UI amount.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::FixedPrice;
+///
+/// let price: FixedPrice<6> = "1.5".parse().unwrap();
+/// assert_eq!(price.base_units(), 1_500_000);
+/// let lamports: FixedPrice<9> = price.rescale(false).unwrap();
+/// assert_eq!(lamports.base_units(), 1_500_000_000);
+/// assert_eq!(lamports.to_string(), "1.5");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct FixedPrice<const DECIMALS: u8>(pub u64);
+
+impl<const DECIMALS: u8> FixedPrice<DECIMALS> {
+    /// The decimals of the amount.
+    pub const DECIMALS: Decimals = Decimals(DECIMALS);
+
+    /// Creates a new `FixedPrice`.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_units` - The amount in base units.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `FixedPrice`.
+    pub const fn new(base_units: u64) -> Self {
+        Self(base_units)
+    }
+
+    /// Returns the amount in base units.
+    pub const fn base_units(self) -> u64 {
+        self.0
+    }
+
+    /// Converts a whole number of tokens into a `FixedPrice`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui_amount` - The number of whole tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the amount, or `BondingCurveError::Overflow`.
+    pub fn from_ui_amount(ui_amount: u64) -> Result<Self, BondingCurveError> {
+        Self::DECIMALS.to_base_units(ui_amount).map(Self)
+    }
+
+    /// Converts the amount to another number of decimals.
+    ///
+    /// # Arguments
+    ///
+    /// * `round_up` - Whether dropping decimals rounds the amount up rather than down.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the converted amount, or `BondingCurveError::Overflow` if it does
+    /// not fit in a `u64`.
+    pub fn rescale<const TO: u8>(
+        self,
+        round_up: bool,
+    ) -> Result<FixedPrice<TO>, BondingCurveError> {
+        if TO >= DECIMALS {
+            let scale = Decimals(TO - DECIMALS).unit()?;
+            return self
+                .0
+                .checked_mul(scale)
+                .map(FixedPrice)
+                .ok_or(BondingCurveError::Overflow);
+        }
+        // Dropping more than 19 decimals leaves nothing but the rounding.
+        let Ok(scale) = Decimals(DECIMALS - TO).unit() else {
+            return Ok(FixedPrice((round_up && self.0 > 0) as u64));
+        };
+        Ok(FixedPrice(if round_up {
+            self.0.div_ceil(scale)
+        } else {
+            self.0 / scale
+        }))
+    }
+
+    /// Adds two amounts of the same decimals, returning `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Subtracts two amounts of the same decimals, returning `None` if `other` is larger.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+}
+
+impl<const DECIMALS: u8> From<FixedPrice<DECIMALS>> for u64 {
+    fn from(price: FixedPrice<DECIMALS>) -> Self {
+        price.0
+    }
+}
+
+impl<const DECIMALS: u8> Display for FixedPrice<DECIMALS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&Self::DECIMALS.format_ui_amount(self.0))
+    }
+}
+
+impl<const DECIMALS: u8> FromStr for FixedPrice<DECIMALS> {
+    type Err = ParseAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::DECIMALS.parse_ui_amount(s).map(Self)
+    }
+}
+
+/// An integer curve whose prices are base units of a token with `DECIMALS` decimals.
+///
+/// The decimals are fixed by the curve's type, so every price it quotes is a
+/// `FixedPrice<DECIMALS>` and a caller cannot read a quote at the wrong scale. Bind a plain
+/// curve to its reserve's decimals with `FixedCurve`.
+pub trait FixedPriceCurve<const DECIMALS: u8>: BondingCurveWithCheckedOperations<u64> {
+    /// Calculates the price at `supply`, like `calculate_price_checked`.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, or the `BondingCurveError` of
+    /// `calculate_price_checked`.
+    fn calculate_fixed_price(
+        &self,
+        supply: u64,
+    ) -> Result<FixedPrice<DECIMALS>, BondingCurveError> {
+        self.calculate_price_checked(supply).map(FixedPrice)
+    }
+
+    /// Calculates the price of a trade, like `calculate_price_many_checked`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, or the `BondingCurveError` of
+    /// `calculate_price_many_checked`.
+    fn calculate_fixed_price_many(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<FixedPrice<DECIMALS>, BondingCurveError> {
+        self.calculate_price_many_checked(starting_supply, amount, side)
+            .map(FixedPrice)
+    }
+}
+
+/// Represents an integer curve bound to the decimals of its reserve token.
+///
+/// It prices exactly like the wrapped curve, and implements `FixedPriceCurve<DECIMALS>`.
+///
+/// # Fields
+///
+/// * `curve`: The integer curve, priced in base units of the reserve token.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{FixedCurve, FixedPriceCurve, LinearBondingCurve, OperationSide};
+///
+/// let curve = FixedCurve::<_, 6>::new(LinearBondingCurve::new(100, 1000));
+/// let price = curve.calculate_fixed_price_many(10, 2, OperationSide::Add).unwrap();
+/// assert_eq!(price.to_string(), "0.0041");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FixedCurve<C, const DECIMALS: u8> {
+    pub curve: C,
+}
+
+impl<C, const DECIMALS: u8> FixedCurve<C, DECIMALS> {
+    /// Creates a new `FixedCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The integer curve, priced in base units of the reserve token.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `FixedCurve`.
+    pub fn new(curve: C) -> Self {
+        Self { curve }
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurve<u64>, const DECIMALS: u8> BondingCurve<u64> for FixedCurve<C, DECIMALS> {
+    fn calculate_price(&self, supply: u64) -> u64 {
+        self.curve.calculate_price(supply)
+    }
+
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        self.curve
+            .calculate_price_many(starting_supply, amount, side)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>, const DECIMALS: u8>
+    BondingCurveWithCheckedOperations<u64> for FixedCurve<C, DECIMALS>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.curve.calculate_price_checked(supply)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        self.curve
+            .calculate_price_many_checked(starting_supply, amount, side)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>, const DECIMALS: u8> FixedPriceCurve<DECIMALS>
+    for FixedCurve<C, DECIMALS>
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, Decimals, FixedCurve, FixedPrice, FixedPriceCurve, LinearBondingCurve,
+        OperationSide, ParseAmountError,
+    };
+
+    #[test]
+    pub fn test_decimals_base_units() {
+        assert_eq!(Decimals::SOL.unit().unwrap(), 1_000_000_000);
+        assert_eq!(Decimals::USDC.to_base_units(3).unwrap(), 3_000_000);
+        assert_eq!(
+            Decimals::SOL.to_base_units(u64::MAX),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(Decimals(20).unit(), Err(BondingCurveError::Overflow));
+        assert_eq!(
+            Decimals::SOL.split_base_units(1_500_000_001).unwrap(),
+            (1, 500_000_001)
+        );
+    }
+
+    #[test]
+    pub fn test_decimals_parse_and_format() {
+        assert_eq!(Decimals::SOL.parse_ui_amount("1").unwrap(), 1_000_000_000);
+        assert_eq!(Decimals::SOL.parse_ui_amount(".5").unwrap(), 500_000_000);
+        assert_eq!(Decimals::USDC.parse_ui_amount("0.000001").unwrap(), 1);
+        for invalid in ["", ".", "1.0000001", "-1", "1.2.3", "abc", "18446744073710"] {
+            assert_eq!(
+                Decimals::USDC.parse_ui_amount(invalid),
+                Err(ParseAmountError(invalid.to_string()))
+            );
+        }
+
+        assert_eq!(Decimals::USDC.format_ui_amount(1), "0.000001");
+        assert_eq!(Decimals::USDC.format_ui_amount(0), "0");
+        assert_eq!(Decimals::SOL.format_ui_amount(12_000_000_000), "12");
+        assert_eq!(Decimals(0).format_ui_amount(42), "42");
+        for base_units in [0, 1, 999_999, 1_234_567, u64::MAX] {
+            let formatted = Decimals::USDC.format_ui_amount(base_units);
+            assert_eq!(
+                Decimals::USDC.parse_ui_amount(&formatted).unwrap(),
+                base_units
+            );
+        }
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_decimals_float() {
+        assert_eq!(Decimals::SOL.ui_to_base_units(1.5).unwrap(), 1_500_000_000);
+        assert_eq!(Decimals::USDC.ui_to_base_units(0.1).unwrap(), 100_000);
+        assert_eq!(
+            Decimals::SOL.ui_to_base_units(-1.0),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            Decimals::SOL.ui_to_base_units(f64::NAN),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(Decimals::USDC.base_units_to_ui(2_500_000), 2.5);
+    }
+
+    #[test]
+    pub fn test_fixed_price() {
+        let price = FixedPrice::<6>::from_ui_amount(3).unwrap();
+        assert_eq!(price, FixedPrice::new(3_000_000));
+        assert_eq!(FixedPrice::<6>::DECIMALS, Decimals::USDC);
+        let lamports = FixedPrice::<9>::new(1_234_567_891);
+        assert_eq!(lamports.rescale::<6>(false), Ok(FixedPrice::new(1_234_567)));
+        assert_eq!(lamports.rescale::<6>(true), Ok(FixedPrice::new(1_234_568)));
+        assert_eq!(lamports.rescale::<9>(true), Ok(lamports));
+        assert_eq!(
+            FixedPrice::<0>::new(u64::MAX).rescale::<9>(false),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            FixedPrice::<30>::new(1).rescale::<0>(true),
+            Ok(FixedPrice::new(1))
+        );
+        assert_eq!(
+            FixedPrice::<30>::new(1).rescale::<0>(false),
+            Ok(FixedPrice::new(0))
+        );
+        assert_eq!(
+            price.checked_add(FixedPrice::new(1)),
+            Some(FixedPrice::new(3_000_001))
+        );
+        assert_eq!(FixedPrice::<6>::new(0).checked_sub(price), None);
+        assert_eq!(lamports.to_string(), "1.234567891");
+        assert_eq!(
+            "1.0000001".parse::<FixedPrice<6>>(),
+            Err(ParseAmountError("1.0000001".to_string()))
+        );
+    }
+
+    #[test]
+    pub fn test_fixed_curve() {
+        let linear = LinearBondingCurve::new(100, 1000);
+        let curve = FixedCurve::<_, 9>::new(linear);
+        let price: FixedPrice<9> = curve.calculate_fixed_price(10).unwrap();
+        assert_eq!(price, FixedPrice::new(2000));
+        assert_eq!(
+            curve.calculate_fixed_price_many(10, 2, OperationSide::Remove),
+            Ok(FixedPrice::new(3900))
+        );
+        assert_eq!(
+            curve.calculate_fixed_price_many(1, 2, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+}
@@ -0,0 +1,542 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{
+    BondingCurveWithCheckedOperations, CurveFamily, LinearBondingCurve, PhasedCurve,
+    QuadraticBondingCurve,
+};
+#[cfg(feature = "float")]
+use super::{ExponentialBondingCurve, OperationSide};
+
+/// Represents the possible errors that can occur while designing a curve.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DesignError {
+    /// Indicates that the curve family cannot be designed from these constraints.
+    UnsupportedFamily(CurveFamily),
+    /// Indicates that the maximum supply is too small to shape a curve.
+    InvalidSupply,
+    /// Indicates that the target raise is lower than selling every token at the start price.
+    Infeasible,
+    /// Indicates that an overflow occurred while solving the constraints.
+    Overflow,
+}
+
+impl Display for DesignError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DesignError::UnsupportedFamily(family) => {
+                write!(
+                    f,
+                    "The {} family cannot be designed from these constraints.",
+                    family
+                )
+            }
+            DesignError::InvalidSupply => {
+                write!(f, "The maximum supply must be at least 2 tokens.")
+            }
+            DesignError::Infeasible => write!(
+                f,
+                "The target raise is lower than selling the whole supply at the start price."
+            ),
+            DesignError::Overflow => {
+                write!(f, "An overflow occurred while solving the constraints.")
+            }
+        }
+    }
+}
+
+impl Error for DesignError {}
+
+/// Represents a curve solved from a set of design constraints.
+///
+/// # Fields
+///
+/// * `curve`: The designed curve.
+/// * `raise`: The amount raised by selling the whole supply, as quoted by `calculate_price_many`.
+///   Integer curves round their coefficients down, so this never exceeds the target.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveDesign<C, T> {
+    pub curve: C,
+    pub raise: T,
+}
+
+/// Designs a `LinearBondingCurve` that starts at `start_price` and raises at most
+/// `target_raise` when all `max_supply` tokens are sold.
+///
+/// # Formula
+///
+/// ```ignore
+/// raise = max_supply * base + linear * max_supply * (max_supply - 1) / 2
+/// ```
+///
+/// The largest `linear` coefficient that keeps `raise <= target_raise` is chosen.
+///
+/// # Arguments
+///
+/// * `start_price` - The price of the first token, used as `base`.
+/// * `target_raise` - The amount to raise by selling the whole supply.
+/// * `max_supply` - The number of tokens that will be sold.
+///
+/// # Returns
+///
+/// A `Result` containing the designed curve, or a `DesignError` if the constraints cannot be met.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::design_linear;
+///
+/// let design = design_linear(1_000, 2_990_000, 1_000).unwrap();
+/// assert_eq!(design.curve.linear, 3);
+/// assert_eq!(design.raise, 2_498_500);
+/// ```
+pub fn design_linear(
+    start_price: u64,
+    target_raise: u64,
+    max_supply: u64,
+) -> Result<CurveDesign<LinearBondingCurve, u64>, DesignError> {
+    let headroom = headroom(start_price, target_raise, max_supply)?;
+    let n = max_supply as u128;
+    let linear = narrow(headroom * 2 / (n * (n - 1)))?;
+    let curve = LinearBondingCurve::new(linear, start_price);
+    Ok(CurveDesign {
+        raise: raise_integer(start_price, linear as u128 * n * (n - 1) / 2, max_supply)?,
+        curve,
+    })
+}
+
+/// Designs a `QuadraticBondingCurve` that starts at `start_price` and raises at most
+/// `target_raise` when all `max_supply` tokens are sold.
+///
+/// # Formula
+///
+/// ```ignore
+/// squares = (max_supply - 1) * max_supply * (2 * max_supply - 1) / 6
+/// raise = max_supply * base + quadratic * squares + linear * max_supply * (max_supply - 1) / 2
+/// ```
+///
+/// The largest `quadratic` coefficient that keeps `raise <= target_raise` is chosen, and the
+/// `linear` coefficient then takes up as much of the remainder as it can. A target too close
+/// to the start price for any quadratic growth is met by the linear term alone.
+///
+/// # Arguments
+///
+/// * `start_price` - The price of the first token, used as `base`.
+/// * `target_raise` - The amount to raise by selling the whole supply.
+/// * `max_supply` - The number of tokens that will be sold.
+///
+/// # Returns
+///
+/// A `Result` containing the designed curve, or a `DesignError` if the constraints cannot be met.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::design_quadratic;
+///
+/// let design = design_quadratic(1_000, 10_000_000, 100).unwrap();
+/// assert_eq!(design.curve.quadratic, 30);
+/// assert_eq!(design.curve.linear, 10);
+/// assert_eq!(design.raise, 10_000_000);
+/// ```
+pub fn design_quadratic(
+    start_price: u64,
+    target_raise: u64,
+    max_supply: u64,
+) -> Result<CurveDesign<QuadraticBondingCurve, u64>, DesignError> {
+    let headroom = headroom(start_price, target_raise, max_supply)?;
+    let n = max_supply as u128;
+    let squares = (n - 1)
+        .checked_mul(n)
+        .and_then(|x| x.checked_mul(2 * n - 1))
+        .ok_or(DesignError::Overflow)?
+        / 6;
+    let sums = n * (n - 1) / 2;
+    let quadratic = headroom / squares;
+    let linear = (headroom - quadratic * squares) / sums;
+    let curve = QuadraticBondingCurve::new(narrow(quadratic)?, narrow(linear)?, start_price);
+    Ok(CurveDesign {
+        raise: raise_integer(start_price, quadratic * squares + linear * sums, max_supply)?,
+        curve,
+    })
+}
+
+/// Designs an `ExponentialBondingCurve` that starts at `start_price` and raises `target_raise`
+/// when all `max_supply` tokens are sold.
+///
+/// # Formula
+///
+/// The growth rate is solved numerically (by bisection) from:
+///
+/// ```ignore
+/// raise = (base / growth) * (e^(growth * max_supply) - 1)
+/// ```
+///
+/// # Arguments
+///
+/// * `start_price` - The price of the first token, used as `base`.
+/// * `target_raise` - The amount to raise by selling the whole supply.
+/// * `max_supply` - The number of tokens that will be sold.
+///
+/// # Returns
+///
+/// A `Result` containing the designed curve, or a `DesignError` if the constraints cannot be met.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::design_exponential;
+///
+/// let design = design_exponential(0.01, 100.0, 1_000).unwrap();
+/// assert!((design.raise - 100.0).abs() < 1e-6);
+/// ```
+#[cfg(feature = "float")]
+pub fn design_exponential(
+    start_price: f64,
+    target_raise: f64,
+    max_supply: u64,
+) -> Result<CurveDesign<ExponentialBondingCurve, f64>, DesignError> {
+    if max_supply < 2 {
+        return Err(DesignError::InvalidSupply);
+    }
+    if !(start_price > 0.0 && target_raise > start_price * max_supply as f64) {
+        return Err(DesignError::Infeasible);
+    }
+    let raise = |growth: f64| {
+        ExponentialBondingCurve::new(start_price, growth).calculate_price_many_f(
+            0.0,
+            max_supply as f64,
+            OperationSide::Add,
+        )
+    };
+
+    // Grow the upper bound until it overshoots the target, then bisect.
+    let (mut low, mut high) = (0.0f64, 1.0 / max_supply as f64);
+    while raise(high) < target_raise {
+        low = high;
+        high *= 2.0;
+        if !raise(high).is_finite() {
+            return Err(DesignError::Overflow);
+        }
+    }
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if mid <= low || mid >= high {
+            break;
+        }
+        if raise(mid) < target_raise {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(CurveDesign {
+        curve: ExponentialBondingCurve::new(start_price, high),
+        raise: raise(high),
+    })
+}
+
+/// Represents one phase of a raise planned by `plan_raise`.
+///
+/// # Fields
+///
+/// * `name`: A label for the phase, such as `"seed"` or `"public"`.
+/// * `family`: The family of the phase's curve, `Linear` or `Quadratic`.
+/// * `supply`: The number of tokens sold during the phase. Must be at least 2.
+/// * `weight`: The phase's share of the target raise, relative to the weights of the other
+///   phases.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PhasePlan {
+    pub name: String,
+    pub family: CurveFamily,
+    pub supply: u64,
+    pub weight: u64,
+}
+
+impl PhasePlan {
+    /// Creates a new `PhasePlan`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A label for the phase.
+    /// * `family` - The family of the phase's curve.
+    /// * `supply` - The number of tokens sold during the phase.
+    /// * `weight` - The phase's share of the target raise, relative to the other phases.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `PhasePlan`.
+    pub fn new(name: impl Into<String>, family: CurveFamily, supply: u64, weight: u64) -> Self {
+        Self {
+            name: name.into(),
+            family,
+            supply,
+            weight,
+        }
+    }
+}
+
+/// Plans a raise across several phases, splitting `target_raise` between them by weight and
+/// designing each phase's curve so prices never jump between phases.
+///
+/// The first phase starts at `start_price`, and every later phase starts at the price the
+/// phase before it would have charged for one more token. Each phase is then designed like
+/// `design_linear` or `design_quadratic` for its share of the target. Integer curves round
+/// their coefficients down, so a phase can raise a little less than its share; the shortfall
+/// is carried into the share of the next phase.
+///
+/// # Arguments
+///
+/// * `start_price` - The price of the first token of the first phase.
+/// * `target_raise` - The amount to raise by selling every phase.
+/// * `phases` - The phases, in the order they sell.
+///
+/// # Returns
+///
+/// A `Result` containing the `PhasedCurve` of quadratic curves, linear phases with no
+/// quadratic term, and the amount it raises, or a `DesignError` if a phase is of another
+/// family, sells fewer than 2 tokens, or cannot raise its share from its start price, or if
+/// there are no phases. Every weight being zero is `DesignError::Infeasible`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{plan_raise, BondingCurve, CurveFamily, OperationSide, PhasePlan};
+///
+/// let design = plan_raise(
+///     1_000,
+///     10_000_000,
+///     &[
+///         PhasePlan::new("seed", CurveFamily::Linear, 1_000, 1),
+///         PhasePlan::new("public", CurveFamily::Quadratic, 1_000, 3),
+///     ],
+/// )
+/// .unwrap();
+/// let curve = design.curve;
+/// assert!(curve.calculate_price(1_000) >= curve.calculate_price(999));
+/// assert_eq!(curve.calculate_price_many(0, 2_000, OperationSide::Add), design.raise);
+/// assert!(design.raise <= 10_000_000);
+/// ```
+pub fn plan_raise(
+    start_price: u64,
+    target_raise: u64,
+    phases: &[PhasePlan],
+) -> Result<CurveDesign<PhasedCurve<QuadraticBondingCurve>, u64>, DesignError> {
+    if phases.is_empty() {
+        return Err(DesignError::InvalidSupply);
+    }
+    let total_weight: u128 = phases.iter().map(|phase| phase.weight as u128).sum();
+    if total_weight == 0 {
+        return Err(DesignError::Infeasible);
+    }
+    let mut curve = PhasedCurve::new();
+    let mut price = start_price;
+    let mut raised = 0u64;
+    let mut weight = 0u128;
+    for phase in phases {
+        weight += phase.weight as u128;
+        let goal = narrow(target_raise as u128 * weight / total_weight)?;
+        let share = goal.saturating_sub(raised);
+        let design = match phase.family {
+            CurveFamily::Linear => {
+                let design = design_linear(price, share, phase.supply)?;
+                CurveDesign {
+                    curve: QuadraticBondingCurve::new(0, design.curve.linear, design.curve.base),
+                    raise: design.raise,
+                }
+            }
+            CurveFamily::Quadratic => design_quadratic(price, share, phase.supply)?,
+            family => return Err(DesignError::UnsupportedFamily(family)),
+        };
+        raised = raised
+            .checked_add(design.raise)
+            .ok_or(DesignError::Overflow)?;
+        price = design
+            .curve
+            .calculate_price_checked(phase.supply)
+            .map_err(|_| DesignError::Overflow)?;
+        curve = curve.with(phase.name.clone(), phase.supply, design.curve);
+    }
+    Ok(CurveDesign {
+        curve,
+        raise: raised,
+    })
+}
+
+/// Returns how much of the target raise is left after selling every token at the start price.
+fn headroom(start_price: u64, target_raise: u64, max_supply: u64) -> Result<u128, DesignError> {
+    if max_supply < 2 {
+        return Err(DesignError::InvalidSupply);
+    }
+    (target_raise as u128)
+        .checked_sub(start_price as u128 * max_supply as u128)
+        .ok_or(DesignError::Infeasible)
+}
+
+fn raise_integer(start_price: u64, shape: u128, max_supply: u64) -> Result<u64, DesignError> {
+    narrow(start_price as u128 * max_supply as u128 + shape)
+}
+
+fn narrow(value: u128) -> Result<u64, DesignError> {
+    u64::try_from(value).map_err(|_| DesignError::Overflow)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{design_linear, design_quadratic, plan_raise, CurveFamily, DesignError, PhasePlan};
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{BondingCurve, OperationSide};
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_design_linear() {
+        let design = design_linear(1_000, 3_000_000, 1_000).unwrap();
+        assert_eq!(design.curve.base, 1_000);
+        assert_eq!(design.curve.linear, 4);
+        let mut looped = 0u64;
+        for i in 0..1_000u64 {
+            looped += design.curve.calculate_price(i);
+        }
+        assert_eq!(looped, design.raise);
+        assert_eq!(
+            design.raise,
+            design
+                .curve
+                .calculate_price_many(0, 1_000, OperationSide::Add)
+        );
+        assert!(design.raise <= 3_000_000);
+        assert!(design.raise + 999 * 1_000 / 2 > 3_000_000);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_design_quadratic() {
+        let design = design_quadratic(1_000, 10_000_000, 100).unwrap();
+        let mut looped = 0u64;
+        for i in 0..100u64 {
+            looped += design.curve.calculate_price(i);
+        }
+        assert_eq!(looped, design.raise);
+        assert!(design.raise <= 10_000_000);
+        assert_eq!(design.curve.quadratic, 30);
+        assert!(design.raise + 328_350 > 10_000_000);
+        assert_eq!(design.curve.linear, 10);
+        assert_eq!(design.raise, 10_000_000);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_design_quadratic_small_headroom() {
+        // 100_000 of headroom is less than one unit of quadratic growth over 100 tokens.
+        let design = design_quadratic(1_000, 200_000, 100).unwrap();
+        assert_eq!(design.curve.quadratic, 0);
+        assert_eq!(design.curve.linear, 20);
+        assert_eq!(design.raise, 199_000);
+        assert_eq!(
+            design
+                .curve
+                .calculate_price_many(0, 100, OperationSide::Add),
+            design.raise
+        );
+    }
+
+    #[cfg(all(feature = "float", not(feature = "panic-free")))]
+    #[test]
+    pub fn test_design_exponential() {
+        use crate::design_exponential;
+
+        let design = design_exponential(0.01, 100.0, 1_000).unwrap();
+        assert_eq!(design.curve.base, 0.01);
+        let raise = design
+            .curve
+            .calculate_price_many(0, 1_000, OperationSide::Add);
+        assert!(crate::abs_diff_eq(raise, 100.0, 1e-9));
+        assert_eq!(
+            design_exponential(0.01, 1.0, 1_000),
+            Err(DesignError::Infeasible)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_plan_raise() {
+        let phases = [
+            PhasePlan::new("seed", CurveFamily::Linear, 5_000, 1),
+            PhasePlan::new("public", CurveFamily::Quadratic, 5_000, 6),
+            PhasePlan::new("graduation", CurveFamily::Linear, 500, 3),
+        ];
+        let design = plan_raise(1_000, 1_000_000_000_000, &phases).unwrap();
+        let curve = &design.curve;
+        assert_eq!(curve.phases[0].curve.base, 1_000);
+        // Each phase starts where the one before it would have continued.
+        let mut start = 0;
+        for pair in curve.phases.windows(2) {
+            start += pair[0].supply;
+            assert_eq!(
+                pair[1].curve.base,
+                pair[0].curve.calculate_price(pair[0].supply)
+            );
+            assert!(curve.calculate_price(start) >= curve.calculate_price(start - 1));
+        }
+        // The quadratic phase's linear term only takes up what its quadratic term rounded
+        // away, which is less than one more unit of quadratic growth.
+        assert!(curve.phases[1].curve.linear < (2 * 5_000 - 1) / 3);
+        assert_eq!(curve.phases[2].curve.quadratic, 0);
+        assert_eq!(
+            curve.calculate_price_many(0, 10_500, OperationSide::Add),
+            design.raise
+        );
+        // The graduation phase makes up what the earlier phases rounded away.
+        assert!(design.raise <= 1_000_000_000_000);
+        assert!(design.raise > 999_999_000_000);
+    }
+
+    #[test]
+    pub fn test_plan_raise_errors() {
+        let seed = PhasePlan::new("seed", CurveFamily::Linear, 1_000, 1);
+        assert_eq!(plan_raise(1_000, 0, &[]), Err(DesignError::InvalidSupply));
+        assert_eq!(
+            plan_raise(
+                1_000,
+                10_000_000,
+                &[PhasePlan {
+                    weight: 0,
+                    ..seed.clone()
+                }]
+            ),
+            Err(DesignError::Infeasible)
+        );
+        // The second phase starts at 2_000, so 1_000 tokens cannot raise its share of 1_500_000.
+        assert_eq!(
+            plan_raise(1_000, 3_000_000, &[seed.clone(), seed.clone()]),
+            Err(DesignError::Infeasible)
+        );
+        assert_eq!(
+            plan_raise(
+                1_000,
+                10_000_000,
+                &[PhasePlan::new("late", CurveFamily::Sigmoid, 1_000, 1)]
+            ),
+            Err(DesignError::UnsupportedFamily(CurveFamily::Sigmoid))
+        );
+    }
+
+    #[test]
+    pub fn test_design_infeasible() {
+        assert_eq!(
+            design_linear(1_000, 999_999, 1_000),
+            Err(DesignError::Infeasible)
+        );
+        assert_eq!(
+            design_quadratic(1_000, 10, 1_000),
+            Err(DesignError::Infeasible)
+        );
+        assert_eq!(
+            design_linear(1_000, 1_000, 1),
+            Err(DesignError::InvalidSupply)
+        );
+    }
+}
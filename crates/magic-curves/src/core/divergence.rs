@@ -0,0 +1,146 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents one step of `holding_divergence`.
+///
+/// # Fields
+///
+/// * `supply`: The supply of the curve after the step's sale.
+/// * `sold`: The tokens sold so far.
+/// * `proceeds`: The amount received for every sale so far.
+/// * `holding`: The value of holding every token, marked at the spot price of the market
+///   supply.
+/// * `selling`: The value of selling over time, the proceeds plus the unsold tokens marked at
+///   the spot price of `supply`.
+/// * `divergence`: `selling - holding`, negative while selling has done worse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DivergencePoint {
+    pub supply: u64,
+    pub sold: u64,
+    pub proceeds: u128,
+    pub holding: u128,
+    pub selling: u128,
+    pub divergence: i128,
+}
+
+/// Compares holding tokens with selling them into the curve over a simulated supply path.
+///
+/// `trajectory` is the supply of the market at each step, driven by everyone but the holder,
+/// and includes the holder's `tokens`. The selling strategy sells an even share of `tokens`
+/// at every step, so the last step sells the rest; its sales lower the supply it trades at,
+/// so step `i` sells at `trajectory[i]` less the tokens sold before it. Holding marks every
+/// token at the spot price of `trajectory[i]`.
+///
+/// # Arguments
+///
+/// * `curve` - The curve tokens are sold into.
+/// * `tokens` - The tokens held at the start.
+/// * `trajectory` - The market supply at every step.
+///
+/// # Returns
+///
+/// A `Result` containing a `DivergencePoint` per step, `BondingCurveError::SupplyUnderflow`
+/// if a step's supply is below the tokens still to sell, or the `BondingCurveError` of the
+/// curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{holding_divergence, LinearBondingCurve};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let points = holding_divergence(&curve, 10, &[10, 20, 30]).unwrap();
+/// // The market kept buying, so holding beat selling 3, 3, and then 4 tokens.
+/// let last = points[2];
+/// assert_eq!((last.sold, last.supply), (10, 20));
+/// assert_eq!((last.proceeds, last.holding), (26_500, 40_000));
+/// assert_eq!(last.divergence, -13_500);
+/// ```
+pub fn holding_divergence<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    tokens: u64,
+    trajectory: &[u64],
+) -> Result<Vec<DivergencePoint>, BondingCurveError> {
+    #[cfg(feature = "tracing")]
+    let _span =
+        tracing::debug_span!("holding_divergence", tokens, steps = trajectory.len()).entered();
+    let steps = trajectory.len() as u128;
+    let mut points = Vec::with_capacity(trajectory.len());
+    let mut sold = 0;
+    let mut proceeds = 0u128;
+    for (step, &market) in trajectory.iter().enumerate() {
+        let target = (tokens as u128 * (step as u128 + 1) / steps) as u64;
+        let amount = target - sold;
+        let supply = market
+            .checked_sub(sold)
+            .filter(|supply| *supply >= tokens - sold)
+            .ok_or(BondingCurveError::SupplyUnderflow)?;
+        proceeds +=
+            curve.calculate_price_many_checked(supply, amount, OperationSide::Remove)? as u128;
+        sold = target;
+        let supply = supply - amount;
+        let holding = tokens as u128 * curve.calculate_price_checked(market)? as u128;
+        let selling =
+            proceeds + (tokens - sold) as u128 * curve.calculate_price_checked(supply)? as u128;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            step,
+            supply,
+            sold,
+            divergence = selling as i128 - holding as i128,
+            "divergence step"
+        );
+        points.push(DivergencePoint {
+            supply,
+            sold,
+            proceeds,
+            holding,
+            selling,
+            divergence: selling as i128 - holding as i128,
+        });
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{holding_divergence, BondingCurveError, LinearBondingCurve, QuadraticBondingCurve};
+
+    #[test]
+    pub fn test_holding_divergence() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let points = holding_divergence(&curve, 10, &[10, 20, 30]).unwrap();
+        let paths: Vec<(u64, u128, i128)> = points
+            .iter()
+            .map(|point| (point.sold, point.selling, point.divergence))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (3, 17_600, -2_400),
+                (6, 23_100, -6_900),
+                (10, 26_500, -13_500)
+            ]
+        );
+        // On a falling market, selling early beats holding once it outruns its own slippage.
+        let points = holding_divergence(&curve, 10, &[30, 20, 10]).unwrap();
+        let divergences: Vec<i128> = points.iter().map(|point| point.divergence).collect();
+        assert_eq!(divergences, vec![-2_400, -900, 4_500]);
+        assert!(holding_divergence(&curve, 10, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn test_holding_divergence_errors() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        assert_eq!(
+            holding_divergence(&curve, 10, &[100, 5]),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            holding_divergence(&curve, 10, &[9]),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let point = holding_divergence(&curve, 0, &[1_000]).unwrap()[0];
+        assert_eq!((point.holding, point.selling, point.divergence), (0, 0, 0));
+    }
+}
@@ -0,0 +1,140 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, BASIS_POINTS};
+
+/// Represents the outcome of `drawdowns`, with one entry per step of the supply path in
+/// every array, so each can be rendered as a row of a heatmap.
+///
+/// Ratios are in basis points of the price they fall from, and rounded down.
+///
+/// # Fields
+///
+/// * `prices`: The spot price at every step, the price a buyer entering there pays.
+/// * `peaks`: The highest price from the entry on.
+/// * `max_drawdowns`: The largest fall from a peak to a later price, from the entry on.
+/// * `max_losses`: The largest fall below the entry price, from the entry on.
+/// * `under_water`: The number of later steps priced below the entry price.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawdownReport {
+    pub prices: Vec<u64>,
+    pub peaks: Vec<u64>,
+    pub max_drawdowns: Vec<u64>,
+    pub max_losses: Vec<u64>,
+    pub under_water: Vec<u64>,
+}
+
+/// Analyzes what a buyer entering at each step of a supply path goes through afterwards: the
+/// best price they could exit at, the worst drawdown they sit through, and how long they are
+/// under water.
+///
+/// Every step is priced at the spot price of its supply, so `trajectory` is the supply of
+/// the market over time, such as one simulated with a random walk. The analysis is quadratic
+/// in the length of the path.
+///
+/// # Arguments
+///
+/// * `curve` - The curve that prices the path.
+/// * `trajectory` - The supply at every step.
+///
+/// # Returns
+///
+/// A `Result` containing the `DrawdownReport`, or the `BondingCurveError` a price fails with.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{drawdowns, LinearBondingCurve};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let report = drawdowns(&curve, &[10, 30, 10, 0, 20]).unwrap();
+/// assert_eq!(report.prices, vec![2_000, 4_000, 2_000, 1_000, 3_000]);
+/// // Buying at the top sits through a 75% drawdown and is under water to the end.
+/// assert_eq!((report.peaks[1], report.max_drawdowns[1]), (4_000, 7_500));
+/// assert_eq!((report.max_losses[1], report.under_water[1]), (7_500, 3));
+/// // Buying first loses half at worst, but gives back 75% of the peak after it.
+/// assert_eq!((report.max_drawdowns[0], report.max_losses[0]), (7_500, 5_000));
+/// ```
+pub fn drawdowns<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    trajectory: &[u64],
+) -> Result<DrawdownReport, BondingCurveError> {
+    let prices = trajectory
+        .iter()
+        .map(|&supply| curve.calculate_price_checked(supply))
+        .collect::<Result<Vec<u64>, _>>()?;
+    let mut report = DrawdownReport {
+        prices: Vec::with_capacity(prices.len()),
+        peaks: Vec::with_capacity(prices.len()),
+        max_drawdowns: Vec::with_capacity(prices.len()),
+        max_losses: Vec::with_capacity(prices.len()),
+        under_water: Vec::with_capacity(prices.len()),
+    };
+    for (entry, &price) in prices.iter().enumerate() {
+        let (mut peak, mut max_drawdown, mut trough, mut under_water) = (price, 0, price, 0);
+        for &later in &prices[entry + 1..] {
+            peak = peak.max(later);
+            max_drawdown = max_drawdown.max(ratio(peak - later, peak));
+            trough = trough.min(later);
+            under_water += (later < price) as u64;
+        }
+        report.peaks.push(peak);
+        report.max_drawdowns.push(max_drawdown);
+        report.max_losses.push(ratio(price - trough, price));
+        report.under_water.push(under_water);
+    }
+    report.prices = prices;
+    Ok(report)
+}
+
+/// Returns `fall` in basis points of `from`, or zero if `from` is zero.
+fn ratio(fall: u64, from: u64) -> u64 {
+    if from == 0 {
+        return 0;
+    }
+    (fall as u128 * BASIS_POINTS as u128 / from as u128) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{drawdowns, BondingCurveError, LinearBondingCurve, QuadraticBondingCurve};
+
+    #[test]
+    pub fn test_drawdowns() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        // A rising path never draws down.
+        let report = drawdowns(&curve, &[0, 10, 100, 1_000]).unwrap();
+        assert!(report.max_drawdowns.iter().all(|&drawdown| drawdown == 0));
+        assert!(report.under_water.iter().all(|&steps| steps == 0));
+        assert_eq!(report.peaks, vec![report.prices[3]; 4]);
+
+        let report = drawdowns(&curve, &[1_000, 100, 1_000, 10]).unwrap();
+        assert_eq!(report.under_water, vec![2, 1, 1, 0]);
+        assert_eq!(report.max_drawdowns[0], report.max_losses[0]);
+        // The last entry has nothing after it.
+        assert_eq!(
+            (
+                report.peaks[3],
+                report.max_drawdowns[3],
+                report.max_losses[3]
+            ),
+            (report.prices[3], 0, 0)
+        );
+        assert!(report.max_losses[1] < report.max_drawdowns[1]);
+    }
+
+    #[test]
+    pub fn test_drawdowns_edges() {
+        assert_eq!(
+            drawdowns(&LinearBondingCurve::new(1, 0), &[]),
+            Ok(Default::default())
+        );
+        let free = drawdowns(&LinearBondingCurve::new(0, 0), &[5, 0]).unwrap();
+        assert_eq!(
+            (free.max_drawdowns, free.max_losses),
+            (vec![0, 0], vec![0, 0])
+        );
+        assert_eq!(
+            drawdowns(&LinearBondingCurve::new(u64::MAX, 0), &[1, 2]),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
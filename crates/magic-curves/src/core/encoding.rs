@@ -0,0 +1,133 @@
+use super::{AnyBondingCurve, BondingCurveError};
+
+/// The version byte written by `AnyBondingCurve::encode`.
+pub const CURVE_ENCODING_VERSION: u8 = 1;
+
+/// The size in bytes of an encoded curve.
+pub const ENCODED_CURVE_LEN: usize = 1 + 1 + 3 * 8;
+
+impl AnyBondingCurve {
+    /// Encodes the curve in the compact binary format.
+    ///
+    /// The layout is fixed and does not depend on borsh or any other serialization library:
+    ///
+    /// | Offset | Size | Field                                           |
+    /// | ------ | ---- | ----------------------------------------------- |
+    /// | 0      | 1    | `CURVE_ENCODING_VERSION`                        |
+    /// | 1      | 1    | `CurveFamily::tag`                              |
+    /// | 2      | 24   | Three little-endian `u64` parameters            |
+    ///
+    /// Parameters are stored in declaration order of the curve's fields, with `f64`
+    /// fields stored as their raw bits. Unused slots are zero.
+    ///
+    /// # Returns
+    ///
+    /// The encoded curve.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{AnyBondingCurve, LinearBondingCurve, ENCODED_CURVE_LEN};
+    ///
+    /// let curve = AnyBondingCurve::from(LinearBondingCurve::new(100, 1000));
+    /// let bytes = curve.encode();
+    /// assert_eq!(bytes.len(), ENCODED_CURVE_LEN);
+    /// assert_eq!(AnyBondingCurve::decode(&bytes).unwrap(), curve);
+    /// ```
+    pub fn encode(&self) -> [u8; ENCODED_CURVE_LEN] {
+        let mut bytes = [0u8; ENCODED_CURVE_LEN];
+        bytes[0] = CURVE_ENCODING_VERSION;
+        bytes[1] = self.family().tag();
+        for (chunk, param) in bytes[2..].chunks_exact_mut(8).zip(self.params()) {
+            chunk.copy_from_slice(&param.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a curve from the compact binary format written by `encode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Exactly `ENCODED_CURVE_LEN` bytes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded curve, `BondingCurveError::InvalidEncoding` if the
+    /// length or version is wrong, `BondingCurveError::InvalidCurveTag` if the tag is unknown,
+    /// or the error of the float curve's `try_new` if its parameters are invalid.
+    pub fn decode(bytes: &[u8]) -> Result<Self, BondingCurveError> {
+        if bytes.len() != ENCODED_CURVE_LEN || bytes[0] != CURVE_ENCODING_VERSION {
+            return Err(BondingCurveError::InvalidEncoding);
+        }
+        let mut params = [0u64; 3];
+        for (param, chunk) in params.iter_mut().zip(bytes[2..].chunks_exact(8)) {
+            let mut le = [0u8; 8];
+            le.copy_from_slice(chunk);
+            *param = u64::from_le_bytes(le);
+        }
+        Self::from_params(bytes[1], params)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        AnyBondingCurve, BondingCurveError, QuadraticBondingCurve, CURVE_ENCODING_VERSION,
+    };
+
+    #[test]
+    pub fn test_curve_encoding_layout() {
+        let curve = AnyBondingCurve::from(QuadraticBondingCurve::new(3, 2, 1));
+        let bytes = curve.encode();
+        assert_eq!(bytes[..2], [CURVE_ENCODING_VERSION, 1]);
+        assert_eq!(bytes[2..10], 3u64.to_le_bytes());
+        assert_eq!(bytes[10..18], 2u64.to_le_bytes());
+        assert_eq!(bytes[18..], 1u64.to_le_bytes());
+        assert_eq!(AnyBondingCurve::decode(&bytes).unwrap(), curve);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_curve_encoding_float_round_trip() {
+        use crate::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+        for curve in [
+            AnyBondingCurve::from(ExponentialBondingCurve::new(0.01, 0.02)),
+            AnyBondingCurve::from(LogarithmicBondingCurve::new(0.02, 0.5)),
+            AnyBondingCurve::from(SigmoidBondingCurve::new(1_000.0, 0.01, 500)),
+        ] {
+            assert_eq!(AnyBondingCurve::decode(&curve.encode()).unwrap(), curve);
+        }
+        let invalid = AnyBondingCurve::from(ExponentialBondingCurve::new(f64::NAN, 0.02));
+        assert_eq!(
+            AnyBondingCurve::decode(&invalid.encode()),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        let flat = AnyBondingCurve::from(SigmoidBondingCurve::new(1_000.0, 0.0, 500));
+        assert_eq!(
+            AnyBondingCurve::decode(&flat.encode()),
+            Err(BondingCurveError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    pub fn test_curve_decoding_errors() {
+        let bytes = AnyBondingCurve::from(QuadraticBondingCurve::new(3, 2, 1)).encode();
+        assert_eq!(
+            AnyBondingCurve::decode(&bytes[..25]),
+            Err(BondingCurveError::InvalidEncoding)
+        );
+        let mut unversioned = bytes;
+        unversioned[0] = 0;
+        assert_eq!(
+            AnyBondingCurve::decode(&unversioned),
+            Err(BondingCurveError::InvalidEncoding)
+        );
+        let mut untagged = bytes;
+        untagged[1] = 200;
+        assert_eq!(
+            AnyBondingCurve::decode(&untagged),
+            Err(BondingCurveError::InvalidCurveTag)
+        );
+    }
+}
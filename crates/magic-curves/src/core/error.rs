@@ -1,28 +1,206 @@
-use std::{
-    error::Error,
-    fmt::{self, Display, Formatter},
-};
+/// Represents the category of a `BondingCurveError`, for layers that handle whole classes of
+/// errors the same way.
+///
+/// # Variants
+///
+/// * `Math`: The arithmetic of a formula failed.
+/// * `Parameter`: A curve parameter or encoded curve is invalid.
+/// * `Policy`: A rule of the market, such as a supply cap or graduation, rejects the trade.
+/// * `State`: The current supply or reserve cannot support the trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorCategory {
+    Math,
+    Parameter,
+    Policy,
+    State,
+}
 
 /// Represents the possible errors that can occur during decimal operations.
-#[derive(Debug)]
+///
+/// Every variant has a stable numeric `code` and an `ErrorCategory`. The enum is
+/// `#[non_exhaustive]`, so new variants can be added without a breaking release; match on
+/// `category` or `code` to handle the ones a caller does not know yet. Variants keep their
+/// declaration order, which serialized errors depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum BondingCurveError {
     /// Indicates that an overflow occurred during the operation.
+    #[error("An overflow occurred during the operation.")]
     Overflow,
     /// Indicates that a division by zero occurred during the operation.
+    #[error("A division by zero occurred during the operation.")]
     DivisionByZero,
+    /// Indicates that serialized curve data is tagged with an unknown or unexpected curve family.
+    #[error("The curve data is tagged with an unexpected curve family.")]
+    InvalidCurveTag,
+    /// Indicates that encoded curve data has an unsupported version or the wrong length.
+    #[error("The encoded curve data has an unsupported version or length.")]
+    InvalidEncoding,
+    /// Indicates that more tokens would be removed than the supply holds.
+    #[error("More tokens would be removed than the supply holds.")]
+    SupplyUnderflow,
+    /// Indicates that a curve parameter is outside the range the curve's formulas support.
+    #[error("A curve parameter is outside the supported range.")]
+    InvalidParameter,
+    /// Indicates that more tokens would be added than the supply cap allows.
+    #[error("More tokens would be added than the supply cap allows.")]
+    SupplyCapExceeded,
+    /// Indicates that the curve has graduated and no longer accepts the trade.
+    #[error("The curve has graduated and no longer accepts the trade.")]
+    Graduated,
+    /// Indicates that a reserve does not hold enough to pay for the trade.
+    #[error("The reserve does not hold enough to pay for the trade.")]
+    InsufficientReserve,
 }
 
-impl Display for BondingCurveError {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+impl BondingCurveError {
+    /// Returns the stable numeric code of this error.
+    ///
+    /// Codes start at `7000`, above the first codes Anchor assigns to a program's own
+    /// `#[error_code]` enum, and never change once assigned. They are the codes used by
+    /// the `solana` and `anchor` error conversions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::BondingCurveError;
+    ///
+    /// assert_eq!(BondingCurveError::Overflow.code(), 7000);
+    /// ```
+    pub fn code(&self) -> u32 {
         match self {
-            BondingCurveError::Overflow => {
-                write!(f, "An overflow occurred during the operation.")
+            BondingCurveError::Overflow => 7000,
+            BondingCurveError::DivisionByZero => 7001,
+            BondingCurveError::InvalidCurveTag => 7002,
+            BondingCurveError::InvalidEncoding => 7003,
+            BondingCurveError::SupplyUnderflow => 7004,
+            BondingCurveError::InvalidParameter => 7005,
+            BondingCurveError::SupplyCapExceeded => 7006,
+            BondingCurveError::Graduated => 7007,
+            BondingCurveError::InsufficientReserve => 7008,
+        }
+    }
+
+    /// Returns the category of this error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveError, ErrorCategory};
+    ///
+    /// assert_eq!(BondingCurveError::Graduated.category(), ErrorCategory::Policy);
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            BondingCurveError::Overflow | BondingCurveError::DivisionByZero => ErrorCategory::Math,
+            BondingCurveError::InvalidCurveTag
+            | BondingCurveError::InvalidEncoding
+            | BondingCurveError::InvalidParameter => ErrorCategory::Parameter,
+            BondingCurveError::SupplyCapExceeded | BondingCurveError::Graduated => {
+                ErrorCategory::Policy
             }
-            BondingCurveError::DivisionByZero => {
-                write!(f, "A division by zero occurred during the operation.")
+            BondingCurveError::SupplyUnderflow | BondingCurveError::InsufficientReserve => {
+                ErrorCategory::State
             }
         }
     }
+
+    /// Returns the name of this error variant.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BondingCurveError::Overflow => "Overflow",
+            BondingCurveError::DivisionByZero => "DivisionByZero",
+            BondingCurveError::InvalidCurveTag => "InvalidCurveTag",
+            BondingCurveError::InvalidEncoding => "InvalidEncoding",
+            BondingCurveError::SupplyUnderflow => "SupplyUnderflow",
+            BondingCurveError::InvalidParameter => "InvalidParameter",
+            BondingCurveError::SupplyCapExceeded => "SupplyCapExceeded",
+            BondingCurveError::Graduated => "Graduated",
+            BondingCurveError::InsufficientReserve => "InsufficientReserve",
+        }
+    }
+}
+
+#[cfg(feature = "solana")]
+impl From<BondingCurveError> for solana_program_error::ProgramError {
+    fn from(error: BondingCurveError) -> Self {
+        solana_program_error::ProgramError::Custom(error.code())
+    }
+}
+
+#[cfg(feature = "anchor")]
+impl From<BondingCurveError> for anchor_lang_error::Error {
+    fn from(error: BondingCurveError) -> Self {
+        anchor_lang_error::AnchorError {
+            error_name: error.name().to_string(),
+            error_code_number: error.code(),
+            error_msg: error.to_string(),
+            error_origin: None,
+            compared_values: None,
+        }
+        .into()
+    }
 }
 
-impl Error for BondingCurveError {}
+#[cfg(test)]
+mod test {
+    use crate::{BondingCurveError, ErrorCategory};
+
+    #[test]
+    pub fn test_error_codes_are_stable() {
+        assert_eq!(BondingCurveError::Overflow.code(), 7000);
+        assert_eq!(BondingCurveError::DivisionByZero.code(), 7001);
+        assert_eq!(BondingCurveError::InvalidCurveTag.code(), 7002);
+        assert_eq!(BondingCurveError::InvalidEncoding.code(), 7003);
+        assert_eq!(BondingCurveError::SupplyUnderflow.code(), 7004);
+        assert_eq!(BondingCurveError::InvalidParameter.code(), 7005);
+        assert_eq!(BondingCurveError::SupplyCapExceeded.code(), 7006);
+        assert_eq!(BondingCurveError::Graduated.code(), 7007);
+        assert_eq!(BondingCurveError::InsufficientReserve.code(), 7008);
+    }
+
+    #[test]
+    pub fn test_error_categories() {
+        assert_eq!(BondingCurveError::Overflow.category(), ErrorCategory::Math);
+        assert_eq!(
+            BondingCurveError::InvalidEncoding.category(),
+            ErrorCategory::Parameter
+        );
+        assert_eq!(
+            BondingCurveError::SupplyCapExceeded.category(),
+            ErrorCategory::Policy
+        );
+        assert_eq!(
+            BondingCurveError::InsufficientReserve.category(),
+            ErrorCategory::State
+        );
+        assert_eq!(
+            BondingCurveError::Overflow.to_string(),
+            "An overflow occurred during the operation."
+        );
+    }
+
+    #[cfg(feature = "solana")]
+    #[test]
+    pub fn test_program_error_conversion() {
+        use solana_program_error::ProgramError;
+
+        let error: ProgramError = BondingCurveError::DivisionByZero.into();
+        assert_eq!(error, ProgramError::Custom(7001));
+    }
+
+    #[cfg(feature = "anchor")]
+    #[test]
+    pub fn test_anchor_error_conversion() {
+        let error: anchor_lang_error::Error = BondingCurveError::Overflow.into();
+        match error {
+            anchor_lang_error::Error::AnchorError(error) => {
+                assert_eq!(error.error_name, "Overflow");
+                assert_eq!(error.error_code_number, 7000);
+            }
+            _ => panic!("expected an AnchorError"),
+        }
+    }
+}
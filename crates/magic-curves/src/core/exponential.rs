@@ -1,6 +1,16 @@
-use std::f64::consts::E;
+use std::{
+    cmp::Ordering,
+    f64::consts::{E, LN_2},
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
 
-use super::{BondingCurve, OperationSide};
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    canonical_key, finite, math, supply_range, BondingCurveError,
+    BondingCurveWithCheckedOperations, OperationSide,
+};
 
 /// Represents an exponential bonding curve.
 ///
@@ -10,7 +20,16 @@ use super::{BondingCurve, OperationSide};
 ///
 /// * `base`: The base price, which is the initial price for the first token.
 /// * `growth`: The growth rate that determines how quickly the price increases.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "super::ExponentialConfig",
+        into = "super::ExponentialConfig"
+    )
+)]
 pub struct ExponentialBondingCurve {
     pub base: f64,
     pub growth: f64,
@@ -38,8 +57,303 @@ impl ExponentialBondingCurve {
     pub fn new(base: f64, growth: f64) -> Self {
         Self { base, growth }
     }
+
+    /// Creates a new `ExponentialBondingCurve`, validating its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base price, which is the initial price for the first token.
+    /// * `growth` - The growth rate that determines how quickly the price increases.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the curve, or the error from `ExponentialBondingCurve::validate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveError, ExponentialBondingCurve};
+    ///
+    /// assert!(ExponentialBondingCurve::try_new(0.01, 0.02).is_ok());
+    /// assert_eq!(
+    ///     ExponentialBondingCurve::try_new(0.01, 0.0),
+    ///     Err(BondingCurveError::DivisionByZero)
+    /// );
+    /// ```
+    pub fn try_new(base: f64, growth: f64) -> Result<Self, BondingCurveError> {
+        let curve = Self::new(base, growth);
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    /// Checks that the parameters are supported by the curve's formulas.
+    ///
+    /// # Returns
+    ///
+    /// `BondingCurveError::InvalidParameter` if a parameter is not finite or `base` is
+    /// negative, or `BondingCurveError::DivisionByZero` if `growth` is zero, since the
+    /// integral used by `calculate_price_many` divides by it.
+    pub fn validate(&self) -> Result<(), BondingCurveError> {
+        if !self.base.is_finite() || !self.growth.is_finite() || self.base < 0.0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        if self.growth == 0.0 {
+            return Err(BondingCurveError::DivisionByZero);
+        }
+        Ok(())
+    }
+
+    /// Calculates how many more tokens must be sold to double the price, a simple measure
+    /// of how aggressive the curve is.
+    ///
+    /// Exponential prices double every `ln(2) / growth` tokens, whatever the supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_supply` - The supply to measure from.
+    ///
+    /// # Returns
+    ///
+    /// The fractional number of tokens, or `None` if the price is not positive or never
+    /// doubles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::ExponentialBondingCurve;
+    ///
+    /// let curve = ExponentialBondingCurve::new(0.01, 0.02);
+    /// let tokens = curve.supply_to_double_price(100).unwrap();
+    /// assert!((tokens - 34.657).abs() < 1e-3);
+    /// ```
+    pub fn supply_to_double_price(&self, from_supply: u64) -> Option<f64> {
+        let price = self.calculate_price_f(from_supply as f64);
+        if !(price > 0.0 && price.is_finite() && self.growth > 0.0) {
+            return None;
+        }
+        Some(LN_2 / self.growth)
+    }
+
+    /// Returns a copy of the curve with a new `growth`, and the `base` adjusted so the price
+    /// at `supply` does not change, so a live curve can be made steeper or flatter without a
+    /// price jump.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply, whose price is kept.
+    /// * `growth` - The new growth rate.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retuned curve, or the error from
+    /// `ExponentialBondingCurve::validate` if the new curve is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{relative_eq, BondingCurve, ExponentialBondingCurve};
+    ///
+    /// let curve = ExponentialBondingCurve::new(0.01, 0.02);
+    /// let steeper = curve.retune_growth(100, 0.03).unwrap();
+    /// assert!(steeper.base < curve.base);
+    /// assert!(relative_eq(steeper.calculate_price(100), curve.calculate_price(100), 1e-12));
+    /// ```
+    pub fn retune_growth(&self, supply: u64, growth: f64) -> Result<Self, BondingCurveError> {
+        // `base * e^(old * s) / e^(new * s)`, without the overflow of either power.
+        let base = self.base * math::exp((self.growth - growth) * supply as f64);
+        Self::try_new(base, growth)
+    }
+
+    /// Calculates the price at a fractional supply, for continuous-token models.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens, in fractional units.
+    ///
+    /// # Returns
+    ///
+    /// The price at `supply`, equal to `calculate_price` at whole supplies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, ExponentialBondingCurve};
+    ///
+    /// let curve = ExponentialBondingCurve::new(0.01, 0.02);
+    /// assert_eq!(curve.calculate_price_f(100.0), curve.calculate_price(100));
+    /// assert!(curve.calculate_price_f(100.5) > curve.calculate_price(100));
+    /// ```
+    pub fn calculate_price_f(&self, supply: f64) -> f64 {
+        self.base * math::powf(E, self.growth * supply)
+    }
+
+    /// Calculates the price for a fractional amount of tokens from a fractional supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens, in fractional units.
+    /// * `amount` - The amount of tokens to calculate the price for, in fractional units.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price, the integral of the price between the two supplies. Removing more
+    /// than `starting_supply` integrates over negative supplies.
+    pub fn calculate_price_many_f(
+        &self,
+        starting_supply: f64,
+        amount: f64,
+        side: OperationSide,
+    ) -> f64 {
+        if amount == 0.0 {
+            return 0.0;
+        }
+        let end = match side {
+            OperationSide::Add => starting_supply + amount,
+            OperationSide::Remove => starting_supply - amount,
+        };
+        let integral = self.base / self.growth
+            * (math::powf(E, self.growth * end) - math::powf(E, self.growth * starting_supply));
+        match side {
+            OperationSide::Add => integral,
+            OperationSide::Remove => -integral,
+        }
+    }
+
+    /// The largest relative error of `calculate_price_fast` against the exact price, for
+    /// prices that are normal floats.
+    pub const FAST_MAX_RELATIVE_ERROR: f64 = 2e-7;
+
+    /// Calculates the price based on the supply with a fast `exp` approximation, for
+    /// latency-critical repricing loops that do not need full precision.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// The price of the token, within `FAST_MAX_RELATIVE_ERROR` of `calculate_price`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{relative_eq, BondingCurve, ExponentialBondingCurve};
+    ///
+    /// let curve = ExponentialBondingCurve::new(0.01, 0.02);
+    /// assert!(relative_eq(
+    ///     curve.calculate_price_fast(100),
+    ///     curve.calculate_price(100),
+    ///     ExponentialBondingCurve::FAST_MAX_RELATIVE_ERROR
+    /// ));
+    /// ```
+    pub fn calculate_price_fast(&self, supply: u64) -> f64 {
+        self.base * math::fast_exp(self.growth * supply as f64)
+    }
+
+    /// Calculates the price for a given amount of tokens with a fast `exp` approximation.
+    ///
+    /// Each of the two exponentials in the integral is within `FAST_MAX_RELATIVE_ERROR`, so
+    /// the absolute error is at most `FAST_MAX_RELATIVE_ERROR * (base / growth) *
+    /// (e^(growth * end) + e^(growth * start))`. Small amounts subtract two close values and
+    /// may therefore lose far more relative precision than `calculate_price_fast`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The approximate total price for the given amount of tokens.
+    pub fn calculate_price_many_fast(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> f64 {
+        if amount == 0 {
+            return 0.0;
+        }
+        let start = starting_supply as f64;
+        let end = match side {
+            OperationSide::Add => (starting_supply + amount) as f64,
+            OperationSide::Remove => (starting_supply - amount) as f64,
+        };
+        let integral = self.base / self.growth
+            * (math::fast_exp(self.growth * end) - math::fast_exp(self.growth * start));
+        match side {
+            OperationSide::Add => integral,
+            OperationSide::Remove => -integral,
+        }
+    }
+}
+
+impl ExponentialBondingCurve {
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64) {
+        (canonical_key(self.base), canonical_key(self.growth))
+    }
+
+    /// Returns the integral of the price over a trade, which must stay within `0..=u64::MAX`.
+    fn price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
+        if amount == 0 {
+            return 0.0;
+        }
+
+        let start = starting_supply as f64;
+        let end = match side {
+            OperationSide::Add => (starting_supply + amount) as f64,
+            OperationSide::Remove => (starting_supply - amount) as f64,
+        };
+        // Calculate the integral of the exponential function
+        let integral = self.base / self.growth
+            * (math::powf(E, self.growth * end) - math::powf(E, self.growth * start));
+        match side {
+            OperationSide::Add => integral,
+            OperationSide::Remove => -integral,
+        }
+    }
+}
+
+impl PartialEq for ExponentialBondingCurve {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ExponentialBondingCurve {}
+
+impl Hash for ExponentialBondingCurve {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for ExponentialBondingCurve {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
+impl Ord for ExponentialBondingCurve {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Display for ExponentialBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 0.01·e^(0.02x)`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "f(x) = {}·e^({}x)", self.base, self.growth)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
 impl BondingCurve<f64> for ExponentialBondingCurve {
     /// Calculates the price based on the supply.
     ///
@@ -57,7 +371,7 @@ impl BondingCurve<f64> for ExponentialBondingCurve {
     ///
     /// The price of the token based on the supply.
     fn calculate_price(&self, supply: u64) -> f64 {
-        self.base * E.powf(self.growth * supply as f64)
+        self.calculate_price_f(supply as f64)
     }
 
     /// Calculates the price for a given amount of tokens.
@@ -79,44 +393,87 @@ impl BondingCurve<f64> for ExponentialBondingCurve {
     ///
     /// The total price for the given amount of tokens.
     fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
-        let start = starting_supply as f64;
-        let end = match side {
-            OperationSide::Add => (starting_supply + amount) as f64,
-            OperationSide::Remove => (starting_supply - amount) as f64,
-        };
-        // Calculate the integral of the exponential function
-        let integral =
-            self.base / self.growth * (E.powf(self.growth * end) - E.powf(self.growth * start));
-        match side {
-            OperationSide::Add => integral,
-            OperationSide::Remove => -integral,
+        self.price_many(starting_supply, amount, side)
+    }
+}
+
+impl BondingCurveWithCheckedOperations<f64> for ExponentialBondingCurve {
+    /// Calculates the price based on the supply, validating the curve parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, the error from `ExponentialBondingCurve::validate`, or
+    /// `BondingCurveError::Overflow` if the price is not finite.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        finite(self.calculate_price_f(supply as f64))
+    }
+
+    /// Calculates the price for a given amount of tokens, validating the curve parameters
+    /// and the supply range.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, the error from `ExponentialBondingCurve::validate`,
+    /// `BondingCurveError::SupplyUnderflow` if more than `starting_supply` tokens are
+    /// removed, or `BondingCurveError::Overflow` if the supply or the price leaves its range.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        if supply_range(starting_supply, amount, side)?.is_none() {
+            return Ok(0.0);
+        }
+        if side == OperationSide::Add && starting_supply.checked_add(amount).is_none() {
+            return Err(BondingCurveError::Overflow);
         }
+        finite(self.price_many(starting_supply, amount, side))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::ExponentialBondingCurve;
+    #[cfg(not(feature = "panic-free"))]
     use crate::{
-        fixed_point_to_float, float_to_fixed_point, BondingCurve, ExponentialBondingCurve,
-        OperationSide,
+        fixed_point_to_float, float_to_fixed_point, relative_eq, BondingCurve, BondingCurveError,
+        BondingCurveWithCheckedOperations, OperationSide,
     };
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_exponential_price_calculus() {
         let curve = ExponentialBondingCurve::new(0.01, 0.02);
         let price = curve.calculate_price(100);
-        assert_eq!(price, 0.073890560989306492);
+        assert_eq!(price, 0.07389056098930649);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_exponential_price_calculus_fixed_point() {
         let base = fixed_point_to_float(1, 2);
         let growth = fixed_point_to_float(2, 2);
         let curve = ExponentialBondingCurve::new(base, growth);
         let price = curve.calculate_price(100);
-        assert_eq!(float_to_fixed_point(price, 9), 0_073_890_560);
+        assert_eq!(float_to_fixed_point(price, 9), 73_890_560);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_exponential_price_calculus_many() {
         let amount = 10;
@@ -129,4 +486,167 @@ mod test {
             curve.calculate_price_many(starting_supply, amount, OperationSide::Remove);
         assert_eq!(remove_price_many, 10480.476782882088);
     }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_exponential_price_many_zero_and_full_exit() {
+        let curve = ExponentialBondingCurve::new(0.01, 0.02);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert_eq!(curve.calculate_price_many(0, 0, side), 0.0);
+            assert_eq!(curve.calculate_price_many(10, 0, side), 0.0);
+        }
+        let full_exit = curve.calculate_price_many(100, 100, OperationSide::Remove);
+        assert!(full_exit.is_finite() && full_exit > 0.0);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_exponential_checked_guards() {
+        let curve = ExponentialBondingCurve::new(0.05, 0.01);
+        assert_eq!(
+            curve.calculate_price_many_checked(1000, 10, OperationSide::Add),
+            Ok(curve.calculate_price_many(1000, 10, OperationSide::Add))
+        );
+        assert_eq!(
+            ExponentialBondingCurve::new(0.05, 0.0).calculate_price_many_checked(
+                0,
+                10,
+                OperationSide::Add
+            ),
+            Err(BondingCurveError::DivisionByZero)
+        );
+        assert_eq!(
+            ExponentialBondingCurve::try_new(f64::NAN, 0.01),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            ExponentialBondingCurve::try_new(-0.01, 0.01),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        // A negative growth is a decaying curve.
+        assert!(ExponentialBondingCurve::try_new(0.01, -0.01).is_ok());
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            curve.calculate_price_checked(1_000_000),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_exponential_fast_error_bound() {
+        let max = ExponentialBondingCurve::FAST_MAX_RELATIVE_ERROR;
+        for growth in [1e-6, 0.001, 0.02, 0.37, -0.05] {
+            let curve = ExponentialBondingCurve::new(1.0, growth);
+            for supply in (0..20_000).step_by(7) {
+                let exact = curve.calculate_price(supply);
+                if !exact.is_normal() {
+                    continue;
+                }
+                let fast = curve.calculate_price_fast(supply);
+                assert!(relative_eq(fast, exact, max), "{} {}", growth, supply);
+            }
+        }
+        let curve = ExponentialBondingCurve::new(0.05, 0.01);
+        assert!(relative_eq(
+            curve.calculate_price_many_fast(1000, 10, OperationSide::Remove),
+            curve.calculate_price_many(1000, 10, OperationSide::Remove),
+            1e-4
+        ));
+        assert_eq!(
+            curve.calculate_price_many_fast(5, 0, OperationSide::Add),
+            0.0
+        );
+        assert_eq!(
+            ExponentialBondingCurve::new(1.0, 1.0).calculate_price_fast(1_000),
+            f64::INFINITY
+        );
+        assert!(ExponentialBondingCurve::new(f64::NAN, 1.0)
+            .calculate_price_fast(1)
+            .is_nan());
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_exponential_fractional_supply() {
+        let curve = ExponentialBondingCurve::new(0.05, 0.01);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert!(relative_eq(
+                curve.calculate_price_many_f(1000.0, 10.0, side),
+                curve.calculate_price_many(1000, 10, side),
+                1e-12
+            ));
+            assert_eq!(curve.calculate_price_many_f(10.5, 0.0, side), 0.0);
+        }
+        let split = curve.calculate_price_many_f(2.5, 0.25, OperationSide::Add)
+            + curve.calculate_price_many_f(2.75, 1.5, OperationSide::Add);
+        assert!(relative_eq(
+            split,
+            curve.calculate_price_many_f(2.5, 1.75, OperationSide::Add),
+            1e-12
+        ));
+        assert!(relative_eq(
+            curve.calculate_price_many_f(4.25, 1.75, OperationSide::Remove),
+            curve.calculate_price_many_f(2.5, 1.75, OperationSide::Add),
+            1e-12
+        ));
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_exponential_supply_to_double_price() {
+        let curve = ExponentialBondingCurve::new(0.05, 0.01);
+        for supply in [0, 10, 1_000] {
+            let tokens = curve.supply_to_double_price(supply).unwrap();
+            let doubled = curve.calculate_price_f(supply as f64 + tokens);
+            assert!(relative_eq(
+                doubled,
+                2.0 * curve.calculate_price(supply),
+                1e-12
+            ));
+        }
+        assert_eq!(
+            ExponentialBondingCurve::new(0.05, 0.0).supply_to_double_price(10),
+            None
+        );
+        assert_eq!(
+            ExponentialBondingCurve::new(-0.05, 0.01).supply_to_double_price(10),
+            None
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_exponential_retune() {
+        let curve = ExponentialBondingCurve::new(0.01, 0.02);
+        for growth in [0.001, 0.05, -0.01] {
+            let retuned = curve.retune_growth(500, growth).unwrap();
+            assert_eq!(retuned.growth, growth);
+            assert!(relative_eq(
+                retuned.calculate_price(500),
+                curve.calculate_price(500),
+                1e-12
+            ));
+        }
+        assert_eq!(curve.retune_growth(0, 0.5).unwrap().base, 0.01);
+        assert_eq!(
+            curve.retune_growth(500, 0.0),
+            Err(BondingCurveError::DivisionByZero)
+        );
+        assert_eq!(
+            curve.retune_growth(u64::MAX, -1.0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[test]
+    pub fn test_exponential_display() {
+        assert_eq!(
+            ExponentialBondingCurve::new(0.01, 0.02).to_string(),
+            "f(x) = 0.01·e^(0.02x)"
+        );
+    }
 }
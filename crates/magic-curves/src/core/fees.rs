@@ -0,0 +1,176 @@
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuoteRequest, BASIS_POINTS,
+};
+
+/// Represents the outcome of `quote_with_fees`.
+///
+/// # Fields
+///
+/// * `price`: The total price of the tokens before fees, as quoted by the price curve.
+/// * `fee`: The fee on `price`.
+/// * `fee_rate`: The fee rate of the trade, the average of the fee curve over the traded
+///   tokens, in basis points and rounded up.
+/// * `total`: What the buyer pays, `price + fee`, or the seller receives, `price - fee`.
+/// * `ending_supply`: The supply after the trade.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeQuote {
+    pub price: u64,
+    pub fee: u64,
+    pub fee_rate: u64,
+    pub total: u64,
+    pub ending_supply: u64,
+}
+
+/// Quotes a trade whose fee depends on the supply, with a second curve giving the fee rate
+/// in basis points at every supply, such as fees that decay as the supply grows or spike
+/// near graduation.
+///
+/// The fee rate of a trade is the average of the fee curve over the traded tokens, the same
+/// tokens `calculate_price_many_checked` prices, and the fee is that rate of the price,
+/// rounded up. Any integer curve can be a fee curve; a flat fee is a `LinearBondingCurve`
+/// with no slope.
+///
+/// # Arguments
+///
+/// * `curve` - The curve that prices the tokens.
+/// * `fees` - The curve that gives the fee rate at every supply, in basis points.
+/// * `starting_supply` - The supply before the trade.
+/// * `amount` - The number of tokens to trade.
+/// * `side` - The side of the trade.
+///
+/// # Returns
+///
+/// A `Result` containing the `FeeQuote`, `BondingCurveError::InvalidParameter` if the fee
+/// rate is above `BASIS_POINTS`, `BondingCurveError::Overflow` if a purchase with its fee
+/// does not fit in a `u64`, or the `BondingCurveError` of either curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{quote_with_fees, GeometricBondingCurve, LinearBondingCurve, OperationSide};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// // A 5% fee that decays by 1% of itself with every token sold.
+/// let fees = GeometricBondingCurve::new(500, 9_900);
+/// let early = quote_with_fees(&curve, &fees, 0, 10, OperationSide::Add).unwrap();
+/// assert_eq!((early.price, early.fee_rate, early.fee), (14_500, 478, 694));
+/// assert_eq!(early.total, 15_194);
+/// let late = quote_with_fees(&curve, &fees, 200, 10, OperationSide::Add).unwrap();
+/// assert!(late.fee_rate < early.fee_rate);
+/// ```
+pub fn quote_with_fees<C, F>(
+    curve: &C,
+    fees: &F,
+    starting_supply: u64,
+    amount: u64,
+    side: OperationSide,
+) -> Result<FeeQuote, BondingCurveError>
+where
+    C: BondingCurveWithCheckedOperations<u64>,
+    F: BondingCurveWithCheckedOperations<u64>,
+{
+    let response = QuoteRequest::new(starting_supply, amount, side).quote(curve)?;
+    charge_fees(
+        fees,
+        starting_supply,
+        amount,
+        side,
+        response.price,
+        response.ending_supply,
+    )
+}
+
+/// Charges the fee of a trade already priced at `price`, as `quote_with_fees` does.
+pub(crate) fn charge_fees<F: BondingCurveWithCheckedOperations<u64>>(
+    fees: &F,
+    starting_supply: u64,
+    amount: u64,
+    side: OperationSide,
+    price: u64,
+    ending_supply: u64,
+) -> Result<FeeQuote, BondingCurveError> {
+    let (fee, fee_rate) = if amount == 0 {
+        (0, 0)
+    } else {
+        // The fee rates of the tokens sum to `rates`, so the fee is `price * rates / amount`
+        // basis points.
+        let rates = fees.calculate_price_many_checked(starting_supply, amount, side)? as u128;
+        if rates > amount as u128 * BASIS_POINTS as u128 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let fee = (price as u128 * rates).div_ceil(amount as u128 * BASIS_POINTS as u128);
+        (fee as u64, rates.div_ceil(amount as u128) as u64)
+    };
+    let total = match side {
+        OperationSide::Add => price.checked_add(fee).ok_or(BondingCurveError::Overflow)?,
+        OperationSide::Remove => price - fee,
+    };
+    Ok(FeeQuote {
+        price,
+        fee,
+        fee_rate,
+        total,
+        ending_supply,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        quote_with_fees, BondingCurveError, GeometricBondingCurve, LinearBondingCurve,
+        OperationSide, QuadraticBondingCurve, QuoteRequest, BASIS_POINTS,
+    };
+
+    #[test]
+    pub fn test_quote_with_fees() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        // A flat 1% fee.
+        let flat = LinearBondingCurve::new(0, 100);
+        let quote = quote_with_fees(&curve, &flat, 1_000, 50, OperationSide::Remove).unwrap();
+        let response = QuoteRequest::new(1_000, 50, OperationSide::Remove)
+            .quote(&curve)
+            .unwrap();
+        assert_eq!((quote.price, quote.ending_supply), (response.price, 950));
+        assert_eq!(quote.fee_rate, 100);
+        assert_eq!(quote.fee, response.price.div_ceil(100));
+        assert_eq!(quote.total, quote.price - quote.fee);
+
+        // A fee that spikes as the supply nears graduation.
+        let spike = GeometricBondingCurve::new(10, 10_500);
+        let early = quote_with_fees(&curve, &spike, 0, 10, OperationSide::Add).unwrap();
+        let late = quote_with_fees(&curve, &spike, 90, 10, OperationSide::Add).unwrap();
+        assert!(late.fee_rate > 10 * early.fee_rate);
+        assert_eq!(late.total, late.price + late.fee);
+
+        let none = quote_with_fees(&curve, &spike, 5, 0, OperationSide::Add).unwrap();
+        assert_eq!((none.price, none.fee, none.total), (0, 0, 0));
+    }
+
+    #[test]
+    pub fn test_quote_with_fees_errors() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let all = LinearBondingCurve::new(0, BASIS_POINTS);
+        let quote = quote_with_fees(&curve, &all, 10, 10, OperationSide::Remove).unwrap();
+        assert_eq!((quote.fee, quote.total), (quote.price, 0));
+        assert_eq!(
+            quote_with_fees(
+                &curve,
+                &LinearBondingCurve::new(1, BASIS_POINTS),
+                10,
+                10,
+                OperationSide::Add
+            ),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            quote_with_fees(&curve, &all, 5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let expensive = LinearBondingCurve::new(0, u64::MAX / 2);
+        assert_eq!(
+            quote_with_fees(&expensive, &all, 0, 2, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{
+    BondingCurveWithCheckedOperations, CurveFamily, ExponentialBondingCurve, LinearBondingCurve,
+    LogarithmicBondingCurve, QuadraticBondingCurve,
+};
+
+/// Represents the possible errors that can occur while fitting a curve.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitError {
+    /// Indicates that the curve family cannot be fitted with a closed-form regression.
+    UnsupportedFamily(CurveFamily),
+    /// Indicates that there are fewer points than the curve has parameters.
+    NotEnoughPoints { required: usize, found: usize },
+    /// Indicates that the points do not determine a unique solution (e.g. all at the same supply).
+    Degenerate,
+    /// Indicates that a fitted coefficient cannot be represented by the curve (e.g. a
+    /// negative slope on an integer curve, or a non-positive price for a log-linear fit).
+    InvalidCoefficient(&'static str),
+}
+
+impl Display for FitError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FitError::UnsupportedFamily(family) => {
+                write!(f, "The {} family cannot be fitted.", family)
+            }
+            FitError::NotEnoughPoints { required, found } => write!(
+                f,
+                "At least {} points are required to fit this curve, found {}.",
+                required, found
+            ),
+            FitError::Degenerate => {
+                write!(f, "The points do not determine a unique curve.")
+            }
+            FitError::InvalidCoefficient(name) => write!(
+                f,
+                "The fitted `{}` coefficient cannot be represented by the curve.",
+                name
+            ),
+        }
+    }
+}
+
+impl Error for FitError {}
+
+/// Goodness-of-fit metrics for a fitted curve.
+///
+/// The metrics are computed against the fitted curve itself, so for integer curves they
+/// already account for rounding the coefficients to `u64`.
+///
+/// # Fields
+///
+/// * `r_squared`: The coefficient of determination, `1.0` being a perfect fit.
+/// * `rmse`: The root mean squared error of the fitted prices.
+/// * `max_abs_error`: The largest absolute difference between an observed and a fitted price.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitMetrics {
+    pub r_squared: f64,
+    pub rmse: f64,
+    pub max_abs_error: f64,
+}
+
+/// Represents a curve fitted to observed data, along with its goodness-of-fit metrics.
+///
+/// # Fields
+///
+/// * `curve`: The fitted curve.
+/// * `metrics`: How well the curve matches the observed data.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveFit<C> {
+    pub curve: C,
+    pub metrics: FitMetrics,
+}
+
+/// Fits a `LinearBondingCurve` to observed `(supply, price)` points using least squares.
+///
+/// # Arguments
+///
+/// * `points` - The observed `(supply, price)` pairs.
+///
+/// # Returns
+///
+/// A `Result` containing the fitted curve and its metrics, or a `FitError` if the points
+/// do not determine a representable curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::fit_linear;
+///
+/// let points = [(0, 1000.0), (1, 1100.0), (2, 1200.0)];
+/// let fit = fit_linear(&points).unwrap();
+/// assert_eq!(fit.curve.linear, 100);
+/// assert_eq!(fit.curve.base, 1000);
+/// ```
+pub fn fit_linear(points: &[(u64, f64)]) -> Result<CurveFit<LinearBondingCurve>, FitError> {
+    ensure_points(points, 2)?;
+    let xs: Vec<f64> = points.iter().map(|(x, _)| *x as f64).collect();
+    let ys: Vec<f64> = points.iter().map(|(_, y)| *y).collect();
+    let (slope, intercept) = least_squares(&xs, &ys)?;
+    let curve = LinearBondingCurve::new(
+        to_coefficient(slope, "linear")?,
+        to_coefficient(intercept, "base")?,
+    );
+    Ok(CurveFit {
+        metrics: metrics(points, |x| integer_price(&curve, x)),
+        curve,
+    })
+}
+
+/// Fits a `QuadraticBondingCurve` to observed `(supply, price)` points using least squares.
+///
+/// # Arguments
+///
+/// * `points` - The observed `(supply, price)` pairs.
+///
+/// # Returns
+///
+/// A `Result` containing the fitted curve and its metrics, or a `FitError` if the points
+/// do not determine a representable curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::fit_quadratic;
+///
+/// let points = [(0, 1000.0), (1, 1110.0), (2, 1240.0), (3, 1390.0)];
+/// let fit = fit_quadratic(&points).unwrap();
+/// assert_eq!(fit.curve.quadratic, 10);
+/// assert_eq!(fit.curve.linear, 100);
+/// assert_eq!(fit.curve.base, 1000);
+/// ```
+pub fn fit_quadratic(points: &[(u64, f64)]) -> Result<CurveFit<QuadraticBondingCurve>, FitError> {
+    ensure_points(points, 3)?;
+
+    // Scale supplies to [0, 1] so the normal equations stay well conditioned.
+    let scale = points.iter().map(|(x, _)| *x).max().unwrap_or(0).max(1) as f64;
+
+    // Normal equations for y = c2 * t^2 + c1 * t + c0, where t = x / scale.
+    let mut sums = [0.0f64; 5];
+    let mut rhs = [0.0f64; 3];
+    for (x, y) in points {
+        let t = *x as f64 / scale;
+        let mut power = 1.0;
+        for (k, sum) in sums.iter_mut().enumerate() {
+            *sum += power;
+            if k < 3 {
+                rhs[k] += power * y;
+            }
+            power *= t;
+        }
+    }
+    let matrix = [
+        [sums[4], sums[3], sums[2]],
+        [sums[3], sums[2], sums[1]],
+        [sums[2], sums[1], sums[0]],
+    ];
+    let [c2, c1, c0] = solve_3x3(matrix, [rhs[2], rhs[1], rhs[0]])?;
+
+    let curve = QuadraticBondingCurve::new(
+        to_coefficient(c2 / (scale * scale), "quadratic")?,
+        to_coefficient(c1 / scale, "linear")?,
+        to_coefficient(c0, "base")?,
+    );
+    Ok(CurveFit {
+        metrics: metrics(points, |x| integer_price(&curve, x)),
+        curve,
+    })
+}
+
+/// Fits an `ExponentialBondingCurve` to observed `(supply, price)` points.
+///
+/// The fit is a least squares regression on `ln(price)`, so every price must be positive.
+///
+/// # Arguments
+///
+/// * `points` - The observed `(supply, price)` pairs.
+///
+/// # Returns
+///
+/// A `Result` containing the fitted curve and its metrics, or a `FitError` if the points
+/// do not determine a representable curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{fit_exponential, BondingCurve, ExponentialBondingCurve};
+///
+/// let source = ExponentialBondingCurve::new(0.01, 0.02);
+/// let points: Vec<_> = (0..10).map(|x| (x, source.calculate_price(x))).collect();
+/// let fit = fit_exponential(&points).unwrap();
+/// assert!((fit.curve.growth - 0.02).abs() < 1e-12);
+/// ```
+pub fn fit_exponential(
+    points: &[(u64, f64)],
+) -> Result<CurveFit<ExponentialBondingCurve>, FitError> {
+    ensure_points(points, 2)?;
+    if points.iter().any(|(_, y)| *y <= 0.0) {
+        return Err(FitError::InvalidCoefficient("base"));
+    }
+    let xs: Vec<f64> = points.iter().map(|(x, _)| *x as f64).collect();
+    let ys: Vec<f64> = points.iter().map(|(_, y)| y.ln()).collect();
+    let (growth, ln_base) = least_squares(&xs, &ys)?;
+    let curve = ExponentialBondingCurve::new(ln_base.exp(), growth);
+    Ok(CurveFit {
+        metrics: metrics(points, |x| curve.calculate_price_f(x as f64)),
+        curve,
+    })
+}
+
+/// Fits a `LogarithmicBondingCurve` to observed `(supply, price)` points using least squares.
+///
+/// A supply of `0` is treated the same way the curve does, as pricing at `base`.
+///
+/// # Arguments
+///
+/// * `points` - The observed `(supply, price)` pairs.
+///
+/// # Returns
+///
+/// A `Result` containing the fitted curve and its metrics, or a `FitError` if the points
+/// do not determine a representable curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{fit_logarithmic, BondingCurve, LogarithmicBondingCurve};
+///
+/// let source = LogarithmicBondingCurve::new(0.02, 0.01);
+/// let points: Vec<_> = (1..10).map(|x| (x, source.calculate_price(x))).collect();
+/// let fit = fit_logarithmic(&points).unwrap();
+/// assert!((fit.curve.growth - 0.01).abs() < 1e-12);
+/// ```
+pub fn fit_logarithmic(
+    points: &[(u64, f64)],
+) -> Result<CurveFit<LogarithmicBondingCurve>, FitError> {
+    ensure_points(points, 2)?;
+    let xs: Vec<f64> = points
+        .iter()
+        .map(|(x, _)| if *x == 0 { 0.0 } else { (*x as f64).ln() })
+        .collect();
+    let ys: Vec<f64> = points.iter().map(|(_, y)| *y).collect();
+    let (growth, base) = least_squares(&xs, &ys)?;
+    let curve = LogarithmicBondingCurve::new(base, growth);
+    Ok(CurveFit {
+        metrics: metrics(points, |x| curve.calculate_price_f(x as f64)),
+        curve,
+    })
+}
+
+fn ensure_points(points: &[(u64, f64)], required: usize) -> Result<(), FitError> {
+    if points.len() < required {
+        return Err(FitError::NotEnoughPoints {
+            required,
+            found: points.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Ordinary least squares for `y = slope * x + intercept`.
+fn least_squares(xs: &[f64], ys: &[f64]) -> Result<(f64, f64), FitError> {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let (mut sxx, mut sxy) = (0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+    }
+    if sxx == 0.0 {
+        return Err(FitError::Degenerate);
+    }
+    let slope = sxy / sxx;
+    Ok((slope, mean_y - slope * mean_x))
+}
+
+/// Solves a 3x3 linear system using Gaussian elimination with partial pivoting.
+fn solve_3x3(mut m: [[f64; 3]; 3], mut v: [f64; 3]) -> Result<[f64; 3], FitError> {
+    for col in 0..3 {
+        let pivot = (col..3)
+            .max_by(|a, b| m[*a][col].abs().total_cmp(&m[*b][col].abs()))
+            .unwrap_or(col);
+        if m[pivot][col].abs() < f64::EPSILON {
+            return Err(FitError::Degenerate);
+        }
+        m.swap(col, pivot);
+        v.swap(col, pivot);
+        let pivot_row = m[col];
+        for row in col + 1..3 {
+            let factor = m[row][col] / pivot_row[col];
+            for (value, pivot) in m[row].iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot;
+            }
+            v[row] -= factor * v[col];
+        }
+    }
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let tail: f64 = (row + 1..3).map(|k| m[row][k] * x[k]).sum();
+        x[row] = (v[row] - tail) / m[row][row];
+    }
+    Ok(x)
+}
+
+/// Rounds a fitted coefficient to an integer curve parameter.
+fn to_coefficient(value: f64, name: &'static str) -> Result<u64, FitError> {
+    let rounded = value.round();
+    if !rounded.is_finite() || rounded < 0.0 || rounded > u64::MAX as f64 {
+        return Err(FitError::InvalidCoefficient(name));
+    }
+    Ok(rounded as u64)
+}
+
+/// Prices a fitted integer curve at `x` for its metrics, saturating where the price overflows.
+fn integer_price(curve: &impl BondingCurveWithCheckedOperations<u64>, x: u64) -> f64 {
+    curve.calculate_price_checked(x).unwrap_or(u64::MAX) as f64
+}
+
+fn metrics(points: &[(u64, f64)], price: impl Fn(u64) -> f64) -> FitMetrics {
+    let n = points.len() as f64;
+    let mean = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut ss_res, mut ss_tot, mut max_abs_error) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in points {
+        let error = y - price(*x);
+        ss_res += error * error;
+        ss_tot += (y - mean) * (y - mean);
+        max_abs_error = max_abs_error.max(error.abs());
+    }
+    let r_squared = if ss_tot == 0.0 {
+        if ss_res == 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        1.0 - ss_res / ss_tot
+    };
+    FitMetrics {
+        r_squared,
+        rmse: (ss_res / n).sqrt(),
+        max_abs_error,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{fit_exponential, fit_linear, fit_logarithmic, FitError, LinearBondingCurve};
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{fit_quadratic, BondingCurve, QuadraticBondingCurve};
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_fit_linear_exact() {
+        let source = LinearBondingCurve::new(500_000_000, 1_000_000_000);
+        let points: Vec<_> = (0..50)
+            .map(|x| (x * 10, source.calculate_price(x * 10) as f64))
+            .collect();
+        let fit = fit_linear(&points).unwrap();
+        assert_eq!(fit.curve, source);
+        assert_eq!(fit.metrics.r_squared, 1.0);
+        assert_eq!(fit.metrics.max_abs_error, 0.0);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_fit_quadratic_exact() {
+        let source = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let points: Vec<_> = (0..100)
+            .map(|x| (x * 7, source.calculate_price(x * 7) as f64))
+            .collect();
+        let fit = fit_quadratic(&points).unwrap();
+        assert_eq!(fit.curve, source);
+        assert_eq!(fit.metrics.max_abs_error, 0.0);
+    }
+
+    #[test]
+    pub fn test_fit_noisy_linear_metrics() {
+        let points = [(0, 10.0), (1, 21.0), (2, 29.0), (3, 41.0), (4, 49.0)];
+        let fit = fit_linear(&points).unwrap();
+        assert_eq!(fit.curve, LinearBondingCurve::new(10, 10));
+        assert!(fit.metrics.r_squared > 0.99 && fit.metrics.r_squared < 1.0);
+        assert_eq!(fit.metrics.max_abs_error, 1.0);
+    }
+
+    #[test]
+    pub fn test_fit_float_curves() {
+        let exponential = fit_exponential(&[(0, 2.0), (10, 2.0 * 1f64.exp())]).unwrap();
+        assert!(crate::abs_diff_eq(exponential.curve.base, 2.0, 1e-12));
+        assert!(crate::abs_diff_eq(exponential.curve.growth, 0.1, 1e-12));
+
+        let logarithmic = fit_logarithmic(&[(0, 3.0), (100, 3.0 + 100f64.ln())]).unwrap();
+        assert!(crate::abs_diff_eq(logarithmic.curve.base, 3.0, 1e-12));
+        assert!(crate::abs_diff_eq(logarithmic.curve.growth, 1.0, 1e-12));
+        assert!(logarithmic.metrics.rmse < 1e-12);
+    }
+
+    #[test]
+    pub fn test_fit_errors() {
+        assert_eq!(
+            fit_linear(&[(1, 1.0)]),
+            Err(FitError::NotEnoughPoints {
+                required: 2,
+                found: 1
+            })
+        );
+        assert_eq!(fit_linear(&[(1, 1.0), (1, 2.0)]), Err(FitError::Degenerate));
+        assert_eq!(
+            fit_linear(&[(0, 10.0), (1, 5.0)]),
+            Err(FitError::InvalidCoefficient("linear"))
+        );
+        assert_eq!(
+            fit_exponential(&[(0, 0.0), (1, 5.0)]),
+            Err(FitError::InvalidCoefficient("base"))
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use super::{
+    finite, supply_range, BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations,
+    FixedPrice, FixedPriceCurve, OperationSide,
+};
+
+/// Represents a floating point curve quoted in base units, so integrators can price trades in
+/// `u64` without converting every result with `float_to_fixed_point` by hand.
+///
+/// Prices are scaled by `10^DECIMALS` and rounded in the curve's favor: a purchase, and the
+/// price at a supply, round up, and a sale rounds down. Results that are negative, not
+/// finite, or do not fit in a `u64` are a `BondingCurveError::Overflow`, and selling more
+/// tokens than the supply holds is a `BondingCurveError::SupplyUnderflow`.
+///
+/// The decimals of the quote token are part of the type, so the quoter implements
+/// `FixedPriceCurve<DECIMALS>` and its prices can only be read at that scale.
+///
+/// # Fields
+///
+/// * `curve`: The floating point curve, priced in whole quote tokens.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     BondingCurve, BondingCurveWithCheckedOperations, FixedPrice, FixedPriceCurve,
+///     OperationSide, QuoteFixed,
+/// };
+///
+/// struct Flat;
+///
+/// impl BondingCurve<f64> for Flat {
+///     fn calculate_price(&self, _supply: u64) -> f64 {
+///         0.1
+///     }
+/// }
+///
+/// let quoter = Flat.quote_fixed::<6>();
+/// assert_eq!(quoter.calculate_fixed_price(0), Ok(FixedPrice::new(100_000)));
+/// // 0.1 is slightly above a tenth in binary, so a purchase rounds up and a sale down.
+/// assert_eq!(quoter.calculate_price_many_checked(0, 3, OperationSide::Add), Ok(300_001));
+/// assert_eq!(quoter.calculate_price_many_checked(3, 3, OperationSide::Remove), Ok(300_000));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FixedQuoter<C, const DECIMALS: u8> {
+    pub curve: C,
+}
+
+impl<C, const DECIMALS: u8> FixedQuoter<C, DECIMALS> {
+    /// Creates a new `FixedQuoter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The floating point curve, priced in whole quote tokens.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `FixedQuoter`.
+    pub fn new(curve: C) -> Self {
+        Self { curve }
+    }
+
+    /// Converts a price in whole quote tokens into base units.
+    fn to_base_units(&self, price: f64, round_up: bool) -> Result<u64, BondingCurveError> {
+        let scaled = finite(price)? * FixedPrice::<DECIMALS>::DECIMALS.unit()? as f64;
+        let rounded = if round_up {
+            scaled.ceil()
+        } else {
+            scaled.floor()
+        };
+        // `u64::MAX as f64` is `2^64`, which is already out of range.
+        if !(0.0..u64::MAX as f64).contains(&rounded) {
+            return Err(BondingCurveError::Overflow);
+        }
+        Ok(rounded as u64)
+    }
+}
+
+/// Represents a quote in both the native floating point value of a curve and its fixed-point
+/// rendering, from the same computation, so a UI can display the first while a contract
+/// consumes the second.
+///
+/// # Fields
+///
+/// * `value`: The price in whole quote tokens, as the curve computed it.
+/// * `fixed`: The price in base units, rounded like `FixedQuoter`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DualQuote<const DECIMALS: u8> {
+    pub value: f64,
+    pub fixed: FixedPrice<DECIMALS>,
+}
+
+impl<C: BondingCurve<f64>, const DECIMALS: u8> FixedQuoter<C, DECIMALS> {
+    /// Quotes a trade as both a floating point value and a fixed-point amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to trade.
+    /// * `side` - The side of the trade.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `DualQuote`, or a `BondingCurveError` as for
+    /// `calculate_price_many_checked`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, FixedPrice, OperationSide, QuoteFixed};
+    ///
+    /// struct Flat;
+    ///
+    /// impl BondingCurve<f64> for Flat {
+    ///     fn calculate_price(&self, _supply: u64) -> f64 {
+    ///         1.25
+    ///     }
+    /// }
+    ///
+    /// let quote = Flat.quote_fixed::<6>().quote_dual(0, 2, OperationSide::Add).unwrap();
+    /// assert_eq!((quote.value, quote.fixed), (2.5, FixedPrice::new(2_500_000)));
+    /// ```
+    pub fn quote_dual(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<DualQuote<DECIMALS>, BondingCurveError> {
+        let value = match supply_range(starting_supply, amount, side)? {
+            Some(_) => self
+                .curve
+                .calculate_price_many(starting_supply, amount, side),
+            None => 0.0,
+        };
+        Ok(DualQuote {
+            value,
+            fixed: FixedPrice(self.to_base_units(value, side == OperationSide::Add)?),
+        })
+    }
+}
+
+/// Wraps a floating point curve in a `FixedQuoter`.
+pub trait QuoteFixed: BondingCurve<f64> + Sized {
+    /// Quotes the curve in base units of a token with `DECIMALS` decimals.
+    ///
+    /// # Returns
+    ///
+    /// A `FixedQuoter` over the curve.
+    fn quote_fixed<const DECIMALS: u8>(self) -> FixedQuoter<Self, DECIMALS> {
+        FixedQuoter::new(self)
+    }
+}
+
+impl<C: BondingCurve<f64>> QuoteFixed for C {}
+
+impl<C: BondingCurve<f64>, const DECIMALS: u8> BondingCurve<u64> for FixedQuoter<C, DECIMALS> {
+    /// Calculates the price at `supply` in base units, panicking if it cannot be quoted.
+    fn calculate_price(&self, supply: u64) -> u64 {
+        match self.calculate_price_checked(supply) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Calculates the total price of a trade in base units, panicking if it cannot be quoted.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        match self.calculate_price_many_checked(starting_supply, amount, side) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<C: BondingCurve<f64>, const DECIMALS: u8> BondingCurveWithCheckedOperations<u64>
+    for FixedQuoter<C, DECIMALS>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.to_base_units(self.curve.calculate_price(supply), true)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        Ok(self.quote_dual(starting_supply, amount, side)?.fixed.0)
+    }
+}
+
+impl<C: BondingCurve<f64>, const DECIMALS: u8> FixedPriceCurve<DECIMALS>
+    for FixedQuoter<C, DECIMALS>
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, Decimals, ExponentialBondingCurve,
+        FixedPrice, FixedPriceCurve, OperationSide, QuoteFixed, QuoteRequest,
+    };
+
+    #[test]
+    pub fn test_fixed_quoter_rounds_in_the_curves_favor() {
+        let curve = ExponentialBondingCurve::new(0.01, 0.02);
+        let quoter = curve.quote_fixed::<9>();
+        for (starting_supply, amount) in [(0, 1), (10, 5), (100, 50)] {
+            let bought = quoter
+                .calculate_price_many_checked(starting_supply, amount, OperationSide::Add)
+                .unwrap();
+            let sold = quoter
+                .calculate_price_many_checked(
+                    starting_supply + amount,
+                    amount,
+                    OperationSide::Remove,
+                )
+                .unwrap();
+            // Selling back what was bought prices the same range, so it never pays out more.
+            assert!(sold <= bought);
+            assert!(bought - sold <= 1);
+        }
+        let quote = QuoteRequest::new(10, 5, OperationSide::Add)
+            .quote(&quoter)
+            .unwrap();
+        assert_eq!(
+            Ok(quote.price),
+            quoter.calculate_price_many_checked(10, 5, OperationSide::Add)
+        );
+        assert_eq!(quote.ending_supply, 15);
+        let fixed = QuoteRequest::new(10, 5, OperationSide::Add)
+            .quote_fixed_price(&quoter)
+            .unwrap();
+        assert_eq!(fixed.price, FixedPrice::<9>::new(quote.price));
+    }
+
+    #[test]
+    pub fn test_fixed_quoter_dual_quote() {
+        let quoter = ExponentialBondingCurve::new(1.0, 1.0).quote_fixed::<9>();
+        let quote = quoter.quote_dual(0, 3, OperationSide::Add).unwrap();
+        assert_eq!(
+            Ok(quote.fixed),
+            quoter.calculate_fixed_price_many(0, 3, OperationSide::Add)
+        );
+        assert_eq!(FixedPrice::<9>::DECIMALS, Decimals::SOL);
+        assert!((Decimals::SOL.base_units_to_ui(quote.fixed.0) - quote.value).abs() < 1e-9);
+        assert_eq!(
+            quoter.quote_dual(2, 3, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[test]
+    pub fn test_fixed_quoter_errors() {
+        let quoter = ExponentialBondingCurve::new(1.0, 1.0).quote_fixed::<9>();
+        assert_eq!(
+            quoter.calculate_price_checked(100),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            quoter.calculate_price_many_checked(1, 2, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            quoter.calculate_price_many_checked(10, 0, OperationSide::Add),
+            Ok(0)
+        );
+        let too_precise = ExponentialBondingCurve::new(1.0, 0.0).quote_fixed::<20>();
+        assert_eq!(
+            too_precise.calculate_price_checked(0),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
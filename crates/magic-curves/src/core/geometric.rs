@@ -0,0 +1,301 @@
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, BASIS_POINTS,
+};
+
+/// The fractional bits of the fixed-point powers of `GeometricBondingCurve`.
+const FRACTION_BITS: u32 = 64;
+
+/// Represents an exponential bonding curve for integer prices, where every token costs a
+/// fixed multiple of the one before it.
+///
+/// Unlike `ExponentialBondingCurve`, the curve never touches floats: the multiplier is
+/// raised to the supply in 64-bit fixed point, so results are identical on every platform.
+/// `calculate_price_many` sums the geometric series in closed form in `u128`, so batch
+/// pricing takes the same few operations for any amount, which suits on-chain programs.
+///
+/// ```ignore
+/// f(x) = base * (multiplier / 10_000)^x
+/// sum(f(x) for x in a..b) = base * (r^b - r^a) / (r - 1), where r = multiplier / 10_000
+/// ```
+///
+/// Prices and totals are the exact values rounded down, up to the rounding of the fixed-point
+/// powers, which is always upward and stays below about `supply / 2^64` of the result.
+/// Totals round the whole series once rather than adding up rounded prices, so they can
+/// exceed the sum of `calculate_price` over the same tokens by less than one per token.
+///
+/// # Fields
+///
+/// * `base`: The price of the first token.
+/// * `multiplier`: The factor between two consecutive prices, in basis points. `10_100`
+///   makes every token 1% more expensive than the last.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, GeometricBondingCurve, OperationSide};
+///
+/// let curve = GeometricBondingCurve::new(1_000_000, 20_000);
+/// assert_eq!(curve.calculate_price(3), 8_000_000);
+/// // 1 + 2 + 4 + 8 + 16 million, without a loop.
+/// assert_eq!(curve.calculate_price_many(0, 5, OperationSide::Add), 31_000_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeometricBondingCurve {
+    pub base: u64,
+    pub multiplier: u64,
+}
+
+impl GeometricBondingCurve {
+    /// Creates a new `GeometricBondingCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The price of the first token.
+    /// * `multiplier` - The factor between two consecutive prices, in basis points.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `GeometricBondingCurve`.
+    pub fn new(base: u64, multiplier: u64) -> Self {
+        Self { base, multiplier }
+    }
+
+    /// Returns the multiplier raised to `exponent` in fixed point, with every step rounded
+    /// up if `round_up` is set and down otherwise, or `None` if it does not fit in a `u128`.
+    ///
+    /// Any overflow means the power, and so the price of a non-zero base, exceeds `2^64`.
+    const fn power(&self, exponent: u64, round_up: bool) -> Option<u128> {
+        let scaled = (self.multiplier as u128) << FRACTION_BITS;
+        let mut factor = if round_up {
+            scaled.div_ceil(BASIS_POINTS as u128)
+        } else {
+            scaled / BASIS_POINTS as u128
+        };
+        let mut power = 1u128 << FRACTION_BITS;
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                let Some(next) = fixed_mul(power, factor, round_up) else {
+                    return None;
+                };
+                power = next;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                let Some(next) = fixed_mul(factor, factor, round_up) else {
+                    return None;
+                };
+                factor = next;
+            }
+        }
+        Some(power)
+    }
+
+    /// Returns the price at `supply`, or `None` if it does not fit in a `u64`. A `const fn`,
+    /// so prices can be computed at compile time.
+    pub(crate) const fn price(&self, supply: u64) -> Option<u64> {
+        if self.base == 0 {
+            return Some(0);
+        }
+        // A power past `2^96` makes the price of a non-zero base exceed `2^64`.
+        let Some(power) = self.power(supply, true) else {
+            return None;
+        };
+        match fixed_mul(self.base as u128, power, false) {
+            Some(price) if price <= u64::MAX as u128 => Some(price as u64),
+            _ => None,
+        }
+    }
+}
+
+/// Multiplies two values and drops `FRACTION_BITS` bits, through a 256-bit product.
+///
+/// # Returns
+///
+/// The result, rounded up if `round_up` is set and down otherwise, or `None` if it does not
+/// fit in a `u128`.
+const fn fixed_mul(a: u128, b: u128, round_up: bool) -> Option<u128> {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+    let low = a_low * b_low;
+    let Some(middle) = (a_high * b_low).checked_add(a_low * b_high) else {
+        return None;
+    };
+    let Some(high) = (a_high * b_high).checked_mul(1 << 64) else {
+        return None;
+    };
+    let carry = (round_up && low & MASK != 0) as u128;
+    let Some(sum) = high.checked_add(middle) else {
+        return None;
+    };
+    let Some(sum) = sum.checked_add(low >> 64) else {
+        return None;
+    };
+    sum.checked_add(carry)
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<u64> for GeometricBondingCurve {
+    /// Calculates the price at `supply`, panicking if it does not fit in a `u64`.
+    fn calculate_price(&self, supply: u64) -> u64 {
+        match self.calculate_price_checked(supply) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Calculates the total price of a trade, panicking if it does not fit in a `u64`.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        match self.calculate_price_many_checked(starting_supply, amount, side) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl BondingCurveWithCheckedOperations<u64> for GeometricBondingCurve {
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.price(supply).ok_or(BondingCurveError::Overflow)
+    }
+
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        if self.base == 0 {
+            return Ok(0);
+        }
+        if self.multiplier == BASIS_POINTS {
+            return self
+                .base
+                .checked_mul(amount)
+                .ok_or(BondingCurveError::Overflow);
+        }
+        // `(r^b - r^a) / (r - 1)`, with the larger power rounded up and the smaller one down
+        // so the difference never comes out short.
+        let growing = self.multiplier > BASIS_POINTS;
+        let start = self
+            .power(first, !growing)
+            .ok_or(BondingCurveError::Overflow)?;
+        let end = match last.checked_add(1) {
+            Some(end) => self
+                .power(end, growing)
+                .ok_or(BondingCurveError::Overflow)?,
+            // Only a shrinking curve can price the last supply, and its power rounds to zero.
+            None if !growing => 0,
+            None => return Err(BondingCurveError::Overflow),
+        };
+        let (difference, step) = if growing {
+            (end - start, self.multiplier - BASIS_POINTS)
+        } else {
+            (start - end, BASIS_POINTS - self.multiplier)
+        };
+        let total = fixed_mul(difference, self.base as u128 * BASIS_POINTS as u128, false)
+            .ok_or(BondingCurveError::Overflow)?
+            / step as u128;
+        u64::try_from(total).map_err(|_| BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::BondingCurve;
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, GeometricBondingCurve, OperationSide,
+    };
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_geometric_prices() {
+        let curve = GeometricBondingCurve::new(1_000_000_000, 10_100);
+        assert_eq!(curve.calculate_price(0), 1_000_000_000);
+        assert_eq!(curve.calculate_price(1), 1_010_000_000);
+        assert_eq!(curve.calculate_price(2), 1_020_100_000);
+        assert_eq!(
+            curve.calculate_price_many(0, 3, OperationSide::Add),
+            3_030_100_000
+        );
+        let shrinking = GeometricBondingCurve::new(1_000, 5_000);
+        assert_eq!(shrinking.calculate_price(3), 125);
+        assert_eq!(shrinking.calculate_price(20), 0);
+        assert_eq!(
+            GeometricBondingCurve::new(0, 20_000).calculate_price_checked(1_000),
+            Ok(0)
+        );
+        assert_eq!(
+            GeometricBondingCurve::new(1, 20_000).calculate_price_checked(64),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_geometric_series_matches_sum() {
+        for curve in [
+            GeometricBondingCurve::new(1_000_000_000, 10_100),
+            GeometricBondingCurve::new(1_000_000, 15_000),
+            GeometricBondingCurve::new(1_000_000_000, 9_900),
+            GeometricBondingCurve::new(7, 10_001),
+            GeometricBondingCurve::new(1_000, 0),
+        ] {
+            for (supply, amount) in [(0, 1), (0, 10), (37, 25), (5, 1)] {
+                let summed: u64 = (supply..supply + amount)
+                    .map(|s| curve.calculate_price(s))
+                    .sum();
+                let total = curve.calculate_price_many(supply, amount, OperationSide::Add);
+                assert!(total >= summed && total <= summed + amount, "{:?}", curve);
+                assert_eq!(
+                    curve.calculate_price_many(supply + amount, amount, OperationSide::Remove),
+                    curve.calculate_price_many(supply + 1, amount, OperationSide::Add)
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_geometric_series_limits() {
+        let flat = GeometricBondingCurve::new(u64::MAX / 4, 10_000);
+        assert_eq!(
+            flat.calculate_price_many_checked(u64::MAX - 3, 4, OperationSide::Add),
+            Ok(u64::MAX / 4 * 4)
+        );
+        let shrinking = GeometricBondingCurve::new(1_000, 5_000);
+        assert_eq!(
+            shrinking.calculate_price_many_checked(u64::MAX, 10, OperationSide::Remove),
+            Ok(0)
+        );
+        assert_eq!(
+            shrinking.calculate_price_many_checked(0, u64::MAX, OperationSide::Add),
+            Ok(2_000)
+        );
+        let growing = GeometricBondingCurve::new(1_000, 20_000);
+        assert_eq!(
+            growing.calculate_price_many_checked(0, 100, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            growing.calculate_price_many_checked(1, 2, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            GeometricBondingCurve::new(0, 20_000).calculate_price_many_checked(
+                0,
+                u64::MAX,
+                OperationSide::Add
+            ),
+            Ok(0)
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, BASIS_POINTS};
+
+/// Returns the reserve a pool holds if every token of `supply` was bought on `curve`: the
+/// price of buying the whole supply from zero.
+///
+/// # Arguments
+///
+/// * `curve` - The curve the pool prices trades with.
+/// * `supply` - The current supply of tokens.
+///
+/// # Returns
+///
+/// A `Result` containing the theoretical reserve, or the `BondingCurveError` of the curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{reserve_at, LinearBondingCurve};
+///
+/// assert_eq!(reserve_at(&LinearBondingCurve::new(100, 1000), 10), Ok(14_500));
+/// ```
+pub fn reserve_at<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    supply: u64,
+) -> Result<u64, BondingCurveError> {
+    curve.calculate_price_many_checked(0, supply, OperationSide::Add)
+}
+
+/// Represents how the actual reserve of a live pool compares to what its curve expects.
+///
+/// # Fields
+///
+/// * `reserve`: The reserve the pool actually holds.
+/// * `theoretical`: The reserve the curve expects at the current supply, as `reserve_at`.
+/// * `obligation`: The amount the pool owes if every holder sells, the price of selling the
+///   whole supply. A sale prices the supplies it leaves, `1..=supply`, where buying from zero
+///   prices `0..supply`, so the two differ by the last price less the first, and a curve
+///   that pays less on sales lowers the obligation further.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReserveHealth {
+    pub reserve: u64,
+    pub theoretical: u64,
+    pub obligation: u64,
+}
+
+impl ReserveHealth {
+    /// Measures the health of a pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve the pool prices trades with.
+    /// * `supply` - The current supply of tokens.
+    /// * `reserve` - The reserve the pool actually holds.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ReserveHealth`, or the `BondingCurveError` of the curve.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{LinearBondingCurve, ReserveHealth};
+    ///
+    /// let health = ReserveHealth::measure(&LinearBondingCurve::new(100, 1000), 10, 14_000).unwrap();
+    /// assert_eq!(health.ratio(), Some(9_655));
+    /// assert!(!health.is_solvent());
+    /// assert_eq!(health.obligation, 15_500);
+    /// assert_eq!(health.shortfall(), 1_500);
+    /// assert!(health.is_within(500));
+    /// ```
+    pub fn measure<C: BondingCurveWithCheckedOperations<u64>>(
+        curve: &C,
+        supply: u64,
+        reserve: u64,
+    ) -> Result<Self, BondingCurveError> {
+        Ok(Self {
+            reserve,
+            theoretical: reserve_at(curve, supply)?,
+            obligation: curve.calculate_price_many_checked(
+                supply,
+                supply,
+                OperationSide::Remove,
+            )?,
+        })
+    }
+
+    /// Returns the realized reserve ratio, the actual reserve over the theoretical one, in
+    /// basis points rounded down. `BASIS_POINTS` means the pool holds exactly what its curve
+    /// expects.
+    ///
+    /// # Returns
+    ///
+    /// The ratio, saturating at `u64::MAX`, or `None` if the theoretical reserve is zero.
+    pub fn ratio(&self) -> Option<u64> {
+        if self.theoretical == 0 {
+            return None;
+        }
+        let ratio = self.reserve as u128 * BASIS_POINTS as u128 / self.theoretical as u128;
+        Some(u64::try_from(ratio).unwrap_or(u64::MAX))
+    }
+
+    /// Returns whether the reserve covers every holder selling at once.
+    pub fn is_solvent(&self) -> bool {
+        self.reserve >= self.obligation
+    }
+
+    /// Returns how much the reserve falls short of the sell-side obligation, or zero if the
+    /// pool is solvent.
+    pub fn shortfall(&self) -> u64 {
+        self.obligation.saturating_sub(self.reserve)
+    }
+
+    /// Returns how much the reserve holds beyond the sell-side obligation, or zero if the
+    /// pool is insolvent.
+    pub fn surplus(&self) -> u64 {
+        self.reserve.saturating_sub(self.obligation)
+    }
+
+    /// Returns whether the realized reserve ratio is within `tolerance` basis points of
+    /// `BASIS_POINTS`, in either direction. A pool with a theoretical reserve of zero is within
+    /// any tolerance only if its reserve is zero too.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The largest drift to accept, in basis points.
+    pub fn is_within(&self, tolerance: u64) -> bool {
+        match self.ratio() {
+            Some(ratio) => ratio.abs_diff(BASIS_POINTS) <= tolerance,
+            None => self.reserve == 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{reserve_at, BondingCurve, QuadraticBondingCurve};
+    use crate::{
+        BondingCurveError, DampingSchedule, LinearBondingCurve, ReserveHealth, SellDamping,
+    };
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_reserve_health() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let theoretical = reserve_at(&curve, 1_000).unwrap();
+        let exact = ReserveHealth::measure(&curve, 1_000, theoretical).unwrap();
+        let gap = curve.calculate_price(1_000) - curve.calculate_price(0);
+        assert_eq!(exact.obligation, theoretical + gap);
+        assert_eq!(exact.ratio(), Some(10_000));
+        assert!(exact.is_within(0) && !exact.is_solvent());
+        assert_eq!((exact.shortfall(), exact.surplus()), (gap, 0));
+
+        let rich = ReserveHealth::measure(&curve, 1_000, theoretical * 2).unwrap();
+        assert_eq!(rich.ratio(), Some(20_000));
+        assert_eq!(rich.surplus(), theoretical - gap);
+        assert!(rich.is_solvent() && !rich.is_within(9_999) && rich.is_within(10_000));
+
+        let empty = ReserveHealth::measure(&curve, 0, 0).unwrap();
+        assert_eq!(empty.ratio(), None);
+        assert!(empty.is_solvent() && empty.is_within(0));
+        let stray = ReserveHealth::measure(&curve, 0, 1).unwrap();
+        assert!(stray.is_solvent() && !stray.is_within(u64::MAX));
+    }
+
+    #[test]
+    pub fn test_reserve_health_obligations() {
+        // Sales pay half, so far less than the theoretical reserve covers every holder.
+        let schedule = DampingSchedule::BySupply {
+            start: u64::MAX,
+            end: u64::MAX,
+        };
+        let damped = SellDamping::new(LinearBondingCurve::new(100, 1000), 5_000, schedule);
+        let health = ReserveHealth::measure(&damped, 10, 7_750).unwrap();
+        assert_eq!((health.theoretical, health.obligation), (14_500, 7_750));
+        assert_eq!(health.ratio(), Some(5_344));
+        assert!(health.is_solvent() && !health.is_within(4_000));
+        assert_eq!(health.surplus(), 0);
+        assert_eq!(
+            ReserveHealth::measure(&LinearBondingCurve::new(u64::MAX, 0), 3, 0),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
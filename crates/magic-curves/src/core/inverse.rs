@@ -0,0 +1,634 @@
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, GeometricBondingCurve,
+    GeometricLevelCurve, LinearBondingCurve, OperationSide, PegStabilityCurve, PowerLevelCurve,
+    QuadraticBondingCurve, BASIS_POINTS,
+};
+#[cfg(feature = "float")]
+use super::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve, Tolerance};
+
+/// Inverts `calculate_price_many_checked`: finds the amount of tokens whose trade totals a
+/// given cost, the exact counterpart of searching for the most tokens a budget affords.
+///
+/// Integer curves only return an amount that totals `total_cost` exactly, and float curves
+/// one that totals it within `Tolerance::default()`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     BondingCurveWithCheckedOperations, InverseBondingCurve, LinearBondingCurve, OperationSide,
+/// };
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let total = curve
+///     .calculate_price_many_checked(10, 25, OperationSide::Add)
+///     .unwrap();
+/// assert_eq!(curve.amount_for_total(10, total, OperationSide::Add), Ok(Some(25)));
+/// // No amount costs one more.
+/// assert_eq!(curve.amount_for_total(10, total + 1, OperationSide::Add), Ok(None));
+/// ```
+pub trait InverseBondingCurve<T> {
+    /// Finds the amount of tokens whose trade of `side` from `starting_supply` costs
+    /// `total_cost`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The supply before the trade.
+    /// * `total_cost` - The total the trade must cost, or pay when selling.
+    /// * `side` - The side of the trade.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the smallest such amount, `None` if no amount totals
+    /// `total_cost`, or the `BondingCurveError` of an invalid curve.
+    fn amount_for_total(
+        &self,
+        starting_supply: u64,
+        total_cost: T,
+        side: OperationSide,
+    ) -> Result<Option<u64>, BondingCurveError>;
+}
+
+/// Returns the largest amount a trade of `side` from `starting_supply` can have.
+fn max_amount(starting_supply: u64, side: OperationSide) -> u64 {
+    match side {
+        OperationSide::Add => u64::MAX - starting_supply,
+        OperationSide::Remove => starting_supply,
+    }
+}
+
+/// Bisects for the smallest amount up to `max` whose total reaches `total`, relying on the
+/// total only growing with the amount. A total too large to represent reaches any `total`.
+fn smallest_reaching<T: PartialOrd>(
+    total: T,
+    max: u64,
+    price_many: impl Fn(u64) -> Result<T, BondingCurveError>,
+) -> Result<Option<u64>, BondingCurveError> {
+    let reaches = |amount| match price_many(amount) {
+        Ok(cost) => Ok(cost >= total),
+        Err(BondingCurveError::Overflow) => Ok(true),
+        Err(error) => Err(error),
+    };
+    if !reaches(max)? {
+        return Ok(None);
+    }
+    let (mut low, mut high) = (0u64, max);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if reaches(mid)? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(Some(low))
+}
+
+/// Finds the smallest amount of an integer curve whose total is exactly `total`.
+fn exact_total<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    starting_supply: u64,
+    total: u64,
+    side: OperationSide,
+) -> Result<Option<u64>, BondingCurveError> {
+    let price_many = |amount| curve.calculate_price_many_checked(starting_supply, amount, side);
+    let Some(amount) = smallest_reaching(total, max_amount(starting_supply, side), price_many)?
+    else {
+        return Ok(None);
+    };
+    Ok((price_many(amount) == Ok(total)).then_some(amount))
+}
+
+/// Finds the amount of a float curve whose total is nearest `total`, if it is within the
+/// default tolerance.
+#[cfg(feature = "float")]
+fn nearest_total(
+    starting_supply: u64,
+    total: f64,
+    side: OperationSide,
+    price_many: impl Fn(u64) -> Result<f64, BondingCurveError>,
+) -> Result<Option<u64>, BondingCurveError> {
+    let max = max_amount(starting_supply, side);
+    let reached = smallest_reaching(total, max, &price_many)?.unwrap_or(max);
+    let tolerance = Tolerance::default();
+    let mut nearest: Option<(u64, f64)> = None;
+    // The total crosses `total_cost` between the amount that reaches it and the one before.
+    for amount in [reached.saturating_sub(1), reached] {
+        let Ok(cost) = price_many(amount) else {
+            continue;
+        };
+        let distance = (cost - total).abs();
+        if tolerance.approx_eq(cost, total) && nearest.is_none_or(|(_, best)| distance < best) {
+            nearest = Some((amount, distance));
+        }
+    }
+    Ok(nearest.map(|(amount, _)| amount))
+}
+
+impl InverseBondingCurve<u64> for LinearBondingCurve {
+    /// Solves the quadratic `linear * n^2 + (2 * price -/+ linear) * n = 2 * total_cost` for
+    /// `n`, where `price` is the spot price, and falls back to a bisection only when its
+    /// discriminant does not fit in a `u128`.
+    fn amount_for_total(
+        &self,
+        starting_supply: u64,
+        total_cost: u64,
+        side: OperationSide,
+    ) -> Result<Option<u64>, BondingCurveError> {
+        if total_cost == 0 {
+            return Ok(Some(0));
+        }
+        let linear = self.linear as u128;
+        let total = total_cost as u128;
+        // Both sides price the token at `starting_supply` first, and every token after it
+        // adds to the total.
+        let price = linear * starting_supply as u128 + self.base as u128;
+        if price > total {
+            return Ok(None);
+        }
+        let candidate = if linear == 0 {
+            if price == 0 {
+                return Ok(None);
+            }
+            Some(total / price)
+        } else {
+            let (b, ac) = match side {
+                OperationSide::Add => ((2 * price).abs_diff(linear), linear.checked_mul(8 * total)),
+                OperationSide::Remove => (2 * price + linear, linear.checked_mul(8 * total)),
+            };
+            let b2 = b.checked_mul(b);
+            match (side, b2, ac) {
+                (OperationSide::Add, Some(b2), Some(ac)) => {
+                    b2.checked_add(ac).map(|discriminant| {
+                        let root = discriminant.isqrt();
+                        if 2 * price >= linear {
+                            (root - b) / (2 * linear)
+                        } else {
+                            (root + b) / (2 * linear)
+                        }
+                    })
+                }
+                // Selling takes the smaller root, where the total still grows with the amount.
+                (OperationSide::Remove, Some(b2), Some(ac)) => match b2.checked_sub(ac) {
+                    Some(discriminant) => Some((b - discriminant.isqrt()) / (2 * linear)),
+                    None => return Ok(None),
+                },
+                _ => None,
+            }
+        };
+        let Some(amount) = candidate else {
+            return exact_total(self, starting_supply, total_cost, side);
+        };
+        Ok(u64::try_from(amount)
+            .ok()
+            .filter(|&amount| amount <= max_amount(starting_supply, side))
+            .filter(|&amount| {
+                self.calculate_price_many_checked(starting_supply, amount, side) == Ok(total_cost)
+            }))
+    }
+}
+
+impl InverseBondingCurve<u64> for QuadraticBondingCurve {
+    /// Without a quadratic term the curve is linear and solved in closed form; otherwise the
+    /// amount is bisected over the closed-form total.
+    fn amount_for_total(
+        &self,
+        starting_supply: u64,
+        total_cost: u64,
+        side: OperationSide,
+    ) -> Result<Option<u64>, BondingCurveError> {
+        if self.quadratic == 0 {
+            return LinearBondingCurve::new(self.linear, self.base).amount_for_total(
+                starting_supply,
+                total_cost,
+                side,
+            );
+        }
+        exact_total(self, starting_supply, total_cost, side)
+    }
+}
+
+macro_rules! bisected_inverse {
+    ($($curve:ty),*) => {
+        $(
+            impl InverseBondingCurve<u64> for $curve {
+                fn amount_for_total(
+                    &self,
+                    starting_supply: u64,
+                    total_cost: u64,
+                    side: OperationSide,
+                ) -> Result<Option<u64>, BondingCurveError> {
+                    exact_total(self, starting_supply, total_cost, side)
+                }
+            }
+        )*
+    };
+}
+
+bisected_inverse!(
+    GeometricBondingCurve,
+    GeometricLevelCurve,
+    PowerLevelCurve,
+    PegStabilityCurve
+);
+
+#[cfg(feature = "float")]
+impl InverseBondingCurve<f64> for ExponentialBondingCurve {
+    fn amount_for_total(
+        &self,
+        starting_supply: u64,
+        total_cost: f64,
+        side: OperationSide,
+    ) -> Result<Option<u64>, BondingCurveError> {
+        self.validate()?;
+        nearest_total(starting_supply, total_cost, side, |amount| {
+            self.calculate_price_many_checked(starting_supply, amount, side)
+        })
+    }
+}
+
+#[cfg(feature = "float")]
+impl InverseBondingCurve<f64> for LogarithmicBondingCurve {
+    fn amount_for_total(
+        &self,
+        starting_supply: u64,
+        total_cost: f64,
+        side: OperationSide,
+    ) -> Result<Option<u64>, BondingCurveError> {
+        self.validate()?;
+        nearest_total(starting_supply, total_cost, side, |amount| {
+            self.calculate_price_many_checked(starting_supply, amount, side)
+        })
+    }
+}
+
+#[cfg(feature = "float")]
+impl InverseBondingCurve<f64> for SigmoidBondingCurve {
+    fn amount_for_total(
+        &self,
+        starting_supply: u64,
+        total_cost: f64,
+        side: OperationSide,
+    ) -> Result<Option<u64>, BondingCurveError> {
+        self.validate()?;
+        nearest_total(starting_supply, total_cost, side, |amount| {
+            self.calculate_price_many_checked(starting_supply, amount, side)
+        })
+    }
+}
+
+/// Represents a table of the prices of an integer curve every `step` supplies, which answers
+/// price-to-supply queries with a binary search over the table and a bisection within one
+/// step, instead of a bisection over every supply.
+///
+/// The table only suits curves whose prices never fall as the supply grows. Tables of a
+/// `GeometricBondingCurve`, the fixed-point exponential, can be built at compile time with
+/// `GeometricBondingCurve::inverse_table`, so on-chain programs store them as constants;
+/// other curves build theirs with `InverseTable::new`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, GeometricBondingCurve, InverseTable};
+///
+/// const CURVE: GeometricBondingCurve = GeometricBondingCurve {
+///     base: 1_000_000,
+///     multiplier: 10_100,
+/// };
+/// const TABLE: InverseTable<64> = CURVE.inverse_table(16);
+///
+/// // The first supply priced at 2 is 70, so the last one below it is 69.
+/// assert_eq!(TABLE.approximate_supply(2_000_000), Some(64));
+/// assert_eq!(TABLE.supply_at_price(&CURVE, 2_000_000), Ok(Some(69)));
+/// assert!(CURVE.calculate_price(70) > 2_000_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InverseTable<const N: usize> {
+    prices: [u64; N],
+    step: u64,
+}
+
+impl<const N: usize> InverseTable<N> {
+    /// Builds the table of a curve, pricing the supplies `0`, `step`, and so on up to
+    /// `(N - 1) * step`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve to tabulate.
+    /// * `step` - The supplies between two entries of the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the table, `BondingCurveError::InvalidParameter` if `N` or
+    /// `step` is zero or a price falls, or the `BondingCurveError` of a price.
+    pub fn new<C: BondingCurveWithCheckedOperations<u64>>(
+        curve: &C,
+        step: u64,
+    ) -> Result<Self, BondingCurveError> {
+        if N == 0 || step == 0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let mut prices = [0u64; N];
+        for (index, price) in prices.iter_mut().enumerate() {
+            let supply = (index as u64)
+                .checked_mul(step)
+                .ok_or(BondingCurveError::Overflow)?;
+            *price = curve.calculate_price_checked(supply)?;
+        }
+        if prices.windows(2).any(|pair| pair[1] < pair[0]) {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        Ok(Self { prices, step })
+    }
+
+    /// Returns the supplies between two entries of the table.
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Returns the price of every entry of the table.
+    pub fn prices(&self) -> &[u64; N] {
+        &self.prices
+    }
+
+    /// Estimates the last supply priced at most `price` from the table alone, rounding down
+    /// to an entry, so the exact supply is at most `step - 1` larger, or any larger past the
+    /// last entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The price to look up.
+    ///
+    /// # Returns
+    ///
+    /// The supply of the last entry priced at most `price`, or `None` if even the first
+    /// entry costs more.
+    pub fn approximate_supply(&self, price: u64) -> Option<u64> {
+        let entries = self.prices.partition_point(|&entry| entry <= price) as u64;
+        entries.checked_sub(1).map(|entry| entry * self.step)
+    }
+
+    /// Finds the last supply priced at most `price`, refining the table entry with a
+    /// bisection against the curve the table was built from.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve the table was built from.
+    /// * `price` - The price to look up.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the supply, `None` if the first supply costs more than `price`,
+    /// or the `BondingCurveError` of a price other than an overflow, which counts as costing
+    /// more.
+    pub fn supply_at_price<C: BondingCurveWithCheckedOperations<u64>>(
+        &self,
+        curve: &C,
+        price: u64,
+    ) -> Result<Option<u64>, BondingCurveError> {
+        let Some(mut low) = self.approximate_supply(price) else {
+            return Ok(None);
+        };
+        let mut high = if low / self.step + 1 < N as u64 {
+            low + self.step - 1
+        } else {
+            u64::MAX
+        };
+        let within = |supply| match curve.calculate_price_checked(supply) {
+            Ok(cost) => Ok(cost <= price),
+            Err(BondingCurveError::Overflow) => Ok(false),
+            Err(error) => Err(error),
+        };
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if within(mid)? {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Ok(Some(low))
+    }
+}
+
+impl GeometricBondingCurve {
+    /// Builds the `InverseTable` of the curve, like `InverseTable::new` but as a `const fn`, so
+    /// the table can be built at compile time.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The supplies between two entries of the table.
+    ///
+    /// # Returns
+    ///
+    /// The table of the curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics, which fails the build in a `const`, if `N` or `step` is zero, the multiplier is
+    /// below `BASIS_POINTS`, or a price does not fit in a `u64`.
+    pub const fn inverse_table<const N: usize>(&self, step: u64) -> InverseTable<N> {
+        assert!(N > 0 && step > 0, "the table needs entries and a step");
+        assert!(
+            self.multiplier >= BASIS_POINTS,
+            "the prices of the table must not fall"
+        );
+        let mut prices = [0u64; N];
+        let mut index = 0;
+        while index < N {
+            let Some(supply) = (index as u64).checked_mul(step) else {
+                panic!("the supplies of the table do not fit in a u64");
+            };
+            let Some(price) = self.price(supply) else {
+                panic!("the prices of the table do not fit in a u64");
+            };
+            prices[index] = price;
+            index += 1;
+        }
+        InverseTable { prices, step }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, GeometricBondingCurve,
+        InverseBondingCurve, InverseTable, LinearBondingCurve, OperationSide,
+        QuadraticBondingCurve,
+    };
+
+    /// Asserts that every amount up to 200 round-trips through its total on both sides.
+    fn assert_round_trips<C>(curve: &C, supply: u64)
+    where
+        C: BondingCurveWithCheckedOperations<u64> + InverseBondingCurve<u64>,
+    {
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            for amount in 0..=200 {
+                let total = curve
+                    .calculate_price_many_checked(supply, amount, side)
+                    .unwrap();
+                assert_eq!(
+                    curve.amount_for_total(supply, total, side),
+                    Ok(Some(amount)),
+                    "{side:?} {amount}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_amount_for_total_round_trips() {
+        assert_round_trips(&LinearBondingCurve::new(100, 1000), 500);
+        assert_round_trips(&LinearBondingCurve::new(7, 0), 200);
+        assert_round_trips(&LinearBondingCurve::new(0, 3), 300);
+        assert_round_trips(
+            &QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000),
+            1_000,
+        );
+        assert_round_trips(&QuadraticBondingCurve::new(0, 5, 9), 250);
+        assert_round_trips(&GeometricBondingCurve::new(1_000, 10_100), 300);
+    }
+
+    #[test]
+    pub fn test_amount_for_total_without_exact_amount() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        // One token from supply 10 costs 2_000, and two cost 4_100.
+        assert_eq!(
+            curve.amount_for_total(10, 1_999, OperationSide::Add),
+            Ok(None)
+        );
+        assert_eq!(
+            curve.amount_for_total(10, 3_000, OperationSide::Add),
+            Ok(None)
+        );
+        assert_eq!(
+            curve.amount_for_total(10, 4_100, OperationSide::Add),
+            Ok(Some(2))
+        );
+        // Selling all 10 tokens pays 15_500, and no more.
+        assert_eq!(
+            curve.amount_for_total(10, 15_500, OperationSide::Remove),
+            Ok(Some(10))
+        );
+        assert_eq!(
+            curve.amount_for_total(10, 15_600, OperationSide::Remove),
+            Ok(None)
+        );
+        assert_eq!(
+            curve.amount_for_total(10, u64::MAX, OperationSide::Add),
+            Ok(None)
+        );
+        let free = LinearBondingCurve::new(0, 0);
+        assert_eq!(
+            free.amount_for_total(10, 0, OperationSide::Add),
+            Ok(Some(0))
+        );
+        assert_eq!(free.amount_for_total(10, 1, OperationSide::Add), Ok(None));
+    }
+
+    #[cfg(all(feature = "float", not(feature = "panic-free")))]
+    #[test]
+    pub fn test_amount_for_total_of_float_curves() {
+        use crate::{
+            BondingCurve, BondingCurveError, ExponentialBondingCurve, LogarithmicBondingCurve,
+            SigmoidBondingCurve,
+        };
+
+        let exponential = ExponentialBondingCurve::new(0.01, 0.02);
+        let logarithmic = LogarithmicBondingCurve::new(0.02, 0.01);
+        let sigmoid = SigmoidBondingCurve::new(1_000.0, 0.01, 500);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            for amount in [0, 1, 17, 250] {
+                let total = exponential.calculate_price_many(300, amount, side);
+                assert_eq!(
+                    exponential.amount_for_total(300, total, side),
+                    Ok(Some(amount))
+                );
+                let total = logarithmic.calculate_price_many(300, amount, side);
+                assert_eq!(
+                    logarithmic.amount_for_total(300, total, side),
+                    Ok(Some(amount))
+                );
+                let total = sigmoid.calculate_price_many(300, amount, side);
+                assert_eq!(sigmoid.amount_for_total(300, total, side), Ok(Some(amount)));
+            }
+        }
+        // Halfway between two amounts is outside the tolerance.
+        let between = (exponential.calculate_price_many(0, 10, OperationSide::Add)
+            + exponential.calculate_price_many(0, 11, OperationSide::Add))
+            / 2.0;
+        assert_eq!(
+            exponential.amount_for_total(0, between, OperationSide::Add),
+            Ok(None)
+        );
+        assert_eq!(
+            ExponentialBondingCurve::new(f64::NAN, 0.02).amount_for_total(
+                0,
+                1.0,
+                OperationSide::Add
+            ),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[test]
+    pub fn test_inverse_table_matches_search() {
+        const CURVE: GeometricBondingCurve = GeometricBondingCurve {
+            base: 1_000,
+            multiplier: 10_500,
+        };
+        const TABLE: InverseTable<8> = CURVE.inverse_table(10);
+        assert_eq!(TABLE, InverseTable::new(&CURVE, 10).unwrap());
+        let quadratic = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let runtime = InverseTable::<16>::new(&quadratic, 7).unwrap();
+        for price in (0..20_000).step_by(97) {
+            let expected = (0..200u64)
+                .take_while(|&supply| CURVE.calculate_price_checked(supply).unwrap() <= price)
+                .last();
+            assert_eq!(
+                TABLE.supply_at_price(&CURVE, price),
+                Ok(expected),
+                "{price}"
+            );
+            let price = price * 10;
+            let expected = (0..200u64)
+                .take_while(|&supply| quadratic.calculate_price_checked(supply).unwrap() <= price)
+                .last();
+            assert_eq!(
+                runtime.supply_at_price(&quadratic, price),
+                Ok(expected),
+                "{price}"
+            );
+        }
+        // Past the last entry the search runs up to the supply where prices overflow.
+        let beyond = TABLE.supply_at_price(&CURVE, u64::MAX).unwrap().unwrap();
+        assert!(CURVE.calculate_price_checked(beyond).is_ok());
+        assert_eq!(
+            CURVE.calculate_price_checked(beyond + 1),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[test]
+    pub fn test_inverse_table_errors() {
+        let shrinking = GeometricBondingCurve::new(1_000, 9_000);
+        assert_eq!(
+            InverseTable::<4>::new(&shrinking, 1),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        let curve = LinearBondingCurve::new(100, 1000);
+        assert_eq!(
+            InverseTable::<4>::new(&curve, 0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            InverseTable::<0>::new(&curve, 1),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        let table = InverseTable::<4>::new(&curve, 5).unwrap();
+        assert_eq!(table.prices(), &[1_000, 1_500, 2_000, 2_500]);
+        assert_eq!(table.approximate_supply(999), None);
+        assert_eq!(table.supply_at_price(&curve, 999), Ok(None));
+        assert_eq!(table.supply_at_price(&curve, 1_799), Ok(Some(7)));
+    }
+}
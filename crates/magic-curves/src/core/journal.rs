@@ -0,0 +1,553 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{
+    fnv1a, AnyBondingCurve, BondingCurveError, OperationSide, QuoteRequest, ENCODED_CURVE_LEN,
+    FNV_OFFSET_BASIS,
+};
+
+/// The version byte written by `LogRecord::encode`.
+pub const LOG_FORMAT_VERSION: u8 = 1;
+
+/// Represents a state transition of a curve service, as recorded in its log.
+///
+/// # Variants
+///
+/// * `Trade` - Tokens were bought or sold at the current curve.
+/// * `Retune` - The curve was replaced, keeping the supply.
+/// * `PhaseSwitch` - A later phase of a schedule started, pricing with its curve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Transition {
+    Trade { side: OperationSide, amount: u64 },
+    Retune { curve: AnyBondingCurve },
+    PhaseSwitch { phase: u32, curve: AnyBondingCurve },
+}
+
+/// Represents one entry of a replay log, written by `LogState::apply`.
+///
+/// # Fields
+///
+/// * `sequence`: The position of the record in the log, starting at zero.
+/// * `transition`: The recorded transition.
+/// * `hash`: The hash of the state after the transition, chained from the hash before it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogRecord {
+    pub sequence: u64,
+    pub transition: Transition,
+    pub hash: u64,
+}
+
+/// Represents the state a replay log describes: a curve, its supply, and its phase, together
+/// with the hash chain of every transition applied so far.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     replay_log, LinearBondingCurve, LogState, OperationSide, Transition,
+/// };
+///
+/// let genesis = LogState::new(LinearBondingCurve::new(100, 1000).into(), 0);
+/// let mut service = genesis;
+/// let records = [
+///     Transition::Trade { side: OperationSide::Add, amount: 10 },
+///     Transition::Retune { curve: LinearBondingCurve::new(50, 1500).into() },
+///     Transition::Trade { side: OperationSide::Remove, amount: 4 },
+/// ]
+/// .map(|transition| service.apply(transition).unwrap());
+///
+/// let replayed = replay_log(genesis, &records).unwrap();
+/// assert_eq!(replayed, service);
+/// assert_eq!(replayed.supply(), 6);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogState {
+    curve: AnyBondingCurve,
+    supply: u64,
+    phase: u32,
+    sequence: u64,
+    hash: u64,
+}
+
+/// Represents the possible errors that can occur while replaying a log with `replay_log`.
+///
+/// Every variant carries the position of the offending record in the replayed slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayLogError {
+    /// Indicates that a record is not the next one of the log.
+    Sequence {
+        index: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// Indicates that a recorded transition cannot be applied.
+    Transition {
+        index: usize,
+        error: BondingCurveError,
+    },
+    /// Indicates that the state after a record does not have the recorded hash.
+    HashMismatch {
+        index: usize,
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl Display for ReplayLogError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ReplayLogError::Sequence {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Record {}: expected sequence {}, found {}.",
+                index, expected, found
+            ),
+            ReplayLogError::Transition { index, error } => {
+                write!(f, "Record {}: {}", index, error)
+            }
+            ReplayLogError::HashMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Record {}: expected hash {:016x}, found {:016x}.",
+                index, expected, found
+            ),
+        }
+    }
+}
+
+impl Error for ReplayLogError {}
+
+const TRADE: u8 = 0;
+const RETUNE: u8 = 1;
+const PHASE_SWITCH: u8 = 2;
+
+impl LogState {
+    /// Creates the state at the start of a log, in phase zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve trades are priced with.
+    /// * `supply` - The supply at the start of the log.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `LogState`, whose hash commits to the curve and supply.
+    pub fn new(curve: AnyBondingCurve, supply: u64) -> Self {
+        let mut state = Self {
+            curve,
+            supply,
+            phase: 0,
+            sequence: 0,
+            hash: FNV_OFFSET_BASIS,
+        };
+        state.hash = state.chain(FNV_OFFSET_BASIS, &[]);
+        state
+    }
+
+    /// Returns the curve trades are priced with.
+    pub fn curve(&self) -> &AnyBondingCurve {
+        &self.curve
+    }
+
+    /// Returns the current supply of tokens.
+    pub fn supply(&self) -> u64 {
+        self.supply
+    }
+
+    /// Returns the current phase.
+    pub fn phase(&self) -> u32 {
+        self.phase
+    }
+
+    /// Returns the sequence of the next record.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the hash of the state, which commits to every transition applied so far.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Applies a transition, returning the record to append to the log.
+    ///
+    /// Trades are priced against the curve, so a trade the curve rejects is not applied.
+    /// Phases only move forward.
+    ///
+    /// # Arguments
+    ///
+    /// * `transition` - The transition to apply.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `LogRecord`, the `BondingCurveError` of a rejected trade, or
+    /// `BondingCurveError::InvalidParameter` for a phase switch that does not move forward, in
+    /// which case the state is unchanged.
+    pub fn apply(&mut self, transition: Transition) -> Result<LogRecord, BondingCurveError> {
+        let mut next = *self;
+        match transition {
+            Transition::Trade { side, amount } => {
+                next.supply = self
+                    .curve
+                    .quote(&QuoteRequest::new(self.supply, amount, side))?
+                    .ending_supply;
+            }
+            Transition::Retune { curve } => next.curve = curve,
+            Transition::PhaseSwitch { phase, curve } => {
+                if phase <= self.phase {
+                    return Err(BondingCurveError::InvalidParameter);
+                }
+                next.phase = phase;
+                next.curve = curve;
+            }
+        }
+        let record = LogRecord {
+            sequence: self.sequence,
+            transition,
+            hash: 0,
+        };
+        next.sequence = self.sequence + 1;
+        next.hash = next.chain(self.hash, &record.body());
+        *self = next;
+        Ok(LogRecord {
+            hash: self.hash,
+            ..record
+        })
+    }
+
+    /// Returns the FNV-1a hash of `previous`, a record body, and this state.
+    fn chain(&self, previous: u64, body: &[u8]) -> u64 {
+        let hash = fnv1a(FNV_OFFSET_BASIS, &previous.to_le_bytes());
+        let hash = fnv1a(hash, body);
+        let hash = fnv1a(hash, &self.curve.encode());
+        let hash = fnv1a(hash, &self.supply.to_le_bytes());
+        fnv1a(hash, &self.phase.to_le_bytes())
+    }
+}
+
+impl LogRecord {
+    /// Encodes the record in the compact binary format.
+    ///
+    /// The layout is fixed, with every integer in little-endian:
+    ///
+    /// | Offset | Size | Field                                                   |
+    /// | ------ | ---- | ------------------------------------------------------- |
+    /// | 0      | 1    | `LOG_FORMAT_VERSION`                                    |
+    /// | 1      | 1    | The transition: `0` trade, `1` retune, `2` phase switch |
+    /// | 2      | 8    | `sequence`                                              |
+    /// | 10     | n    | The transition fields, in declaration order             |
+    /// | 10 + n | 8    | `hash`                                                  |
+    ///
+    /// A trade stores its side as one byte, `0` to add and `1` to remove, and its amount, so
+    /// `n` is 9. Curves are stored as `AnyBondingCurve::encode`, so `n` is
+    /// `ENCODED_CURVE_LEN` for a retune and 4 more for a phase switch.
+    ///
+    /// # Returns
+    ///
+    /// The encoded record.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = self.body();
+        bytes.extend_from_slice(&self.hash.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a record from the front of `bytes`, as written by `encode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The encoded record, possibly followed by more records.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the record and the number of bytes it took,
+    /// `BondingCurveError::InvalidEncoding` if the bytes are truncated or have the wrong
+    /// version or transition, or the error of `AnyBondingCurve::decode` for a stored curve.
+    pub fn decode(bytes: &[u8]) -> Result<(Self, usize), BondingCurveError> {
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.take(1)?[0] != LOG_FORMAT_VERSION {
+            return Err(BondingCurveError::InvalidEncoding);
+        }
+        let kind = reader.take(1)?[0];
+        let sequence = reader.u64()?;
+        let transition = match kind {
+            TRADE => {
+                let side = match reader.take(1)?[0] {
+                    0 => OperationSide::Add,
+                    1 => OperationSide::Remove,
+                    _ => return Err(BondingCurveError::InvalidEncoding),
+                };
+                Transition::Trade {
+                    side,
+                    amount: reader.u64()?,
+                }
+            }
+            RETUNE => Transition::Retune {
+                curve: AnyBondingCurve::decode(reader.take(ENCODED_CURVE_LEN)?)?,
+            },
+            PHASE_SWITCH => {
+                let mut phase = [0u8; 4];
+                phase.copy_from_slice(reader.take(4)?);
+                Transition::PhaseSwitch {
+                    phase: u32::from_le_bytes(phase),
+                    curve: AnyBondingCurve::decode(reader.take(ENCODED_CURVE_LEN)?)?,
+                }
+            }
+            _ => return Err(BondingCurveError::InvalidEncoding),
+        };
+        let hash = reader.u64()?;
+        let record = LogRecord {
+            sequence,
+            transition,
+            hash,
+        };
+        Ok((record, reader.offset))
+    }
+
+    /// Returns the encoding of the record without its hash.
+    fn body(&self) -> Vec<u8> {
+        let mut bytes = vec![LOG_FORMAT_VERSION];
+        match self.transition {
+            Transition::Trade { side, amount } => {
+                bytes.push(TRADE);
+                bytes.extend_from_slice(&self.sequence.to_le_bytes());
+                bytes.push(match side {
+                    OperationSide::Add => 0,
+                    OperationSide::Remove => 1,
+                });
+                bytes.extend_from_slice(&amount.to_le_bytes());
+            }
+            Transition::Retune { curve } => {
+                bytes.push(RETUNE);
+                bytes.extend_from_slice(&self.sequence.to_le_bytes());
+                bytes.extend_from_slice(&curve.encode());
+            }
+            Transition::PhaseSwitch { phase, curve } => {
+                bytes.push(PHASE_SWITCH);
+                bytes.extend_from_slice(&self.sequence.to_le_bytes());
+                bytes.extend_from_slice(&phase.to_le_bytes());
+                bytes.extend_from_slice(&curve.encode());
+            }
+        }
+        bytes
+    }
+}
+
+/// Reads the fields of an encoded record in order.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BondingCurveError> {
+        let field = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or(BondingCurveError::InvalidEncoding)?;
+        self.offset += len;
+        Ok(field)
+    }
+
+    fn u64(&mut self) -> Result<u64, BondingCurveError> {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(le))
+    }
+}
+
+/// Encodes records back to back, as read by `decode_log`.
+///
+/// # Arguments
+///
+/// * `records` - The records, in log order.
+///
+/// # Returns
+///
+/// The encoded log.
+pub fn encode_log(records: &[LogRecord]) -> Vec<u8> {
+    records.iter().flat_map(LogRecord::encode).collect()
+}
+
+/// Decodes a log written by `encode_log`.
+///
+/// # Arguments
+///
+/// * `bytes` - The encoded log.
+///
+/// # Returns
+///
+/// A `Result` containing the records in log order, or the `BondingCurveError` of
+/// `LogRecord::decode` for the first record that cannot be read.
+pub fn decode_log(mut bytes: &[u8]) -> Result<Vec<LogRecord>, BondingCurveError> {
+    let mut records = Vec::new();
+    while !bytes.is_empty() {
+        let (record, len) = LogRecord::decode(bytes)?;
+        records.push(record);
+        bytes = &bytes[len..];
+    }
+    Ok(records)
+}
+
+/// Reconstructs the state a log describes, verifying every record against it.
+///
+/// Each record must carry the next sequence, apply to the state before it, and leave a state
+/// with the recorded hash. Since every hash is chained from the one before, a log that
+/// replays to the service's current hash is the exact history of that service.
+///
+/// # Arguments
+///
+/// * `genesis` - The state at the start of the log.
+/// * `records` - The records, in log order.
+///
+/// # Returns
+///
+/// A `Result` containing the state after the last record, or the `ReplayLogError` of the
+/// first record that does not verify.
+pub fn replay_log(genesis: LogState, records: &[LogRecord]) -> Result<LogState, ReplayLogError> {
+    let mut state = genesis;
+    for (index, record) in records.iter().enumerate() {
+        if record.sequence != state.sequence {
+            return Err(ReplayLogError::Sequence {
+                index,
+                expected: state.sequence,
+                found: record.sequence,
+            });
+        }
+        let replayed = state
+            .apply(record.transition)
+            .map_err(|error| ReplayLogError::Transition { index, error })?;
+        if replayed.hash != record.hash {
+            return Err(ReplayLogError::HashMismatch {
+                index,
+                expected: record.hash,
+                found: replayed.hash,
+            });
+        }
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        decode_log, encode_log, replay_log, BondingCurveError, LinearBondingCurve, LogRecord,
+        LogState, OperationSide, QuadraticBondingCurve, ReplayLogError, Transition,
+        ENCODED_CURVE_LEN, LOG_FORMAT_VERSION,
+    };
+
+    fn history() -> (LogState, Vec<LogRecord>) {
+        let genesis = LogState::new(LinearBondingCurve::new(100, 1000).into(), 0);
+        let mut state = genesis;
+        let records = [
+            Transition::Trade {
+                side: OperationSide::Add,
+                amount: 100,
+            },
+            Transition::Retune {
+                curve: LinearBondingCurve::new(80, 3000).into(),
+            },
+            Transition::PhaseSwitch {
+                phase: 1,
+                curve: QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000).into(),
+            },
+            Transition::Trade {
+                side: OperationSide::Remove,
+                amount: 30,
+            },
+        ]
+        .map(|transition| state.apply(transition).unwrap())
+        .to_vec();
+        (genesis, records)
+    }
+
+    #[test]
+    pub fn test_log_round_trip() {
+        let (genesis, records) = history();
+        let bytes = encode_log(&records);
+        assert_eq!(
+            bytes.len(),
+            2 * 27 + (18 + ENCODED_CURVE_LEN) + (22 + ENCODED_CURVE_LEN)
+        );
+        assert_eq!(bytes[0], LOG_FORMAT_VERSION);
+        assert_eq!(decode_log(&bytes), Ok(records.clone()));
+
+        let state = replay_log(genesis, &records).unwrap();
+        assert_eq!(
+            (state.supply(), state.phase(), state.sequence()),
+            (70, 1, 4)
+        );
+        assert_eq!(state.hash(), records[3].hash);
+
+        assert_eq!(
+            decode_log(&bytes[..bytes.len() - 1]),
+            Err(BondingCurveError::InvalidEncoding)
+        );
+        let mut unknown = bytes.clone();
+        unknown[1] = 9;
+        assert_eq!(
+            LogRecord::decode(&unknown),
+            Err(BondingCurveError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    pub fn test_replay_log_detects_tampering() {
+        let (genesis, records) = history();
+        let mut tampered = records.clone();
+        tampered[3].transition = Transition::Trade {
+            side: OperationSide::Remove,
+            amount: 31,
+        };
+        assert_eq!(
+            replay_log(genesis, &tampered),
+            Err(ReplayLogError::HashMismatch {
+                index: 3,
+                expected: records[3].hash,
+                found: replay_log(genesis, &tampered[..3])
+                    .unwrap()
+                    .apply(tampered[3].transition)
+                    .unwrap()
+                    .hash,
+            })
+        );
+        assert_eq!(
+            replay_log(genesis, &records[1..]),
+            Err(ReplayLogError::Sequence {
+                index: 0,
+                expected: 0,
+                found: 1
+            })
+        );
+        // A different genesis gives every record a different hash.
+        let other = LogState::new(LinearBondingCurve::new(100, 1001).into(), 0);
+        assert!(matches!(
+            replay_log(other, &records),
+            Err(ReplayLogError::HashMismatch { index: 0, .. })
+        ));
+
+        let mut state = genesis;
+        assert_eq!(
+            state.apply(Transition::Trade {
+                side: OperationSide::Remove,
+                amount: 1
+            }),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            state.apply(Transition::PhaseSwitch {
+                phase: 0,
+                curve: *state.curve()
+            }),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(state, genesis);
+    }
+}
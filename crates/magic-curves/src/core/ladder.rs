@@ -0,0 +1,145 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents one row of a price ladder.
+///
+/// # Fields
+///
+/// * `supply`: The supply level of the row.
+/// * `price`: The spot price at `supply`.
+/// * `cumulative_cost`: The price of moving the supply from the current supply to `supply`:
+///   the cost of buying up to it for rows above the current supply, or the refund of selling
+///   down to it for rows below.
+/// * `side`: Whether reaching `supply` adds or removes tokens.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LadderRow<T> {
+    pub supply: u64,
+    pub price: T,
+    pub cumulative_cost: T,
+    pub side: OperationSide,
+}
+
+/// Builds a ladder of supply levels around the current supply, ready to seed limit orders
+/// that mirror the curve on an order book.
+///
+/// # Arguments
+///
+/// * `curve` - The curve to price the ladder with.
+/// * `supply` - The current supply.
+/// * `interval` - The number of tokens between two rows.
+/// * `levels_below` - The number of rows below the current supply. Rows below supply zero are
+///   left out.
+/// * `levels_above` - The number of rows above the current supply.
+///
+/// # Returns
+///
+/// A `Result` containing the rows sorted by supply, without the current supply itself,
+/// `BondingCurveError::InvalidParameter` if `interval` is zero, or the first
+/// `BondingCurveError` a price fails with.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{price_ladder, LinearBondingCurve, OperationSide};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let ladder = price_ladder(&curve, 10, 5, 1, 2).unwrap();
+/// let supplies: Vec<u64> = ladder.iter().map(|row| row.supply).collect();
+/// assert_eq!(supplies, vec![5, 15, 20]);
+/// assert_eq!(ladder[0].side, OperationSide::Remove);
+/// assert_eq!(ladder[1].price, 2500);
+/// ```
+pub fn price_ladder<T, C>(
+    curve: &C,
+    supply: u64,
+    interval: u64,
+    levels_below: u64,
+    levels_above: u64,
+) -> Result<Vec<LadderRow<T>>, BondingCurveError>
+where
+    C: BondingCurveWithCheckedOperations<T> + ?Sized,
+{
+    if interval == 0 {
+        return Err(BondingCurveError::InvalidParameter);
+    }
+    let below = levels_below.min(supply / interval);
+    let mut rows = Vec::new();
+    for level in (1..=below).rev() {
+        rows.push(row(curve, supply, level * interval, OperationSide::Remove)?);
+    }
+    for level in 1..=levels_above {
+        let distance = level
+            .checked_mul(interval)
+            .ok_or(BondingCurveError::Overflow)?;
+        rows.push(row(curve, supply, distance, OperationSide::Add)?);
+    }
+    Ok(rows)
+}
+
+fn row<T, C>(
+    curve: &C,
+    supply: u64,
+    distance: u64,
+    side: OperationSide,
+) -> Result<LadderRow<T>, BondingCurveError>
+where
+    C: BondingCurveWithCheckedOperations<T> + ?Sized,
+{
+    let level = match side {
+        OperationSide::Add => supply.checked_add(distance),
+        OperationSide::Remove => supply.checked_sub(distance),
+    }
+    .ok_or(BondingCurveError::Overflow)?;
+    Ok(LadderRow {
+        supply: level,
+        price: curve.calculate_price_checked(level)?,
+        cumulative_cost: curve.calculate_price_many_checked(supply, distance, side)?,
+        side,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        price_ladder, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide,
+        QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_price_ladder() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let ladder = price_ladder(&curve, 100, 10, 3, 2).unwrap();
+        let supplies: Vec<u64> = ladder.iter().map(|row| row.supply).collect();
+        assert_eq!(supplies, vec![70, 80, 90, 110, 120]);
+        assert_eq!(ladder[0].price, curve.calculate_price_checked(70).unwrap());
+        assert_eq!(
+            ladder[0].cumulative_cost,
+            curve
+                .calculate_price_many_checked(100, 30, OperationSide::Remove)
+                .unwrap()
+        );
+        assert_eq!(
+            ladder[4].cumulative_cost,
+            curve
+                .calculate_price_many_checked(100, 20, OperationSide::Add)
+                .unwrap()
+        );
+        assert!(ladder.windows(2).all(|rows| rows[0].price < rows[1].price));
+    }
+
+    #[test]
+    pub fn test_price_ladder_bounds() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let ladder = price_ladder(&curve, 25, 10, 5, 0).unwrap();
+        let supplies: Vec<u64> = ladder.iter().map(|row| row.supply).collect();
+        assert_eq!(supplies, vec![5, 15]);
+        assert_eq!(
+            price_ladder(&curve, 25, 0, 5, 5),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            price_ladder(&curve, u64::MAX - 5, 10, 0, 1),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,287 @@
+#[cfg(not(feature = "panic-free"))]
+use super::{BondingCurve, TimeBondingCurve};
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, BASIS_POINTS};
+
+/// Represents how the premium of a `LaunchRamp` decays over the ramp.
+///
+/// # Variants
+///
+/// * `Linear` - The premium falls linearly from its full value at launch to zero at the end
+///   of the ramp.
+/// * `Halving` - The premium halves every `half_life` units of time, and is dropped at the
+///   end of the ramp.
+/// * `Cliff` - The full premium holds until the end of the ramp.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RampDecay {
+    Linear,
+    Halving { half_life: u64 },
+    Cliff,
+}
+
+/// Represents a curve that charges a decaying premium for the first moments after launch,
+/// so bots that buy in the first block pay far more than buyers who arrive later.
+///
+/// `elapsed` is the time since launch, in seconds, slots, or blocks. While it is below
+/// `duration`, every price of the wrapped curve, on both sides, is raised by the premium in
+/// effect, rounded up:
+///
+/// ```ignore
+/// f(x, t) = curve(x) * (1 + premium(t) / 10_000)
+/// ```
+///
+/// The premium is the same for every token of a trade, so a trade's total scales the wrapped
+/// curve's total and takes no longer to price. As a `BondingCurve`, the curve is priced at
+/// launch; use `TimeBondingCurve` to price it at other times.
+///
+/// # Fields
+///
+/// * `curve`: The curve that prices trades once the ramp is over.
+/// * `premium`: The premium at launch, in basis points of the price. `30_000` quadruples the
+///   price.
+/// * `duration`: The length of the ramp.
+/// * `decay`: How the premium decays over the ramp.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{LaunchRamp, LinearBondingCurve, RampDecay, TimeBondingCurve};
+///
+/// let ramp = LaunchRamp::new(LinearBondingCurve::new(0, 1_000), 10_000, 60, RampDecay::Linear);
+/// assert_eq!(ramp.price_at(0, 0), 2_000);
+/// assert_eq!(ramp.price_at(0, 30), 1_500);
+/// assert_eq!(ramp.price_at(0, 60), 1_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LaunchRamp<C> {
+    pub curve: C,
+    pub premium: u64,
+    pub duration: u64,
+    pub decay: RampDecay,
+}
+
+impl<C> LaunchRamp<C> {
+    /// Creates a new `LaunchRamp`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve that prices trades once the ramp is over.
+    /// * `premium` - The premium at launch, in basis points of the price.
+    /// * `duration` - The length of the ramp.
+    /// * `decay` - How the premium decays over the ramp.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `LaunchRamp`.
+    pub fn new(curve: C, premium: u64, duration: u64, decay: RampDecay) -> Self {
+        Self {
+            curve,
+            premium,
+            duration,
+            decay,
+        }
+    }
+
+    /// Returns the premium at `elapsed` time after launch.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - The time since launch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the premium in basis points, rounded up, or
+    /// `BondingCurveError::InvalidParameter` if a `Halving` decay has a `half_life` of zero.
+    pub fn premium_at(&self, elapsed: u64) -> Result<u64, BondingCurveError> {
+        if elapsed >= self.duration {
+            return Ok(0);
+        }
+        match self.decay {
+            RampDecay::Linear => {
+                let remaining = (self.duration - elapsed) as u128;
+                Ok((self.premium as u128 * remaining).div_ceil(self.duration as u128) as u64)
+            }
+            RampDecay::Halving { half_life: 0 } => Err(BondingCurveError::InvalidParameter),
+            RampDecay::Halving { half_life } => {
+                let halvings = elapsed / half_life;
+                Ok(self.premium.checked_shr(halvings as u32).unwrap_or(0))
+            }
+            RampDecay::Cliff => Ok(self.premium),
+        }
+    }
+}
+
+/// Raises `price` by `premium` basis points, rounding up, or returns `None` on overflow.
+fn raise(price: u64, premium: u64) -> Option<u64> {
+    let raised =
+        (price as u128 * (BASIS_POINTS as u128 + premium as u128)).div_ceil(BASIS_POINTS as u128);
+    u64::try_from(raised).ok()
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> LaunchRamp<C> {
+    /// Calculates the price at the given supply and time, with error checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current total supply of tokens.
+    /// * `elapsed` - The time since launch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, or the `BondingCurveError` of the premium, the wrapped
+    /// curve, or `BondingCurveError::Overflow` if the raised price does not fit in a `u64`.
+    pub fn price_at_checked(&self, supply: u64, elapsed: u64) -> Result<u64, BondingCurveError> {
+        let premium = self.premium_at(elapsed)?;
+        raise(self.curve.calculate_price_checked(supply)?, premium)
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Calculates the price of a trade at a single point in time, with error checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    /// * `elapsed` - The time since launch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, or a `BondingCurveError` as for
+    /// `price_at_checked`.
+    pub fn price_many_at_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        elapsed: u64,
+    ) -> Result<u64, BondingCurveError> {
+        let premium = self.premium_at(elapsed)?;
+        let price = self
+            .curve
+            .calculate_price_many_checked(starting_supply, amount, side)?;
+        raise(price, premium).ok_or(BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurve<u64>> TimeBondingCurve<u64> for LaunchRamp<C> {
+    /// Calculates the price, saturating at `u64::MAX`. A `half_life` of zero drops the
+    /// premium.
+    fn price_at(&self, supply: u64, elapsed: u64) -> u64 {
+        let premium = self.premium_at(elapsed).unwrap_or(0);
+        raise(self.curve.calculate_price(supply), premium).unwrap_or(u64::MAX)
+    }
+
+    /// Calculates the total price of a trade, saturating at `u64::MAX`. A `half_life` of
+    /// zero drops the premium.
+    fn price_many_at(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        elapsed: u64,
+    ) -> u64 {
+        let premium = self.premium_at(elapsed).unwrap_or(0);
+        let price = self
+            .curve
+            .calculate_price_many(starting_supply, amount, side);
+        raise(price, premium).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurve<u64>> BondingCurve<u64> for LaunchRamp<C> {
+    fn calculate_price(&self, supply: u64) -> u64 {
+        self.price_at(supply, 0)
+    }
+
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        self.price_many_at(starting_supply, amount, side, 0)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> BondingCurveWithCheckedOperations<u64>
+    for LaunchRamp<C>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        self.price_at_checked(supply, 0)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        self.price_many_at_checked(starting_supply, amount, side, 0)
+    }
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, LaunchRamp,
+        LinearBondingCurve, OperationSide, QuadraticBondingCurve, RampDecay, TimeBondingCurve,
+    };
+
+    #[test]
+    pub fn test_launch_ramp_decays() {
+        let curve = LinearBondingCurve::new(0, 1_000);
+        let linear = LaunchRamp::new(curve, 30_000, 100, RampDecay::Linear);
+        assert_eq!(linear.premium_at(0), Ok(30_000));
+        assert_eq!(linear.premium_at(99), Ok(300));
+        assert_eq!(linear.premium_at(100), Ok(0));
+        let halving = LaunchRamp::new(curve, 30_000, 100, RampDecay::Halving { half_life: 10 });
+        assert_eq!(halving.premium_at(9), Ok(30_000));
+        assert_eq!(halving.premium_at(25), Ok(7_500));
+        assert_eq!(halving.premium_at(99), Ok(58));
+        assert_eq!(halving.premium_at(100), Ok(0));
+        let long = LaunchRamp::new(curve, 30_000, u64::MAX, RampDecay::Halving { half_life: 1 });
+        assert_eq!(long.premium_at(1_000), Ok(0));
+        let cliff = LaunchRamp::new(curve, 30_000, 100, RampDecay::Cliff);
+        assert_eq!(cliff.price_at(0, 99), 4_000);
+        assert_eq!(cliff.price_at(0, 100), 1_000);
+        let broken = LaunchRamp::new(curve, 30_000, 100, RampDecay::Halving { half_life: 0 });
+        assert_eq!(
+            broken.price_at_checked(0, 0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(broken.price_at(0, 0), 1_000);
+    }
+
+    #[test]
+    pub fn test_launch_ramp_trades() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let ramp = LaunchRamp::new(curve, 5_000, 3_600, RampDecay::Linear);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            let price = curve.calculate_price_many(100, 10, side);
+            assert_eq!(
+                ramp.price_many_at_checked(100, 10, side, 0),
+                Ok(price + price / 2)
+            );
+            assert_eq!(ramp.price_many_at(100, 10, side, 3_600), price);
+        }
+        assert_eq!(
+            ramp.calculate_price_many_checked(0, 10, OperationSide::Add),
+            Ok(ramp.price_many_at(0, 10, OperationSide::Add, 0))
+        );
+        assert_eq!(ramp.calculate_price(10), ramp.price_at(10, 0));
+        // Premiums round up.
+        let odd = LaunchRamp::new(LinearBondingCurve::new(0, 3), 1, 10, RampDecay::Cliff);
+        assert_eq!(odd.price_at_checked(0, 0), Ok(4));
+        let huge = LaunchRamp::new(
+            LinearBondingCurve::new(0, u64::MAX),
+            1,
+            10,
+            RampDecay::Cliff,
+        );
+        assert_eq!(
+            huge.price_at_checked(0, 0),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(huge.price_at(0, 0), u64::MAX);
+        assert_eq!(huge.price_at_checked(0, 10), Ok(u64::MAX));
+    }
+}
@@ -0,0 +1,357 @@
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, BASIS_POINTS,
+};
+
+/// The fractional bits of the fixed-point growth factor of `GeometricLevelCurve`.
+const FRACTION_BITS: u32 = 32;
+
+/// Represents a level-cost curve where every level costs a fixed ratio more than the one
+/// before it, evaluated entirely in integers.
+///
+/// The supply is the current level, so `calculate_price(level)` is the cost of the next
+/// level up and `calculate_price_many(0, level, OperationSide::Add)` the total cost of
+/// reaching `level`. The ratio is raised to the level in 32-bit fixed point and every level
+/// is rounded down, so results are identical on every platform, and a trade's total sums
+/// every level it spans, which takes time proportional to the amount.
+///
+/// ```ignore
+/// f(level) = base * (ratio / 10_000)^level
+/// ```
+///
+/// # Fields
+///
+/// * `base`: The cost of the first level.
+/// * `ratio`: The factor between two consecutive levels, in basis points. `11_000` makes
+///   every level 10% more expensive than the last.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, GeometricLevelCurve, OperationSide};
+///
+/// let xp = GeometricLevelCurve::new(100, 15_000);
+/// assert_eq!(xp.calculate_price(0), 100);
+/// assert_eq!(xp.calculate_price(2), 225);
+/// assert_eq!(xp.calculate_price_many(0, 3, OperationSide::Add), 475);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeometricLevelCurve {
+    pub base: u64,
+    pub ratio: u64,
+}
+
+impl GeometricLevelCurve {
+    /// Creates a new `GeometricLevelCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The cost of the first level.
+    /// * `ratio` - The factor between two consecutive levels, in basis points.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `GeometricLevelCurve`.
+    pub fn new(base: u64, ratio: u64) -> Self {
+        Self { base, ratio }
+    }
+}
+
+/// Multiplies two fixed-point values, rounding down, or returns `None` on overflow.
+fn fixed_mul(a: u128, b: u128) -> Option<u128> {
+    a.checked_mul(b).map(|product| product >> FRACTION_BITS)
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<u64> for GeometricLevelCurve {
+    /// Calculates the cost of the next level, panicking if it does not fit in a `u64`.
+    fn calculate_price(&self, supply: u64) -> u64 {
+        match self.calculate_price_checked(supply) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Calculates the cost of a range of levels, panicking if it does not fit in a `u64`.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        match self.calculate_price_many_checked(starting_supply, amount, side) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl BondingCurveWithCheckedOperations<u64> for GeometricLevelCurve {
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        if self.base == 0 {
+            return Ok(0);
+        }
+        let mut factor = ((self.ratio as u128) << FRACTION_BITS) / BASIS_POINTS as u128;
+        let mut power = 1u128 << FRACTION_BITS;
+        let mut exponent = supply;
+        // Any overflow below means the power, and so the cost of a non-zero base, exceeds
+        // `2^64`.
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = fixed_mul(power, factor).ok_or(BondingCurveError::Overflow)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                factor = fixed_mul(factor, factor).ok_or(BondingCurveError::Overflow)?;
+            }
+        }
+        let cost = fixed_mul(self.base as u128, power).ok_or(BondingCurveError::Overflow)?;
+        u64::try_from(cost).map_err(|_| BondingCurveError::Overflow)
+    }
+
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        if self.ratio == BASIS_POINTS {
+            return self
+                .base
+                .checked_mul(amount)
+                .ok_or(BondingCurveError::Overflow);
+        }
+        let mut total = 0u64;
+        for level in first..=last {
+            let cost = self.calculate_price_checked(level)?;
+            // Shrinking costs stay at zero once they reach it.
+            if cost == 0 && self.ratio < BASIS_POINTS {
+                break;
+            }
+            total = total.checked_add(cost).ok_or(BondingCurveError::Overflow)?;
+        }
+        Ok(total)
+    }
+}
+
+/// Represents a level-cost curve where a level costs a multiple of a power of the level,
+/// evaluated entirely in integers with Faulhaber's formulas.
+///
+/// Power 1 gives the triangular costs of many role-playing games, where the total cost of
+/// reaching a level grows with its square. As with `GeometricLevelCurve`, the supply is the
+/// current level.
+///
+/// ```ignore
+/// f(level) = coefficient * level^power
+/// ```
+///
+/// # Fields
+///
+/// * `coefficient`: The multiple of the power of the level.
+/// * `power`: The power of the level, from 0 through 3.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, OperationSide, PowerLevelCurve};
+///
+/// let xp = PowerLevelCurve::triangular(100);
+/// assert_eq!(xp.calculate_price(4), 400);
+/// // 0 + 100 + 200 + 300 + 400
+/// assert_eq!(xp.calculate_price_many(0, 5, OperationSide::Add), 1_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerLevelCurve {
+    pub coefficient: u64,
+    pub power: u32,
+}
+
+impl PowerLevelCurve {
+    /// The largest power with a Faulhaber formula in the curve.
+    pub const MAX_POWER: u32 = 3;
+
+    /// Creates a new `PowerLevelCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficient` - The multiple of the power of the level.
+    /// * `power` - The power of the level, from 0 through `PowerLevelCurve::MAX_POWER`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `PowerLevelCurve`.
+    pub fn new(coefficient: u64, power: u32) -> Self {
+        Self { coefficient, power }
+    }
+
+    /// Creates a `PowerLevelCurve` where level `n` costs `coefficient * n`.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficient` - The cost added by every level.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `PowerLevelCurve` with power 1.
+    pub fn triangular(coefficient: u64) -> Self {
+        Self::new(coefficient, 1)
+    }
+
+    /// Returns the sum of `i^power` for `i` in `0..n`, or `None` on overflow.
+    fn faulhaber(&self, n: u128) -> Option<u128> {
+        if n == 0 {
+            return Some(0);
+        }
+        let triangle = n * (n - 1) / 2;
+        match self.power {
+            0 => Some(n),
+            1 => Some(triangle),
+            2 => (n * (n - 1)).checked_mul(2 * n - 1).map(|x| x / 6),
+            _ => triangle.checked_mul(triangle),
+        }
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<u64> for PowerLevelCurve {
+    /// Calculates the cost of the next level, panicking if it does not fit in a `u64`.
+    fn calculate_price(&self, supply: u64) -> u64 {
+        match self.calculate_price_checked(supply) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Calculates the cost of a range of levels, panicking if it does not fit in a `u64`.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        match self.calculate_price_many_checked(starting_supply, amount, side) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl BondingCurveWithCheckedOperations<u64> for PowerLevelCurve {
+    /// Calculates the cost of the next level, failing with
+    /// `BondingCurveError::InvalidParameter` if `power` is above `PowerLevelCurve::MAX_POWER`.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        if self.power > Self::MAX_POWER {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        (supply as u128)
+            .checked_pow(self.power)
+            .and_then(|x| x.checked_mul(self.coefficient as u128))
+            .and_then(|x| u64::try_from(x).ok())
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Calculates the cost of a range of levels, failing with
+    /// `BondingCurveError::InvalidParameter` if `power` is above `PowerLevelCurve::MAX_POWER`.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        if self.power > Self::MAX_POWER {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        let upper = self.faulhaber(last as u128 + 1);
+        let lower = self.faulhaber(first as u128);
+        upper
+            .zip(lower)
+            .and_then(|(upper, lower)| (upper - lower).checked_mul(self.coefficient as u128))
+            .and_then(|x| u64::try_from(x).ok())
+            .ok_or(BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, GeometricLevelCurve,
+        OperationSide, PowerLevelCurve,
+    };
+
+    #[test]
+    pub fn test_geometric_level_curve() {
+        let xp = GeometricLevelCurve::new(1_000, 11_000);
+        // The expected cost in units of 10^-18, so the check needs no float arithmetic.
+        const SCALE: u128 = 1_000_000_000_000_000_000;
+        let mut expected = 1_000 * SCALE;
+        for level in 0..50 {
+            let cost = xp.calculate_price(level) as u128 * SCALE;
+            assert!(cost.abs_diff(expected) <= expected / 1_000_000 + SCALE);
+            expected = expected * 11 / 10;
+        }
+        let summed: u64 = (10..20).map(|level| xp.calculate_price(level)).sum();
+        assert_eq!(xp.calculate_price_many(10, 10, OperationSide::Add), summed);
+        assert_eq!(
+            xp.calculate_price_many_checked(19, 10, OperationSide::Remove),
+            Ok(summed)
+        );
+        assert_eq!(
+            xp.calculate_price_checked(1_000),
+            Err(BondingCurveError::Overflow)
+        );
+
+        let flat = GeometricLevelCurve::new(7, 10_000);
+        assert_eq!(flat.calculate_price(u64::MAX), 7);
+        assert_eq!(
+            flat.calculate_price_many_checked(0, u64::MAX / 7, OperationSide::Add),
+            Ok(u64::MAX / 7 * 7)
+        );
+        let shrinking = GeometricLevelCurve::new(1_000, 5_000);
+        assert_eq!(shrinking.calculate_price(3), 125);
+        assert_eq!(shrinking.calculate_price(u64::MAX), 0);
+        assert_eq!(
+            shrinking.calculate_price_many_checked(0, u64::MAX, OperationSide::Add),
+            Ok(1_000 + 500 + 250 + 125 + 62 + 31 + 15 + 7 + 3 + 1)
+        );
+    }
+
+    #[test]
+    pub fn test_power_level_curve() {
+        for power in 0..=PowerLevelCurve::MAX_POWER {
+            let xp = PowerLevelCurve::new(3, power);
+            for (start, amount) in [(1, 1), (1, 10), (7, 13), (1_000, 250)] {
+                let summed: u64 = (start..start + amount)
+                    .map(|level| xp.calculate_price(level))
+                    .sum();
+                assert_eq!(
+                    xp.calculate_price_many_checked(start, amount, OperationSide::Add),
+                    Ok(summed)
+                );
+                assert_eq!(
+                    xp.calculate_price_many_checked(
+                        start + amount - 1,
+                        amount,
+                        OperationSide::Remove
+                    ),
+                    Ok(summed)
+                );
+            }
+        }
+        let invalid = PowerLevelCurve::new(3, 4);
+        assert_eq!(
+            invalid.calculate_price_checked(2),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            PowerLevelCurve::new(1, 3).calculate_price_many_checked(
+                0,
+                u64::MAX,
+                OperationSide::Add
+            ),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
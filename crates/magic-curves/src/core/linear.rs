@@ -1,14 +1,29 @@
-use super::{BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+use super::{
+    largest, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, SafeSupply,
+};
+#[cfg(not(feature = "panic-free"))]
+use super::{narrow, BondingCurve};
+use std::fmt::{self, Display, Formatter};
 
 /// Represents a linear bonding curve.
 ///
 /// This struct defines a linear bonding curve with a linear coefficient and a base price.
+/// It has a fixed `#[repr(C)]` layout, and with the `bytemuck` feature it implements `Pod`
+/// and `Zeroable` so it can live directly in zero-copy accounts.
 ///
 /// # Fields
 ///
 /// * `linear`: The linear coefficient that determines the rate of price increase.
 /// * `base`: The base price, which is the minimum price for the first token.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "super::LinearConfig", into = "super::LinearConfig")
+)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(C)]
 pub struct LinearBondingCurve {
     pub linear: u64,
     pub base: u64,
@@ -36,8 +51,122 @@ impl LinearBondingCurve {
     pub fn new(linear: u64, base: u64) -> Self {
         Self { linear, base }
     }
+
+    /// Computes the range in which the unchecked formulas cannot overflow with these
+    /// coefficients, so a configuration can be validated once at deploy time.
+    ///
+    /// # Returns
+    ///
+    /// A `SafeSupply` with the largest safe supply and the largest safe amount at it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveWithCheckedOperations, LinearBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(1 << 40, 1000);
+    /// let safe = curve.max_safe_supply();
+    /// assert!(curve.calculate_price_checked(safe.supply).is_ok());
+    /// assert!(curve.calculate_price_checked(safe.supply + 1).is_err());
+    /// ```
+    pub fn max_safe_supply(&self) -> SafeSupply {
+        let supply = largest(0, u64::MAX, |supply| {
+            self.calculate_price_checked(supply).is_ok()
+        });
+        let amount = largest(0, supply, |amount| self.remove_fits(supply, amount));
+        SafeSupply { supply, amount }
+    }
+
+    /// Calculates how many more tokens must be sold to double the price, a simple measure
+    /// of how aggressive the curve is.
+    ///
+    /// Linear prices double after `price / linear` more tokens, rounded up.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_supply` - The supply to measure from.
+    ///
+    /// # Returns
+    ///
+    /// The number of tokens, or `None` if the price is zero, never doubles, or would double
+    /// past a `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::LinearBondingCurve;
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// // 100 * 10 + 1000 = 2000, doubled at 100 * 30 + 1000 = 4000.
+    /// assert_eq!(curve.supply_to_double_price(10), Some(20));
+    /// ```
+    pub fn supply_to_double_price(&self, from_supply: u64) -> Option<u64> {
+        let price = self.calculate_price_checked(from_supply).ok()?;
+        price.checked_mul(2)?;
+        if price == 0 || self.linear == 0 {
+            return None;
+        }
+        let tokens = price.div_ceil(self.linear);
+        from_supply.checked_add(tokens).map(|_| tokens)
+    }
+
+    /// Returns a copy of the curve with a new `linear` coefficient, and the `base` adjusted so
+    /// the price at `supply` does not change, so a live curve can be made steeper or flatter
+    /// without a price jump.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply, whose price is kept.
+    /// * `linear` - The new linear coefficient.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retuned curve, `BondingCurveError::Overflow` if the current
+    /// price does not fit in a `u64`, or `BondingCurveError::InvalidParameter` if keeping the
+    /// price would need a negative base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, LinearBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let steeper = curve.retune_linear(10, 150).unwrap();
+    /// assert_eq!(steeper.base, 500);
+    /// assert_eq!(steeper.calculate_price(10), curve.calculate_price(10));
+    /// ```
+    pub fn retune_linear(&self, supply: u64, linear: u64) -> Result<Self, BondingCurveError> {
+        let price = self.calculate_price_checked(supply)?;
+        let base = linear
+            .checked_mul(supply)
+            .and_then(|slope| price.checked_sub(slope))
+            .ok_or(BondingCurveError::InvalidParameter)?;
+        Ok(Self::new(linear, base))
+    }
+
+    /// Checks every intermediate of `calculate_price_many` for a `Remove` trade.
+    fn remove_fits(&self, starting_supply: u64, amount: u64) -> bool {
+        if amount == 0 {
+            return true;
+        }
+        let s = starting_supply as u128;
+        let n = amount as u128;
+        let linear = self.linear as u128;
+        let base = self.base as u128;
+        let price = |supply: u128| linear.checked_mul(supply)?.checked_add(base);
+        let sum = || n.checked_mul(price(s)?.checked_add(price(s + 1 - n)?)?);
+        sum().is_some_and(|sum| sum / 2 <= u64::MAX as u128)
+    }
 }
 
+impl Display for LinearBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 100x + 1000`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "f(x) = {}x + {}", self.linear, self.base)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
 impl BondingCurve<u64> for LinearBondingCurve {
     /// Calculates the price based on the supply.
     ///
@@ -79,14 +208,26 @@ impl BondingCurve<u64> for LinearBondingCurve {
     ///
     /// # Returns
     ///
-    /// The total price for the given amount of tokens.
+    /// The total price for the given amount of tokens. Like native arithmetic, a result that
+    /// does not fit in a `u64` panics in debug builds and wraps in release builds.
     fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
-        let a1 = self.linear * starting_supply + self.base;
+        if amount == 0 {
+            return 0;
+        }
+
+        // Intermediates are computed in `u128` and only narrowed at the end, so the formula
+        // holds for every result that fits in a `u64`.
+        let s = starting_supply as u128;
+        let n = amount as u128;
+        let linear = self.linear as u128;
+        let base = self.base as u128;
+
+        let a1 = linear * s + base;
         let an = match side {
-            OperationSide::Add => self.linear * (starting_supply + amount - 1) + self.base,
-            OperationSide::Remove => self.linear * (starting_supply - amount + 1) + self.base,
+            OperationSide::Add => linear * (s + n - 1) + base,
+            OperationSide::Remove => linear * (s + 1 - n) + base,
         };
-        (amount * (a1 + an)) / 2
+        narrow(n * (a1 + an) / 2)
     }
 }
 
@@ -100,6 +241,7 @@ impl BondingCurveWithCheckedOperations<u64> for LinearBondingCurve {
     /// # Returns
     ///
     /// The price of the token based on the supply. If the operation would cause an overflow, it returns an error.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
     fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
         let result = self
             .linear
@@ -111,6 +253,16 @@ impl BondingCurveWithCheckedOperations<u64> for LinearBondingCurve {
 
     /// Calculates the price for a given amount of tokens.
     ///
+    /// # Cost
+    ///
+    /// Every intermediate is computed in `u128` and only narrowed to `u64` once at the end,
+    /// so the whole calculation is three multiplications and one division. The first price
+    /// cannot overflow a `u128`, and every remaining check fails only when the range is out
+    /// of bounds or the result would not fit in a `u64` anyway.
+    ///
+    /// These are operation counts, not compute units: the cost of this path under SBF has
+    /// not been measured.
+    ///
     /// # Arguments
     ///
     /// * `starting_supply` - The current supply of tokens.
@@ -120,46 +272,52 @@ impl BondingCurveWithCheckedOperations<u64> for LinearBondingCurve {
     /// # Returns
     ///
     /// The total price for the given amount of tokens. If the operation would cause an overflow, it returns an error.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
     fn calculate_price_many_checked(
         &self,
         starting_supply: u64,
         amount: u64,
         side: OperationSide,
     ) -> Result<u64, BondingCurveError> {
-        let a1 = self
-            .linear
-            .checked_mul(starting_supply)
-            .and_then(|x| x.checked_add(self.base))
-            .ok_or(BondingCurveError::Overflow)?;
+        if amount == 0 {
+            return Ok(0);
+        }
+        if side == OperationSide::Remove && amount > starting_supply {
+            return Err(BondingCurveError::SupplyUnderflow);
+        }
 
-        let an = match side {
-            OperationSide::Add => self
-                .linear
-                .checked_mul(starting_supply + amount - 1)
-                .and_then(|x| x.checked_add(self.base))
-                .ok_or(BondingCurveError::Overflow)?,
-            OperationSide::Remove => self
-                .linear
-                .checked_mul(starting_supply - amount + 1)
-                .and_then(|x| x.checked_add(self.base))
-                .ok_or(BondingCurveError::Overflow)?,
-        };
+        let s = starting_supply as u128;
+        let n = amount as u128;
+        let linear = self.linear as u128;
+        let base = self.base as u128;
+
+        // Supply of the last token priced by the operation.
+        let last = match side {
+            OperationSide::Add => (s + n).checked_sub(1),
+            OperationSide::Remove => (s + 1).checked_sub(n),
+        }
+        .ok_or(BondingCurveError::Overflow)?;
 
-        let sum = a1
-            .checked_add(an)
-            .and_then(|x| x.checked_mul(amount))
-            .and_then(|x| x.checked_div(2))
-            .ok_or(BondingCurveError::Overflow)?;
-        Ok(sum)
+        let a1 = linear * s + base;
+        let sum = linear
+            .checked_mul(last)
+            .and_then(|an| an.checked_add(base + a1))
+            .and_then(|x| x.checked_mul(n))
+            .ok_or(BondingCurveError::Overflow)?
+            / 2;
+        u64::try_from(sum).map_err(|_| BondingCurveError::Overflow)
     }
 }
 
 #[cfg(test)]
 mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::BondingCurve;
     use crate::{
-        BondingCurve, BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
+        BondingCurveError, BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
     };
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_linear_price_calculus() {
         let linear = 500_000_000u64;
@@ -192,6 +350,7 @@ mod test {
         assert_eq!(price, r4);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_increase_linear_price_many() {
         let linear = 500_000_000u64;
@@ -234,4 +393,173 @@ mod test {
 
         assert_eq!(checked_many_price_remove, looped_price_remove);
     }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_linear_price_many_checked_matches_loop() {
+        let curve = LinearBondingCurve::new(500_000_000, 1_000_000_000);
+        for starting_supply in [0u64, 1, 9, 250] {
+            for amount in 1..=10u64 {
+                let looped_add: u64 = (0..amount)
+                    .map(|i| curve.calculate_price(starting_supply + i))
+                    .sum();
+                let checked_add = curve
+                    .calculate_price_many_checked(starting_supply, amount, OperationSide::Add)
+                    .unwrap();
+                assert_eq!(checked_add, looped_add);
+
+                if amount <= starting_supply {
+                    let looped_remove: u64 = (0..amount)
+                        .map(|i| curve.calculate_price(starting_supply - i))
+                        .sum();
+                    let checked_remove = curve
+                        .calculate_price_many_checked(
+                            starting_supply,
+                            amount,
+                            OperationSide::Remove,
+                        )
+                        .unwrap();
+                    assert_eq!(checked_remove, looped_remove);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_linear_price_many_checked_overflow() {
+        let curve = LinearBondingCurve::new(500_000_000, 1_000_000_000);
+        assert_eq!(
+            curve.calculate_price_many_checked(u64::MAX / 2, 10, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_linear_price_many_zero_and_full_exit() {
+        let curve = LinearBondingCurve::new(500_000_000, 1_000_000_000);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert_eq!(curve.calculate_price_many(10, 0, side), 0);
+            assert_eq!(curve.calculate_price_many_checked(0, 0, side), Ok(0));
+        }
+        let looped: u64 = (1..=10).map(|x| curve.calculate_price(x)).sum();
+        assert_eq!(
+            curve.calculate_price_many(10, 10, OperationSide::Remove),
+            looped
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(10, 10, OperationSide::Remove),
+            Ok(looped)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_linear_price_many_wide_intermediates() {
+        // a1 + an overflows a u64 here, but the total does not.
+        let curve = LinearBondingCurve::new(1_000_000_000, 0);
+        let price = curve.calculate_price_many(4_000_000_000, 3, OperationSide::Add);
+        assert_eq!(
+            Ok(price),
+            curve.calculate_price_many_checked(4_000_000_000, 3, OperationSide::Add)
+        );
+        assert_eq!(price, 12_000_000_003_000_000_000);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    pub fn test_linear_pod_round_trip() {
+        let curve = LinearBondingCurve::new(500_000_000, 1_000_000_000);
+        let bytes = bytemuck::bytes_of(&curve);
+        assert_eq!(bytes.len(), std::mem::size_of::<LinearBondingCurve>());
+        assert_eq!(*bytemuck::from_bytes::<LinearBondingCurve>(bytes), curve);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_linear_max_safe_supply() {
+        for curve in [
+            LinearBondingCurve::new(1 << 40, 1000),
+            LinearBondingCurve::new(3, u64::MAX / 2),
+            LinearBondingCurve::new(0, 7),
+        ] {
+            let safe = curve.max_safe_supply();
+            assert!(safe.amount <= safe.supply);
+            assert_eq!(
+                curve.calculate_price(safe.supply),
+                curve.calculate_price_checked(safe.supply).unwrap()
+            );
+            if safe.supply < u64::MAX {
+                assert!(curve.calculate_price_checked(safe.supply + 1).is_err());
+            }
+            // The unchecked formulas would panic on overflow in this debug build.
+            assert_eq!(
+                curve.calculate_price_many(safe.supply, safe.amount, OperationSide::Remove),
+                curve
+                    .calculate_price_many_checked(safe.supply, safe.amount, OperationSide::Remove)
+                    .unwrap()
+            );
+            curve.calculate_price_many(safe.supply - safe.amount, safe.amount, OperationSide::Add);
+        }
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_linear_supply_to_double_price() {
+        let curve = LinearBondingCurve::new(3, 10);
+        for supply in [0, 1, 7, 1_000] {
+            let tokens = curve.supply_to_double_price(supply).unwrap();
+            let price = curve.calculate_price(supply);
+            assert!(curve.calculate_price(supply + tokens) >= 2 * price);
+            assert!(curve.calculate_price(supply + tokens - 1) < 2 * price);
+        }
+        assert_eq!(
+            LinearBondingCurve::new(0, 10).supply_to_double_price(5),
+            None
+        );
+        assert_eq!(
+            LinearBondingCurve::new(3, 0).supply_to_double_price(0),
+            None
+        );
+        assert_eq!(curve.supply_to_double_price(u64::MAX / 4), None);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_linear_retune() {
+        let curve = LinearBondingCurve::new(500_000_000, 1_000_000_000);
+        for linear in [0, 1, 250_000_000, 501_000_000] {
+            let retuned = curve.retune_linear(1_000, linear).unwrap();
+            assert_eq!(retuned.linear, linear);
+            assert_eq!(retuned.calculate_price(1_000), curve.calculate_price(1_000));
+        }
+        assert_eq!(
+            curve.retune_linear(0, 7),
+            Ok(LinearBondingCurve::new(7, 1_000_000_000))
+        );
+        assert_eq!(
+            curve.retune_linear(1_000, 501_000_001),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            curve.retune_linear(1_000, u64::MAX),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            curve.retune_linear(u64::MAX, 0),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[test]
+    pub fn test_linear_display() {
+        assert_eq!(
+            LinearBondingCurve::new(100, 1000).to_string(),
+            "f(x) = 100x + 1000"
+        );
+    }
 }
@@ -1,4 +1,14 @@
-use super::{BondingCurve, OperationSide};
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    canonical_key, finite, math, supply_range, BondingCurveError,
+    BondingCurveWithCheckedOperations, OperationSide,
+};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
 
 /// Represents a logarithmic bonding curve.
 ///
@@ -8,7 +18,16 @@ use super::{BondingCurve, OperationSide};
 ///
 /// * `base`: The base price, which is the initial price for the first token.
 /// * `growth`: The growth rate that determines how quickly the price increases.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "super::LogarithmicConfig",
+        into = "super::LogarithmicConfig"
+    )
+)]
 pub struct LogarithmicBondingCurve {
     pub base: f64,
     pub growth: f64,
@@ -36,8 +55,245 @@ impl LogarithmicBondingCurve {
     pub fn new(base: f64, growth: f64) -> Self {
         Self { base, growth }
     }
+
+    /// Creates a new `LogarithmicBondingCurve`, validating its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base price, which is the initial price for the first token.
+    /// * `growth` - The growth rate that determines how quickly the price increases.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the curve, or the error from `LogarithmicBondingCurve::validate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveError, LogarithmicBondingCurve};
+    ///
+    /// assert!(LogarithmicBondingCurve::try_new(0.02, 0.01).is_ok());
+    /// assert_eq!(
+    ///     LogarithmicBondingCurve::try_new(0.02, -0.01),
+    ///     Err(BondingCurveError::InvalidParameter)
+    /// );
+    /// ```
+    pub fn try_new(base: f64, growth: f64) -> Result<Self, BondingCurveError> {
+        let curve = Self::new(base, growth);
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    /// Checks that the parameters are supported by the curve's formulas.
+    ///
+    /// # Returns
+    ///
+    /// `BondingCurveError::InvalidParameter` if a parameter is not finite or `growth` is not
+    /// positive.
+    pub fn validate(&self) -> Result<(), BondingCurveError> {
+        if !self.base.is_finite() || !self.growth.is_finite() || self.growth <= 0.0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    /// Calculates how many more tokens must be sold to double the price, a simple measure
+    /// of how aggressive the curve is.
+    ///
+    /// Solving `growth * ln(y) + base = 2 * price` gives the doubling supply
+    /// `y = e^((2 * price - base) / growth)`, so the distance grows quickly with the supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_supply` - The supply to measure from.
+    ///
+    /// # Returns
+    ///
+    /// The fractional number of tokens, or `None` if the price is not positive or never
+    /// doubles within the range of an `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::LogarithmicBondingCurve;
+    ///
+    /// let curve = LogarithmicBondingCurve::new(0.0, 1.0);
+    /// // ln(y) = 2 * ln(10), so the price doubles at a supply of 100.
+    /// let tokens = curve.supply_to_double_price(10).unwrap();
+    /// assert!((tokens - 90.0).abs() < 1e-9);
+    /// ```
+    pub fn supply_to_double_price(&self, from_supply: u64) -> Option<f64> {
+        let price = self.calculate_price_f(from_supply as f64);
+        if !(price > 0.0 && self.growth > 0.0) {
+            return None;
+        }
+        let target = math::exp((2.0 * price - self.base) / self.growth);
+        target.is_finite().then_some(target - from_supply as f64)
+    }
+
+    /// Returns a copy of the curve with a new `growth`, and the `base` adjusted so the price
+    /// at `supply` does not change, so a live curve can be made steeper or flatter without a
+    /// price jump.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply, whose price is kept.
+    /// * `growth` - The new growth rate.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retuned curve, or the error from
+    /// `LogarithmicBondingCurve::validate` if the new curve is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{relative_eq, BondingCurve, LogarithmicBondingCurve};
+    ///
+    /// let curve = LogarithmicBondingCurve::new(0.02, 0.01);
+    /// let steeper = curve.retune_growth(100, 0.02).unwrap();
+    /// assert!(steeper.base < curve.base);
+    /// assert!(relative_eq(steeper.calculate_price(100), curve.calculate_price(100), 1e-12));
+    /// ```
+    pub fn retune_growth(&self, supply: u64, growth: f64) -> Result<Self, BondingCurveError> {
+        let supply = supply as f64;
+        let base =
+            self.calculate_price_f(supply) - Self::new(0.0, growth).calculate_price_f(supply);
+        Self::try_new(base, growth)
+    }
+
+    /// Calculates the price at a fractional supply, for continuous-token models.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens, in fractional units.
+    ///
+    /// # Returns
+    ///
+    /// The price at `supply`, equal to `calculate_price` at whole supplies. Like
+    /// `calculate_price`, it returns `base` at supply zero.
+    pub fn calculate_price_f(&self, supply: f64) -> f64 {
+        if supply == 0.0 {
+            return self.base; // Avoid taking the log of 0
+        }
+        self.growth * math::ln(supply) + self.base
+    }
+
+    /// Calculates the price for a fractional amount of tokens from a fractional supply.
+    ///
+    /// Unlike `calculate_price_many`, which charges `base` once more for the first token
+    /// when buying from supply zero, this is the plain integral of the price, so splitting a
+    /// trade into smaller ones never changes its total.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens, in fractional units.
+    /// * `amount` - The amount of tokens to calculate the price for, in fractional units.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price, the integral of the price between the two supplies, or `NaN` if the
+    /// trade reaches a negative supply.
+    pub fn calculate_price_many_f(
+        &self,
+        starting_supply: f64,
+        amount: f64,
+        side: OperationSide,
+    ) -> f64 {
+        if amount == 0.0 {
+            return 0.0;
+        }
+        let end = match side {
+            OperationSide::Add => starting_supply + amount,
+            OperationSide::Remove => starting_supply - amount,
+        };
+        match side {
+            OperationSide::Add => self.integral(end) - self.integral(starting_supply),
+            OperationSide::Remove => self.integral(starting_supply) - self.integral(end),
+        }
+    }
+}
+
+impl LogarithmicBondingCurve {
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64) {
+        (canonical_key(self.base), canonical_key(self.growth))
+    }
+
+    /// Returns the antiderivative of the price at `x`. It is 0 at 0, the limit of
+    /// `x * ln(x)`, so a full exit down to zero supply is well defined.
+    fn integral(&self, x: f64) -> f64 {
+        if x == 0.0 {
+            return 0.0;
+        }
+        self.growth * x * math::ln(x) - self.growth * x + self.base * x
+    }
+
+    /// Returns the integral of the price over a trade, which must stay within `0..=u64::MAX`.
+    fn price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
+        if amount == 0 {
+            return 0.0;
+        }
+
+        let start = starting_supply as f64;
+        let end = match side {
+            OperationSide::Add => (starting_supply + amount) as f64,
+            OperationSide::Remove => (starting_supply - amount) as f64,
+        };
+
+        // Calculate the difference between the integrals at the end and start points
+        let price = match side {
+            OperationSide::Add => self.integral(end) - self.integral(start),
+            OperationSide::Remove => self.integral(start) - self.integral(end),
+        };
+
+        // Handle the case where starting_supply is 0 for Add operation
+        if starting_supply == 0 && side == OperationSide::Add {
+            price + self.base // Add base price for the first token
+        } else {
+            price
+        }
+    }
+}
+
+impl PartialEq for LogarithmicBondingCurve {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for LogarithmicBondingCurve {}
+
+impl Hash for LogarithmicBondingCurve {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for LogarithmicBondingCurve {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
+impl Ord for LogarithmicBondingCurve {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Display for LogarithmicBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 0.5·ln(x) + 0.02`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "f(x) = {}·ln(x) + {}", self.growth, self.base)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
 impl BondingCurve<f64> for LogarithmicBondingCurve {
     /// Calculates the price based on the supply.
     ///
@@ -55,10 +311,7 @@ impl BondingCurve<f64> for LogarithmicBondingCurve {
     ///
     /// The price of the token based on the supply.
     fn calculate_price(&self, supply: u64) -> f64 {
-        if supply == 0 {
-            return self.base; // Avoid taking the log of 0
-        }
-        self.growth * (supply as f64).ln() + self.base
+        self.calculate_price_f(supply as f64)
     }
 
     /// Calculates the price for a given amount of tokens.
@@ -80,37 +333,69 @@ impl BondingCurve<f64> for LogarithmicBondingCurve {
     ///
     /// The total price for the given amount of tokens.
     fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
-        let start = starting_supply as f64;
-        let end = match side {
-            OperationSide::Add => (starting_supply + amount) as f64,
-            OperationSide::Remove => (starting_supply - amount) as f64,
-        };
-
-        // Calculate the integral of the logarithmic function
-        let integral = |x: f64| self.growth * x * x.ln() - self.growth * x + self.base * x;
+        self.price_many(starting_supply, amount, side)
+    }
+}
 
-        // Calculate the difference between the integrals at the end and start points
-        let price = match side {
-            OperationSide::Add => integral(end) - integral(start),
-            OperationSide::Remove => integral(start) - integral(end),
-        };
+impl BondingCurveWithCheckedOperations<f64> for LogarithmicBondingCurve {
+    /// Calculates the price based on the supply, validating the curve parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, the error from `LogarithmicBondingCurve::validate`, or
+    /// `BondingCurveError::Overflow` if the price is not finite.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        finite(self.calculate_price_f(supply as f64))
+    }
 
-        // Handle the case where starting_supply is 0 for Add operation
-        if starting_supply == 0 && side == OperationSide::Add {
-            price + self.base // Add base price for the first token
-        } else {
-            price
+    /// Calculates the price for a given amount of tokens, validating the curve parameters
+    /// and the supply range.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, the error from `LogarithmicBondingCurve::validate`,
+    /// `BondingCurveError::SupplyUnderflow` if more than `starting_supply` tokens are
+    /// removed, or `BondingCurveError::Overflow` if the supply or the price leaves its range.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        if supply_range(starting_supply, amount, side)?.is_none() {
+            return Ok(0.0);
         }
+        if side == OperationSide::Add && starting_supply.checked_add(amount).is_none() {
+            return Err(BondingCurveError::Overflow);
+        }
+        finite(self.price_many(starting_supply, amount, side))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::LogarithmicBondingCurve;
+    #[cfg(not(feature = "panic-free"))]
     use crate::{
-        fixed_point_to_float, float_to_fixed_point, BondingCurve, LogarithmicBondingCurve,
-        OperationSide,
+        fixed_point_to_float, float_to_fixed_point, relative_eq, BondingCurve, BondingCurveError,
+        BondingCurveWithCheckedOperations, OperationSide,
     };
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_logarithmic_price_calculus() {
         let curve = LogarithmicBondingCurve::new(0.02, 0.01);
@@ -118,15 +403,17 @@ mod test {
         assert_eq!(price, 0.06605170185988092);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_logarithmic_price_calculus_fixed_point() {
         let base = fixed_point_to_float(2, 2);
         let growth = fixed_point_to_float(1, 2);
         let curve = LogarithmicBondingCurve::new(base, growth);
         let price = curve.calculate_price(100);
-        assert_eq!(float_to_fixed_point(price, 9), 0_066_051_701);
+        assert_eq!(float_to_fixed_point(price, 9), 66_051_701);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_logarithmic_price_calculus_many() {
         let curve = LogarithmicBondingCurve::new(0.02, 0.01);
@@ -135,4 +422,117 @@ mod test {
         let price_remove = curve.calculate_price_many(100, 10, OperationSide::Remove);
         assert_eq!(price_remove, 0.6553414826908526);
     }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_logarithmic_price_many_zero_and_full_exit() {
+        let curve = LogarithmicBondingCurve::new(0.02, 0.01);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert_eq!(curve.calculate_price_many(0, 0, side), 0.0);
+            assert_eq!(curve.calculate_price_many(10, 0, side), 0.0);
+        }
+        let full_exit = curve.calculate_price_many(100, 100, OperationSide::Remove);
+        assert!(full_exit.is_finite() && full_exit > 0.0);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_logarithmic_checked_guards() {
+        let curve = LogarithmicBondingCurve::new(0.02, 0.01);
+        assert_eq!(
+            curve.calculate_price_many_checked(100, 10, OperationSide::Remove),
+            Ok(curve.calculate_price_many(100, 10, OperationSide::Remove))
+        );
+        for growth in [0.0, -0.01, f64::INFINITY] {
+            let invalid = LogarithmicBondingCurve::new(0.02, growth);
+            assert_eq!(
+                invalid.calculate_price_checked(10),
+                Err(BondingCurveError::InvalidParameter)
+            );
+            assert_eq!(
+                LogarithmicBondingCurve::try_new(0.02, growth),
+                Err(BondingCurveError::InvalidParameter)
+            );
+        }
+        assert_eq!(
+            curve.calculate_price_many_checked(u64::MAX, 1, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_logarithmic_fractional_supply() {
+        let curve = LogarithmicBondingCurve::new(0.02, 0.01);
+        assert_eq!(curve.calculate_price_f(0.0), curve.calculate_price(0));
+        assert_eq!(curve.calculate_price_f(250.0), curve.calculate_price(250));
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert_eq!(
+                curve.calculate_price_many_f(1000.0, 10.0, side),
+                curve.calculate_price_many(1000, 10, side)
+            );
+        }
+        let split = curve.calculate_price_many_f(0.0, 0.5, OperationSide::Add)
+            + curve.calculate_price_many_f(0.5, 2.0, OperationSide::Add);
+        assert!(relative_eq(
+            split,
+            curve.calculate_price_many_f(0.0, 2.5, OperationSide::Add),
+            1e-12
+        ));
+        assert!(curve
+            .calculate_price_many_f(1.0, 1.5, OperationSide::Remove)
+            .is_nan());
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_logarithmic_supply_to_double_price() {
+        let curve = LogarithmicBondingCurve::new(0.5, 0.2);
+        for supply in [0, 1, 10] {
+            let tokens = curve.supply_to_double_price(supply).unwrap();
+            let doubled = curve.calculate_price_f(supply as f64 + tokens);
+            assert!(relative_eq(
+                doubled,
+                2.0 * curve.calculate_price(supply),
+                1e-12
+            ));
+        }
+        assert_eq!(
+            LogarithmicBondingCurve::new(0.5, 0.0).supply_to_double_price(10),
+            None
+        );
+        assert_eq!(
+            LogarithmicBondingCurve::new(0.0, 0.2).supply_to_double_price(0),
+            None
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_logarithmic_retune() {
+        let curve = LogarithmicBondingCurve::new(0.02, 0.01);
+        for growth in [0.001, 0.5, 3.0] {
+            let retuned = curve.retune_growth(1_000, growth).unwrap();
+            assert_eq!(retuned.growth, growth);
+            assert!(relative_eq(
+                retuned.calculate_price(1_000),
+                curve.calculate_price(1_000),
+                1e-12
+            ));
+        }
+        // The price at supply zero is the base, whatever the growth.
+        assert_eq!(curve.retune_growth(0, 0.5).unwrap().base, 0.02);
+        assert_eq!(
+            curve.retune_growth(1_000, 0.0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[test]
+    pub fn test_logarithmic_display() {
+        assert_eq!(
+            LogarithmicBondingCurve::new(0.02, 0.5).to_string(),
+            "f(x) = 0.5·ln(x) + 0.02"
+        );
+    }
 }
@@ -0,0 +1,88 @@
+// Transcendental functions used by the float curves.
+//
+// With the `soft-float` feature they come from `libm`, a pure Rust implementation whose
+// results do not depend on the target's math library, so float prices are bit-identical on
+// x86, ARM, and wasm. Otherwise they use the standard library, which is usually faster.
+
+/// Returns `x` raised to the power `y`.
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    #[cfg(feature = "soft-float")]
+    return libm::pow(x, y);
+    #[cfg(not(feature = "soft-float"))]
+    x.powf(y)
+}
+
+/// Returns `e` raised to the power `x`.
+pub(crate) fn exp(x: f64) -> f64 {
+    #[cfg(feature = "soft-float")]
+    return libm::exp(x);
+    #[cfg(not(feature = "soft-float"))]
+    x.exp()
+}
+
+/// Returns the natural logarithm of `x`.
+pub(crate) fn ln(x: f64) -> f64 {
+    #[cfg(feature = "soft-float")]
+    return libm::log(x);
+    #[cfg(not(feature = "soft-float"))]
+    x.ln()
+}
+
+/// Returns `x` raised to the power `y`, in single precision.
+#[cfg(not(feature = "panic-free"))]
+pub(crate) fn powf_f32(x: f32, y: f32) -> f32 {
+    #[cfg(feature = "soft-float")]
+    return libm::powf(x, y);
+    #[cfg(not(feature = "soft-float"))]
+    x.powf(y)
+}
+
+/// Returns `e` raised to the power `x`, in single precision.
+#[cfg(not(feature = "panic-free"))]
+pub(crate) fn exp_f32(x: f32) -> f32 {
+    #[cfg(feature = "soft-float")]
+    return libm::expf(x);
+    #[cfg(not(feature = "soft-float"))]
+    x.exp()
+}
+
+/// Returns the natural logarithm of `x`, in single precision.
+#[cfg(not(feature = "panic-free"))]
+pub(crate) fn ln_f32(x: f32) -> f32 {
+    #[cfg(feature = "soft-float")]
+    return libm::logf(x);
+    #[cfg(not(feature = "soft-float"))]
+    x.ln()
+}
+
+/// Returns an approximation of `e` raised to the power `x`, within a relative error of
+/// `ExponentialBondingCurve::FAST_MAX_RELATIVE_ERROR` for normal results.
+///
+/// The argument is reduced to `x = k·ln(2) + r` with `|r| <= ln(2) / 2`, `e^r` is evaluated
+/// with a degree 6 Taylor polynomial, and the result is scaled by `2^k` through its exponent
+/// bits. It only uses basic arithmetic, so it is reproducible with or without `soft-float`.
+pub(crate) fn fast_exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.8 {
+        return f64::INFINITY;
+    }
+    if x < -745.2 {
+        return 0.0;
+    }
+    let k = (x * std::f64::consts::LOG2_E).round();
+    let r = x - k * std::f64::consts::LN_2;
+    let p = 1.0
+        + r * (1.0
+            + r * (1.0 / 2.0
+                + r * (1.0 / 6.0 + r * (1.0 / 24.0 + r * (1.0 / 120.0 + r * (1.0 / 720.0))))));
+    // Splitting `2^k` in two keeps both factors normal for every `k` in range.
+    let k = k as i64;
+    p * pow2(k / 2) * pow2(k - k / 2)
+}
+
+/// Returns `2^k` for `-1022 <= k <= 1023`.
+fn pow2(k: i64) -> f64 {
+    f64::from_bits(((k + 1023) as u64) << 52)
+}
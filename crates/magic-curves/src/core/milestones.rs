@@ -0,0 +1,100 @@
+use super::{reserve_at, BondingCurveError, BondingCurveWithCheckedOperations};
+
+/// Represents one row of the tokenomics table returned by `milestones`.
+///
+/// # Fields
+///
+/// * `supply`: The supply of the milestone.
+/// * `price`: The spot price at the milestone, the price of the next token.
+/// * `market_cap`: The whole supply valued at `price`.
+/// * `raised`: The total paid for every token up to the milestone, as returned by
+///   `reserve_at`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MilestoneRow {
+    pub supply: u64,
+    pub price: u64,
+    pub market_cap: u128,
+    pub raised: u64,
+}
+
+/// Computes the price, market cap, and cumulative raise of a curve at each supply milestone,
+/// the tokenomics table of a whitepaper generated from the curve itself.
+///
+/// # Arguments
+///
+/// * `curve` - The curve to tabulate.
+/// * `supplies` - The milestones, in the order the rows should be in.
+///
+/// # Returns
+///
+/// A `Result` containing a `MilestoneRow` per milestone, or the `BondingCurveError` of the
+/// curve.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{milestones, LinearBondingCurve};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let rows = milestones(&curve, &[0, 10, 100]).unwrap();
+/// assert_eq!(rows[1].price, 2_000);
+/// assert_eq!((rows[1].market_cap, rows[1].raised), (20_000, 14_500));
+/// assert_eq!(rows[2].raised, 595_000);
+/// ```
+pub fn milestones<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    supplies: &[u64],
+) -> Result<Vec<MilestoneRow>, BondingCurveError> {
+    supplies
+        .iter()
+        .map(|&supply| {
+            let price = curve.calculate_price_checked(supply)?;
+            Ok(MilestoneRow {
+                supply,
+                price,
+                market_cap: price as u128 * supply as u128,
+                raised: reserve_at(curve, supply)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        milestones, BondingCurveError, BondingCurveWithCheckedOperations, LinearBondingCurve,
+        MilestoneRow, OperationSide, QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_milestones() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let rows = milestones(&curve, &[1_000, 0, 250]).unwrap();
+        assert_eq!(
+            rows.iter().map(|row| row.supply).collect::<Vec<_>>(),
+            vec![1_000, 0, 250]
+        );
+        assert_eq!(
+            rows[1],
+            MilestoneRow {
+                supply: 0,
+                price: curve.calculate_price_checked(0).unwrap(),
+                market_cap: 0,
+                raised: 0,
+            }
+        );
+        // The raise between two milestones is the price of buying from one to the other.
+        assert_eq!(
+            rows[0].raised - rows[2].raised,
+            curve
+                .calculate_price_many_checked(250, 750, OperationSide::Add)
+                .unwrap()
+        );
+        assert!(milestones(&curve, &[]).unwrap().is_empty());
+        assert_eq!(
+            milestones(&LinearBondingCurve::new(u64::MAX, 0), &[1, 2]),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
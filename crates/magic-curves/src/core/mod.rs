@@ -1,17 +1,191 @@
+#[cfg(feature = "borsh")]
+pub mod account;
+#[cfg(test)]
+mod allocations;
+#[cfg(feature = "typed-amounts")]
+pub mod amounts;
+pub mod any;
+pub mod augmented;
+pub mod basket;
+pub mod batch;
+pub mod blend;
+#[cfg(all(feature = "float", not(feature = "panic-free")))]
+pub mod cached;
+#[cfg(feature = "float")]
+pub mod compare;
+pub mod config;
+#[cfg(feature = "float")]
+pub mod consistency;
+#[cfg(feature = "rich-errors")]
+pub mod context;
+pub mod cost;
+pub mod cubic;
+pub mod damping;
+pub mod decimals;
+pub mod design;
+pub mod divergence;
+pub mod drawdown;
+pub mod encoding;
 pub mod error;
+#[cfg(feature = "float")]
 pub mod exponential;
+pub mod fees;
+#[cfg(feature = "float")]
+pub mod fit;
+#[cfg(all(feature = "float", not(feature = "panic-free")))]
+pub mod fixed;
+pub mod geometric;
+pub mod health;
+pub mod inverse;
+pub mod journal;
+pub mod ladder;
+pub mod launch;
+pub mod levels;
 pub mod linear;
+#[cfg(feature = "float")]
 pub mod logarithmic;
+#[cfg(feature = "float")]
+mod math;
+pub mod milestones;
+#[cfg(feature = "u256")]
+pub mod mul_div;
+pub mod netting;
+pub mod oracle;
+pub mod orders;
+pub mod organization;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod peg;
+pub mod phase;
+pub mod phased;
+pub mod polynomial;
+pub mod position;
+#[cfg(feature = "float")]
+pub mod power;
 pub mod quadratic;
+pub mod quote;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod rebase;
+pub mod registry;
+pub mod replay;
+pub mod safety;
+pub mod shared;
+#[cfg(feature = "float")]
 pub mod sigmoid;
+pub mod sim;
+#[cfg(not(feature = "panic-free"))]
+pub mod simd;
+#[cfg(all(feature = "float", not(feature = "panic-free")))]
+pub mod single_precision;
+pub mod spec;
+pub mod state;
+#[cfg(feature = "float")]
+pub mod stats;
+#[cfg(not(feature = "panic-free"))]
+pub mod stream;
+pub mod swap;
+#[cfg(not(feature = "panic-free"))]
+pub mod time;
+#[cfg(feature = "float")]
 pub mod tools;
+pub mod tranche;
 pub mod types;
+#[cfg(feature = "u256")]
+pub mod u256;
+pub mod unchecked;
+pub mod vectors;
 
+#[cfg(feature = "borsh")]
+pub use account::*;
+#[cfg(feature = "typed-amounts")]
+pub use amounts::*;
+pub use any::*;
+pub use augmented::*;
+pub use basket::*;
+pub use batch::*;
+pub use blend::*;
+#[cfg(all(feature = "float", not(feature = "panic-free")))]
+pub use cached::*;
+#[cfg(feature = "float")]
+pub use compare::*;
+pub use config::*;
+#[cfg(feature = "float")]
+pub use consistency::*;
+#[cfg(feature = "rich-errors")]
+pub use context::*;
+pub use cost::*;
+pub use cubic::*;
+pub use damping::*;
+pub use decimals::*;
+pub use design::*;
+pub use divergence::*;
+pub use drawdown::*;
+pub use encoding::*;
 pub use error::*;
+#[cfg(feature = "float")]
 pub use exponential::*;
+pub use fees::*;
+#[cfg(feature = "float")]
+pub use fit::*;
+#[cfg(all(feature = "float", not(feature = "panic-free")))]
+pub use fixed::*;
+pub use geometric::*;
+pub use health::*;
+pub use inverse::*;
+pub use journal::*;
+pub use ladder::*;
+pub use launch::*;
+pub use levels::*;
 pub use linear::*;
+#[cfg(feature = "float")]
 pub use logarithmic::*;
+pub use milestones::*;
+#[cfg(feature = "u256")]
+pub use mul_div::*;
+pub use netting::*;
+pub use oracle::*;
+pub use orders::*;
+pub use organization::*;
+#[cfg(feature = "parallel")]
+pub use parallel::*;
+pub use peg::*;
+pub use phase::*;
+pub use phased::*;
+pub use polynomial::*;
+pub use position::*;
+#[cfg(feature = "float")]
+pub use power::*;
 pub use quadratic::*;
+pub use quote::*;
+#[cfg(feature = "rand")]
+pub use random::*;
+pub use rebase::*;
+pub use registry::*;
+pub use replay::*;
+pub use safety::*;
+pub use shared::*;
+#[cfg(feature = "float")]
 pub use sigmoid::*;
+pub use sim::*;
+#[cfg(not(feature = "panic-free"))]
+pub use simd::*;
+#[cfg(all(feature = "float", not(feature = "panic-free")))]
+pub use single_precision::*;
+pub use spec::*;
+pub use state::*;
+#[cfg(feature = "float")]
+pub use stats::*;
+#[cfg(not(feature = "panic-free"))]
+pub use stream::*;
+pub use swap::*;
+#[cfg(not(feature = "panic-free"))]
+pub use time::*;
+#[cfg(feature = "float")]
 pub use tools::*;
+pub use tranche::*;
 pub use types::*;
+#[cfg(feature = "u256")]
+pub use u256::*;
+pub use unchecked::*;
+pub use vectors::*;
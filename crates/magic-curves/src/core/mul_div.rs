@@ -0,0 +1,127 @@
+use ruint::{
+    aliases::{U256, U512},
+    UintTryFrom,
+};
+
+use super::BondingCurveError;
+
+/// Represents the rounding direction of `mul_div`.
+///
+/// # Variants
+///
+/// * `Floor` - Rounds down, like OpenZeppelin's `Rounding.Floor` and Uniswap's `mulDiv`.
+/// * `Ceil` - Rounds up, like OpenZeppelin's `Rounding.Ceil` and Uniswap's `mulDivRoundingUp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    Floor,
+    Ceil,
+}
+
+/// Computes `a * b / denominator` with a full 512-bit intermediate product.
+///
+/// This matches the Solidity `mulDiv` found in OpenZeppelin's `Math` and Uniswap's `FullMath`
+/// bit-for-bit: the product never overflows, and the call fails exactly where the Solidity
+/// version reverts.
+///
+/// # Arguments
+///
+/// * `a` - The multiplicand.
+/// * `b` - The multiplier.
+/// * `denominator` - The divisor.
+/// * `rounding` - The rounding direction of the division.
+///
+/// # Returns
+///
+/// A `Result` containing the quotient, `BondingCurveError::DivisionByZero` if `denominator`
+/// is zero, or `BondingCurveError::Overflow` if the rounded quotient does not fit in a `U256`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{mul_div, Rounding};
+/// use ruint::aliases::U256;
+///
+/// let quotient = mul_div(U256::MAX, U256::from(3), U256::from(4), Rounding::Floor).unwrap();
+/// assert_eq!(quotient, U256::MAX / U256::from(4) * U256::from(3) + U256::from(2));
+/// ```
+pub fn mul_div(
+    a: U256,
+    b: U256,
+    denominator: U256,
+    rounding: Rounding,
+) -> Result<U256, BondingCurveError> {
+    if denominator.is_zero() {
+        return Err(BondingCurveError::DivisionByZero);
+    }
+    let product: U512 = a.widening_mul(b);
+    let (quotient, remainder) = product.div_rem(U512::from(denominator));
+    let quotient = U256::uint_try_from(quotient).map_err(|_| BondingCurveError::Overflow)?;
+    match rounding {
+        Rounding::Ceil if !remainder.is_zero() => quotient
+            .checked_add(U256::from(1))
+            .ok_or(BondingCurveError::Overflow),
+        _ => Ok(quotient),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ruint::aliases::U256;
+
+    use crate::{mul_div, BondingCurveError, Rounding};
+
+    #[test]
+    pub fn test_mul_div_rounding() {
+        let (seven, three, two) = (U256::from(7), U256::from(3), U256::from(2));
+        assert_eq!(
+            mul_div(seven, three, two, Rounding::Floor),
+            Ok(U256::from(10))
+        );
+        assert_eq!(
+            mul_div(seven, three, two, Rounding::Ceil),
+            Ok(U256::from(11))
+        );
+        assert_eq!(mul_div(seven, two, two, Rounding::Ceil), Ok(seven));
+        assert_eq!(
+            mul_div(U256::MAX, U256::MAX, U256::MAX, Rounding::Ceil),
+            Ok(U256::MAX)
+        );
+    }
+
+    #[test]
+    pub fn test_mul_div_full_width() {
+        // (2^200 + 12345) * (2^100 + 7) / 3^90, checked against arbitrary precision arithmetic.
+        let a = (U256::from(1) << 200) + U256::from(12345);
+        let b = (U256::from(1) << 100) + U256::from(7);
+        let denominator = U256::from(3).pow(U256::from(90));
+        let expected: U256 = "233391897255684639766217891560231099986368199905"
+            .parse()
+            .unwrap();
+        assert_eq!(mul_div(a, b, denominator, Rounding::Floor), Ok(expected));
+        assert_eq!(
+            mul_div(a, b, denominator, Rounding::Ceil),
+            Ok(expected + U256::from(1))
+        );
+    }
+
+    #[test]
+    pub fn test_mul_div_errors() {
+        assert_eq!(
+            mul_div(U256::from(1), U256::from(1), U256::ZERO, Rounding::Floor),
+            Err(BondingCurveError::DivisionByZero)
+        );
+        assert_eq!(
+            mul_div(
+                U256::MAX,
+                U256::MAX,
+                U256::MAX - U256::from(2),
+                Rounding::Floor
+            ),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            mul_div(U256::MAX, U256::from(2), U256::from(2), Rounding::Ceil),
+            Ok(U256::MAX)
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuoteRequest, TradeResult,
+};
+
+/// Represents a trade waiting for the next batch of `net_batch`.
+///
+/// # Fields
+///
+/// * `side`: `Add` to buy tokens, `Remove` to sell them.
+/// * `amount`: The number of tokens to trade.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingTrade {
+    pub side: OperationSide,
+    pub amount: u64,
+}
+
+impl PendingTrade {
+    /// Creates a new `PendingTrade`.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Whether the trade buys or sells tokens.
+    /// * `amount` - The number of tokens to trade.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `PendingTrade`.
+    pub fn new(side: OperationSide, amount: u64) -> Self {
+        Self { side, amount }
+    }
+}
+
+/// Represents the outcome of `net_batch`.
+///
+/// # Fields
+///
+/// * `net`: The single trade against the curve, for the difference between the bought and
+///   sold tokens.
+/// * `allocations`: The settlement of every pending trade, in the order given: the tokens
+///   traded, what the buyer pays or the seller receives in `cost`, and the supply after the
+///   batch.
+/// * `surplus`: What the buyers pay and the curve's proceeds, less what the sellers receive
+///   and the curve's cost: the rounding left over after settling everyone.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NettedBatch {
+    pub net: TradeResult,
+    pub allocations: Vec<TradeResult>,
+    pub surplus: u64,
+}
+
+/// Executes a batch of trades the way an auction does: buys and sells are netted against
+/// each other, and only the difference is traded against the curve.
+///
+/// Every trade in the batch settles at the same clearing price, the average price of the net
+/// trade, so a buy and a sale of equal size cancel out at that price. When the trades net to
+/// zero, nothing touches the curve and they clear at the spot price. Buyers are rounded up and
+/// sellers down, so the `surplus` is never negative and settling never costs more than the
+/// curve charges.
+///
+/// # Arguments
+///
+/// * `curve` - The curve the net trade is priced with.
+/// * `supply` - The supply before the batch.
+/// * `trades` - The pending trades.
+///
+/// # Returns
+///
+/// A `Result` containing the `NettedBatch`, `BondingCurveError::Overflow` if the batch's
+/// tokens or a settlement do not fit in a `u64`, or the `BondingCurveError` the net trade
+/// fails with, such as selling more than the supply.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{net_batch, LinearBondingCurve, OperationSide, PendingTrade};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let trades = [
+///     PendingTrade::new(OperationSide::Add, 15),
+///     PendingTrade::new(OperationSide::Remove, 5),
+/// ];
+/// let batch = net_batch(&curve, 0, &trades).unwrap();
+/// // Only the 10 net tokens are bought, for 14_500, or 1_450 apiece.
+/// assert_eq!((batch.net.amount, batch.net.cost), (10, 14_500));
+/// assert_eq!(batch.allocations[0].cost, 21_750);
+/// assert_eq!(batch.allocations[1].cost, 7_250);
+/// assert_eq!(batch.surplus, 0);
+/// ```
+pub fn net_batch<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    supply: u64,
+    trades: &[PendingTrade],
+) -> Result<NettedBatch, BondingCurveError> {
+    let total = |side: OperationSide| {
+        trades
+            .iter()
+            .filter(|trade| trade.side == side)
+            .try_fold(0u64, |total, trade| total.checked_add(trade.amount))
+            .ok_or(BondingCurveError::Overflow)
+    };
+    let (bought, sold) = (total(OperationSide::Add)?, total(OperationSide::Remove)?);
+    let (side, amount) = if bought >= sold {
+        (OperationSide::Add, bought - sold)
+    } else {
+        (OperationSide::Remove, sold - bought)
+    };
+    let response = QuoteRequest::new(supply, amount, side).quote(curve)?;
+    let net = TradeResult {
+        side,
+        amount,
+        cost: response.price,
+        supply: response.ending_supply,
+        fee: 0,
+    };
+    // The clearing price, as the fraction `value / tokens`.
+    let (value, tokens) = if amount == 0 {
+        (curve.calculate_price_checked(supply)? as u128, 1)
+    } else {
+        (net.cost as u128, amount as u128)
+    };
+
+    let mut collected = match side {
+        OperationSide::Add => 0,
+        OperationSide::Remove => net.cost as u128,
+    };
+    let mut paid = match side {
+        OperationSide::Add => net.cost as u128,
+        OperationSide::Remove => 0,
+    };
+    let mut allocations = Vec::with_capacity(trades.len());
+    for trade in trades {
+        let owed = trade.amount as u128 * value;
+        let cost = match trade.side {
+            OperationSide::Add => owed.div_ceil(tokens),
+            OperationSide::Remove => owed / tokens,
+        };
+        match trade.side {
+            OperationSide::Add => collected += cost,
+            OperationSide::Remove => paid += cost,
+        }
+        allocations.push(TradeResult {
+            side: trade.side,
+            amount: trade.amount,
+            cost: u64::try_from(cost).map_err(|_| BondingCurveError::Overflow)?,
+            supply: net.supply,
+            fee: 0,
+        });
+    }
+    Ok(NettedBatch {
+        net,
+        allocations,
+        surplus: u64::try_from(collected - paid).map_err(|_| BondingCurveError::Overflow)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{net_batch, BondingCurveError, LinearBondingCurve, OperationSide, PendingTrade};
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{BondingCurve, QuadraticBondingCurve};
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_net_batch_rounds_in_favor_of_the_curve() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let trades = [
+            PendingTrade::new(OperationSide::Add, 7),
+            PendingTrade::new(OperationSide::Remove, 3),
+            PendingTrade::new(OperationSide::Add, 5),
+            PendingTrade::new(OperationSide::Remove, 6),
+        ];
+        let batch = net_batch(&curve, 1_000, &trades).unwrap();
+        assert_eq!((batch.net.side, batch.net.amount), (OperationSide::Add, 3));
+        assert_eq!(
+            batch.net.cost,
+            curve.calculate_price_many(1_000, 3, OperationSide::Add)
+        );
+        let (paid, received) = batch
+            .allocations
+            .iter()
+            .fold((0, 0), |(paid, received), a| match a.side {
+                OperationSide::Add => (paid + a.cost, received),
+                OperationSide::Remove => (paid, received + a.cost),
+            });
+        assert_eq!(paid - received - batch.net.cost, batch.surplus);
+        assert!(batch.surplus < trades.len() as u64);
+        assert!(batch.allocations.iter().all(|a| a.supply == 1_003));
+
+        // Selling on net pays the sellers out of the curve's proceeds.
+        let batch_sold = net_batch(&curve, 1_004, &[trades[1], trades[3]]).unwrap();
+        assert_eq!(
+            (batch_sold.net.side, batch_sold.net.supply),
+            (OperationSide::Remove, 995)
+        );
+        assert_eq!(
+            batch_sold.net.cost,
+            batch_sold.allocations[0].cost + batch_sold.allocations[1].cost + batch_sold.surplus
+        );
+    }
+
+    #[test]
+    pub fn test_net_batch_balanced_and_invalid() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let balanced = [
+            PendingTrade::new(OperationSide::Add, 4),
+            PendingTrade::new(OperationSide::Remove, 4),
+        ];
+        let batch = net_batch(&curve, 20, &balanced).unwrap();
+        assert_eq!(
+            (batch.net.amount, batch.net.cost, batch.net.supply),
+            (0, 0, 20)
+        );
+        // Trades that cancel out clear at the spot price.
+        assert!(batch.allocations.iter().all(|a| a.cost == 4 * 3_000));
+        assert_eq!(batch.surplus, 0);
+        assert!(net_batch(&curve, 0, &[]).unwrap().allocations.is_empty());
+        assert_eq!(
+            net_batch(&curve, 3, &[PendingTrade::new(OperationSide::Remove, 4)]),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let huge = [
+            PendingTrade::new(OperationSide::Add, u64::MAX),
+            PendingTrade::new(OperationSide::Add, 1),
+        ];
+        assert_eq!(
+            net_batch(&curve, 0, &huge),
+            Err(BondingCurveError::Overflow)
+        );
+        let cancelling = [
+            PendingTrade::new(OperationSide::Add, u64::MAX),
+            PendingTrade::new(OperationSide::Remove, u64::MAX),
+        ];
+        assert_eq!(
+            net_batch(&curve, 0, &cancelling),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
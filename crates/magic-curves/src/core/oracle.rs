@@ -0,0 +1,258 @@
+use super::{BondingCurveError, Decimals, QuoteResponse};
+
+/// Represents the USD price of one whole token, as `price * 10^expo`, the way Pyth and most
+/// on-chain oracles publish prices.
+///
+/// # Fields
+///
+/// * `price`: The price mantissa.
+/// * `expo`: The power of ten the mantissa is scaled by, usually negative.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::ExchangeRate;
+///
+/// // 142.5 USD per token.
+/// let rate = ExchangeRate::new(14_250, -2);
+/// assert_eq!((rate.price, rate.expo), (14_250, -2));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExchangeRate {
+    pub price: i64,
+    pub expo: i32,
+}
+
+impl ExchangeRate {
+    /// Creates a new `ExchangeRate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The price mantissa.
+    /// * `expo` - The power of ten the mantissa is scaled by.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `ExchangeRate`.
+    pub fn new(price: i64, expo: i32) -> Self {
+        Self { price, expo }
+    }
+}
+
+/// A source of the USD price of the token curve quotes are denominated in, such as an oracle
+/// account read on-chain or a price fetched off-chain.
+///
+/// Sources are responsible for their own staleness and confidence checks, and should fail
+/// rather than return a price they do not trust.
+pub trait ExchangeRateSource {
+    /// Returns the current USD price of one whole token.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ExchangeRate`, or a `BondingCurveError` if no usable price
+    /// is available.
+    fn exchange_rate(&self) -> Result<ExchangeRate, BondingCurveError>;
+}
+
+impl ExchangeRateSource for ExchangeRate {
+    fn exchange_rate(&self) -> Result<ExchangeRate, BondingCurveError> {
+        Ok(*self)
+    }
+}
+
+impl<S: ExchangeRateSource + ?Sized> ExchangeRateSource for &S {
+    fn exchange_rate(&self) -> Result<ExchangeRate, BondingCurveError> {
+        (**self).exchange_rate()
+    }
+}
+
+/// Reads the price straight from a Pyth `Price`.
+///
+/// Get the `Price` with `PriceFeed::get_price_no_older_than` (or the on-chain
+/// equivalent), so stale prices are rejected before they reach a quote. Prices that are
+/// zero or negative fail the conversion with `BondingCurveError::InvalidParameter`.
+#[cfg(feature = "pyth")]
+impl ExchangeRateSource for pyth_sdk::Price {
+    fn exchange_rate(&self) -> Result<ExchangeRate, BondingCurveError> {
+        Ok(ExchangeRate::new(self.price, self.expo))
+    }
+}
+
+/// Converts an amount of the quote token into USD, through any `ExchangeRateSource`.
+///
+/// # Arguments
+///
+/// * `amount` - The amount in base units of the quote token, such as a curve price.
+/// * `decimals` - The decimals of the quote token.
+/// * `usd_decimals` - The decimals of the USD result, such as `Decimals::USDC`.
+/// * `source` - The source of the quote token's USD price.
+///
+/// # Returns
+///
+/// A `Result` containing the amount in USD base units, rounded down, or the error of the
+/// source; `BondingCurveError::InvalidParameter` if the price is zero or negative, or
+/// `BondingCurveError::Overflow` if the result does not fit in a `u64`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{convert_to_usd, Decimals, ExchangeRate};
+///
+/// // 1.5 SOL at 142.5 USD is 213.75 USDC.
+/// let rate = ExchangeRate::new(14_250, -2);
+/// let usd = convert_to_usd(1_500_000_000, Decimals::SOL, Decimals::USDC, &rate).unwrap();
+/// assert_eq!(usd, 213_750_000);
+/// ```
+pub fn convert_to_usd<S: ExchangeRateSource + ?Sized>(
+    amount: u64,
+    decimals: Decimals,
+    usd_decimals: Decimals,
+    source: &S,
+) -> Result<u64, BondingCurveError> {
+    let rate = source.exchange_rate()?;
+    if rate.price <= 0 {
+        return Err(BondingCurveError::InvalidParameter);
+    }
+    let value = amount as u128 * rate.price as u128;
+    let scale = rate.expo as i64 + usd_decimals.0 as i64 - decimals.0 as i64;
+    let value = if scale >= 0 {
+        10u128
+            .checked_pow(scale as u32)
+            .and_then(|factor| value.checked_mul(factor))
+            .ok_or(BondingCurveError::Overflow)?
+    } else {
+        // Divisors past `u128::MAX` round every amount down to zero.
+        10u128
+            .checked_pow(scale.unsigned_abs() as u32)
+            .map_or(0, |divisor| value / divisor)
+    };
+    u64::try_from(value).map_err(|_| BondingCurveError::Overflow)
+}
+
+impl QuoteResponse {
+    /// Converts the quoted price into USD, through any `ExchangeRateSource`.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimals` - The decimals of the token the price is denominated in.
+    /// * `usd_decimals` - The decimals of the USD result.
+    /// * `source` - The source of the quote token's USD price.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price in USD base units, as returned by `convert_to_usd`.
+    pub fn price_in_usd<S: ExchangeRateSource + ?Sized>(
+        &self,
+        decimals: Decimals,
+        usd_decimals: Decimals,
+        source: &S,
+    ) -> Result<u64, BondingCurveError> {
+        convert_to_usd(self.price, decimals, usd_decimals, source)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        convert_to_usd, BondingCurveError, Decimals, ExchangeRate, ExchangeRateSource,
+        LinearBondingCurve, OperationSide, QuoteRequest,
+    };
+
+    struct Offline;
+
+    impl ExchangeRateSource for Offline {
+        fn exchange_rate(&self) -> Result<ExchangeRate, BondingCurveError> {
+            Err(BondingCurveError::InvalidParameter)
+        }
+    }
+
+    #[test]
+    pub fn test_convert_to_usd() {
+        let rate = ExchangeRate::new(14_250, -2);
+        assert_eq!(
+            convert_to_usd(1, Decimals::SOL, Decimals::USDC, &rate),
+            Ok(0)
+        );
+        assert_eq!(
+            convert_to_usd(1_000, Decimals::SOL, Decimals::USDC, &rate),
+            Ok(142)
+        );
+        // A positive exponent scales up, and the result can use more decimals than the token.
+        let rate = ExchangeRate::new(3, 2);
+        assert_eq!(
+            convert_to_usd(7, Decimals(0), Decimals(2), &rate),
+            Ok(210_000)
+        );
+        let sources: [&dyn ExchangeRateSource; 2] = [&ExchangeRate::new(1, 0), &Offline];
+        assert_eq!(
+            convert_to_usd(5, Decimals(0), Decimals(0), sources[0]),
+            Ok(5)
+        );
+        assert_eq!(
+            convert_to_usd(5, Decimals(0), Decimals(0), sources[1]),
+            Err(BondingCurveError::InvalidParameter)
+        );
+
+        let curve = LinearBondingCurve::new(0, 2_000_000_000);
+        let quote = QuoteRequest::new(0, 3, OperationSide::Add)
+            .quote(&curve)
+            .unwrap();
+        let rate = ExchangeRate::new(150, 0);
+        assert_eq!(
+            quote.price_in_usd(Decimals::SOL, Decimals::USDC, &rate),
+            Ok(900_000_000)
+        );
+    }
+
+    #[test]
+    pub fn test_convert_to_usd_errors() {
+        for price in [0, -1] {
+            assert_eq!(
+                convert_to_usd(1, Decimals(0), Decimals(0), &ExchangeRate::new(price, 0)),
+                Err(BondingCurveError::InvalidParameter)
+            );
+        }
+        assert_eq!(
+            convert_to_usd(u64::MAX, Decimals(0), Decimals(0), &ExchangeRate::new(2, 0)),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            convert_to_usd(1, Decimals(0), Decimals(0), &ExchangeRate::new(1, 39)),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            convert_to_usd(
+                u64::MAX,
+                Decimals(0),
+                Decimals(0),
+                &ExchangeRate::new(1, -39)
+            ),
+            Ok(0)
+        );
+        assert_eq!(
+            convert_to_usd(1, Decimals(0), Decimals(0), &ExchangeRate::new(1, i32::MIN)),
+            Ok(0)
+        );
+    }
+
+    #[cfg(feature = "pyth")]
+    #[test]
+    pub fn test_pyth_exchange_rate() {
+        let price = pyth_sdk::Price {
+            price: 14_250_000_000,
+            conf: 1_000_000,
+            expo: -8,
+            publish_time: 0,
+        };
+        assert_eq!(
+            convert_to_usd(1_500_000_000, Decimals::SOL, Decimals::USDC, &price),
+            Ok(213_750_000)
+        );
+    }
+}
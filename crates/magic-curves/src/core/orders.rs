@@ -0,0 +1,179 @@
+use super::{BondingCurveWithCheckedOperations, OperationSide, QuoteRequest, TradeResult};
+
+/// Represents a resting limit order of a hybrid order book and curve venue.
+///
+/// # Fields
+///
+/// * `side`: `Add` for an order to buy tokens from the curve, `Remove` for one to sell them
+///   to it.
+/// * `price`: The limit, the highest average price per token a buy pays or the lowest a sale
+///   accepts.
+/// * `size`: The number of tokens to trade.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitOrder {
+    pub side: OperationSide,
+    pub price: u64,
+    pub size: u64,
+}
+
+impl LimitOrder {
+    /// Creates a new `LimitOrder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Whether the order buys or sells tokens.
+    /// * `price` - The limit price per token.
+    /// * `size` - The number of tokens to trade.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `LimitOrder`.
+    pub fn new(side: OperationSide, price: u64, size: u64) -> Self {
+        Self { side, price, size }
+    }
+}
+
+/// Represents an order filled by `fill_limit_orders`.
+///
+/// # Fields
+///
+/// * `index`: The position of the order in the list passed to `fill_limit_orders`.
+/// * `trade`: The trade against the curve that fills the order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderFill {
+    pub index: usize,
+    pub trade: TradeResult,
+}
+
+/// Represents the outcome of `fill_limit_orders`.
+///
+/// # Fields
+///
+/// * `fills`: The orders that fill, in the order they were filled.
+/// * `supply`: The supply after every fill.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitOrderFills {
+    pub fills: Vec<OrderFill>,
+    pub supply: u64,
+}
+
+/// Determines which resting limit orders fill by trading against a curve, without changing
+/// anything.
+///
+/// Orders are walked in the order given, so the caller decides their priority, such as best
+/// price first. An order fills in full when the curve prices its whole size within its
+/// limit at the supply left by the fills before it; otherwise it keeps resting, including when
+/// the curve cannot price it, such as a sale larger than the supply. Orders are never filled
+/// in part.
+///
+/// # Arguments
+///
+/// * `curve` - The curve orders trade against.
+/// * `supply` - The current supply of the curve.
+/// * `orders` - The resting orders, in priority order.
+///
+/// # Returns
+///
+/// The `LimitOrderFills` with the filled orders and the resulting supply.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{fill_limit_orders, LimitOrder, LinearBondingCurve, OperationSide};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let orders = [
+///     LimitOrder::new(OperationSide::Add, 1_500, 10),
+///     LimitOrder::new(OperationSide::Add, 1_500, 10),
+///     LimitOrder::new(OperationSide::Remove, 1_700, 5),
+/// ];
+/// let filled = fill_limit_orders(&curve, 0, &orders);
+/// // The first buy costs 14_500, the second would cost 24_500, and the sale pays 9_000.
+/// let indices: Vec<usize> = filled.fills.iter().map(|fill| fill.index).collect();
+/// assert_eq!(indices, vec![0, 2]);
+/// assert_eq!(filled.fills[1].trade.cost, 9_000);
+/// assert_eq!(filled.supply, 5);
+/// ```
+pub fn fill_limit_orders<C: BondingCurveWithCheckedOperations<u64>>(
+    curve: &C,
+    supply: u64,
+    orders: &[LimitOrder],
+) -> LimitOrderFills {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("fill_limit_orders", supply, orders = orders.len()).entered();
+    let mut supply = supply;
+    let mut fills = Vec::new();
+    for (index, order) in orders.iter().enumerate() {
+        let Ok(response) = QuoteRequest::new(supply, order.size, order.side).quote(curve) else {
+            continue;
+        };
+        let limit = order.price as u128 * order.size as u128;
+        let within = match order.side {
+            OperationSide::Add => response.price as u128 <= limit,
+            OperationSide::Remove => response.price as u128 >= limit,
+        };
+        if !within {
+            continue;
+        }
+        supply = response.ending_supply;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(index, supply, cost = response.price, "filled order");
+        fills.push(OrderFill {
+            index,
+            trade: TradeResult {
+                side: order.side,
+                amount: order.size,
+                cost: response.price,
+                supply,
+                fee: 0,
+            },
+        });
+    }
+    LimitOrderFills { fills, supply }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{fill_limit_orders, QuadraticBondingCurve};
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{BondingCurve, CurveState, LimitOrder, OperationSide};
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_fill_limit_orders() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let spot = curve.calculate_price(1_000);
+        let orders = [
+            // Too low to buy at the current supply.
+            LimitOrder::new(OperationSide::Add, spot, 100),
+            LimitOrder::new(OperationSide::Add, spot * 2, 100),
+            // The buy above raised the price enough for this sale.
+            LimitOrder::new(OperationSide::Remove, spot, 50),
+            LimitOrder::new(OperationSide::Remove, 0, 10_000),
+            LimitOrder::new(OperationSide::Add, u64::MAX, u64::MAX),
+            LimitOrder::new(OperationSide::Add, 0, 0),
+        ];
+        let filled = fill_limit_orders(&curve, 1_000, &orders);
+        let indices: Vec<usize> = filled.fills.iter().map(|fill| fill.index).collect();
+        assert_eq!(indices, vec![1, 2, 5]);
+        assert_eq!(filled.supply, 1_050);
+        // Every fill is the trade a `CurveState` would make.
+        let mut state = CurveState::new(curve, 1_000);
+        for fill in &filled.fills {
+            let order = orders[fill.index];
+            assert_eq!(state.apply(order.size, order.side), Ok(fill.trade));
+        }
+        assert_eq!(state.supply(), filled.supply);
+    }
+
+    #[test]
+    pub fn test_fill_limit_orders_empty() {
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let filled = fill_limit_orders(&curve, 10, &[]);
+        assert!(filled.fills.is_empty());
+        assert_eq!(filled.supply, 10);
+    }
+}
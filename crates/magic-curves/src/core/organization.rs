@@ -0,0 +1,259 @@
+use super::{bps_of, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents a trade applied to a `ContinuousOrganization`.
+///
+/// # Fields
+///
+/// * `side`: Whether tokens were bought or sold.
+/// * `amount`: The number of tokens traded.
+/// * `price`: The amount the buyer paid, or the amount the seller received.
+/// * `reserve`: The part of `price` paid into the buyback reserve on a buy, or out of it on a
+///   sell.
+/// * `beneficiary`: The part of a buy's `price` paid to the organization. Zero on sells.
+/// * `supply`: The supply after the trade.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrganizationTrade {
+    pub side: OperationSide,
+    pub amount: u64,
+    pub price: u64,
+    pub reserve: u64,
+    pub beneficiary: u64,
+    pub supply: u64,
+}
+
+/// Represents a continuous organization, whose tokens are bought on a curve and sold back to
+/// a buyback reserve.
+///
+/// Of every buy, `reserve_ratio` basis points are kept in the reserve and the rest goes to the
+/// organization. Sellers are refunded the same fraction of the price the sold tokens were
+/// bought at, so the sell curve is the buy curve scaled down by `reserve_ratio`, and a round
+/// trip costs the buyer the organization's share. Refunds never exceed the reserve, and the
+/// last token sold takes whatever rounding left in it.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{ContinuousOrganization, LinearBondingCurve};
+///
+/// let mut org = ContinuousOrganization::new(LinearBondingCurve::new(10, 100), 2_000);
+/// let buy = org.buy(10).unwrap();
+/// assert_eq!((buy.price, buy.reserve, buy.beneficiary), (1_450, 290, 1_160));
+/// assert_eq!(org.sell_price(5), Ok(170));
+/// let sell = org.sell(10).unwrap();
+/// assert_eq!(sell.price, 290);
+/// assert_eq!((org.supply(), org.reserve()), (0, 0));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContinuousOrganization<C> {
+    curve: C,
+    reserve_ratio: u64,
+    supply: u64,
+    reserve: u64,
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> ContinuousOrganization<C> {
+    /// Creates a new `ContinuousOrganization` with no tokens sold.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve buys are priced with.
+    /// * `reserve_ratio` - The part of every buy kept in the buyback reserve, in basis points.
+    ///   Must not exceed `BASIS_POINTS`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `ContinuousOrganization`.
+    pub fn new(curve: C, reserve_ratio: u64) -> Self {
+        Self::with_state(curve, reserve_ratio, 0, 0)
+    }
+
+    /// Creates a `ContinuousOrganization` that has already sold tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve buys are priced with.
+    /// * `reserve_ratio` - The part of every buy kept in the buyback reserve, in basis points.
+    ///   Must not exceed `BASIS_POINTS`.
+    /// * `supply` - The current supply of tokens.
+    /// * `reserve` - The current balance of the buyback reserve.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `ContinuousOrganization`.
+    pub fn with_state(curve: C, reserve_ratio: u64, supply: u64, reserve: u64) -> Self {
+        Self {
+            curve,
+            reserve_ratio,
+            supply,
+            reserve,
+        }
+    }
+
+    /// Returns the curve buys are priced with.
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the part of every buy kept in the buyback reserve, in basis points.
+    pub fn reserve_ratio(&self) -> u64 {
+        self.reserve_ratio
+    }
+
+    /// Returns the current supply of tokens.
+    pub fn supply(&self) -> u64 {
+        self.supply
+    }
+
+    /// Returns the current balance of the buyback reserve.
+    pub fn reserve(&self) -> u64 {
+        self.reserve
+    }
+
+    /// Prices a buy of `amount` tokens at the current supply without applying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to buy.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, or a `BondingCurveError` if the curve fails.
+    pub fn buy_price(&self, amount: u64) -> Result<u64, BondingCurveError> {
+        self.curve
+            .calculate_price_many_checked(self.supply, amount, OperationSide::Add)
+    }
+
+    /// Prices a sale of `amount` tokens at the current supply without applying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to sell.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the refund, `BondingCurveError::SupplyUnderflow` if `amount`
+    /// exceeds the supply, `BondingCurveError::InvalidParameter` if `reserve_ratio` is above
+    /// `BASIS_POINTS`, or a `BondingCurveError` if the curve fails.
+    pub fn sell_price(&self, amount: u64) -> Result<u64, BondingCurveError> {
+        let start = self
+            .supply
+            .checked_sub(amount)
+            .ok_or(BondingCurveError::SupplyUnderflow)?;
+        if start == 0 && amount > 0 {
+            return Ok(self.reserve);
+        }
+        let bought = self
+            .curve
+            .calculate_price_many_checked(start, amount, OperationSide::Add)?;
+        Ok(bps_of(bought, self.reserve_ratio)?.min(self.reserve))
+    }
+
+    /// Buys `amount` tokens, splitting the price between the reserve and the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to buy.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the applied `OrganizationTrade`, or a `BondingCurveError` if the
+    /// buy cannot be applied, in which case the organization is unchanged.
+    pub fn buy(&mut self, amount: u64) -> Result<OrganizationTrade, BondingCurveError> {
+        let price = self.buy_price(amount)?;
+        let reserve = bps_of(price, self.reserve_ratio)?;
+        let balance = self
+            .reserve
+            .checked_add(reserve)
+            .ok_or(BondingCurveError::Overflow)?;
+        // The price succeeded, so the supply stays in range.
+        self.supply += amount;
+        self.reserve = balance;
+        Ok(OrganizationTrade {
+            side: OperationSide::Add,
+            amount,
+            price,
+            reserve,
+            beneficiary: price - reserve,
+            supply: self.supply,
+        })
+    }
+
+    /// Sells `amount` tokens back to the reserve.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to sell.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the applied `OrganizationTrade`, or a `BondingCurveError` if the
+    /// sale cannot be applied, in which case the organization is unchanged.
+    pub fn sell(&mut self, amount: u64) -> Result<OrganizationTrade, BondingCurveError> {
+        let price = self.sell_price(amount)?;
+        self.supply -= amount;
+        self.reserve -= price;
+        Ok(OrganizationTrade {
+            side: OperationSide::Remove,
+            amount,
+            price,
+            reserve: price,
+            beneficiary: 0,
+            supply: self.supply,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, ContinuousOrganization,
+        OperationSide, QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_continuous_organization_mirrors_buy_curve() {
+        let curve = QuadraticBondingCurve::new(1_000, 20_000, 3_000_000);
+        let mut org = ContinuousOrganization::new(curve, 2_500);
+        let buy = org.buy(100).unwrap();
+        assert_eq!(
+            buy.price,
+            curve
+                .calculate_price_many_checked(0, 100, OperationSide::Add)
+                .unwrap()
+        );
+        assert_eq!(buy.reserve, buy.price / 4);
+        assert_eq!(buy.reserve + buy.beneficiary, buy.price);
+        assert_eq!(org.reserve(), buy.reserve);
+
+        let bought = curve
+            .calculate_price_many_checked(90, 10, OperationSide::Add)
+            .unwrap();
+        assert_eq!(org.sell_price(10), Ok(bought / 4));
+        assert_eq!(org.sell_price(101), Err(BondingCurveError::SupplyUnderflow));
+        assert_eq!(
+            org.buy_price(10),
+            curve.calculate_price_many_checked(100, 10, OperationSide::Add)
+        );
+    }
+
+    #[test]
+    pub fn test_continuous_organization_stays_solvent() {
+        let curve = QuadraticBondingCurve::new(3, 7, 11);
+        let mut org = ContinuousOrganization::new(curve, 3_333);
+        let mut deposited = 0;
+        for amount in [1, 2, 3, 5, 8] {
+            deposited += org.buy(amount).unwrap().reserve;
+        }
+        let mut refunded = 0;
+        while org.supply() > 0 {
+            refunded += org.sell(4.min(org.supply())).unwrap().price;
+        }
+        assert_eq!(org.reserve(), 0);
+        assert_eq!(refunded, deposited);
+        let before = org;
+        assert_eq!(org.sell(1), Err(BondingCurveError::SupplyUnderflow));
+        assert_eq!(org, before);
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(not(feature = "panic-free"))]
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use rayon::prelude::*;
+
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, QuoteRequest, QuoteResponse};
+#[cfg(feature = "rand")]
+use super::{CurveState, Simulation, SimulationStep, TradeObserver};
+
+/// Prices many supplies across the rayon thread pool.
+///
+/// Implemented for every `Sync` curve, so analytics jobs can price in bulk without setting up
+/// their own thread pools.
+#[cfg(not(feature = "panic-free"))]
+pub trait ParallelBondingCurve<T: Send>: BondingCurve<T> + Sync {
+    /// Calculates the price at each of the given supplies in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `supplies` - The supplies to price.
+    ///
+    /// # Returns
+    ///
+    /// The price at each supply, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{LinearBondingCurve, ParallelBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// assert_eq!(curve.par_calculate_prices(&[0, 1, 2]), vec![1000, 1100, 1200]);
+    /// ```
+    fn par_calculate_prices(&self, supplies: &[u64]) -> Vec<T> {
+        supplies
+            .par_iter()
+            .map(|&supply| self.calculate_price(supply))
+            .collect()
+    }
+
+    /// Samples the price over a supply range in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `supplies` - The supply range to sample.
+    /// * `step` - The distance between sampled supplies.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `(supply, price)` pairs in ascending supply order, or
+    /// `BondingCurveError::InvalidParameter` if `step` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{LinearBondingCurve, ParallelBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// assert_eq!(
+    ///     curve.par_sample_prices(0..=20, 10),
+    ///     Ok(vec![(0, 1000), (10, 2000), (20, 3000)])
+    /// );
+    /// ```
+    fn par_sample_prices(
+        &self,
+        supplies: RangeInclusive<u64>,
+        step: u64,
+    ) -> Result<Vec<(u64, T)>, BondingCurveError> {
+        if step == 0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let (start, end) = supplies.into_inner();
+        if start > end {
+            return Ok(Vec::new());
+        }
+        Ok((0..=(end - start) / step)
+            .into_par_iter()
+            .map(|index| {
+                let supply = start + index * step;
+                (supply, self.calculate_price(supply))
+            })
+            .collect())
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<T: Send, C: BondingCurve<T> + Sync> ParallelBondingCurve<T> for C {}
+
+/// Answers many quote requests against one curve in parallel.
+///
+/// # Arguments
+///
+/// * `curve` - The curve to quote against.
+/// * `requests` - The quote requests.
+///
+/// # Returns
+///
+/// The result of `QuoteRequest::quote` for each request, in the same order.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{par_quote, LinearBondingCurve, OperationSide, QuoteRequest};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// let requests = [
+///     QuoteRequest::new(0, 1, OperationSide::Add),
+///     QuoteRequest::new(10, 2, OperationSide::Add),
+/// ];
+/// let quotes = par_quote(&curve, &requests);
+/// assert_eq!(quotes[1], requests[1].quote(&curve));
+/// ```
+pub fn par_quote<C>(
+    curve: &C,
+    requests: &[QuoteRequest],
+) -> Vec<Result<QuoteResponse, BondingCurveError>>
+where
+    C: BondingCurveWithCheckedOperations<u64> + Sync,
+{
+    requests
+        .par_iter()
+        .map(|request| request.quote(curve))
+        .collect()
+}
+
+/// Runs many independent seeded simulations in parallel, for Monte-Carlo analysis.
+///
+/// Every path starts from a copy of `state` and runs as a `Simulation::seeded` with its own
+/// seed, drawn in path order from a `StdRng` seeded with `seed`. Each path is therefore
+/// reproducible on its own, and the results do not depend on how the paths are scheduled
+/// across threads.
+///
+/// # Arguments
+///
+/// * `paths` - The number of simulations to run.
+/// * `seed` - The seed the per-path seeds are drawn from.
+/// * `state` - The state every path starts from.
+/// * `steps` - The number of steps each path runs.
+/// * `agents` - Adds the agents of a path to its simulation.
+///
+/// # Returns
+///
+/// The `SimulationStep` records of each path, in path order.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{par_simulate, CurveState, LinearBondingCurve, RandomTrader};
+///
+/// let state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+/// let run = || par_simulate(8, 7, &state, 50, |simulation| {
+///     simulation.with_agent(RandomTrader::new(20))
+/// });
+/// let paths = run();
+/// assert_eq!(paths.len(), 8);
+/// assert_eq!(paths, run());
+/// assert_ne!(paths[0], paths[1]);
+/// ```
+#[cfg(feature = "rand")]
+pub fn par_simulate<C, O, F>(
+    paths: usize,
+    seed: u64,
+    state: &CurveState<C, O>,
+    steps: u64,
+    agents: F,
+) -> Vec<Vec<SimulationStep>>
+where
+    C: BondingCurveWithCheckedOperations<u64> + Clone + Sync,
+    O: TradeObserver + Clone + Sync,
+    F: Fn(Simulation<C, O>) -> Simulation<C, O> + Sync,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    let seeds: Vec<u64> = (0..paths).map(|_| rng.next_u64()).collect();
+    seeds
+        .into_par_iter()
+        .map(|seed| agents(Simulation::seeded(state.clone(), seed)).run(steps))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{par_quote, BondingCurveError, LinearBondingCurve, OperationSide, QuoteRequest};
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{BondingCurve, ParallelBondingCurve, QuadraticBondingCurve};
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_parallel_prices() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let supplies: Vec<u64> = (0..10_000).collect();
+        let sequential: Vec<u64> = supplies
+            .iter()
+            .map(|&supply| curve.calculate_price(supply))
+            .collect();
+        assert_eq!(curve.par_calculate_prices(&supplies), sequential);
+
+        let samples = curve.par_sample_prices(5..=9_999, 1_000).unwrap();
+        assert_eq!(samples.len(), 10);
+        assert_eq!(samples[9], (9_005, curve.calculate_price(9_005)));
+        assert_eq!(
+            curve.par_sample_prices(0..=10, 0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[test]
+    pub fn test_parallel_quotes() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let requests = [
+            QuoteRequest::new(10, 5, OperationSide::Add),
+            QuoteRequest::new(u64::MAX, 1, OperationSide::Add),
+        ];
+        let quotes = par_quote(&curve, &requests);
+        assert_eq!(quotes[0], requests[0].quote(&curve));
+        assert_eq!(quotes[1], Err(BondingCurveError::Overflow));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    pub fn test_parallel_simulations() {
+        use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+        use crate::{par_simulate, CurveState, QuadraticBondingCurve, RandomTrader, Simulation};
+
+        let state = CurveState::new(QuadraticBondingCurve::new(1, 0, 500), 1_000);
+        let agents = |simulation: Simulation<_>| {
+            simulation
+                .with_agent(RandomTrader::new(40))
+                .with_agent(RandomTrader::new(40))
+        };
+        let paths = par_simulate(16, 42, &state, 100, agents);
+        assert_eq!(paths.len(), 16);
+
+        // Each path is the sequential run of its own seed, whatever thread it ran on.
+        let mut seeds = StdRng::seed_from_u64(42);
+        for path in &paths {
+            let sequential = agents(Simulation::seeded(state, seeds.next_u64())).run(100);
+            assert_eq!(path, &sequential);
+        }
+        assert!(paths.windows(2).all(|pair| pair[0] != pair[1]));
+        assert_ne!(paths, par_simulate(16, 43, &state, 100, agents));
+        assert!(par_simulate(0, 42, &state, 100, agents).is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, BASIS_POINTS,
+};
+
+/// Represents a peg stability module: a flat curve that mints and redeems a stablecoin at a
+/// fixed price of one, charging a fee on each side.
+///
+/// `unit` is the base-unit amount of collateral worth one stablecoin, such as `1_000_000`
+/// for a 6-decimal collateral. Mints cost `unit` per token plus `buy_fee` basis points, and
+/// redemptions pay `unit` per token minus `sell_fee` basis points, both rounded in the
+/// module's favor. The supply is the stablecoin debt the module has issued, which the checked
+/// operations keep at or below `debt_ceiling`.
+///
+/// # Fields
+///
+/// * `unit`: The price of one token at the peg.
+/// * `buy_fee`: The fee on mints, in basis points of the peg price.
+/// * `sell_fee`: The fee on redemptions, in basis points of the peg price.
+/// * `debt_ceiling`: The largest supply the module may issue.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{OperationSide, PegStabilityCurve, QuoteRequest};
+///
+/// let psm = PegStabilityCurve::new(1_000_000, 10, 5, 1_000);
+/// let mint = QuoteRequest::new(0, 100, OperationSide::Add).quote(&psm).unwrap();
+/// assert_eq!(mint.price, 100_100_000);
+/// let redeem = QuoteRequest::new(100, 100, OperationSide::Remove).quote(&psm).unwrap();
+/// assert_eq!(redeem.price, 99_950_000);
+/// assert!(QuoteRequest::new(990, 11, OperationSide::Add).quote(&psm).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PegStabilityCurve {
+    pub unit: u64,
+    pub buy_fee: u64,
+    pub sell_fee: u64,
+    pub debt_ceiling: u64,
+}
+
+impl PegStabilityCurve {
+    /// Creates a new `PegStabilityCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - The price of one token at the peg.
+    /// * `buy_fee` - The fee on mints, in basis points of the peg price.
+    /// * `sell_fee` - The fee on redemptions, in basis points of the peg price.
+    /// * `debt_ceiling` - The largest supply the module may issue.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `PegStabilityCurve`.
+    pub fn new(unit: u64, buy_fee: u64, sell_fee: u64, debt_ceiling: u64) -> Self {
+        Self {
+            unit,
+            buy_fee,
+            sell_fee,
+            debt_ceiling,
+        }
+    }
+
+    /// Returns the fee charged on a trade of `amount` tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens traded.
+    /// * `side` - Whether tokens are being minted or redeemed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the fee, rounded up, `BondingCurveError::InvalidParameter` if the
+    /// fee is above `BASIS_POINTS`, or `BondingCurveError::Overflow` if the trade is too large.
+    pub fn fee(&self, amount: u64, side: OperationSide) -> Result<u64, BondingCurveError> {
+        let gross = self.gross(amount)?;
+        let bps = match side {
+            OperationSide::Add => self.buy_fee,
+            OperationSide::Remove => self.sell_fee,
+        };
+        if bps > BASIS_POINTS {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        Ok(fee_of(gross, bps))
+    }
+
+    fn gross(&self, amount: u64) -> Result<u64, BondingCurveError> {
+        amount
+            .checked_mul(self.unit)
+            .ok_or(BondingCurveError::Overflow)
+    }
+}
+
+/// Returns `bps` basis points of `gross`, rounded up.
+fn fee_of(gross: u64, bps: u64) -> u64 {
+    (gross as u128 * bps as u128).div_ceil(BASIS_POINTS as u128) as u64
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<u64> for PegStabilityCurve {
+    /// Returns the peg price, `unit`, at every supply.
+    fn calculate_price(&self, _supply: u64) -> u64 {
+        self.unit
+    }
+
+    /// Calculates the price of a mint or redemption, including the fee.
+    ///
+    /// This does not enforce the debt ceiling; use `calculate_price_many_checked` for that.
+    fn calculate_price_many(&self, _starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        let gross = amount * self.unit;
+        let bps = match side {
+            OperationSide::Add => self.buy_fee,
+            OperationSide::Remove => self.sell_fee,
+        };
+        let fee = fee_of(gross, bps);
+        match side {
+            OperationSide::Add => gross + fee,
+            OperationSide::Remove => gross - fee,
+        }
+    }
+}
+
+impl BondingCurveWithCheckedOperations<u64> for PegStabilityCurve {
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, _supply: u64) -> Result<u64, BondingCurveError> {
+        Ok(self.unit)
+    }
+
+    /// Calculates the price of a mint or redemption, including the fee.
+    ///
+    /// Fails with `BondingCurveError::SupplyCapExceeded` if a mint would take the supply past
+    /// `debt_ceiling`, and with `BondingCurveError::SupplyUnderflow` if a redemption exceeds
+    /// the supply.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        if supply_range(starting_supply, amount, side)?.is_none() {
+            return Ok(0);
+        }
+        let past_ceiling = starting_supply
+            .checked_add(amount)
+            .is_none_or(|supply| supply > self.debt_ceiling);
+        if side == OperationSide::Add && past_ceiling {
+            return Err(BondingCurveError::SupplyCapExceeded);
+        }
+        let gross = self.gross(amount)?;
+        let fee = self.fee(amount, side)?;
+        match side {
+            OperationSide::Add => gross.checked_add(fee).ok_or(BondingCurveError::Overflow),
+            // A fee of at most `BASIS_POINTS` never exceeds the gross amount.
+            OperationSide::Remove => Ok(gross - fee),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::BondingCurve;
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, PegStabilityCurve,
+    };
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_peg_stability_fees() {
+        let psm = PegStabilityCurve::new(1_000_000, 30, 15, u64::MAX);
+        assert_eq!(psm.calculate_price(0), 1_000_000);
+        assert_eq!(psm.calculate_price_checked(u64::MAX), Ok(1_000_000));
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            for amount in [1, 7, 1_000] {
+                assert_eq!(
+                    psm.calculate_price_many_checked(5_000, amount, side),
+                    Ok(psm.calculate_price_many(5_000, amount, side))
+                );
+            }
+        }
+        assert_eq!(
+            psm.calculate_price_many_checked(0, 3, OperationSide::Add),
+            Ok(3_009_000)
+        );
+        // Fees on odd amounts round up in the module's favor.
+        let odd = PegStabilityCurve::new(3, 1, 1, u64::MAX);
+        assert_eq!(odd.fee(1, OperationSide::Add), Ok(1));
+        assert_eq!(
+            odd.calculate_price_many_checked(1, 1, OperationSide::Remove),
+            Ok(2)
+        );
+        assert_eq!(odd.fee(0, OperationSide::Add), Ok(0));
+    }
+
+    #[test]
+    pub fn test_peg_stability_limits() {
+        let psm = PegStabilityCurve::new(1_000_000, 30, 15, 1_000);
+        assert_eq!(
+            psm.calculate_price_many_checked(990, 10, OperationSide::Add),
+            Ok(10_030_000)
+        );
+        assert_eq!(
+            psm.calculate_price_many_checked(990, 11, OperationSide::Add),
+            Err(BondingCurveError::SupplyCapExceeded)
+        );
+        assert_eq!(
+            psm.calculate_price_many_checked(10, 11, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let greedy = PegStabilityCurve::new(1_000_000, 10_001, 15, 1_000);
+        assert_eq!(
+            greedy.calculate_price_many_checked(0, 1, OperationSide::Add),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        // Minting the last supply would pass any ceiling, even `u64::MAX`.
+        assert_eq!(
+            PegStabilityCurve::new(1, 0, 0, u64::MAX).calculate_price_many_checked(
+                u64::MAX,
+                1,
+                OperationSide::Add
+            ),
+            Err(BondingCurveError::SupplyCapExceeded)
+        );
+        let huge = PegStabilityCurve::new(u64::MAX, 0, 0, u64::MAX);
+        assert_eq!(
+            huge.calculate_price_many_checked(0, 2, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
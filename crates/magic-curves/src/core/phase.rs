@@ -0,0 +1,202 @@
+use super::{bps_of, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents one time window of a `PhaseSchedule`.
+///
+/// # Fields
+///
+/// * `name`: A label for the phase, such as `"allowlist"` or `"public"`.
+/// * `start`: The first timestamp of the phase.
+/// * `end`: The timestamp the phase ends at, exclusive.
+/// * `curve`: The curve mints are priced with during the phase.
+/// * `discount`: The discount off the curve price, in basis points.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintPhase<C> {
+    pub name: String,
+    pub start: u64,
+    pub end: u64,
+    pub curve: C,
+    pub discount: u64,
+}
+
+/// Represents the price of a mint quoted by `PhaseSchedule::quote_at`.
+///
+/// # Fields
+///
+/// * `phase`: The index of the phase the mint falls in.
+/// * `full_price`: The curve price before the discount.
+/// * `price`: The price after the discount, rounded up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseQuote {
+    pub phase: usize,
+    pub full_price: u64,
+    pub price: u64,
+}
+
+/// Represents a whole sale as a sequence of time windows, each with its own curve and
+/// discount, such as the allowlist, public, and late phases of an NFT mint.
+///
+/// Phases are looked up in order, so the first phase whose window contains a timestamp is
+/// the one that prices it; timestamps outside every window have no price. Timestamps are in
+/// whatever unit the phases are configured in, usually seconds.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{LinearBondingCurve, PhaseSchedule};
+///
+/// let schedule = PhaseSchedule::new()
+///     .with("allowlist", 100, 200, LinearBondingCurve::new(0, 1_000), 2_000)
+///     .with("public", 200, 300, LinearBondingCurve::new(10, 1_000), 0);
+/// assert_eq!(schedule.quote_at(50, 0, 1), Ok(None));
+/// let allowlist = schedule.quote_at(150, 0, 2).unwrap().unwrap();
+/// assert_eq!((allowlist.phase, allowlist.price), (0, 1_600));
+/// let public = schedule.quote_at(250, 10, 1).unwrap().unwrap();
+/// assert_eq!((public.phase, public.price), (1, 1_100));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseSchedule<C> {
+    pub phases: Vec<MintPhase<C>>,
+}
+
+impl<C> Default for PhaseSchedule<C> {
+    fn default() -> Self {
+        Self { phases: Vec::new() }
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> PhaseSchedule<C> {
+    /// Creates a new, empty `PhaseSchedule`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a phase after the existing ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A label for the phase.
+    /// * `start` - The first timestamp of the phase.
+    /// * `end` - The timestamp the phase ends at, exclusive.
+    /// * `curve` - The curve mints are priced with during the phase.
+    /// * `discount` - The discount off the curve price, in basis points.
+    ///
+    /// # Returns
+    ///
+    /// The schedule, with the phase added.
+    pub fn with(
+        mut self,
+        name: impl Into<String>,
+        start: u64,
+        end: u64,
+        curve: C,
+        discount: u64,
+    ) -> Self {
+        self.phases.push(MintPhase {
+            name: name.into(),
+            start,
+            end,
+            curve,
+            discount,
+        });
+        self
+    }
+
+    /// Returns the index and phase active at `timestamp`, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The time to look up.
+    pub fn phase_at(&self, timestamp: u64) -> Option<(usize, &MintPhase<C>)> {
+        self.phases
+            .iter()
+            .enumerate()
+            .find(|(_, phase)| phase.start <= timestamp && timestamp < phase.end)
+    }
+
+    /// Quotes a mint of `amount` tokens at `timestamp`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The time of the mint.
+    /// * `supply` - The supply before the mint.
+    /// * `amount` - The number of tokens to mint.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `PhaseQuote`, or `None` if no phase is active at
+    /// `timestamp`; `BondingCurveError::InvalidParameter` if the phase's discount is above
+    /// `BASIS_POINTS`, or the `BondingCurveError` its curve fails with.
+    pub fn quote_at(
+        &self,
+        timestamp: u64,
+        supply: u64,
+        amount: u64,
+    ) -> Result<Option<PhaseQuote>, BondingCurveError> {
+        let Some((index, phase)) = self.phase_at(timestamp) else {
+            return Ok(None);
+        };
+        let full_price =
+            phase
+                .curve
+                .calculate_price_many_checked(supply, amount, OperationSide::Add)?;
+        let discount = bps_of(full_price, phase.discount)?;
+        Ok(Some(PhaseQuote {
+            phase: index,
+            full_price,
+            price: full_price - discount,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, PhaseSchedule,
+        QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_phase_schedule() {
+        let allowlist = QuadraticBondingCurve::new(0, 0, 5_000);
+        let public = QuadraticBondingCurve::new(1_000, 20_000, 3_000_000);
+        let schedule = PhaseSchedule::new()
+            .with("allowlist", 1_000, 2_000, allowlist, 1_500)
+            .with("public", 2_000, 5_000, public, 0)
+            .with("late", 4_000, 9_000, public, 5_000);
+        assert_eq!(schedule.phase_at(999), None);
+        assert_eq!(schedule.phase_at(1_999).unwrap().1.name, "allowlist");
+        assert_eq!(schedule.phase_at(2_000).unwrap().0, 1);
+        // Overlapping windows resolve to the earlier phase.
+        assert_eq!(schedule.phase_at(4_500).unwrap().1.name, "public");
+        assert_eq!(schedule.phase_at(5_000).unwrap().1.name, "late");
+        assert_eq!(schedule.quote_at(9_000, 0, 1), Ok(None));
+
+        let quote = schedule.quote_at(1_500, 10, 4).unwrap().unwrap();
+        assert_eq!(quote.full_price, 20_000);
+        assert_eq!(quote.price, 17_000);
+        let quote = schedule.quote_at(6_000, 100, 10).unwrap().unwrap();
+        let full = public
+            .calculate_price_many_checked(100, 10, OperationSide::Add)
+            .unwrap();
+        assert_eq!((quote.phase, quote.full_price), (2, full));
+        assert_eq!(quote.price, full - full / 2);
+    }
+
+    #[test]
+    pub fn test_phase_schedule_errors() {
+        let curve = QuadraticBondingCurve::new(0, 0, 5_000);
+        let schedule = PhaseSchedule::new().with("free", 0, 10, curve, 10_001);
+        assert_eq!(
+            schedule.quote_at(5, 0, 1),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        let schedule = PhaseSchedule::new().with("public", 0, 10, curve, 0);
+        assert_eq!(
+            schedule.quote_at(5, 0, u64::MAX),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
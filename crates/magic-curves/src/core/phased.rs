@@ -0,0 +1,256 @@
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents one supply range of a `PhasedCurve`.
+///
+/// # Fields
+///
+/// * `name`: A label for the phase, such as `"seed"` or `"public"`.
+/// * `supply`: The number of tokens sold during the phase.
+/// * `curve`: The curve that prices the phase, read from supply zero at the phase's first
+///   token.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurvePhase<C> {
+    pub name: String,
+    pub supply: u64,
+    pub curve: C,
+}
+
+/// Represents a curve made of consecutive supply ranges, each priced by its own curve, such
+/// as the seed, public, and graduation phases of a raise.
+///
+/// The first phase prices supplies from zero, and each later phase starts where the one
+/// before it ends. Every phase's curve is read at the supply sold within the phase, so a
+/// phase's prices do not depend on what came before it. The phases together cap the supply:
+/// pricing a token past the last phase fails with `BondingCurveError::SupplyCapExceeded`, and
+/// the unchecked methods panic.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, LinearBondingCurve, OperationSide, PhasedCurve};
+///
+/// let curve = PhasedCurve::new()
+///     .with("seed", 10, LinearBondingCurve::new(0, 100))
+///     .with("public", 90, LinearBondingCurve::new(10, 200));
+/// assert_eq!(curve.calculate_price(9), 100);
+/// assert_eq!(curve.calculate_price(11), 210);
+/// // The last two seed tokens and the first public one.
+/// assert_eq!(curve.calculate_price_many(8, 3, OperationSide::Add), 400);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhasedCurve<C> {
+    pub phases: Vec<CurvePhase<C>>,
+}
+
+impl<C> Default for PhasedCurve<C> {
+    fn default() -> Self {
+        Self { phases: Vec::new() }
+    }
+}
+
+impl<C> PhasedCurve<C> {
+    /// Creates a new `PhasedCurve` with no phases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a phase after the existing ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A label for the phase.
+    /// * `supply` - The number of tokens sold during the phase.
+    /// * `curve` - The curve that prices the phase.
+    ///
+    /// # Returns
+    ///
+    /// The curve, with the phase added.
+    pub fn with(mut self, name: impl Into<String>, supply: u64, curve: C) -> Self {
+        self.phases.push(CurvePhase {
+            name: name.into(),
+            supply,
+            curve,
+        });
+        self
+    }
+
+    /// Returns the total supply of every phase, or `None` if it exceeds `u64::MAX`.
+    pub fn max_supply(&self) -> Option<u64> {
+        self.phases
+            .iter()
+            .try_fold(0u64, |total, phase| total.checked_add(phase.supply))
+    }
+
+    /// Returns the index of the phase that prices `supply`, and the supply the phase starts
+    /// at.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The supply to look up.
+    ///
+    /// # Returns
+    ///
+    /// The index and start of the phase, or `None` if `supply` is past the last phase.
+    pub fn phase_at(&self, supply: u64) -> Option<(usize, u64)> {
+        let mut start = 0u64;
+        for (index, phase) in self.phases.iter().enumerate() {
+            if supply - start < phase.supply {
+                return Some((index, start));
+            }
+            start = start.checked_add(phase.supply)?;
+        }
+        None
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl<C: BondingCurveWithCheckedOperations<u64>> BondingCurve<u64> for PhasedCurve<C> {
+    /// Calculates the price at `supply`, panicking if it is past the last phase or the phase's
+    /// curve fails.
+    fn calculate_price(&self, supply: u64) -> u64 {
+        match self.calculate_price_checked(supply) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Calculates the total price of a trade, panicking if it reaches past the last phase or
+    /// a phase's curve fails.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+        match self.calculate_price_many_checked(starting_supply, amount, side) {
+            Ok(price) => price,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> BondingCurveWithCheckedOperations<u64>
+    for PhasedCurve<C>
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
+        let (index, start) = self
+            .phase_at(supply)
+            .ok_or(BondingCurveError::SupplyCapExceeded)?;
+        self.phases[index]
+            .curve
+            .calculate_price_checked(supply - start)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(0);
+        };
+        let (index, mut start) = self
+            .phase_at(first)
+            .ok_or(BondingCurveError::SupplyCapExceeded)?;
+        let mut total = 0u64;
+        let mut supply = first;
+        for phase in &self.phases[index..] {
+            if phase.supply == 0 {
+                continue;
+            }
+            let until = start.saturating_add(phase.supply - 1).min(last);
+            let price = phase.curve.calculate_price_many_checked(
+                supply - start,
+                until - supply + 1,
+                OperationSide::Add,
+            )?;
+            total = total
+                .checked_add(price)
+                .ok_or(BondingCurveError::Overflow)?;
+            if until == last {
+                return Ok(total);
+            }
+            supply = until + 1;
+            start = supply;
+        }
+        Err(BondingCurveError::SupplyCapExceeded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{BondingCurve, QuadraticBondingCurve};
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
+        PhasedCurve,
+    };
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_phased_curve_matches_prices() {
+        let curve = PhasedCurve::new()
+            .with("seed", 100, QuadraticBondingCurve::new(0, 3, 1_000))
+            .with("empty", 0, QuadraticBondingCurve::new(0, 0, u64::MAX))
+            .with("public", 250, QuadraticBondingCurve::new(1, 5, 2_000))
+            .with("graduation", 50, QuadraticBondingCurve::new(0, 0, 90_000));
+        assert_eq!(curve.max_supply(), Some(400));
+        assert_eq!(curve.phase_at(99), Some((0, 0)));
+        assert_eq!(curve.phase_at(100), Some((2, 100)));
+        assert_eq!(curve.phase_at(399), Some((3, 350)));
+        assert_eq!(curve.phase_at(400), None);
+        for (supply, amount) in [(0, 400), (90, 20), (99, 1), (100, 250), (120, 260)] {
+            let summed: u64 = (supply..supply + amount)
+                .map(|s| curve.calculate_price(s))
+                .sum();
+            assert_eq!(
+                curve.calculate_price_many_checked(supply, amount, OperationSide::Add),
+                Ok(summed)
+            );
+            assert_eq!(
+                curve.calculate_price_many_checked(supply + amount, amount, OperationSide::Remove),
+                curve.calculate_price_many_checked(supply + 1, amount, OperationSide::Add)
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_phased_curve_cap() {
+        let curve = PhasedCurve::new().with("only", 10, LinearBondingCurve::new(1, 0));
+        assert_eq!(
+            curve.calculate_price_checked(10),
+            Err(BondingCurveError::SupplyCapExceeded)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Add),
+            Err(BondingCurveError::SupplyCapExceeded)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 5, OperationSide::Add),
+            Ok(35)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(20, 0, OperationSide::Add),
+            Ok(0)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let empty = PhasedCurve::<LinearBondingCurve>::new();
+        assert_eq!(
+            empty.calculate_price_checked(0),
+            Err(BondingCurveError::SupplyCapExceeded)
+        );
+        let whole = PhasedCurve::new()
+            .with("first", u64::MAX, LinearBondingCurve::new(0, 0))
+            .with("second", 1, LinearBondingCurve::new(0, 7));
+        assert_eq!(whole.max_supply(), None);
+        assert_eq!(whole.calculate_price_checked(u64::MAX - 1), Ok(0));
+        assert_eq!(whole.calculate_price_checked(u64::MAX), Ok(7));
+        assert_eq!(
+            whole.calculate_price_many_checked(u64::MAX - 1, 2, OperationSide::Add),
+            Ok(7)
+        );
+    }
+}
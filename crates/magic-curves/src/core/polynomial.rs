@@ -0,0 +1,311 @@
+use super::{supply_range, BondingCurveError, OperationSide};
+
+/// The highest degree supported by `power_sum` and the `polynomial_curve!` macro.
+pub const MAX_POLYNOMIAL_DEGREE: u32 = 8;
+
+/// Computes `1^exponent + 2^exponent + ... + n^exponent` exactly.
+///
+/// # Formula
+///
+/// The sums are built up from lower exponents with the binomial identity:
+///
+/// ```ignore
+/// (n + 1)^(k + 1) - 1 = Σ_{j=0}^{k} C(k + 1, j) * S_j(n)
+/// ```
+///
+/// # Arguments
+///
+/// * `exponent` - The exponent, at most `MAX_POLYNOMIAL_DEGREE`.
+/// * `n` - The last term of the sum.
+///
+/// # Returns
+///
+/// The sum, or `None` if the exponent is too high or an intermediate value overflows a `u128`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::power_sum;
+///
+/// assert_eq!(power_sum(2, 3), Some(1 + 4 + 9));
+/// ```
+pub fn power_sum(exponent: u32, n: u64) -> Option<u128> {
+    if exponent > MAX_POLYNOMIAL_DEGREE {
+        return None;
+    }
+    let n = n as u128;
+    let mut sums = [0u128; MAX_POLYNOMIAL_DEGREE as usize + 1];
+    sums[0] = n;
+    for k in 1..=exponent as usize {
+        let mut rest = (n + 1).checked_pow(k as u32 + 1)? - 1;
+        let mut binomial = 1u128;
+        for (j, sum) in sums.iter().enumerate().take(k) {
+            rest = rest.checked_sub(binomial.checked_mul(*sum)?)?;
+            binomial = binomial * (k as u128 + 1 - j as u128) / (j as u128 + 1);
+        }
+        sums[k] = rest / (k as u128 + 1);
+    }
+    Some(sums[exponent as usize])
+}
+
+/// Evaluates a polynomial price with overflow checks.
+///
+/// # Arguments
+///
+/// * `terms` - The `(coefficient, degree)` pairs of the polynomial.
+/// * `supply` - The current supply of tokens.
+///
+/// # Returns
+///
+/// A `Result` containing the price, or `BondingCurveError::Overflow`.
+pub fn polynomial_price(terms: &[(u64, u32)], supply: u64) -> Result<u64, BondingCurveError> {
+    let mut price = 0u128;
+    for &(coefficient, degree) in terms {
+        if coefficient == 0 {
+            continue;
+        }
+        let term = (supply as u128)
+            .checked_pow(degree)
+            .and_then(|power| power.checked_mul(coefficient as u128))
+            .ok_or(BondingCurveError::Overflow)?;
+        price = price.checked_add(term).ok_or(BondingCurveError::Overflow)?;
+    }
+    u64::try_from(price).map_err(|_| BondingCurveError::Overflow)
+}
+
+/// Sums a polynomial price over a supply range exactly, with overflow checks.
+///
+/// # Arguments
+///
+/// * `terms` - The `(coefficient, degree)` pairs of the polynomial.
+/// * `starting_supply` - The current supply of tokens.
+/// * `amount` - The amount of tokens to calculate the price for.
+/// * `side` - The side of the operation (add or remove).
+///
+/// # Returns
+///
+/// A `Result` containing the total price, `BondingCurveError::SupplyUnderflow` if more than
+/// `starting_supply` tokens are removed, or `BondingCurveError::Overflow` if the supply range
+/// or the price leaves the integer range.
+pub fn polynomial_price_many(
+    terms: &[(u64, u32)],
+    starting_supply: u64,
+    amount: u64,
+    side: OperationSide,
+) -> Result<u64, BondingCurveError> {
+    let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+        return Ok(0);
+    };
+
+    let mut total = 0u128;
+    for &(coefficient, degree) in terms {
+        if coefficient == 0 {
+            continue;
+        }
+        let powers = if degree == 0 {
+            amount as u128
+        } else {
+            let below = match first {
+                0 => 0,
+                first => power_sum(degree, first - 1).ok_or(BondingCurveError::Overflow)?,
+            };
+            power_sum(degree, last).ok_or(BondingCurveError::Overflow)? - below
+        };
+        let term = powers
+            .checked_mul(coefficient as u128)
+            .ok_or(BondingCurveError::Overflow)?;
+        total = total.checked_add(term).ok_or(BondingCurveError::Overflow)?;
+    }
+    u64::try_from(total).map_err(|_| BondingCurveError::Overflow)
+}
+
+/// Declares a fixed-degree polynomial curve type with exact summation formulas.
+///
+/// Each field is a `u64` coefficient followed by the degree of its term. The generated
+/// struct has a `new` constructor taking the coefficients in declaration order, and
+/// implements `BondingCurve<u64>` and `BondingCurveWithCheckedOperations<u64>` through
+/// `polynomial_price` and `polynomial_price_many`, without allocating.
+///
+/// The unchecked functions panic where the checked ones fail. With the `panic-free` feature
+/// only the checked trait is implemented.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{polynomial_curve, BondingCurve, OperationSide};
+///
+/// polynomial_curve! {
+///     /// A cubic bonding curve.
+///     pub struct CubicCurve {
+///         cubic: 3,
+///         linear: 1,
+///         base: 0,
+///     }
+/// }
+///
+/// let curve = CubicCurve::new(2, 5, 100);
+/// assert_eq!(curve.calculate_price(3), 2 * 27 + 5 * 3 + 100);
+/// assert_eq!(
+///     curve.calculate_price_many(0, 4, OperationSide::Add),
+///     (0..4).map(|x| curve.calculate_price(x)).sum::<u64>()
+/// );
+/// ```
+#[macro_export]
+macro_rules! polynomial_curve {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($coefficient:ident: $degree:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis struct $name {
+            $(pub $coefficient: u64,)+
+        }
+
+        #[allow(unused_comparisons)]
+        const _: () = assert!(
+            $($degree <= $crate::MAX_POLYNOMIAL_DEGREE)&&+,
+            "polynomial_curve! supports degrees up to MAX_POLYNOMIAL_DEGREE"
+        );
+
+        impl $name {
+            /// Creates a new curve with the given coefficients.
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($coefficient: u64),+) -> Self {
+                Self { $($coefficient),+ }
+            }
+
+            fn terms(&self) -> [(u64, u32); [$($degree),+].len()] {
+                [$((self.$coefficient, $degree)),+]
+            }
+        }
+
+        $crate::__unchecked! {
+            impl $crate::BondingCurve<u64> for $name {
+                fn calculate_price(&self, supply: u64) -> u64 {
+                    $crate::polynomial_price(&self.terms(), supply)
+                        .expect("price does not fit in a u64")
+                }
+
+                fn calculate_price_many(
+                    &self,
+                    starting_supply: u64,
+                    amount: u64,
+                    side: $crate::OperationSide,
+                ) -> u64 {
+                    $crate::polynomial_price_many(&self.terms(), starting_supply, amount, side)
+                        .expect("price does not fit in a u64")
+                }
+            }
+        }
+
+        impl $crate::BondingCurveWithCheckedOperations<u64> for $name {
+            fn calculate_price_checked(
+                &self,
+                supply: u64,
+            ) -> Result<u64, $crate::BondingCurveError> {
+                $crate::polynomial_price(&self.terms(), supply)
+            }
+
+            fn calculate_price_many_checked(
+                &self,
+                starting_supply: u64,
+                amount: u64,
+                side: $crate::OperationSide,
+            ) -> Result<u64, $crate::BondingCurveError> {
+                $crate::polynomial_price_many(&self.terms(), starting_supply, amount, side)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::BondingCurve;
+    use crate::{
+        power_sum, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide,
+        QuadraticBondingCurve,
+    };
+
+    polynomial_curve! {
+        struct Quadratic {
+            quadratic: 2,
+            linear: 1,
+            base: 0,
+        }
+    }
+
+    polynomial_curve! {
+        struct Quintic {
+            quintic: 5,
+            base: 0,
+        }
+    }
+
+    #[test]
+    pub fn test_power_sum() {
+        for exponent in 0..=8u32 {
+            let looped: u128 = (1..=20u128).map(|i| i.pow(exponent)).sum();
+            assert_eq!(power_sum(exponent, 20), Some(looped));
+        }
+        assert_eq!(power_sum(3, 0), Some(0));
+        assert_eq!(power_sum(9, 10), None);
+        assert_eq!(power_sum(8, u64::MAX), None);
+    }
+
+    #[test]
+    pub fn test_polynomial_curve_matches_quadratic() {
+        let builtin = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let generated = Quadratic::new(10_000_000, 500_000_000, 1_000_000_000);
+        for (supply, amount, side) in [
+            (0, 1, OperationSide::Add),
+            (10, 5, OperationSide::Add),
+            (10, 5, OperationSide::Remove),
+            (10, 11, OperationSide::Remove),
+            (1_000, 100, OperationSide::Add),
+        ] {
+            assert_eq!(
+                generated.calculate_price_many_checked(supply, amount, side),
+                builtin.calculate_price_many_checked(supply, amount, side)
+            );
+        }
+        assert_eq!(
+            generated.calculate_price_checked(7),
+            builtin.calculate_price_checked(7)
+        );
+        #[cfg(not(feature = "panic-free"))]
+        assert_eq!(generated.calculate_price(7), builtin.calculate_price(7));
+    }
+
+    #[test]
+    pub fn test_polynomial_curve_checked() {
+        let quintic = Quintic::new(3, 1);
+        let looped: u64 = (5..=9)
+            .map(|x| quintic.calculate_price_checked(x).unwrap())
+            .sum();
+        assert_eq!(
+            quintic.calculate_price_many_checked(9, 5, OperationSide::Remove),
+            Ok(looped)
+        );
+        #[cfg(not(feature = "panic-free"))]
+        assert_eq!(
+            quintic.calculate_price_many(9, 5, OperationSide::Remove),
+            looped
+        );
+        assert_eq!(
+            quintic.calculate_price_checked(u64::MAX),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            quintic.calculate_price_many_checked(3, 5, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            quintic.calculate_price_many_checked(3, 0, OperationSide::Add),
+            Ok(0)
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, TradeResult};
+
+/// Represents the holdings of one wallet in a curve, built up from its trades.
+///
+/// The cost basis follows the average cost method: a sale takes the same share of the basis
+/// as of the tokens held, rounded down, and the rest of its proceeds is realized profit. Once
+/// every token is sold, the basis is zero and the whole round trip is realized.
+///
+/// # Fields
+///
+/// * `tokens`: The number of tokens held.
+/// * `cost_basis`: The total price paid for the tokens held.
+/// * `realized_pnl`: The profit, or loss if negative, of every sale so far.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{CurveState, LinearBondingCurve, Position};
+///
+/// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+/// let mut position = Position::default();
+/// position.apply(&state.buy(10).unwrap()).unwrap();
+/// assert_eq!((position.tokens, position.cost_basis), (10, 14_500));
+/// position.apply(&state.sell(5).unwrap()).unwrap();
+/// assert_eq!((position.tokens, position.cost_basis, position.realized_pnl), (5, 7_250, 1_750));
+/// // Five tokens at the spot price of 1_500.
+/// assert_eq!(position.unrealized_pnl(state.curve(), state.supply()), Ok(250));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub tokens: u64,
+    pub cost_basis: u64,
+    pub realized_pnl: i128,
+}
+
+impl Position {
+    /// Creates a new `Position` from existing holdings.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - The number of tokens held.
+    /// * `cost_basis` - The total price paid for the tokens held.
+    /// * `realized_pnl` - The profit of every sale so far.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Position`.
+    pub fn new(tokens: u64, cost_basis: u64, realized_pnl: i128) -> Self {
+        Self {
+            tokens,
+            cost_basis,
+            realized_pnl,
+        }
+    }
+
+    /// Updates the position with a trade of its wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - The trade, as returned by `CurveState::apply`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that is `BondingCurveError::SupplyUnderflow` if a sale is larger than the
+    /// tokens held, or `BondingCurveError::Overflow` if a buy takes the tokens or the basis
+    /// past `u64::MAX`, in which case the position is unchanged.
+    pub fn apply(&mut self, trade: &TradeResult) -> Result<(), BondingCurveError> {
+        match trade.side {
+            OperationSide::Add => {
+                let tokens = self
+                    .tokens
+                    .checked_add(trade.amount)
+                    .ok_or(BondingCurveError::Overflow)?;
+                let cost_basis = self
+                    .cost_basis
+                    .checked_add(trade.cost)
+                    .ok_or(BondingCurveError::Overflow)?;
+                self.tokens = tokens;
+                self.cost_basis = cost_basis;
+            }
+            OperationSide::Remove => {
+                if trade.amount > self.tokens {
+                    return Err(BondingCurveError::SupplyUnderflow);
+                }
+                if trade.amount == 0 {
+                    return Ok(());
+                }
+                let basis =
+                    (self.cost_basis as u128 * trade.amount as u128 / self.tokens as u128) as u64;
+                self.tokens -= trade.amount;
+                self.cost_basis -= basis;
+                self.realized_pnl += trade.cost as i128 - basis as i128;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the average price paid per token held, rounded down, or `None` if no tokens
+    /// are held.
+    pub fn average_cost(&self) -> Option<u64> {
+        self.cost_basis.checked_div(self.tokens)
+    }
+
+    /// Calculates the profit the tokens held would show if marked at the curve's spot price.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve the tokens trade on.
+    /// * `supply` - The current supply of the curve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `tokens * price - cost_basis`, or the `BondingCurveError` of the
+    /// curve.
+    pub fn unrealized_pnl<C: BondingCurveWithCheckedOperations<u64>>(
+        &self,
+        curve: &C,
+        supply: u64,
+    ) -> Result<i128, BondingCurveError> {
+        let price = curve.calculate_price_checked(supply)?;
+        Ok((self.tokens as u128 * price as u128) as i128 - self.cost_basis as i128)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, CurveState, OperationSide, Position, QuadraticBondingCurve, TradeResult,
+    };
+
+    #[test]
+    pub fn test_position_round_trip() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 0);
+        let mut position = Position::default();
+        let first = state.buy(100).unwrap();
+        let second = state.buy(100).unwrap();
+        position.apply(&first).unwrap();
+        position.apply(&second).unwrap();
+        assert_eq!(position.cost_basis, first.cost + second.cost);
+        assert_eq!(position.average_cost(), Some(position.cost_basis / 200));
+        // Selling into a rising curve realizes a profit on early buys.
+        let marked = position
+            .unrealized_pnl(state.curve(), state.supply())
+            .unwrap();
+        assert!(marked > 0);
+        let sale = state.sell(50).unwrap();
+        position.apply(&sale).unwrap();
+        assert!(position.realized_pnl > 0);
+        let rest = state.sell(150).unwrap();
+        position.apply(&rest).unwrap();
+        assert_eq!(position.average_cost(), None);
+        let proceeds = sale.cost as i128 + rest.cost as i128;
+        let costs = first.cost as i128 + second.cost as i128;
+        assert_eq!(position, Position::new(0, 0, proceeds - costs));
+        assert_eq!(
+            position.unrealized_pnl(state.curve(), state.supply()),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    pub fn test_position_errors() {
+        let mut position = Position::new(10, 1_000, -5);
+        let sale = TradeResult {
+            side: OperationSide::Remove,
+            amount: 11,
+            cost: 0,
+            supply: 0,
+            fee: 0,
+        };
+        assert_eq!(
+            position.apply(&sale),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let buy = TradeResult {
+            side: OperationSide::Add,
+            amount: 1,
+            cost: u64::MAX,
+            supply: 11,
+            fee: 0,
+        };
+        assert_eq!(position.apply(&buy), Err(BondingCurveError::Overflow));
+        assert_eq!(position, Position::new(10, 1_000, -5));
+        let empty = TradeResult {
+            side: OperationSide::Remove,
+            amount: 0,
+            cost: 0,
+            supply: 10,
+            fee: 0,
+        };
+        position.apply(&empty).unwrap();
+        assert_eq!(position, Position::new(10, 1_000, -5));
+        // Three tokens take 300 of the basis, so selling them for 200 realizes a loss.
+        let sale = TradeResult {
+            side: OperationSide::Remove,
+            amount: 3,
+            cost: 200,
+            supply: 7,
+            fee: 0,
+        };
+        position.apply(&sale).unwrap();
+        assert_eq!(position, Position::new(7, 700, -105));
+    }
+}
@@ -0,0 +1,378 @@
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    canonical_key, finite, math, supply_range, BondingCurveError,
+    BondingCurveWithCheckedOperations, OperationSide,
+};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// Represents a power-law bonding curve, `f(x) = coefficient * x^exponent`.
+///
+/// The exponent does not have to be an integer, so the curve generalizes the linear and
+/// quadratic curves to shapes such as `x^1.5`, common in curation markets.
+///
+/// # Fields
+///
+/// * `coefficient`: The coefficient that scales every price.
+/// * `exponent`: The exponent of the supply, zero or more.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "super::PowerConfig", into = "super::PowerConfig")
+)]
+pub struct PowerBondingCurve {
+    pub coefficient: f64,
+    pub exponent: f64,
+}
+
+impl PowerBondingCurve {
+    /// Creates a new `PowerBondingCurve` with the specified coefficient and exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficient` - The coefficient that scales every price.
+    /// * `exponent` - The exponent of the supply, zero or more.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `PowerBondingCurve`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::PowerBondingCurve;
+    ///
+    /// let curve = PowerBondingCurve::new(0.001, 1.5);
+    /// ```
+    pub fn new(coefficient: f64, exponent: f64) -> Self {
+        Self {
+            coefficient,
+            exponent,
+        }
+    }
+
+    /// Creates a new `PowerBondingCurve`, validating its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficient` - The coefficient that scales every price.
+    /// * `exponent` - The exponent of the supply, zero or more.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the curve, or the error from `PowerBondingCurve::validate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveError, PowerBondingCurve};
+    ///
+    /// assert!(PowerBondingCurve::try_new(0.001, 1.5).is_ok());
+    /// assert_eq!(
+    ///     PowerBondingCurve::try_new(0.001, -0.5),
+    ///     Err(BondingCurveError::InvalidParameter)
+    /// );
+    /// ```
+    pub fn try_new(coefficient: f64, exponent: f64) -> Result<Self, BondingCurveError> {
+        let curve = Self::new(coefficient, exponent);
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    /// Checks that the parameters are supported by the curve's formulas.
+    ///
+    /// # Returns
+    ///
+    /// `BondingCurveError::InvalidParameter` if a parameter is not finite, `coefficient` is
+    /// negative, which would make every price negative, or `exponent` is negative, which
+    /// would make the price at supply zero infinite.
+    pub fn validate(&self) -> Result<(), BondingCurveError> {
+        if !self.coefficient.is_finite()
+            || !self.exponent.is_finite()
+            || self.coefficient < 0.0
+            || self.exponent < 0.0
+        {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    /// Calculates the price at a fractional supply, for continuous-token models.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens, in fractional units.
+    ///
+    /// # Returns
+    ///
+    /// The price at `supply`, equal to `calculate_price` at whole supplies.
+    pub fn calculate_price_f(&self, supply: f64) -> f64 {
+        self.coefficient * math::powf(supply, self.exponent)
+    }
+
+    /// Calculates the price for a fractional amount of tokens from a fractional supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens, in fractional units.
+    /// * `amount` - The amount of tokens to calculate the price for, in fractional units.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price, the integral of the price between the two supplies, or `NaN` if the
+    /// trade reaches a negative supply with a fractional exponent.
+    pub fn calculate_price_many_f(
+        &self,
+        starting_supply: f64,
+        amount: f64,
+        side: OperationSide,
+    ) -> f64 {
+        if amount == 0.0 {
+            return 0.0;
+        }
+        match side {
+            OperationSide::Add => {
+                self.integral(starting_supply + amount) - self.integral(starting_supply)
+            }
+            OperationSide::Remove => {
+                self.integral(starting_supply) - self.integral(starting_supply - amount)
+            }
+        }
+    }
+
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64) {
+        (
+            canonical_key(self.coefficient),
+            canonical_key(self.exponent),
+        )
+    }
+
+    /// Returns the antiderivative of the price at `x`, which is 0 at 0.
+    fn integral(&self, x: f64) -> f64 {
+        let raised = self.exponent + 1.0;
+        self.coefficient * math::powf(x, raised) / raised
+    }
+}
+
+impl PartialEq for PowerBondingCurve {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for PowerBondingCurve {}
+
+impl Hash for PowerBondingCurve {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for PowerBondingCurve {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PowerBondingCurve {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Display for PowerBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 0.001·x^1.5`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "f(x) = {}·x^{}", self.coefficient, self.exponent)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<f64> for PowerBondingCurve {
+    /// Calculates the price based on the supply.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) = coefficient * x^exponent
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// The price of the token based on the supply.
+    fn calculate_price(&self, supply: u64) -> f64 {
+        self.calculate_price_f(supply as f64)
+    }
+
+    /// Calculates the price for a given amount of tokens.
+    ///
+    /// # Formula
+    ///
+    /// The integral of the power function is used:
+    /// ```ignore
+    /// F(x) = coefficient * x^(exponent + 1) / (exponent + 1)
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price for the given amount of tokens.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
+        self.calculate_price_many_f(starting_supply as f64, amount as f64, side)
+    }
+}
+
+impl BondingCurveWithCheckedOperations<f64> for PowerBondingCurve {
+    /// Calculates the price based on the supply, validating the curve parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, the error from `PowerBondingCurve::validate`, or
+    /// `BondingCurveError::Overflow` if the price is not finite.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        finite(self.calculate_price_f(supply as f64))
+    }
+
+    /// Calculates the price for a given amount of tokens, validating the curve parameters
+    /// and the supply range.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, the error from `PowerBondingCurve::validate`,
+    /// `BondingCurveError::SupplyUnderflow` if more than `starting_supply` tokens are
+    /// removed, or `BondingCurveError::Overflow` if the price is not finite.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        if supply_range(starting_supply, amount, side)?.is_none() {
+            return Ok(0.0);
+        }
+        finite(self.calculate_price_many_f(starting_supply as f64, amount as f64, side))
+    }
+}
+
+#[cfg(all(test, not(feature = "panic-free")))]
+mod test {
+    use crate::{
+        relative_eq, BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations,
+        OperationSide, PowerBondingCurve,
+    };
+
+    #[test]
+    pub fn test_power_price_calculus() {
+        let curve = PowerBondingCurve::new(2.0, 1.5);
+        assert_eq!(curve.calculate_price(0), 0.0);
+        assert_eq!(curve.calculate_price(4), 16.0);
+        assert_eq!(curve.calculate_price(100), 2_000.0);
+        // An exponent of zero is a constant price.
+        assert_eq!(PowerBondingCurve::new(3.0, 0.0).calculate_price(0), 3.0);
+        assert_eq!(curve.to_string(), "f(x) = 2·x^1.5");
+    }
+
+    #[test]
+    pub fn test_power_price_many_is_the_integral() {
+        let curve = PowerBondingCurve::new(2.0, 1.5);
+        // F(x) = 2 * x^2.5 / 2.5, so F(4) - F(0) = 32 * 2 / 2.5.
+        assert!(relative_eq(
+            curve.calculate_price_many(0, 4, OperationSide::Add),
+            25.6,
+            1e-12
+        ));
+        assert_eq!(
+            curve.calculate_price_many(100, 10, OperationSide::Add),
+            curve.calculate_price_many(110, 10, OperationSide::Remove)
+        );
+        // The integral of a linear and a quadratic price.
+        let linear = PowerBondingCurve::new(3.0, 1.0);
+        assert!(relative_eq(
+            linear.calculate_price_many(10, 10, OperationSide::Add),
+            3.0 * (400.0 - 100.0) / 2.0,
+            1e-12
+        ));
+        let quadratic = PowerBondingCurve::new(3.0, 2.0);
+        assert!(relative_eq(
+            quadratic.calculate_price_many(10, 10, OperationSide::Remove),
+            1_000.0,
+            1e-12
+        ));
+        let split = curve.calculate_price_many_f(0.0, 0.5, OperationSide::Add)
+            + curve.calculate_price_many_f(0.5, 2.0, OperationSide::Add);
+        assert!(relative_eq(
+            split,
+            curve.calculate_price_many_f(0.0, 2.5, OperationSide::Add),
+            1e-12
+        ));
+    }
+
+    #[test]
+    pub fn test_power_checked_guards() {
+        let curve = PowerBondingCurve::new(2.0, 1.5);
+        assert_eq!(
+            curve.calculate_price_many_checked(100, 10, OperationSide::Remove),
+            Ok(curve.calculate_price_many(100, 10, OperationSide::Remove))
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        // Minting the last supply is priced like on the integer curves.
+        assert_eq!(
+            curve.calculate_price_many_checked(u64::MAX, 1, OperationSide::Add),
+            Ok(curve.calculate_price_many(u64::MAX, 1, OperationSide::Add))
+        );
+        assert_eq!(
+            PowerBondingCurve::new(1.0, 400.0).calculate_price_checked(100),
+            Err(BondingCurveError::Overflow)
+        );
+        for exponent in [-0.5, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                PowerBondingCurve::new(2.0, exponent).calculate_price_checked(10),
+                Err(BondingCurveError::InvalidParameter)
+            );
+        }
+        for coefficient in [-2.0, f64::NAN, f64::NEG_INFINITY] {
+            assert_eq!(
+                PowerBondingCurve::try_new(coefficient, 1.5),
+                Err(BondingCurveError::InvalidParameter)
+            );
+        }
+        assert!(PowerBondingCurve::try_new(0.0, 1.5).is_ok());
+    }
+}
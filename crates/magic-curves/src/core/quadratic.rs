@@ -1,15 +1,30 @@
-use super::{BondingCurve, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+use super::{
+    largest, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, SafeSupply,
+};
+#[cfg(not(feature = "panic-free"))]
+use super::{narrow, BondingCurve};
+use std::fmt::{self, Display, Formatter};
 
 /// Represents a quadratic bonding curve.
 ///
 /// This struct defines a quadratic bonding curve with quadratic, linear, and base coefficients.
+/// It has a fixed `#[repr(C)]` layout, and with the `bytemuck` feature it implements `Pod`
+/// and `Zeroable` so it can live directly in zero-copy accounts.
 ///
 /// # Fields
 ///
 /// * `quadratic`: The quadratic coefficient that determines the rate of price increase.
 /// * `linear`: The linear coefficient that affects the price linearly.
 /// * `base`: The base price, which is the minimum price for the first token.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "super::QuadraticConfig", into = "super::QuadraticConfig")
+)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(C)]
 pub struct QuadraticBondingCurve {
     pub quadratic: u64,
     pub linear: u64,
@@ -43,8 +58,162 @@ impl QuadraticBondingCurve {
             base,
         }
     }
+
+    /// Computes the range in which the unchecked formulas cannot overflow with these
+    /// coefficients, so a configuration can be validated once at deploy time.
+    ///
+    /// # Returns
+    ///
+    /// A `SafeSupply` with the largest safe supply and the largest safe amount at it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveWithCheckedOperations, OperationSide, QuadraticBondingCurve};
+    ///
+    /// let curve = QuadraticBondingCurve::new(10, 100, 1000);
+    /// let safe = curve.max_safe_supply();
+    /// assert!(curve.calculate_price_checked(safe.supply + 1).is_err());
+    /// assert!(curve
+    ///     .calculate_price_many_checked(safe.supply, safe.amount, OperationSide::Remove)
+    ///     .is_ok());
+    /// ```
+    pub fn max_safe_supply(&self) -> SafeSupply {
+        let supply = largest(0, u64::MAX, |supply| {
+            self.calculate_price_checked(supply).is_ok()
+        });
+        let amount = largest(0, supply, |amount| self.remove_fits(supply, amount));
+        SafeSupply { supply, amount }
+    }
+
+    /// Calculates how many more tokens must be sold to double the price, a simple measure
+    /// of how aggressive the curve is.
+    ///
+    /// The doubling supply is found by bisection, so it is exact despite the rounding of a
+    /// closed-form square root.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_supply` - The supply to measure from.
+    ///
+    /// # Returns
+    ///
+    /// The smallest number of tokens after which the price is at least double, or `None` if
+    /// the price is zero, never doubles, or would double past a `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::QuadraticBondingCurve;
+    ///
+    /// let curve = QuadraticBondingCurve::new(1, 0, 100);
+    /// // 10^2 + 100 = 200, doubled at 18^2 + 100 = 424 (17^2 + 100 = 389 falls short).
+    /// assert_eq!(curve.supply_to_double_price(10), Some(8));
+    /// ```
+    pub fn supply_to_double_price(&self, from_supply: u64) -> Option<u64> {
+        let price = self.calculate_price_checked(from_supply).ok()?;
+        let target = price.checked_mul(2)?;
+        if price == 0 {
+            return None;
+        }
+        let below = largest(from_supply, u64::MAX, |supply| {
+            self.calculate_price_checked(supply)
+                .is_ok_and(|price| price < target)
+        });
+        let doubled = below.checked_add(1)?;
+        self.calculate_price_checked(doubled).ok()?;
+        Some(doubled - from_supply)
+    }
+
+    /// Returns a copy of the curve with a new `quadratic` coefficient, and the `base` adjusted
+    /// so the price at `supply` does not change, so a live curve can be made steeper or
+    /// flatter without a price jump.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply, whose price is kept.
+    /// * `quadratic` - The new quadratic coefficient.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retuned curve, `BondingCurveError::Overflow` if the current
+    /// price does not fit in a `u64`, or `BondingCurveError::InvalidParameter` if keeping the
+    /// price would need a negative base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, QuadraticBondingCurve};
+    ///
+    /// let curve = QuadraticBondingCurve::new(10, 100, 1000);
+    /// let flatter = curve.retune_quadratic(10, 5).unwrap();
+    /// assert_eq!(flatter.base, 1500);
+    /// assert_eq!(flatter.calculate_price(10), curve.calculate_price(10));
+    /// ```
+    pub fn retune_quadratic(&self, supply: u64, quadratic: u64) -> Result<Self, BondingCurveError> {
+        Self::new(quadratic, self.linear, 0).rebase(self.calculate_price_checked(supply)?, supply)
+    }
+
+    /// Returns a copy of the curve with a new `linear` coefficient, and the `base` adjusted so
+    /// the price at `supply` does not change. See `QuadraticBondingCurve::retune_quadratic`.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply, whose price is kept.
+    /// * `linear` - The new linear coefficient.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retuned curve, or a `BondingCurveError` as for
+    /// `QuadraticBondingCurve::retune_quadratic`.
+    pub fn retune_linear(&self, supply: u64, linear: u64) -> Result<Self, BondingCurveError> {
+        Self::new(self.quadratic, linear, 0).rebase(self.calculate_price_checked(supply)?, supply)
+    }
+
+    /// Sets the base of a curve with a zero base so its price at `supply` is `price`.
+    fn rebase(mut self, price: u64, supply: u64) -> Result<Self, BondingCurveError> {
+        self.base = self
+            .calculate_price_checked(supply)
+            .ok()
+            .and_then(|shape| price.checked_sub(shape))
+            .ok_or(BondingCurveError::InvalidParameter)?;
+        Ok(self)
+    }
+
+    /// Checks every intermediate of `calculate_price_many` for a `Remove` trade.
+    fn remove_fits(&self, starting_supply: u64, amount: u64) -> bool {
+        if amount == 0 {
+            return true;
+        }
+        let n = amount as u128;
+        let a = starting_supply as u128;
+        let sum = || {
+            let sum_i = n * (n - 1) / 2;
+            let sum_i2 = n.checked_mul(n - 1)?.checked_mul(2 * n - 1)? / 6;
+            let squares = (a * a).checked_mul(n)? - 2 * a * sum_i;
+            let sum_quadratic =
+                (self.quadratic as u128).checked_mul(squares.checked_add(sum_i2)?)?;
+            let sum_linear = (self.linear as u128).checked_mul(a * n - sum_i)?;
+            sum_quadratic
+                .checked_add(sum_linear)?
+                .checked_add(self.base as u128 * n)
+        };
+        sum().is_some_and(|sum| sum <= u64::MAX as u128)
+    }
 }
 
+impl Display for QuadraticBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 10000000x^2 + 500000000x + 1000000000`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "f(x) = {}x^2 + {}x + {}",
+            self.quadratic, self.linear, self.base
+        )
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
 impl BondingCurve<u64> for QuadraticBondingCurve {
     /// Calculates the price based on the supply.
     ///
@@ -79,40 +248,54 @@ impl BondingCurve<u64> for QuadraticBondingCurve {
     ///
     /// # Returns
     ///
-    /// The total price for the given amount of tokens.
+    /// The total price for the given amount of tokens. Like native arithmetic, a result that
+    /// does not fit in a `u64` panics in debug builds and wraps in release builds.
     fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
-        let n = amount;
-        let a = starting_supply;
-
-        let sum_quadratic = match side {
-            OperationSide::Add => {
-                // Sum of quadratic terms: (a^2 * n) + (a * n * (n-1)) + (n * (n-1) * (2n-1) / 6)
-                (self.quadratic * a * a * n)
-                    + (self.quadratic * a * n * (n - 1))
-                    + (self.quadratic * n * (n - 1) * (2 * n - 1) / 6)
-            }
-            OperationSide::Remove => {
-                // Sum of quadratic terms: (a^2 * n) - (a * n * (n-1)) + (n * (n-1) * (2n-1) / 6)
-                (self.quadratic * a * a * n) - (self.quadratic * a * n * (n - 1))
-                    + (self.quadratic * n * (n - 1) * (2 * n - 1) / 6)
-            }
-        };
+        if amount == 0 {
+            return 0;
+        }
+
+        // Intermediates are computed in `u128` and only narrowed at the end, so the Remove
+        // side no longer overflows on terms that cancel out in the result.
+        let n = amount as u128;
+        let a = starting_supply as u128;
+        let quadratic = self.quadratic as u128;
+        let linear = self.linear as u128;
+        let base = self.base as u128;
 
-        let sum_linear = match side {
-            OperationSide::Add => {
-                // Sum of linear terms: b * (a * n + n * (n-1) / 2)
-                self.linear * (a * n + n * (n - 1) / 2)
+        // Sum of i for i in 0..n
+        let sum_i = n * (n - 1) / 2;
+
+        // Terms with a zero coefficient are skipped, as in the checked path, so their sums
+        // cannot overflow the intermediates of a price that fits.
+        let sum_quadratic = if quadratic == 0 {
+            0
+        } else {
+            // Sum of i^2 for i in 0..n. `sum_i * (2n - 1)` is a multiple of 3, and dividing
+            // by 2 first keeps the product within a `u128` whenever the sum fits.
+            let sum_i2 = sum_i * (2 * n - 1) / 3;
+            match side {
+                // Sum of quadratic terms: (a^2 * n) + (2 * a * sum_i) + sum_i2
+                OperationSide::Add => quadratic * (a * a * n + 2 * a * sum_i + sum_i2),
+                // Sum of quadratic terms: (a^2 * n) - (2 * a * sum_i) + sum_i2
+                OperationSide::Remove => quadratic * (a * a * n - 2 * a * sum_i + sum_i2),
             }
-            OperationSide::Remove => {
-                // Sum of linear terms: b * (a * n - n * (n-1) / 2)
-                self.linear * (a * n - n * (n - 1) / 2)
+        };
+        let sum_linear = if linear == 0 {
+            0
+        } else {
+            match side {
+                // Sum of linear terms: b * (a * n + sum_i)
+                OperationSide::Add => linear * (a * n + sum_i),
+                // Sum of linear terms: b * (a * n - sum_i)
+                OperationSide::Remove => linear * (a * n - sum_i),
             }
         };
 
         // Sum of constant terms: c * n
-        let sum_constant = self.base * n;
+        let sum_constant = base * n;
 
-        sum_quadratic + sum_linear + sum_constant
+        narrow(sum_quadratic + sum_linear + sum_constant)
     }
 }
 
@@ -127,6 +310,7 @@ impl BondingCurveWithCheckedOperations<u64> for QuadraticBondingCurve {
     ///
     /// A `Result` containing the price of the token based on the supply,
     /// or a `BondingCurveError` if the calculation overflows.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
     fn calculate_price_checked(&self, supply: u64) -> Result<u64, BondingCurveError> {
         let result = self
             .quadratic
@@ -139,6 +323,17 @@ impl BondingCurveWithCheckedOperations<u64> for QuadraticBondingCurve {
 
     /// Calculates the price for a given amount of tokens with overflow checking.
     ///
+    /// # Cost
+    ///
+    /// The power sums are computed once in `u128` and shared by both sides, terms with a
+    /// zero coefficient are skipped entirely, and the result is only narrowed to `u64` at
+    /// the end. `n * (n - 1)` and `a^2` cannot overflow a `u128`, and every remaining check
+    /// fails only when the range is out of bounds or the result would not fit in a `u64`
+    /// anyway.
+    ///
+    /// This describes the operations performed, not a measured compute unit cost; the path
+    /// has not been profiled under SBF.
+    ///
     /// # Arguments
     ///
     /// * `starting_supply` - The current supply of tokens.
@@ -149,100 +344,82 @@ impl BondingCurveWithCheckedOperations<u64> for QuadraticBondingCurve {
     ///
     /// A `Result` containing the total price for the given amount of tokens,
     /// or a `BondingCurveError` if the calculation overflows.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
     fn calculate_price_many_checked(
         &self,
         starting_supply: u64,
         amount: u64,
         side: OperationSide,
     ) -> Result<u64, BondingCurveError> {
-        let n = amount;
-        let a = starting_supply;
-        let n_minus_1 = n.checked_sub(1).ok_or(BondingCurveError::Overflow)?;
+        if amount == 0 {
+            return Ok(0);
+        }
 
-        // Sum of quadratic terms
-        let first_term = self
-            .quadratic
-            .checked_mul(
-                a.checked_mul(a)
-                    .and_then(|x| x.checked_mul(n))
-                    .ok_or(BondingCurveError::Overflow)?,
-            )
-            .ok_or(BondingCurveError::Overflow)?;
+        let n = amount as u128;
+        let a = starting_supply as u128;
+        let n_minus_1 = n - 1;
+        if side == OperationSide::Remove && n > a {
+            return Err(BondingCurveError::SupplyUnderflow);
+        }
 
-        let second_term = self
-            .quadratic
-            .checked_mul(
-                a.checked_mul(n)
-                    .and_then(|x| x.checked_mul(n_minus_1))
-                    .ok_or(BondingCurveError::Overflow)?,
-            )
-            .ok_or(BondingCurveError::Overflow)?;
+        // Sum of i and of i^2 for i in 0..n
+        let sum_i = n * n_minus_1 / 2;
 
-        let third_term_pow = 2u64
-            .checked_mul(n)
-            .and_then(|x| x.checked_sub(1))
-            .ok_or(BondingCurveError::Overflow)?;
+        // Sum of linear terms: a * n +/- sum_i
+        let sum_linear = if self.linear == 0 {
+            0
+        } else {
+            let a_n = a * n;
+            let x = match side {
+                OperationSide::Add => a_n.checked_add(sum_i).ok_or(BondingCurveError::Overflow)?,
+                OperationSide::Remove => a_n - sum_i,
+            };
+            x.checked_mul(self.linear as u128)
+                .ok_or(BondingCurveError::Overflow)?
+        };
 
-        let third_term = self
-            .quadratic
-            .checked_mul(
-                n.checked_mul(n_minus_1)
-                    .and_then(|x| x.checked_mul(third_term_pow))
-                    .and_then(|x| x.checked_div(6))
-                    .ok_or(BondingCurveError::Overflow)?,
-            )
+        // Sum of quadratic terms: a^2 * n +/- 2 * a * sum_i + sum_i2
+        let sum_quadratic = if self.quadratic == 0 {
+            0
+        } else {
+            let sum_i2 = (n * n_minus_1)
+                .checked_mul(2 * n - 1)
+                .ok_or(BondingCurveError::Overflow)?
+                / 6;
+            let a2_n = (a * a).checked_mul(n).ok_or(BondingCurveError::Overflow)?;
+            let cross = (2 * a)
+                .checked_mul(sum_i)
+                .ok_or(BondingCurveError::Overflow)?;
+            let x = match side {
+                OperationSide::Add => a2_n.checked_add(cross),
+                OperationSide::Remove => Some(a2_n - cross),
+            }
+            .and_then(|x| x.checked_add(sum_i2))
             .ok_or(BondingCurveError::Overflow)?;
-
-        let sum_quadratic = match side {
-            OperationSide::Add => first_term
-                .checked_add(second_term)
-                .and_then(|x| x.checked_add(third_term)),
-            OperationSide::Remove => first_term
-                .checked_sub(second_term)
-                .and_then(|x| x.checked_add(third_term)),
-        }
-        .ok_or(BondingCurveError::Overflow)?;
-
-        // Sum of linear terms
-        let sum_linear = match side {
-            OperationSide::Add => self
-                .linear
-                .checked_mul(
-                    a.checked_mul(n)
-                        .and_then(|x| x.checked_add(n.checked_mul(n_minus_1)?.checked_div(2)?))
-                        .ok_or(BondingCurveError::Overflow)?,
-                )
-                .ok_or(BondingCurveError::Overflow)?,
-            OperationSide::Remove => self
-                .linear
-                .checked_mul(
-                    a.checked_mul(n)
-                        .and_then(|x| x.checked_sub(n.checked_mul(n_minus_1)?.checked_div(2)?))
-                        .ok_or(BondingCurveError::Overflow)?,
-                )
-                .ok_or(BondingCurveError::Overflow)?,
+            x.checked_mul(self.quadratic as u128)
+                .ok_or(BondingCurveError::Overflow)?
         };
 
-        // Sum of constant terms
-        let sum_constant = self
-            .base
-            .checked_mul(n)
-            .ok_or(BondingCurveError::Overflow)?;
+        // Sum of constant terms: c * n
+        let sum_constant = self.base as u128 * n;
 
-        // Final sum
-        sum_quadratic
+        let sum = sum_quadratic
             .checked_add(sum_linear)
             .and_then(|x| x.checked_add(sum_constant))
-            .ok_or(BondingCurveError::Overflow)
+            .ok_or(BondingCurveError::Overflow)?;
+        u64::try_from(sum).map_err(|_| BondingCurveError::Overflow)
     }
 }
 
 #[cfg(test)]
 mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::BondingCurve;
     use crate::{
-        BondingCurve, BondingCurveWithCheckedOperations, OperationSide, QuadraticBondingCurve,
+        BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuadraticBondingCurve,
     };
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_quadratic_price_calculus() {
         let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
@@ -274,6 +451,7 @@ mod test {
         assert_eq!(price, r4);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_quadratic_price_many_calculus() {
         let quadratic = 10_000_000u64;
@@ -310,4 +488,230 @@ mod test {
             .unwrap();
         assert_eq!(checked_many_price_remove, many_price_remove);
     }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_price_many_checked_matches_loop() {
+        let curves = [
+            QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000),
+            QuadraticBondingCurve::new(0, 500_000_000, 1_000_000_000),
+            QuadraticBondingCurve::new(3, 0, 7),
+        ];
+        for curve in curves {
+            for starting_supply in [0u64, 1, 9, 250] {
+                for amount in 1..=10u64 {
+                    let looped_add: u64 = (0..amount)
+                        .map(|i| curve.calculate_price(starting_supply + i))
+                        .sum();
+                    let checked_add = curve
+                        .calculate_price_many_checked(starting_supply, amount, OperationSide::Add)
+                        .unwrap();
+                    assert_eq!(checked_add, looped_add);
+
+                    if amount <= starting_supply {
+                        let looped_remove: u64 = (0..amount)
+                            .map(|i| curve.calculate_price(starting_supply - i))
+                            .sum();
+                        let checked_remove = curve
+                            .calculate_price_many_checked(
+                                starting_supply,
+                                amount,
+                                OperationSide::Remove,
+                            )
+                            .unwrap();
+                        assert_eq!(checked_remove, looped_remove);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_quadratic_price_many_checked_overflow() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        assert_eq!(
+            curve.calculate_price_many_checked(u64::MAX / 2, 10, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+
+        // Terms with a zero coefficient never overflow on their own.
+        let flat = QuadraticBondingCurve::new(0, 0, 1);
+        assert_eq!(
+            flat.calculate_price_many_checked(u64::MAX / 2, 10, OperationSide::Add),
+            Ok(10)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_price_many_zero_and_full_exit() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert_eq!(curve.calculate_price_many(10, 0, side), 0);
+            assert_eq!(curve.calculate_price_many_checked(0, 0, side), Ok(0));
+        }
+        let looped: u64 = (1..=10).map(|x| curve.calculate_price(x)).sum();
+        assert_eq!(
+            curve.calculate_price_many(10, 10, OperationSide::Remove),
+            looped
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(10, 10, OperationSide::Remove),
+            Ok(looped)
+        );
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_price_many_wide_intermediates() {
+        // a^2 * n overflows a u64 on the Remove side, but the total does not.
+        let curve = QuadraticBondingCurve::new(1, 0, 0);
+        let price = curve.calculate_price_many(3_000_000, 3_000_000, OperationSide::Remove);
+        assert_eq!(
+            Ok(price),
+            curve.calculate_price_many_checked(3_000_000, 3_000_000, OperationSide::Remove)
+        );
+        let looped: u64 = (1..=3_000_000u64).map(|x| x * x).sum();
+        assert_eq!(price, looped);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_price_many_matches_checked_at_large_supplies() {
+        // Zero terms are skipped, so their sums never overflow.
+        assert_eq!(
+            QuadraticBondingCurve::new(0, 0, 5).calculate_price_many(
+                1 << 60,
+                1 << 10,
+                OperationSide::Add
+            ),
+            5120
+        );
+        assert_eq!(
+            QuadraticBondingCurve::new(0, 0, 1).calculate_price_many(
+                0,
+                1 << 43,
+                OperationSide::Add
+            ),
+            1 << 43
+        );
+        for curve in [
+            QuadraticBondingCurve::new(0, 0, 1),
+            QuadraticBondingCurve::new(0, 1, 0),
+            QuadraticBondingCurve::new(0, 3, 7),
+        ] {
+            for (starting_supply, amount) in [(1 << 31, 1 << 31), (1 << 60, 2), (1 << 40, 1 << 20)]
+            {
+                for side in [OperationSide::Add, OperationSide::Remove] {
+                    let checked = curve.calculate_price_many_checked(starting_supply, amount, side);
+                    if let Ok(price) = checked {
+                        assert_eq!(
+                            curve.calculate_price_many(starting_supply, amount, side),
+                            price
+                        );
+                    }
+                }
+            }
+        }
+        // The sum of squares fits whenever the quadratic term does.
+        let quadratic = QuadraticBondingCurve::new(1, 0, 0);
+        assert_eq!(
+            Ok(quadratic.calculate_price_many(0, 3_000_000, OperationSide::Add)),
+            quadratic.calculate_price_many_checked(0, 3_000_000, OperationSide::Add)
+        );
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    pub fn test_quadratic_pod_round_trip() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let bytes = bytemuck::bytes_of(&curve);
+        assert_eq!(bytes.len(), std::mem::size_of::<QuadraticBondingCurve>());
+        assert_eq!(*bytemuck::from_bytes::<QuadraticBondingCurve>(bytes), curve);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_max_safe_supply() {
+        for curve in [
+            QuadraticBondingCurve::new(10, 100, 1000),
+            QuadraticBondingCurve::new(0, 1 << 20, 5),
+            QuadraticBondingCurve::new(0, 0, 7),
+        ] {
+            let safe = curve.max_safe_supply();
+            assert!(safe.amount <= safe.supply);
+            assert_eq!(
+                curve.calculate_price(safe.supply),
+                curve.calculate_price_checked(safe.supply).unwrap()
+            );
+            if safe.supply < u64::MAX {
+                assert!(curve.calculate_price_checked(safe.supply + 1).is_err());
+            }
+            // The unchecked formulas would panic on overflow in this debug build.
+            assert_eq!(
+                curve.calculate_price_many(safe.supply, safe.amount, OperationSide::Remove),
+                curve
+                    .calculate_price_many_checked(safe.supply, safe.amount, OperationSide::Remove)
+                    .unwrap()
+            );
+            curve.calculate_price_many(safe.supply - safe.amount, safe.amount, OperationSide::Add);
+        }
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_supply_to_double_price() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        for supply in [0, 1, 7, 1_000] {
+            let tokens = curve.supply_to_double_price(supply).unwrap();
+            let price = curve.calculate_price(supply);
+            assert!(curve.calculate_price(supply + tokens) >= 2 * price);
+            assert!(curve.calculate_price(supply + tokens - 1) < 2 * price);
+        }
+        let flat = QuadraticBondingCurve::new(0, 0, 10);
+        assert_eq!(flat.supply_to_double_price(5), None);
+        assert_eq!(curve.supply_to_double_price(1 << 30), None);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_quadratic_retune() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let price = curve.calculate_price(1_000);
+        for coefficient in [0, 1, 5_000_000, 10_000_001] {
+            let retuned = curve.retune_quadratic(1_000, coefficient).unwrap();
+            assert_eq!(retuned.quadratic, coefficient);
+            assert_eq!(retuned.calculate_price(1_000), price);
+            let retuned = curve.retune_linear(1_000, coefficient).unwrap();
+            assert_eq!(
+                (retuned.quadratic, retuned.linear),
+                (10_000_000, coefficient)
+            );
+            assert_eq!(retuned.calculate_price(1_000), price);
+        }
+        assert_eq!(
+            curve.retune_quadratic(1_000, 11_500_001),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            curve.retune_linear(1_000, u64::MAX),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            curve.retune_quadratic(u64::MAX, 0),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+
+    #[test]
+    pub fn test_quadratic_display() {
+        assert_eq!(
+            QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000).to_string(),
+            "f(x) = 10000000x^2 + 500000000x + 1000000000"
+        );
+    }
 }
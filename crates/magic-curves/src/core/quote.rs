@@ -0,0 +1,392 @@
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, FixedPrice, FixedPriceCurve,
+    OperationSide,
+};
+
+/// Represents a request for a quote, as sent in instruction data.
+///
+/// With the `borsh` feature this struct serializes to exactly `QuoteRequest::LEN` bytes,
+/// so programs can embed it in instruction data and callers can size buffers up front.
+///
+/// # Fields
+///
+/// * `starting_supply`: The supply before the operation.
+/// * `amount`: The number of tokens to add or remove.
+/// * `side`: Whether tokens are being added or removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteRequest {
+    pub starting_supply: u64,
+    pub amount: u64,
+    pub side: OperationSide,
+}
+
+/// Represents the answer to a `QuoteRequest`, as returned through CPI return data.
+///
+/// With the `borsh` feature this struct serializes to exactly `QuoteResponse::LEN` bytes.
+///
+/// # Fields
+///
+/// * `price`: The total price of the requested tokens.
+/// * `ending_supply`: The supply after the operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteResponse {
+    pub price: u64,
+    pub ending_supply: u64,
+}
+
+/// Represents the answer to a `QuoteRequest` against a `FixedPriceCurve`, whose price carries
+/// the decimals of the curve's reserve token in its type.
+///
+/// It serializes like a `QuoteResponse`, to exactly `FixedQuoteResponse::LEN` bytes with
+/// the `borsh` feature.
+///
+/// # Fields
+///
+/// * `price`: The total price of the requested tokens.
+/// * `ending_supply`: The supply after the operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedQuoteResponse<const DECIMALS: u8> {
+    pub price: FixedPrice<DECIMALS>,
+    pub ending_supply: u64,
+}
+
+/// Represents one line of an `ItemizedQuote`: a run of consecutive tokens and their price.
+///
+/// # Fields
+///
+/// * `starting_supply`: The supply before this run of tokens is traded. When adding, the run
+///   mints tokens `starting_supply..starting_supply + amount`.
+/// * `amount`: The number of tokens in the run.
+/// * `price`: The total price of the run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteItem<T> {
+    pub starting_supply: u64,
+    pub amount: u64,
+    pub price: T,
+}
+
+/// Represents a batch quote broken down into per-token or per-chunk prices, as returned by
+/// `BondingCurveWithCheckedOperations::quote_itemized`.
+///
+/// # Fields
+///
+/// * `items`: The runs of tokens in trade order, so the first item is traded first.
+/// * `total`: The price of the whole batch, as quoted by `calculate_price_many_checked`.
+///   Integer items always sum to it; float items may differ from it by rounding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemizedQuote<T> {
+    pub items: Vec<QuoteItem<T>>,
+    pub total: T,
+}
+
+impl QuoteRequest {
+    /// The serialized size of a `QuoteRequest`.
+    pub const LEN: usize = 8 + 8 + 1;
+
+    /// Creates a new `QuoteRequest`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `QuoteRequest`.
+    pub fn new(starting_supply: u64, amount: u64, side: OperationSide) -> Self {
+        Self {
+            starting_supply,
+            amount,
+            side,
+        }
+    }
+
+    /// Quotes this request against a curve.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve to price the request with.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `QuoteResponse`, or a `BondingCurveError` if pricing
+    /// overflows, the supply would pass `u64::MAX`, or more tokens are removed than the
+    /// supply holds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{LinearBondingCurve, OperationSide, QuoteRequest};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let response = QuoteRequest::new(10, 2, OperationSide::Add).quote(&curve).unwrap();
+    /// assert_eq!(response.price, 4100);
+    /// assert_eq!(response.ending_supply, 12);
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "quote", skip(curve), ret, err)
+    )]
+    pub fn quote<C>(&self, curve: &C) -> Result<QuoteResponse, BondingCurveError>
+    where
+        C: BondingCurveWithCheckedOperations<u64>,
+    {
+        let ending_supply = self.ending_supply()?;
+        let price =
+            curve.calculate_price_many_checked(self.starting_supply, self.amount, self.side)?;
+        Ok(QuoteResponse {
+            price,
+            ending_supply,
+        })
+    }
+
+    /// Quotes this request against a curve bound to the decimals of its reserve token.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve to price the request with.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FixedQuoteResponse`, or the `BondingCurveError` of `quote`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{FixedCurve, LinearBondingCurve, OperationSide, QuoteRequest};
+    ///
+    /// let curve = FixedCurve::<_, 6>::new(LinearBondingCurve::new(100, 1000));
+    /// let response = QuoteRequest::new(10, 2, OperationSide::Add)
+    ///     .quote_fixed_price(&curve)
+    ///     .unwrap();
+    /// assert_eq!(response.price.to_string(), "0.0041");
+    /// assert_eq!(response.ending_supply, 12);
+    /// ```
+    pub fn quote_fixed_price<C, const DECIMALS: u8>(
+        &self,
+        curve: &C,
+    ) -> Result<FixedQuoteResponse<DECIMALS>, BondingCurveError>
+    where
+        C: FixedPriceCurve<DECIMALS>,
+    {
+        let response = self.quote(curve)?;
+        Ok(FixedQuoteResponse {
+            price: FixedPrice(response.price),
+            ending_supply: response.ending_supply,
+        })
+    }
+
+    /// Returns the supply after the operation, or the error `quote` fails with when it does
+    /// not fit in a `u64`.
+    pub(crate) fn ending_supply(&self) -> Result<u64, BondingCurveError> {
+        match self.side {
+            OperationSide::Add => self
+                .starting_supply
+                .checked_add(self.amount)
+                .ok_or(BondingCurveError::Overflow),
+            OperationSide::Remove => self
+                .starting_supply
+                .checked_sub(self.amount)
+                .ok_or(BondingCurveError::SupplyUnderflow),
+        }
+    }
+}
+
+impl QuoteResponse {
+    /// The serialized size of a `QuoteResponse`.
+    pub const LEN: usize = 8 + 8;
+}
+
+impl<const DECIMALS: u8> FixedQuoteResponse<DECIMALS> {
+    /// The serialized size of a `FixedQuoteResponse`.
+    pub const LEN: usize = QuoteResponse::LEN;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, FixedCurve, FixedPrice,
+        FixedQuoteResponse, OperationSide, QuadraticBondingCurve, QuoteRequest, QuoteResponse,
+    };
+
+    #[test]
+    pub fn test_quote_request() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let request = QuoteRequest::new(100, 10, OperationSide::Remove);
+        let response = request.quote(&curve).unwrap();
+        assert_eq!(
+            response,
+            QuoteResponse {
+                price: curve
+                    .calculate_price_many_checked(100, 10, OperationSide::Remove)
+                    .unwrap(),
+                ending_supply: 90,
+            }
+        );
+        assert_eq!(
+            QuoteRequest::new(u64::MAX, 1, OperationSide::Add).quote(&curve),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            QuoteRequest::new(5, 6, OperationSide::Remove).quote(&curve),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[test]
+    pub fn test_quote_fixed_price() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let request = QuoteRequest::new(100, 10, OperationSide::Remove);
+        let response = request.quote(&curve).unwrap();
+        let fixed = request
+            .quote_fixed_price(&FixedCurve::<_, 9>::new(curve))
+            .unwrap();
+        assert_eq!(
+            fixed,
+            FixedQuoteResponse::<9> {
+                price: FixedPrice::new(response.price),
+                ending_supply: response.ending_supply,
+            }
+        );
+        assert_eq!(
+            QuoteRequest::new(5, 6, OperationSide::Remove)
+                .quote_fixed_price(&FixedCurve::<_, 9>::new(curve)),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[test]
+    pub fn test_quote_itemized() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let quote = curve.quote_itemized(100, 3, OperationSide::Add).unwrap();
+        let supplies: Vec<u64> = quote
+            .items
+            .iter()
+            .map(|item| item.starting_supply)
+            .collect();
+        assert_eq!(supplies, vec![100, 101, 102]);
+        assert_eq!(
+            quote.items[1].price,
+            curve.calculate_price_checked(101).unwrap()
+        );
+        assert_eq!(
+            quote.items.iter().map(|item| item.price).sum::<u64>(),
+            quote.total
+        );
+
+        let quote = curve.quote_itemized(100, 3, OperationSide::Remove).unwrap();
+        let supplies: Vec<u64> = quote
+            .items
+            .iter()
+            .map(|item| item.starting_supply)
+            .collect();
+        assert_eq!(supplies, vec![100, 99, 98]);
+        assert_eq!(
+            quote.items.iter().map(|item| item.price).sum::<u64>(),
+            quote.total
+        );
+        assert!(curve
+            .quote_itemized(5, 0, OperationSide::Add)
+            .unwrap()
+            .items
+            .is_empty());
+    }
+
+    #[test]
+    pub fn test_quote_itemized_chunked() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let quote = curve
+            .quote_itemized_chunked(0, 1_000, OperationSide::Add, 300)
+            .unwrap();
+        let sizes: Vec<u64> = quote.items.iter().map(|item| item.amount).collect();
+        assert_eq!(sizes, vec![300, 300, 300, 100]);
+        assert_eq!(quote.items[3].starting_supply, 900);
+        assert_eq!(
+            quote.items.iter().map(|item| item.price).sum::<u64>(),
+            quote.total
+        );
+        assert_eq!(
+            curve.quote_itemized_chunked(0, 10, OperationSide::Add, 0),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            curve.quote_itemized(5, 6, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    pub fn test_quote_borsh_sizes() {
+        let request = QuoteRequest::new(100, 10, OperationSide::Remove);
+        let bytes = borsh::to_vec(&request).unwrap();
+        assert_eq!(bytes.len(), QuoteRequest::LEN);
+        assert_eq!(borsh::from_slice::<QuoteRequest>(&bytes).unwrap(), request);
+
+        let response = QuoteResponse {
+            price: 1,
+            ending_supply: 2,
+        };
+        assert_eq!(borsh::to_vec(&response).unwrap().len(), QuoteResponse::LEN);
+
+        let fixed = FixedQuoteResponse::<6> {
+            price: FixedPrice::new(1),
+            ending_supply: 2,
+        };
+        assert_eq!(
+            borsh::to_vec(&fixed).unwrap(),
+            borsh::to_vec(&response).unwrap()
+        );
+        assert_eq!(FixedQuoteResponse::<6>::LEN, QuoteResponse::LEN);
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    pub fn test_quote_json_schema() {
+        let schema = schemars::schema_for!(QuoteRequest);
+        let properties = schema.get("properties").unwrap();
+        for field in ["starting_supply", "amount", "side"] {
+            assert!(properties.get(field).is_some(), "missing {}", field);
+        }
+        let schema = schemars::schema_for!(QuoteResponse);
+        assert!(schema.get("properties").unwrap().get("price").is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_quote_json() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let request: QuoteRequest =
+            serde_json::from_str(r#"{"starting_supply":100,"amount":10,"side":"remove"}"#).unwrap();
+        assert_eq!(request, QuoteRequest::new(100, 10, OperationSide::Remove));
+        let response = request.quote(&curve).unwrap();
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            format!(r#"{{"price":{},"ending_supply":90}}"#, response.price)
+        );
+        assert_eq!(
+            serde_json::to_string(&BondingCurveError::SupplyUnderflow).unwrap(),
+            r#""SupplyUnderflow""#
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use rand::Rng;
+
+#[cfg(feature = "float")]
+use super::{math, ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+use super::{AnyBondingCurve, LinearBondingCurve, QuadraticBondingCurve};
+
+/// Generates a random `LinearBondingCurve` that does not overflow up to `max_supply`.
+///
+/// The coefficients are drawn so that the price at every supply up to `max_supply`, and the
+/// cost of buying every token from zero through `max_supply`, fit in a `u64`. Past about
+/// `2^32` tokens the sum of supplies alone fills the budget, so `linear` is then zero.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `max_supply` - The highest supply the curve must price without overflowing.
+///
+/// # Returns
+///
+/// A random `LinearBondingCurve`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{random_linear, BondingCurveWithCheckedOperations, OperationSide};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(7);
+/// let curve = random_linear(&mut rng, 1_000_000);
+/// assert!(curve
+///     .calculate_price_many_checked(0, 1_000_001, OperationSide::Add)
+///     .is_ok());
+/// ```
+pub fn random_linear<R: Rng + ?Sized>(rng: &mut R, max_supply: u64) -> LinearBondingCurve {
+    // Half of `u64::MAX` is left to each term of the sum over `0..=max_supply`.
+    let budget = u64::MAX as u128 / 2;
+    let (n, sum_i, _) = sums(max_supply);
+    LinearBondingCurve::new(coefficient(rng, budget, sum_i), coefficient(rng, budget, n))
+}
+
+/// Generates a random `QuadraticBondingCurve` that does not overflow up to `max_supply`.
+///
+/// The coefficients are drawn so that the price at every supply up to `max_supply`, and the
+/// cost of buying every token from zero through `max_supply`, fit in a `u64`. Past about
+/// 2.6 million tokens the sum of squares alone fills the budget, so `quadratic` is then zero.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `max_supply` - The highest supply the curve must price without overflowing.
+///
+/// # Returns
+///
+/// A random `QuadraticBondingCurve`.
+pub fn random_quadratic<R: Rng + ?Sized>(rng: &mut R, max_supply: u64) -> QuadraticBondingCurve {
+    // A third of `u64::MAX` is left to each term of the sum over `0..=max_supply`.
+    let budget = u64::MAX as u128 / 3;
+    let (n, sum_i, sum_i2) = sums(max_supply);
+    QuadraticBondingCurve::new(
+        coefficient(rng, budget, sum_i2),
+        coefficient(rng, budget, sum_i),
+        coefficient(rng, budget, n),
+    )
+}
+
+/// Generates a random `ExponentialBondingCurve` whose prices stay finite up to `max_supply`.
+///
+/// `base` is drawn log-uniformly from `[1e-9, 1e3]`, and `growth` log-uniformly from the
+/// nine decades below `min(1, 600 / (max_supply + 1))`, which keeps the exponent at most 600.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `max_supply` - The highest supply the curve must price with finite results.
+///
+/// # Returns
+///
+/// A random `ExponentialBondingCurve`.
+#[cfg(feature = "float")]
+pub fn random_exponential<R: Rng + ?Sized>(
+    rng: &mut R,
+    max_supply: u64,
+) -> ExponentialBondingCurve {
+    let base = log_uniform(rng, -9.0, 3.0);
+    let growth = growth_cap(max_supply) * log_uniform(rng, -9.0, 0.0);
+    ExponentialBondingCurve::new(base, growth)
+}
+
+/// Generates a random `LogarithmicBondingCurve`.
+///
+/// `base` and `growth` are drawn log-uniformly from `[1e-9, 1e6]`. Logarithmic prices stay
+/// finite at every supply, so no envelope depends on a maximum supply.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+///
+/// # Returns
+///
+/// A random `LogarithmicBondingCurve`.
+#[cfg(feature = "float")]
+pub fn random_logarithmic<R: Rng + ?Sized>(rng: &mut R) -> LogarithmicBondingCurve {
+    LogarithmicBondingCurve::new(log_uniform(rng, -9.0, 6.0), log_uniform(rng, -9.0, 6.0))
+}
+
+/// Generates a random `SigmoidBondingCurve` whose prices stay finite up to `max_supply`.
+///
+/// `max_price` is drawn log-uniformly from `[1e-3, 1e6]`, `mid_supply` uniformly from
+/// `[0, max_supply]`, and `growth` log-uniformly from the six decades below
+/// `min(1, 600 / (max_supply + 1))`.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `max_supply` - The highest supply the curve must price with finite results.
+///
+/// # Returns
+///
+/// A random `SigmoidBondingCurve`.
+#[cfg(feature = "float")]
+pub fn random_sigmoid<R: Rng + ?Sized>(rng: &mut R, max_supply: u64) -> SigmoidBondingCurve {
+    let max_price = log_uniform(rng, -3.0, 6.0);
+    let growth = growth_cap(max_supply) * log_uniform(rng, -6.0, 0.0);
+    SigmoidBondingCurve::new(max_price, growth, rng.random_range(0..=max_supply))
+}
+
+/// Generates a random curve of a random family, within the envelope of that family's
+/// generator.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `max_supply` - The highest supply the curve must price without overflowing.
+///
+/// # Returns
+///
+/// A random `AnyBondingCurve`. Without the `float` feature only integer families are drawn.
+pub fn random_curve<R: Rng + ?Sized>(rng: &mut R, max_supply: u64) -> AnyBondingCurve {
+    #[cfg(feature = "float")]
+    const FAMILIES: u8 = 5;
+    #[cfg(not(feature = "float"))]
+    const FAMILIES: u8 = 2;
+    match rng.random_range(0..FAMILIES) {
+        0 => random_linear(rng, max_supply).into(),
+        #[cfg(feature = "float")]
+        2 => random_exponential(rng, max_supply).into(),
+        #[cfg(feature = "float")]
+        3 => random_logarithmic(rng).into(),
+        #[cfg(feature = "float")]
+        4 => random_sigmoid(rng, max_supply).into(),
+        _ => random_quadratic(rng, max_supply).into(),
+    }
+}
+
+/// Returns the number of priced supplies in `0..=max_supply`, and the sums of those supplies
+/// and of their squares, saturating at `u128::MAX`.
+fn sums(max_supply: u64) -> (u128, u128, u128) {
+    let m = max_supply as u128;
+    let n = m + 1;
+    let sum_i = m * n / 2;
+    let sum_i2 = m
+        .checked_mul(n)
+        .and_then(|x| x.checked_mul(2 * m + 1))
+        .map_or(u128::MAX, |x| x / 6);
+    (n, sum_i, sum_i2)
+}
+
+/// Draws a coefficient whose product with `weight` stays within `budget`.
+fn coefficient<R: Rng + ?Sized>(rng: &mut R, budget: u128, weight: u128) -> u64 {
+    let max = (budget / weight.max(1)).min(u64::MAX as u128) as u64;
+    rng.random_range(0..=max)
+}
+
+#[cfg(feature = "float")]
+fn log_uniform<R: Rng + ?Sized>(rng: &mut R, low: f64, high: f64) -> f64 {
+    math::powf(10.0, rng.random_range(low..=high))
+}
+
+#[cfg(feature = "float")]
+fn growth_cap(max_supply: u64) -> f64 {
+    (600.0 / (max_supply as f64 + 1.0)).min(1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{
+        random_linear, random_quadratic, BondingCurveWithCheckedOperations, OperationSide,
+    };
+
+    #[test]
+    pub fn test_random_integer_curves_do_not_overflow() {
+        let mut rng = SmallRng::seed_from_u64(1952);
+        for max_supply in [0, 1, 1_000, 1 << 32, 1 << 45, u64::MAX - 1] {
+            for _ in 0..50 {
+                let linear = random_linear(&mut rng, max_supply);
+                let quadratic = random_quadratic(&mut rng, max_supply);
+                for curve in [
+                    &linear as &dyn BondingCurveWithCheckedOperations<u64>,
+                    &quadratic,
+                ] {
+                    assert!(curve.calculate_price_checked(max_supply).is_ok());
+                    assert!(curve
+                        .calculate_price_many_checked(0, max_supply + 1, OperationSide::Add)
+                        .is_ok());
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "float", not(feature = "panic-free")))]
+    #[test]
+    pub fn test_random_float_curves_stay_finite() {
+        use crate::{random_curve, AnyBondingCurve, BondingCurve};
+
+        let mut rng = SmallRng::seed_from_u64(1952);
+        for max_supply in [0, 1, 1_000, 1 << 32, u64::MAX - 1] {
+            for _ in 0..50 {
+                let curve = random_curve(&mut rng, max_supply);
+                let (price, cost) = match curve {
+                    AnyBondingCurve::Exponential(curve) => (
+                        curve.calculate_price_checked(max_supply).is_ok(),
+                        curve
+                            .calculate_price_many_checked(0, max_supply + 1, OperationSide::Add)
+                            .is_ok(),
+                    ),
+                    AnyBondingCurve::Logarithmic(curve) => (
+                        curve.calculate_price_checked(max_supply).is_ok(),
+                        curve
+                            .calculate_price_many_checked(0, max_supply + 1, OperationSide::Add)
+                            .is_ok(),
+                    ),
+                    AnyBondingCurve::Sigmoid(curve) => (
+                        curve.calculate_price(max_supply).is_finite(),
+                        curve
+                            .calculate_price_many(0, max_supply + 1, OperationSide::Add)
+                            .is_finite(),
+                    ),
+                    _ => (true, true),
+                };
+                assert!(price && cost, "{:?} at {}", curve, max_supply);
+            }
+        }
+    }
+}
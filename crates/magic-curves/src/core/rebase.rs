@@ -0,0 +1,255 @@
+use super::{BondingCurveError, BondingCurveWithCheckedOperations, OperationSide};
+
+/// Represents a curve priced in the raw shares of an elastic-supply token, whose balances are
+/// rebased: every share is worth `supply / shares` tokens, a factor that rebases change.
+///
+/// The curve prices shares, which rebases do not move, while trades are given in rebased
+/// tokens, the amounts wallets show. Conversions round in the curve's favor, so a trade never
+/// gets more shares than it pays for. Before any share is minted, shares and tokens are one
+/// to one.
+///
+/// # Fields
+///
+/// * `curve`: The curve that prices shares.
+/// * `shares`: The total raw shares, the supply of the curve.
+/// * `supply`: The rebased supply those shares hold.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{LinearBondingCurve, OperationSide, RebaseAdapter};
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// // 1_000 shares that rebased to 2_000 tokens, so a token is half a share.
+/// let adapter = RebaseAdapter::new(curve, 1_000, 2_000);
+/// let quote = adapter.quote(20, OperationSide::Add).unwrap();
+/// assert_eq!((quote.shares, quote.ending_shares, quote.ending_supply), (10, 1_010, 2_020));
+/// assert_eq!(quote.price, 1_014_500);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RebaseAdapter<C> {
+    pub curve: C,
+    pub shares: u64,
+    pub supply: u64,
+}
+
+/// Represents the outcome of `RebaseAdapter::quote`.
+///
+/// # Fields
+///
+/// * `shares`: The shares the trade mints or burns.
+/// * `price`: The total price of those shares on the curve.
+/// * `ending_shares`: The total shares after the trade.
+/// * `ending_supply`: The rebased supply after the trade.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RebasedQuote {
+    pub shares: u64,
+    pub price: u64,
+    pub ending_shares: u64,
+    pub ending_supply: u64,
+}
+
+impl<C> RebaseAdapter<C> {
+    /// Creates a new `RebaseAdapter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve that prices shares.
+    /// * `shares` - The total raw shares.
+    /// * `supply` - The rebased supply those shares hold.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `RebaseAdapter`.
+    pub fn new(curve: C, shares: u64, supply: u64) -> Self {
+        Self {
+            curve,
+            shares,
+            supply,
+        }
+    }
+
+    /// Applies a rebase, which changes the supply the shares hold but not the shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The rebased supply after the rebase.
+    pub fn rebase(&mut self, supply: u64) {
+        self.supply = supply;
+    }
+
+    /// Converts rebased tokens into raw shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of rebased tokens.
+    /// * `round_up` - Whether to round the shares up rather than down.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the shares, `BondingCurveError::DivisionByZero` if existing
+    /// shares were rebased to nothing, or `BondingCurveError::Overflow` if they do not fit in
+    /// a `u64`.
+    pub fn to_shares(&self, amount: u64, round_up: bool) -> Result<u64, BondingCurveError> {
+        if self.shares == 0 {
+            return Ok(amount);
+        }
+        scale(amount, self.shares, self.supply, round_up)
+    }
+
+    /// Converts raw shares into rebased tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `shares` - The number of raw shares.
+    /// * `round_up` - Whether to round the tokens up rather than down.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the rebased tokens, or `BondingCurveError::Overflow` if they do
+    /// not fit in a `u64`.
+    pub fn to_rebased(&self, shares: u64, round_up: bool) -> Result<u64, BondingCurveError> {
+        if self.shares == 0 {
+            return Ok(shares);
+        }
+        scale(shares, self.supply, self.shares, round_up)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> RebaseAdapter<C> {
+    /// Quotes trading rebased tokens against the curve, from the current shares.
+    ///
+    /// Buying and selling both round the shares up: a buyer pays for every share their tokens
+    /// need, and a seller burns every share their tokens hold.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of rebased tokens to trade.
+    /// * `side` - The side of the trade.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `RebasedQuote`, or the `BondingCurveError` of the conversion
+    /// or of the curve, such as selling more shares than exist.
+    pub fn quote(
+        &self,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<RebasedQuote, BondingCurveError> {
+        let shares = self.to_shares(amount, true)?;
+        let price = self
+            .curve
+            .calculate_price_many_checked(self.shares, shares, side)?;
+        let ending_shares = match side {
+            OperationSide::Add => self.shares.checked_add(shares),
+            OperationSide::Remove => self.shares.checked_sub(shares),
+        }
+        .ok_or(BondingCurveError::Overflow)?;
+        let ending_supply = if self.shares == 0 {
+            ending_shares
+        } else {
+            scale(ending_shares, self.supply, self.shares, false)?
+        };
+        Ok(RebasedQuote {
+            shares,
+            price,
+            ending_shares,
+            ending_supply,
+        })
+    }
+
+    /// Returns the spot price of one rebased token, the spot price of a share scaled by the
+    /// shares a token holds, rounded up.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, or the `BondingCurveError` of the curve or of the
+    /// conversion.
+    pub fn spot_price(&self) -> Result<u64, BondingCurveError> {
+        let price = self.curve.calculate_price_checked(self.shares)?;
+        if self.shares == 0 {
+            return Ok(price);
+        }
+        scale(price, self.shares, self.supply, true)
+    }
+}
+
+/// Returns `value * numerator / denominator`.
+fn scale(
+    value: u64,
+    numerator: u64,
+    denominator: u64,
+    round_up: bool,
+) -> Result<u64, BondingCurveError> {
+    if denominator == 0 {
+        return Err(BondingCurveError::DivisionByZero);
+    }
+    let product = value as u128 * numerator as u128;
+    let scaled = if round_up {
+        product.div_ceil(denominator as u128)
+    } else {
+        product / denominator as u128
+    };
+    u64::try_from(scaled).map_err(|_| BondingCurveError::Overflow)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
+        QuadraticBondingCurve, RebaseAdapter,
+    };
+
+    #[test]
+    pub fn test_rebase_adapter_prices_shares() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut adapter = RebaseAdapter::new(curve, 3_000, 3_000);
+        let before = adapter.quote(300, OperationSide::Add).unwrap();
+        assert_eq!(before.shares, 300);
+        // Doubling the supply halves the shares a token holds, and what it costs.
+        adapter.rebase(6_000);
+        let after = adapter.quote(600, OperationSide::Add).unwrap();
+        assert_eq!((after.shares, after.price), (300, before.price));
+        assert_eq!(after.ending_supply, 6_600);
+        assert_eq!(adapter.to_rebased(after.shares, false), Ok(600));
+        // One token is only a third of a share, but still burns a whole one.
+        adapter.rebase(9_000);
+        let sale = adapter.quote(1, OperationSide::Remove).unwrap();
+        assert_eq!(sale.shares, 1);
+        assert_eq!(sale.price, curve.calculate_price_checked(3_000).unwrap());
+        assert_eq!(
+            adapter.spot_price(),
+            Ok(curve.calculate_price_checked(3_000).unwrap().div_ceil(3))
+        );
+    }
+
+    #[test]
+    pub fn test_rebase_adapter_edges() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let empty = RebaseAdapter::new(curve, 0, 0);
+        assert_eq!(
+            empty.quote(10, OperationSide::Add).unwrap().ending_supply,
+            10
+        );
+        assert_eq!(empty.spot_price(), Ok(1_000));
+        let wiped = RebaseAdapter::new(curve, 10, 0);
+        assert_eq!(
+            wiped.quote(1, OperationSide::Add),
+            Err(BondingCurveError::DivisionByZero)
+        );
+        assert_eq!(
+            RebaseAdapter::new(curve, 10, 10).quote(11, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            RebaseAdapter::new(curve, u64::MAX, 1).to_rebased(u64::MAX, true),
+            Ok(1)
+        );
+        assert_eq!(
+            RebaseAdapter::new(curve, 1, u64::MAX).to_rebased(2, false),
+            Err(BondingCurveError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use super::{AnyBondingCurve, ParseCurveSpecError};
+
+/// Represents the possible errors that can occur while registering curves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveRegistryError {
+    /// Indicates that a curve is already registered under this name.
+    AlreadyRegistered(String),
+    /// Indicates that the curve spec could not be parsed.
+    InvalidSpec(ParseCurveSpecError),
+}
+
+impl Display for CurveRegistryError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CurveRegistryError::AlreadyRegistered(name) => {
+                write!(f, "A curve is already registered as `{}`.", name)
+            }
+            CurveRegistryError::InvalidSpec(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for CurveRegistryError {}
+
+impl From<ParseCurveSpecError> for CurveRegistryError {
+    fn from(error: ParseCurveSpecError) -> Self {
+        CurveRegistryError::InvalidSpec(error)
+    }
+}
+
+type CurveFactory = Box<dyn Fn() -> AnyBondingCurve + Send + Sync>;
+
+enum Entry {
+    Curve(AnyBondingCurve),
+    Factory(CurveFactory),
+}
+
+/// Represents a set of curves registered under names, resolved at runtime.
+///
+/// Applications register curve instances (or factories that build them) once, and
+/// pool configurations then reference curves by name.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{AnyBondingCurve, CurveRegistry, LinearBondingCurve};
+///
+/// let mut registry = CurveRegistry::new();
+/// registry.register("launch", LinearBondingCurve::new(100, 1000)).unwrap();
+/// registry.register_spec("steep", "quadratic:10,0,1000").unwrap();
+///
+/// assert_eq!(
+///     registry.resolve("launch"),
+///     Some(AnyBondingCurve::Linear(LinearBondingCurve::new(100, 1000)))
+/// );
+/// assert!(registry.resolve("missing").is_none());
+/// ```
+#[derive(Default)]
+pub struct CurveRegistry {
+    entries: HashMap<String, Entry>,
+}
+
+impl CurveRegistry {
+    /// Creates a new, empty `CurveRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a curve instance under `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to resolve the curve by.
+    /// * `curve` - The curve, of any family.
+    ///
+    /// # Returns
+    ///
+    /// `CurveRegistryError::AlreadyRegistered` if `name` is taken.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        curve: impl Into<AnyBondingCurve>,
+    ) -> Result<(), CurveRegistryError> {
+        self.insert(name.into(), Entry::Curve(curve.into()))
+    }
+
+    /// Registers a factory under `name`. The factory is called on every `resolve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to resolve the curve by.
+    /// * `factory` - A function building the curve.
+    ///
+    /// # Returns
+    ///
+    /// `CurveRegistryError::AlreadyRegistered` if `name` is taken.
+    pub fn register_factory<F>(
+        &mut self,
+        name: impl Into<String>,
+        factory: F,
+    ) -> Result<(), CurveRegistryError>
+    where
+        F: Fn() -> AnyBondingCurve + Send + Sync + 'static,
+    {
+        self.insert(name.into(), Entry::Factory(Box::new(factory)))
+    }
+
+    /// Parses a compact curve spec and registers the curve under `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to resolve the curve by.
+    /// * `spec` - A curve spec such as `linear:100,1000`.
+    ///
+    /// # Returns
+    ///
+    /// `CurveRegistryError::InvalidSpec` if the spec does not parse, or
+    /// `CurveRegistryError::AlreadyRegistered` if `name` is taken.
+    pub fn register_spec(
+        &mut self,
+        name: impl Into<String>,
+        spec: &str,
+    ) -> Result<(), CurveRegistryError> {
+        let curve: AnyBondingCurve = spec.parse()?;
+        self.register(name, curve)
+    }
+
+    /// Returns the curve registered under `name`, if any.
+    pub fn resolve(&self, name: &str) -> Option<AnyBondingCurve> {
+        self.entries.get(name).map(|entry| match entry {
+            Entry::Curve(curve) => *curve,
+            Entry::Factory(factory) => factory(),
+        })
+    }
+
+    /// Returns whether a curve is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Removes the curve registered under `name`, returning whether it was registered.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Returns the registered names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn insert(&mut self, name: String, entry: Entry) -> Result<(), CurveRegistryError> {
+        if self.entries.contains_key(&name) {
+            return Err(CurveRegistryError::AlreadyRegistered(name));
+        }
+        self.entries.insert(name, entry);
+        Ok(())
+    }
+}
+
+impl Debug for CurveRegistry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CurveRegistry")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        AnyBondingCurve, CurveRegistry, CurveRegistryError, LinearBondingCurve,
+        ParseCurveSpecError, QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_curve_registry() {
+        let mut registry = CurveRegistry::new();
+        registry
+            .register("launch", LinearBondingCurve::new(100, 1000))
+            .unwrap();
+        registry
+            .register_factory("default", || {
+                QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000).into()
+            })
+            .unwrap();
+        assert_eq!(registry.names(), vec!["default", "launch"]);
+        assert_eq!(
+            registry.resolve("default"),
+            Some(AnyBondingCurve::Quadratic(QuadraticBondingCurve::new(
+                10_000_000,
+                500_000_000,
+                1_000_000_000
+            )))
+        );
+        assert!(registry.unregister("launch"));
+        assert!(!registry.contains("launch"));
+        assert!(!registry.unregister("launch"));
+    }
+
+    #[test]
+    pub fn test_curve_registry_errors() {
+        let mut registry = CurveRegistry::new();
+        registry.register_spec("launch", "linear:100,1000").unwrap();
+        assert_eq!(
+            registry.register("launch", LinearBondingCurve::new(1, 1)),
+            Err(CurveRegistryError::AlreadyRegistered("launch".to_string()))
+        );
+        assert_eq!(
+            registry.register_spec("bad", "linear"),
+            Err(CurveRegistryError::InvalidSpec(
+                ParseCurveSpecError::InvalidFormat("linear".to_string())
+            ))
+        );
+        assert_eq!(
+            registry.resolve("launch"),
+            Some(AnyBondingCurve::Linear(LinearBondingCurve::new(100, 1000)))
+        );
+    }
+}
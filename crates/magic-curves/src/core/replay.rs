@@ -0,0 +1,322 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, CurveState, OperationSide, TradeObserver,
+    TradeResult,
+};
+
+/// Represents a recorded trade to replay against a curve.
+///
+/// # Fields
+///
+/// * `timestamp`: When the trade happened, in whatever unit the export uses.
+/// * `side`: Whether tokens were bought or sold.
+/// * `amount`: The number of tokens traded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeEvent {
+    pub timestamp: u64,
+    pub side: OperationSide,
+    pub amount: u64,
+}
+
+/// Represents the possible errors that can occur while parsing a trades CSV.
+///
+/// Every variant carries the 1-based line number of the offending row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTradesError {
+    /// Indicates that a row does not have exactly the `timestamp`, `side`, and `amount`
+    /// columns.
+    ColumnCount { line: usize, found: usize },
+    /// Indicates that a timestamp is not a whole number.
+    InvalidTimestamp { line: usize, value: String },
+    /// Indicates that a side is not `buy`, `sell`, `add`, or `remove`.
+    InvalidSide { line: usize, value: String },
+    /// Indicates that an amount is not a whole number.
+    InvalidAmount { line: usize, value: String },
+    /// Indicates that a timestamp is earlier than the one of the row before it.
+    OutOfOrder { line: usize },
+}
+
+impl Display for ParseTradesError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseTradesError::ColumnCount { line, found } => write!(
+                f,
+                "Line {}: expected 3 columns (timestamp, side, amount), found {}.",
+                line, found
+            ),
+            ParseTradesError::InvalidTimestamp { line, value } => write!(
+                f,
+                "Line {}: the timestamp `{}` is not a whole number.",
+                line, value
+            ),
+            ParseTradesError::InvalidSide { line, value } => {
+                write!(f, "Line {}: the side `{}` is not buy or sell.", line, value)
+            }
+            ParseTradesError::InvalidAmount { line, value } => write!(
+                f,
+                "Line {}: the amount `{}` is not a whole number.",
+                line, value
+            ),
+            ParseTradesError::OutOfOrder { line } => write!(
+                f,
+                "Line {}: the timestamp is earlier than the one before it.",
+                line
+            ),
+        }
+    }
+}
+
+impl Error for ParseTradesError {}
+
+/// Parses a CSV export of trades into `TradeEvent`s, ready for `replay_trades`.
+///
+/// Every row holds a timestamp, a side, and an amount, in that order. Sides are `buy` or
+/// `sell`, or `add` or `remove`, in any case. Fields are trimmed, blank lines are skipped,
+/// and a first row of `timestamp,side,amount` is taken as a header. Rows must be sorted by
+/// timestamp, so an export in the wrong order is caught rather than replayed.
+///
+/// # Arguments
+///
+/// * `csv` - The contents of the CSV file.
+///
+/// # Returns
+///
+/// A `Result` containing the events in file order, or the `ParseTradesError` of the first
+/// invalid row.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{parse_trades_csv, OperationSide, ParseTradesError};
+///
+/// let events = parse_trades_csv("timestamp,side,amount\n100,buy,10\n160,sell,4\n").unwrap();
+/// assert_eq!(events.len(), 2);
+/// assert_eq!(events[1].side, OperationSide::Remove);
+/// assert_eq!(
+///     parse_trades_csv("100,buy,ten"),
+///     Err(ParseTradesError::InvalidAmount { line: 1, value: "ten".to_string() })
+/// );
+/// ```
+pub fn parse_trades_csv(csv: &str) -> Result<Vec<TradeEvent>, ParseTradesError> {
+    let mut events: Vec<TradeEvent> = Vec::new();
+    let mut header_allowed = true;
+    for (index, row) in csv.lines().enumerate() {
+        let line = index + 1;
+        // Spreadsheet exports often start with a byte order mark.
+        let row = row.trim_start_matches('\u{feff}');
+        if row.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+        let is_header = fields.iter().map(|field| field.to_ascii_lowercase()).eq([
+            "timestamp",
+            "side",
+            "amount",
+        ]);
+        if std::mem::take(&mut header_allowed) && is_header {
+            continue;
+        }
+        let [timestamp, side, amount] = fields[..] else {
+            return Err(ParseTradesError::ColumnCount {
+                line,
+                found: fields.len(),
+            });
+        };
+        let timestamp = timestamp
+            .parse()
+            .map_err(|_| ParseTradesError::InvalidTimestamp {
+                line,
+                value: timestamp.to_string(),
+            })?;
+        let side = match side.to_ascii_lowercase().as_str() {
+            "buy" | "add" => OperationSide::Add,
+            "sell" | "remove" => OperationSide::Remove,
+            _ => {
+                return Err(ParseTradesError::InvalidSide {
+                    line,
+                    value: side.to_string(),
+                })
+            }
+        };
+        let amount = amount
+            .parse()
+            .map_err(|_| ParseTradesError::InvalidAmount {
+                line,
+                value: amount.to_string(),
+            })?;
+        if events.last().is_some_and(|last| timestamp < last.timestamp) {
+            return Err(ParseTradesError::OutOfOrder { line });
+        }
+        events.push(TradeEvent {
+            timestamp,
+            side,
+            amount,
+        });
+    }
+    Ok(events)
+}
+
+/// Represents a trade that failed while replaying events.
+///
+/// # Fields
+///
+/// * `index`: The position of the failed event in the replayed slice.
+/// * `error`: The `BondingCurveError` the trade failed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayError {
+    pub index: usize,
+    pub error: BondingCurveError,
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Event {}: {}", self.index, self.error)
+    }
+}
+
+impl Error for ReplayError {}
+
+/// Applies recorded events to a `CurveState` in order, notifying its observer of each trade.
+///
+/// # Arguments
+///
+/// * `state` - The state to replay the events on.
+/// * `events` - The events, as parsed by `parse_trades_csv`.
+///
+/// # Returns
+///
+/// A `Result` containing the `TradeResult` of every event, or the `ReplayError` of the first
+/// event that fails, in which case `state` holds the trades of the events before it.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{parse_trades_csv, replay_trades, CurveState, LinearBondingCurve};
+///
+/// let events = parse_trades_csv("100,buy,10\n160,sell,4\n").unwrap();
+/// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+/// let trades = replay_trades(&mut state, &events).unwrap();
+/// assert_eq!(trades[0].cost, 14_500);
+/// assert_eq!(state.supply(), 6);
+/// ```
+pub fn replay_trades<C, O>(
+    state: &mut CurveState<C, O>,
+    events: &[TradeEvent],
+) -> Result<Vec<TradeResult>, ReplayError>
+where
+    C: BondingCurveWithCheckedOperations<u64>,
+    O: TradeObserver,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("replay_trades", events = events.len()).entered();
+    let mut trades = Vec::with_capacity(events.len());
+    for (index, event) in events.iter().enumerate() {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(index, timestamp = event.timestamp, "replaying event");
+        let trade = state
+            .apply(event.amount, event.side)
+            .map_err(|error| ReplayError { index, error })?;
+        trades.push(trade);
+    }
+    Ok(trades)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse_trades_csv, replay_trades, BondingCurveError, CurveState, OperationSide,
+        ParseTradesError, QuadraticBondingCurve, ReplayError, TradeEvent,
+    };
+
+    #[test]
+    pub fn test_parse_trades_csv() {
+        let csv =
+            "\u{feff}Timestamp, Side, Amount\r\n\n1, BUY, 100\n1,add,5\n  \n7 ,Sell, 20\n9,remove,0\n";
+        let events = parse_trades_csv(csv).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                TradeEvent {
+                    timestamp: 1,
+                    side: OperationSide::Add,
+                    amount: 100,
+                },
+                TradeEvent {
+                    timestamp: 1,
+                    side: OperationSide::Add,
+                    amount: 5,
+                },
+                TradeEvent {
+                    timestamp: 7,
+                    side: OperationSide::Remove,
+                    amount: 20,
+                },
+                TradeEvent {
+                    timestamp: 9,
+                    side: OperationSide::Remove,
+                    amount: 0,
+                },
+            ]
+        );
+        assert_eq!(parse_trades_csv(""), Ok(vec![]));
+        assert_eq!(parse_trades_csv("timestamp,side,amount"), Ok(vec![]));
+    }
+
+    #[test]
+    pub fn test_parse_trades_csv_errors() {
+        assert_eq!(
+            parse_trades_csv("1,buy"),
+            Err(ParseTradesError::ColumnCount { line: 1, found: 2 })
+        );
+        assert_eq!(
+            parse_trades_csv("1,buy,1\n2,buy,1,note"),
+            Err(ParseTradesError::ColumnCount { line: 2, found: 4 })
+        );
+        // Only the first row can be a header.
+        assert_eq!(
+            parse_trades_csv("1,buy,1\ntimestamp,side,amount").map_err(|e| e.to_string()),
+            Err("Line 2: the timestamp `timestamp` is not a whole number.".to_string())
+        );
+        assert_eq!(
+            parse_trades_csv("1,hold,1"),
+            Err(ParseTradesError::InvalidSide {
+                line: 1,
+                value: "hold".to_string()
+            })
+        );
+        assert_eq!(
+            parse_trades_csv("1,buy,-3"),
+            Err(ParseTradesError::InvalidAmount {
+                line: 1,
+                value: "-3".to_string()
+            })
+        );
+        assert_eq!(
+            parse_trades_csv("\n5,buy,1\n4,sell,1"),
+            Err(ParseTradesError::OutOfOrder { line: 3 })
+        );
+    }
+
+    #[test]
+    pub fn test_replay_trades() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let events = parse_trades_csv("1,buy,100\n2,sell,30\n3,sell,80\n4,buy,1").unwrap();
+        let mut state = CurveState::new(curve, 0).with_observer(Vec::new());
+        assert_eq!(
+            replay_trades(&mut state, &events),
+            Err(ReplayError {
+                index: 2,
+                error: BondingCurveError::SupplyUnderflow,
+            })
+        );
+        assert_eq!(state.supply(), 70);
+        assert_eq!(state.observer().len(), 2);
+        let trades = replay_trades(&mut state, &events[3..]).unwrap();
+        assert_eq!(trades[0].supply, 71);
+    }
+}
@@ -0,0 +1,31 @@
+/// Represents the range in which a `u64` curve's unchecked formulas cannot overflow, as
+/// returned by `max_safe_supply`.
+///
+/// Every spot price at a supply up to `supply` is safe, and so is every trade of at most
+/// `amount` tokens that stays within `0..=supply`, on either side.
+///
+/// # Fields
+///
+/// * `supply`: The largest supply whose spot price, and every lower one, fits.
+/// * `amount`: The largest amount that can be removed at `supply`, which bounds every trade
+///   within `0..=supply`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SafeSupply {
+    pub supply: u64,
+    pub amount: u64,
+}
+
+/// Returns the largest value in `low..=high` for which `fits` holds, given that it holds for
+/// `low` and never holds again once it stops holding.
+pub(crate) fn largest(mut low: u64, mut high: u64, fits: impl Fn(u64) -> bool) -> u64 {
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
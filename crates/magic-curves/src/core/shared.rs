@@ -0,0 +1,383 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, CurveSnapshot, Graduation, OperationSide,
+    QuoteRequest, TradeResult,
+};
+
+/// Represents a curve and its current supply that many threads can quote and trade against
+/// at once, without a lock.
+///
+/// The supply is a single atomic, so quoting never blocks: a quote reads the supply once and
+/// prices the trade against it. Applying a trade prices it the same way and then publishes
+/// the new supply with a compare-and-swap, which fails if another trade was applied in
+/// between; the trade is then priced again against the new supply and retried. The applied
+/// trades are therefore serialized, each one priced against exactly the supply it is
+/// applied to, as if a single `CurveState` had applied them one after another.
+///
+/// Unlike a `CurveState`, a `SharedCurveState` has no `TradeObserver`, since observers need
+/// exclusive access; record the `TradeResult`s `apply` returns instead. It cannot graduate
+/// either, but a state restored from a graduated snapshot keeps its graduation and prices
+/// trades like the graduated `CurveState`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{LinearBondingCurve, SharedCurveState};
+///
+/// let state = SharedCurveState::new(LinearBondingCurve::new(100, 1000), 0);
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| state.buy(10).unwrap());
+///     }
+/// });
+/// assert_eq!(state.supply(), 40);
+/// ```
+#[derive(Debug)]
+pub struct SharedCurveState<C> {
+    curve: C,
+    supply: AtomicU64,
+    graduation: Option<Graduation>,
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> SharedCurveState<C> {
+    /// Creates a new `SharedCurveState`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve trades are priced with.
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SharedCurveState`.
+    pub fn new(curve: C, supply: u64) -> Self {
+        Self {
+            curve,
+            supply: AtomicU64::new(supply),
+            graduation: None,
+        }
+    }
+
+    /// Recreates the state captured by `SharedCurveState::snapshot` or
+    /// `CurveState::snapshot`, including its graduation. A `SharedCurveState` keeps no
+    /// reserve tranches, so the tranches of a snapshot are dropped; use
+    /// `SharedCurveState::try_restore` to reject such snapshots instead.
+    pub fn restore(snapshot: CurveSnapshot<C>) -> Self {
+        Self {
+            graduation: snapshot.graduation,
+            ..Self::new(snapshot.curve, snapshot.supply)
+        }
+    }
+
+    /// Recreates the state captured by a snapshot, like `SharedCurveState::restore`, unless
+    /// the snapshot keeps reserve tranches, which a `SharedCurveState` cannot settle.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The snapshot to restore.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the restored state, or `BondingCurveError::InvalidParameter` if
+    /// the snapshot has tranches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{
+    ///     BondingCurveError, CurveState, LinearBondingCurve, SharedCurveState, TrancheBalances,
+    ///     TranchePolicy,
+    /// };
+    ///
+    /// let state = CurveState::new(LinearBondingCurve::new(100, 1000), 10);
+    /// assert!(SharedCurveState::try_restore(state.snapshot()).is_ok());
+    /// let state = state
+    ///     .with_tranches(TranchePolicy::new(100, 50), TrancheBalances::default())
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     SharedCurveState::try_restore(state.snapshot()).map(|shared| shared.supply()),
+    ///     Err(BondingCurveError::InvalidParameter)
+    /// );
+    /// ```
+    pub fn try_restore(snapshot: CurveSnapshot<C>) -> Result<Self, BondingCurveError> {
+        if snapshot.tranches.is_some() {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        Ok(Self::restore(snapshot))
+    }
+
+    /// Captures the curve and supply at the time of the call.
+    pub fn snapshot(&self) -> CurveSnapshot<C>
+    where
+        C: Clone,
+    {
+        CurveSnapshot {
+            curve: self.curve.clone(),
+            supply: self.supply(),
+            graduation: self.graduation,
+            tranches: None,
+        }
+    }
+
+    /// Returns the curve trades are priced with.
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the graduation of the restored state, if it had graduated.
+    pub fn graduation(&self) -> Option<Graduation> {
+        self.graduation
+    }
+
+    /// Returns the current supply of tokens, which other threads may change at any time.
+    pub fn supply(&self) -> u64 {
+        self.supply.load(Ordering::Acquire)
+    }
+
+    /// Prices a trade against the current supply without applying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TradeResult` the trade would produce at the supply it was
+    /// quoted at, or a `BondingCurveError` if it cannot be applied there.
+    pub fn quote(
+        &self,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        self.quote_at(self.supply(), amount, side)
+    }
+
+    /// Applies a trade, retrying it against the new supply whenever another thread applies a
+    /// trade first.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the applied `TradeResult`, or a `BondingCurveError` if the
+    /// trade cannot be applied at the latest supply, in which case the supply is unchanged.
+    pub fn apply(
+        &self,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        let mut supply = self.supply();
+        loop {
+            let trade = self.quote_at(supply, amount, side)?;
+            match self.supply.compare_exchange_weak(
+                supply,
+                trade.supply,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        from = supply,
+                        to = trade.supply,
+                        amount,
+                        ?side,
+                        cost = trade.cost,
+                        "applied shared trade"
+                    );
+                    return Ok(trade);
+                }
+                Err(current) => supply = current,
+            }
+        }
+    }
+
+    /// Applies a trade only if the supply is still `expected`, such as the supply a quote was
+    /// shown to a user at.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The supply the trade must be applied at.
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `Ok` with the applied `TradeResult`, `Err` with the current
+    /// supply if it is no longer `expected`, or a `BondingCurveError` if the trade cannot be
+    /// applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{LinearBondingCurve, OperationSide, SharedCurveState};
+    ///
+    /// let state = SharedCurveState::new(LinearBondingCurve::new(100, 1000), 0);
+    /// let quote = state.quote(10, OperationSide::Add).unwrap();
+    /// state.buy(1).unwrap();
+    /// // The buy above moved the supply the quote was taken at.
+    /// assert_eq!(state.apply_at(0, quote.amount, quote.side), Ok(Err(1)));
+    /// ```
+    pub fn apply_at(
+        &self,
+        expected: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<Result<TradeResult, u64>, BondingCurveError> {
+        let trade = self.quote_at(expected, amount, side)?;
+        Ok(self
+            .supply
+            .compare_exchange(expected, trade.supply, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| trade))
+    }
+
+    /// Adds `amount` tokens to the supply. See `SharedCurveState::apply`.
+    pub fn buy(&self, amount: u64) -> Result<TradeResult, BondingCurveError> {
+        self.apply(amount, OperationSide::Add)
+    }
+
+    /// Removes `amount` tokens from the supply. See `SharedCurveState::apply`.
+    pub fn sell(&self, amount: u64) -> Result<TradeResult, BondingCurveError> {
+        self.apply(amount, OperationSide::Remove)
+    }
+
+    fn quote_at(
+        &self,
+        supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        if let Some(graduation) = self.graduation {
+            return graduation.quote(supply, amount, side);
+        }
+        let response = QuoteRequest::new(supply, amount, side).quote(&self.curve)?;
+        Ok(TradeResult {
+            side,
+            amount,
+            cost: response.price,
+            supply: response.ending_supply,
+            fee: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::BondingCurve;
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, CurveState, OperationSide,
+        QuadraticBondingCurve, SharedCurveState, TrancheBalances, TranchePolicy,
+    };
+
+    #[test]
+    pub fn test_shared_curve_state_serializes_trades() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let state = SharedCurveState::new(curve, 0);
+        let trades: Vec<_> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..200)
+                            .map(|_| {
+                                assert!(state.quote(1, OperationSide::Add).is_ok());
+                                state.buy(1).unwrap()
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        assert_eq!(state.supply(), 1_600);
+        // Every trade was priced at a distinct supply, as one `CurveState` would have.
+        let mut supplies: Vec<u64> = trades.iter().map(|trade| trade.supply).collect();
+        supplies.sort_unstable();
+        assert_eq!(supplies, (1..=1_600).collect::<Vec<_>>());
+        let mut sequential = CurveState::new(curve, 0);
+        let mut trades = trades;
+        trades.sort_by_key(|trade| trade.supply);
+        for trade in trades {
+            assert_eq!(sequential.buy(1), Ok(trade));
+        }
+        assert_eq!(
+            state.sell(1_600).unwrap().cost,
+            curve
+                .calculate_price_many_checked(1_600, 1_600, OperationSide::Remove)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_shared_curve_state_rejections() {
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let state = SharedCurveState::restore(CurveState::new(curve, 10).snapshot());
+        assert_eq!(state.sell(11), Err(BondingCurveError::SupplyUnderflow));
+        assert_eq!(state.supply(), 10);
+        assert_eq!(state.apply_at(3, 2, OperationSide::Remove), Ok(Err(10)));
+        assert_eq!(
+            state.apply_at(3, 4, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        let trade = state
+            .apply_at(10, 5, OperationSide::Remove)
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.supply, 5);
+        assert_eq!(state.snapshot().supply, 5);
+    }
+
+    #[test]
+    pub fn test_shared_curve_state_try_restore() {
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let mut graduated = CurveState::new(curve, 10);
+        graduated.graduate(true).unwrap();
+        let state = SharedCurveState::try_restore(graduated.snapshot()).unwrap();
+        assert_eq!(state.snapshot(), graduated.snapshot());
+
+        let tranched = CurveState::new(curve, 10)
+            .with_tranches(TranchePolicy::new(100, 50), TrancheBalances::default())
+            .unwrap();
+        assert_eq!(
+            SharedCurveState::try_restore(tranched.snapshot()).map(|state| state.supply()),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        // `restore` keeps accepting it, without the tranches.
+        let state = SharedCurveState::restore(tranched.snapshot());
+        assert_eq!(state.supply(), 10);
+        assert_eq!(state.snapshot().tranches, None);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_shared_curve_state_restores_graduation() {
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let mut graduated = CurveState::new(curve, 10);
+        let graduation = graduated.graduate(true).unwrap();
+        let state = SharedCurveState::restore(graduated.snapshot());
+        assert_eq!(state.graduation(), Some(graduation));
+        assert_eq!(state.buy(1), Err(BondingCurveError::Graduated));
+        assert_eq!(state.supply(), 10);
+        let trade = state.sell(4).unwrap();
+        assert_eq!(trade, graduated.sell(4).unwrap());
+        assert_eq!(trade.cost, 4 * curve.calculate_price(10));
+        assert_eq!(state.snapshot(), graduated.snapshot());
+
+        let mut closed = CurveState::new(curve, 10);
+        closed.graduate(false).unwrap();
+        let state = SharedCurveState::restore(closed.snapshot());
+        assert_eq!(state.sell(1), Err(BondingCurveError::Graduated));
+        assert_eq!(
+            state.apply_at(10, 1, OperationSide::Remove),
+            Err(BondingCurveError::Graduated)
+        );
+    }
+}
@@ -1,4 +1,14 @@
-use super::{BondingCurve, OperationSide};
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    canonical_key, finite, math, supply_range, BondingCurveError,
+    BondingCurveWithCheckedOperations, OperationSide,
+};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
 
 /// Represents a sigmoid bonding curve.
 ///
@@ -9,7 +19,13 @@ use super::{BondingCurve, OperationSide};
 /// * `max_price`: The maximum price that the curve approaches asymptotically.
 /// * `growth`: The growth rate that determines how quickly the price increases.
 /// * `mid_supply`: The supply at which the price is half of the maximum price.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "super::SigmoidConfig", into = "super::SigmoidConfig")
+)]
 pub struct SigmoidBondingCurve {
     pub max_price: f64,
     pub growth: f64,
@@ -43,8 +59,186 @@ impl SigmoidBondingCurve {
             mid_supply,
         }
     }
+
+    /// Creates a new `SigmoidBondingCurve`, validating its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_price` - The maximum price that the curve approaches asymptotically.
+    /// * `growth` - The growth rate that determines how quickly the price increases.
+    /// * `mid_supply` - The supply at which the price is half of the maximum price.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the curve, or the error from `SigmoidBondingCurve::validate`.
+    pub fn try_new(
+        max_price: f64,
+        growth: f64,
+        mid_supply: u64,
+    ) -> Result<Self, BondingCurveError> {
+        let curve = Self::new(max_price, growth, mid_supply);
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    /// Checks that the parameters are supported by the curve's formulas.
+    ///
+    /// # Returns
+    ///
+    /// `BondingCurveError::InvalidParameter` if a parameter is not finite or `max_price` is
+    /// negative, or `BondingCurveError::DivisionByZero` if `growth` is zero, since the
+    /// integral used by `calculate_price_many` divides by it.
+    pub fn validate(&self) -> Result<(), BondingCurveError> {
+        if !self.max_price.is_finite() || !self.growth.is_finite() || self.max_price < 0.0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        if self.growth == 0.0 {
+            return Err(BondingCurveError::DivisionByZero);
+        }
+        Ok(())
+    }
+
+    /// Calculates how many more tokens must be sold to double the price, a simple measure
+    /// of how aggressive the curve is.
+    ///
+    /// Solving `max_price / (1 + e^(-growth * (y - mid_supply))) = 2 * price` gives the
+    /// doubling supply `y = mid_supply - ln(max_price / (2 * price) - 1) / growth`.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_supply` - The supply to measure from.
+    ///
+    /// # Returns
+    ///
+    /// The fractional number of tokens, or `None` if the price is not positive, or already at
+    /// half of `max_price` or above, so that it never doubles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::SigmoidBondingCurve;
+    ///
+    /// let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
+    /// assert!(curve.supply_to_double_price(500).is_none());
+    /// let tokens = curve.supply_to_double_price(0).unwrap();
+    /// assert!(tokens > 60.0 && tokens < 80.0);
+    /// ```
+    pub fn supply_to_double_price(&self, from_supply: u64) -> Option<f64> {
+        let price = self.calculate_price_f(from_supply as f64);
+        if !(price > 0.0 && self.growth > 0.0 && 2.0 * price < self.max_price) {
+            return None;
+        }
+        let target =
+            self.mid_supply as f64 - math::ln(self.max_price / (2.0 * price) - 1.0) / self.growth;
+        Some(target - from_supply as f64)
+    }
+
+    /// Calculates the price at a fractional supply, for continuous-token models.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens, in fractional units.
+    ///
+    /// # Returns
+    ///
+    /// The price at `supply`, equal to `calculate_price` at whole supplies.
+    pub fn calculate_price_f(&self, supply: f64) -> f64 {
+        self.max_price / (1.0 + math::exp(-self.growth * (supply - self.mid_supply as f64)))
+    }
+
+    /// Calculates the price for a fractional amount of tokens from a fractional supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens, in fractional units.
+    /// * `amount` - The amount of tokens to calculate the price for, in fractional units.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price, equal to `calculate_price_many` at whole supplies.
+    pub fn calculate_price_many_f(
+        &self,
+        starting_supply: f64,
+        amount: f64,
+        side: OperationSide,
+    ) -> f64 {
+        if amount == 0.0 {
+            return 0.0;
+        }
+
+        // Get the bounds based on whether it's an Add or Remove operation
+        let (start_supply, end_supply) = match side {
+            OperationSide::Add => (starting_supply, starting_supply + amount), // Buying (adding supply)
+            OperationSide::Remove => (starting_supply - amount, starting_supply), // Selling (removing supply)
+        };
+
+        // Perform the integral of the sigmoid function over the range
+        let price_at_end_supply = self.term(end_supply);
+        let price_at_start_supply = self.term(start_supply);
+
+        // Total price is the difference in the integral values
+        (self.max_price / self.growth) * (price_at_end_supply - price_at_start_supply)
+    }
+}
+
+impl SigmoidBondingCurve {
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64, u64) {
+        (
+            canonical_key(self.max_price),
+            canonical_key(self.growth),
+            self.mid_supply,
+        )
+    }
+
+    /// Returns the antiderivative term `ln(1 + e^(growth * (x - mid_supply)))` at `x`.
+    fn term(&self, x: f64) -> f64 {
+        math::ln(1.0 + math::exp(self.growth * (x - self.mid_supply as f64)))
+    }
+}
+
+impl PartialEq for SigmoidBondingCurve {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SigmoidBondingCurve {}
+
+impl Hash for SigmoidBondingCurve {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for SigmoidBondingCurve {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SigmoidBondingCurve {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
+impl Display for SigmoidBondingCurve {
+    /// Formats the curve as its instantiated formula, such as `f(x) = 1000 / (1 + e^(-0.01(x - 500)))`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "f(x) = {} / (1 + e^(-{}(x - {})))",
+            self.max_price, self.growth, self.mid_supply
+        )
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
 impl BondingCurve<f64> for SigmoidBondingCurve {
     /// Calculates the price based on the supply.
     ///
@@ -62,8 +256,7 @@ impl BondingCurve<f64> for SigmoidBondingCurve {
     ///
     /// The price of the token based on the supply.
     fn calculate_price(&self, supply: u64) -> f64 {
-        let s = supply as f64;
-        self.max_price / (1.0 + (-self.growth * (s - self.mid_supply as f64)).exp())
+        self.calculate_price_f(supply as f64)
     }
 
     /// Calculates the price for a given amount of tokens.
@@ -85,34 +278,189 @@ impl BondingCurve<f64> for SigmoidBondingCurve {
     ///
     /// The total price for the given amount of tokens.
     fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f64 {
-        let s = starting_supply as f64;
-        let n = amount as f64;
-        let mid_supply = self.mid_supply as f64;
-        let growth = self.growth;
-        let max_price = self.max_price;
+        self.calculate_price_many_f(starting_supply as f64, amount as f64, side)
+    }
+}
 
-        // Get the bounds based on whether it's an Add or Remove operation
-        let (start_supply, end_supply) = match side {
-            OperationSide::Add => (s, s + n),    // Buying (adding supply)
-            OperationSide::Remove => (s - n, s), // Selling (removing supply)
+impl BondingCurveWithCheckedOperations<f64> for SigmoidBondingCurve {
+    /// Calculates the price based on the supply, validating the curve parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the price, the error from `SigmoidBondingCurve::validate`, or
+    /// `BondingCurveError::Overflow` if the price is not finite.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_checked(&self, supply: u64) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        finite(self.calculate_price_f(supply as f64))
+    }
+
+    /// Calculates the price for a given amount of tokens, validating the curve parameters
+    /// and the supply range.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The current supply of tokens.
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, the error from `SigmoidBondingCurve::validate`,
+    /// `BondingCurveError::SupplyUnderflow` if more than `starting_supply` tokens are
+    /// removed, or `BondingCurveError::Overflow` if the price is not finite.
+    #[cfg_attr(feature = "panic-free", no_panic::no_panic)]
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<f64, BondingCurveError> {
+        self.validate()?;
+        if supply_range(starting_supply, amount, side)?.is_none() {
+            return Ok(0.0);
+        }
+        finite(self.calculate_price_many_f(starting_supply as f64, amount as f64, side))
+    }
+}
+
+/// Quotes a `SigmoidBondingCurve` repeatedly from a moving supply, reusing work between quotes.
+///
+/// `calculate_price_many` evaluates `ln(1 + e^(growth * (x - mid_supply)))` at both ends of
+/// the range. The quoter keeps that term for its current supply, so each quote only evaluates
+/// the far end, and `apply` carries the far end over as the new current term. This halves the
+/// `exp` and `ln` calls per quote while returning exactly what `calculate_price_many` does.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurve, OperationSide, SigmoidBondingCurve, SigmoidQuoter};
+///
+/// let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
+/// let mut quoter = SigmoidQuoter::new(curve, 480);
+/// let price = quoter.quote(10, OperationSide::Add);
+/// assert_eq!(price, curve.calculate_price_many(480, 10, OperationSide::Add));
+/// assert_eq!(quoter.apply(10, OperationSide::Add), Ok(price));
+/// assert_eq!(quoter.supply(), 490);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SigmoidQuoter {
+    curve: SigmoidBondingCurve,
+    supply: u64,
+    term: f64,
+}
+
+impl SigmoidQuoter {
+    /// Creates a new `SigmoidQuoter` at the given supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve to quote.
+    /// * `supply` - The current supply.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SigmoidQuoter`.
+    pub fn new(curve: SigmoidBondingCurve, supply: u64) -> Self {
+        Self {
+            curve,
+            supply,
+            term: curve.term(supply as f64),
+        }
+    }
+
+    /// Returns the quoted curve.
+    pub fn curve(&self) -> &SigmoidBondingCurve {
+        &self.curve
+    }
+
+    /// Returns the current supply.
+    pub fn supply(&self) -> u64 {
+        self.supply
+    }
+
+    /// Calculates the price for a given amount of tokens from the current supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount of tokens to calculate the price for.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// The total price, equal to `calculate_price_many` at the current supply.
+    pub fn quote(&self, amount: u64, side: OperationSide) -> f64 {
+        if amount == 0 {
+            return 0.0;
+        }
+        self.price_to(self.far_term(amount, side), side)
+    }
+
+    /// Trades a given amount of tokens, moving the current supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount of tokens to add or remove.
+    /// * `side` - The side of the operation (add or remove).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, `BondingCurveError::SupplyUnderflow` if more
+    /// than the current supply is removed, or `BondingCurveError::Overflow` if the supply
+    /// would pass `u64::MAX`.
+    pub fn apply(&mut self, amount: u64, side: OperationSide) -> Result<f64, BondingCurveError> {
+        let supply = match side {
+            OperationSide::Add => self
+                .supply
+                .checked_add(amount)
+                .ok_or(BondingCurveError::Overflow)?,
+            OperationSide::Remove => self
+                .supply
+                .checked_sub(amount)
+                .ok_or(BondingCurveError::SupplyUnderflow)?,
         };
+        if amount == 0 {
+            return Ok(0.0);
+        }
+        let term = self.far_term(amount, side);
+        let price = self.price_to(term, side);
+        self.supply = supply;
+        self.term = term;
+        Ok(price)
+    }
 
-        // Perform the integral of the sigmoid function over the range
-        let price_at_end_supply = (1.0 + (growth * (end_supply - mid_supply)).exp()).ln();
-        let price_at_start_supply = (1.0 + (growth * (start_supply - mid_supply)).exp()).ln();
+    /// Returns the term at the end of the range that does not hold the current supply.
+    fn far_term(&self, amount: u64, side: OperationSide) -> f64 {
+        let (s, n) = (self.supply as f64, amount as f64);
+        self.curve.term(match side {
+            OperationSide::Add => s + n,
+            OperationSide::Remove => s - n,
+        })
+    }
 
-        // Total price is the difference in the integral values
-        (max_price / growth) * (price_at_end_supply - price_at_start_supply)
+    fn price_to(&self, far: f64, side: OperationSide) -> f64 {
+        let (start, end) = match side {
+            OperationSide::Add => (self.term, far),
+            OperationSide::Remove => (far, self.term),
+        };
+        (self.curve.max_price / self.curve.growth) * (end - start)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::SigmoidBondingCurve;
+    #[cfg(not(feature = "panic-free"))]
     use crate::{
-        fixed_point_to_float, float_to_fixed_point, BondingCurve, OperationSide,
-        SigmoidBondingCurve,
+        fixed_point_to_float, float_to_fixed_point, BondingCurve, BondingCurveError, OperationSide,
+        SigmoidQuoter,
     };
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_sigmoid_price_calculus() {
         let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
@@ -120,6 +468,7 @@ mod test {
         assert_eq!(price, 45.016600268752214);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_sigmoid_price_calculus_fixed_point() {
         let curve = crate::SigmoidBondingCurve::new(
@@ -131,15 +480,116 @@ mod test {
         assert_eq!(float_to_fixed_point(price, 9), 45_016_600_268);
     }
 
+    #[cfg(not(feature = "panic-free"))]
     #[test]
     pub fn test_sigmoid_price_calculus_many() {
         let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
         let many_price_add = curve.calculate_price_many(480, 10, OperationSide::Add);
         assert_eq!(many_price_add, 462.5779069197911, "Add price is wrong");
         let many_price_remove = curve.calculate_price_many(480, 10, OperationSide::Remove);
+        // `libm` rounds the last bit of this one differently from the platform's math library.
+        let expected_remove = if cfg!(feature = "soft-float") {
+            437.8362491306464
+        } else {
+            437.83624913064756
+        };
+        assert_eq!(many_price_remove, expected_remove, "Remove price is wrong");
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_sigmoid_price_many_zero_and_full_exit() {
+        let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            assert_eq!(curve.calculate_price_many(0, 0, side), 0.0);
+            assert_eq!(curve.calculate_price_many(10, 0, side), 0.0);
+        }
+        let full_exit = curve.calculate_price_many(100, 100, OperationSide::Remove);
+        assert!(full_exit.is_finite() && full_exit > 0.0);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_sigmoid_quoter_matches_curve() {
+        let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
+        let mut quoter = SigmoidQuoter::new(curve, 480);
+        let trades = [
+            (10, OperationSide::Add),
+            (35, OperationSide::Add),
+            (0, OperationSide::Remove),
+            (300, OperationSide::Remove),
+            (1, OperationSide::Add),
+        ];
+        for (amount, side) in trades {
+            let supply = quoter.supply();
+            let expected = curve.calculate_price_many(supply, amount, side);
+            assert_eq!(quoter.quote(amount, side), expected);
+            assert_eq!(quoter.apply(amount, side), Ok(expected));
+            assert_eq!(quoter, SigmoidQuoter::new(curve, quoter.supply()));
+        }
+        assert_eq!(quoter.supply(), 226);
         assert_eq!(
-            many_price_remove, 437.83624913064756,
-            "Remove price is wrong"
+            quoter.apply(227, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
         );
+        assert_eq!(quoter.supply(), 226);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_sigmoid_fractional_supply() {
+        let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
+        assert_eq!(curve.calculate_price_f(500.0), 50.0);
+        let between = curve.calculate_price_f(480.5);
+        assert!(between > curve.calculate_price(480) && between < curve.calculate_price(481));
+        let split = curve.calculate_price_many_f(480.0, 0.25, OperationSide::Add)
+            + curve.calculate_price_many_f(480.25, 9.75, OperationSide::Add);
+        assert!(crate::relative_eq(
+            split,
+            curve.calculate_price_many(480, 10, OperationSide::Add),
+            1e-12
+        ));
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_sigmoid_supply_to_double_price() {
+        let curve = SigmoidBondingCurve::new(100.0, 0.01, 500);
+        for supply in [0, 100, 400] {
+            let tokens = curve.supply_to_double_price(supply).unwrap();
+            let doubled = curve.calculate_price_f(supply as f64 + tokens);
+            assert!(crate::relative_eq(
+                doubled,
+                2.0 * curve.calculate_price(supply),
+                1e-12
+            ));
+        }
+        assert_eq!(curve.supply_to_double_price(500), None);
+        assert_eq!(curve.supply_to_double_price(800), None);
+    }
+
+    #[test]
+    pub fn test_sigmoid_display() {
+        assert_eq!(
+            SigmoidBondingCurve::new(1000.0, 0.01, 500).to_string(),
+            "f(x) = 1000 / (1 + e^(-0.01(x - 500)))"
+        );
+    }
+
+    #[test]
+    pub fn test_sigmoid_curves_as_keys() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let curves = [
+            SigmoidBondingCurve::new(100.0, 0.01, 500),
+            SigmoidBondingCurve::new(100.0, -0.0, 500),
+            SigmoidBondingCurve::new(100.0, 0.0, 500),
+            SigmoidBondingCurve::new(f64::NAN, 0.01, 500),
+            SigmoidBondingCurve::new(f64::NAN, 0.01, 500),
+            SigmoidBondingCurve::new(100.0, 0.01, 400),
+        ];
+        assert_eq!(curves.iter().collect::<HashSet<_>>().len(), 4);
+        let sorted: Vec<_> = curves.iter().collect::<BTreeSet<_>>().into_iter().collect();
+        assert_eq!(sorted, [&curves[2], &curves[5], &curves[0], &curves[3]]);
     }
 }
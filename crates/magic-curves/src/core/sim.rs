@@ -0,0 +1,507 @@
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, CurveState, OperationSide, TradeObserver,
+};
+
+/// A participant in a `Simulation` that decides one trade per step.
+///
+/// Any `FnMut(u64, u64) -> Option<(OperationSide, u64)>` closure taking the step and the
+/// current supply is an agent.
+pub trait Agent {
+    /// Decides the agent's trade at `step`.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The index of the current step, starting at 0.
+    /// * `supply` - The supply when the agent acts.
+    ///
+    /// # Returns
+    ///
+    /// The side and amount of the trade, or `None` to sit the step out.
+    fn act(&mut self, step: u64, supply: u64) -> Option<(OperationSide, u64)>;
+
+    /// Reseeds any randomness of the agent. It is called by a seeded `Simulation` with a
+    /// seed derived from the simulation's, and does nothing by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The agent's seed.
+    fn reseed(&mut self, seed: u64) {
+        let _ = seed;
+    }
+}
+
+impl<F: FnMut(u64, u64) -> Option<(OperationSide, u64)>> Agent for F {
+    fn act(&mut self, step: u64, supply: u64) -> Option<(OperationSide, u64)> {
+        self(step, supply)
+    }
+}
+
+/// Represents one trade attempted during a `Simulation`, as a flat record that serializes to
+/// a single JSON Lines row.
+///
+/// # Fields
+///
+/// * `step`: The step the trade was attempted at.
+/// * `agent`: The index of the agent that attempted it, in the order agents were added.
+/// * `side`: Whether tokens were added or removed.
+/// * `amount`: The number of tokens traded.
+/// * `cost`: The total price of the trade, or 0 if it was rejected.
+/// * `supply`: The supply after the trade, which a rejected trade leaves unchanged.
+/// * `error`: The `BondingCurveError` the trade was rejected with, if any.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationStep {
+    pub step: u64,
+    pub agent: usize,
+    pub side: OperationSide,
+    pub amount: u64,
+    pub cost: u64,
+    pub supply: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<BondingCurveError>,
+}
+
+/// Represents a set of agents trading against a `CurveState`, one round per step.
+///
+/// Every step, each agent acts once in the order it was added, against the supply left by
+/// the agents before it. Rejected trades leave the state untouched and are recorded with
+/// their error, so the simulation keeps going.
+///
+/// With the `rand` feature, `Simulation::seeded` makes a run reproducible: every agent is
+/// reseeded from the simulation's seed as it is added, and each step the agents act in an
+/// order shuffled by the same seeded `StdRng`. Re-running a scenario with the same seed then
+/// produces the same trajectory, record for record.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{CurveState, LinearBondingCurve, OperationSide, Simulation};
+///
+/// let mut simulation = Simulation::new(CurveState::new(LinearBondingCurve::new(100, 1000), 0))
+///     .with_agent(|_step, _supply| Some((OperationSide::Add, 10)))
+///     .with_agent(|_step, supply| Some((OperationSide::Remove, supply / 2)));
+/// let steps = simulation.run(3);
+/// assert_eq!(steps.len(), 6);
+/// assert_eq!(steps[1].supply, 5);
+/// assert_eq!(simulation.state().supply(), steps[5].supply);
+/// ```
+pub struct Simulation<C, O = ()> {
+    state: CurveState<C, O>,
+    agents: Vec<Box<dyn Agent>>,
+    step: u64,
+    #[cfg(feature = "rand")]
+    rng: Option<StdRng>,
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>, O: TradeObserver> Simulation<C, O> {
+    /// Creates a new `Simulation` without agents.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state the agents trade against.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Simulation`.
+    pub fn new(state: CurveState<C, O>) -> Self {
+        Self {
+            state,
+            agents: Vec::new(),
+            step: 0,
+            #[cfg(feature = "rand")]
+            rng: None,
+        }
+    }
+
+    /// Creates a new `Simulation` without agents, whose agents and scheduling are seeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state the agents trade against.
+    /// * `seed` - The seed of the run.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Simulation`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{CurveState, LinearBondingCurve, RandomTrader, Simulation};
+    ///
+    /// let run = |seed| {
+    ///     Simulation::seeded(CurveState::new(LinearBondingCurve::new(100, 1000), 0), seed)
+    ///         .with_agent(RandomTrader::new(50))
+    ///         .with_agent(RandomTrader::new(20))
+    ///         .run(100)
+    /// };
+    /// assert_eq!(run(7), run(7));
+    /// assert_ne!(run(7), run(8));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn seeded(state: CurveState<C, O>, seed: u64) -> Self {
+        Self {
+            rng: Some(StdRng::seed_from_u64(seed)),
+            ..Self::new(state)
+        }
+    }
+
+    /// Adds an agent, which acts after every agent added before it, or in the shuffled order
+    /// of a seeded simulation, which also reseeds the agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - The agent to add.
+    ///
+    /// # Returns
+    ///
+    /// The same simulation, with the agent.
+    pub fn with_agent(mut self, agent: impl Agent + 'static) -> Self {
+        #[allow(unused_mut)]
+        let mut agent = agent;
+        #[cfg(feature = "rand")]
+        if let Some(rng) = &mut self.rng {
+            agent.reseed(rng.next_u64());
+        }
+        self.agents.push(Box::new(agent));
+        self
+    }
+
+    /// Returns the state the agents trade against.
+    pub fn state(&self) -> &CurveState<C, O> {
+        &self.state
+    }
+
+    /// Returns the index of the next step.
+    pub fn current_step(&self) -> u64 {
+        self.step
+    }
+
+    /// Runs one step, in which every agent acts once.
+    ///
+    /// # Returns
+    ///
+    /// The `SimulationStep` of every trade attempted, in the order they were attempted.
+    pub fn step(&mut self) -> Vec<SimulationStep> {
+        let step = self.step;
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("simulation_step", step, agents = self.agents.len()).entered();
+        #[allow(unused_mut)]
+        let mut order: Vec<usize> = (0..self.agents.len()).collect();
+        #[cfg(feature = "rand")]
+        if let Some(rng) = &mut self.rng {
+            // Fisher-Yates, so the order only depends on the seed.
+            for i in (1..order.len()).rev() {
+                order.swap(i, rng.random_range(0..=i));
+            }
+        }
+        let mut records = Vec::with_capacity(self.agents.len());
+        for agent in order {
+            let Some((side, amount)) = self.agents[agent].act(step, self.state.supply()) else {
+                continue;
+            };
+            let record = match self.state.apply(amount, side) {
+                Ok(trade) => SimulationStep {
+                    step,
+                    agent,
+                    side,
+                    amount,
+                    cost: trade.cost,
+                    supply: trade.supply,
+                    error: None,
+                },
+                Err(error) => SimulationStep {
+                    step,
+                    agent,
+                    side,
+                    amount,
+                    cost: 0,
+                    supply: self.state.supply(),
+                    error: Some(error),
+                },
+            };
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                agent,
+                amount,
+                ?side,
+                supply = record.supply,
+                cost = record.cost,
+                rejected = record.error.is_some(),
+                "simulated trade"
+            );
+            records.push(record);
+        }
+        self.step += 1;
+        records
+    }
+
+    /// Runs `steps` steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The number of steps to run.
+    ///
+    /// # Returns
+    ///
+    /// The `SimulationStep` of every trade attempted, in the order they were attempted.
+    pub fn run(&mut self, steps: u64) -> Vec<SimulationStep> {
+        (0..steps).flat_map(|_| self.step()).collect()
+    }
+}
+
+/// Represents an agent that buys or sells a random amount every step.
+///
+/// Each step it buys or sells with equal odds, between 1 and `max_amount` tokens, never
+/// selling more than the supply. Its `StdRng` starts from seed 0, and a seeded `Simulation`
+/// reseeds it.
+///
+/// # Fields
+///
+/// * `max_amount`: The largest amount the agent trades in a step.
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct RandomTrader {
+    pub max_amount: u64,
+    rng: StdRng,
+}
+
+#[cfg(feature = "rand")]
+impl RandomTrader {
+    /// Creates a new `RandomTrader`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_amount` - The largest amount the agent trades in a step.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `RandomTrader`.
+    pub fn new(max_amount: u64) -> Self {
+        Self {
+            max_amount,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Agent for RandomTrader {
+    fn act(&mut self, _step: u64, supply: u64) -> Option<(OperationSide, u64)> {
+        if self.max_amount == 0 {
+            return None;
+        }
+        let amount = self.rng.random_range(1..=self.max_amount);
+        if self.rng.random_bool(0.5) {
+            Some((OperationSide::Add, amount))
+        } else {
+            Some((OperationSide::Remove, amount.min(supply))).filter(|&(_, amount)| amount > 0)
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+/// Writes records as JSON Lines, one JSON object per line, so simulation results load
+/// straight into tools such as pandas or DuckDB.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the lines.
+/// * `records` - The records to write, such as the `SimulationStep`s of a run.
+///
+/// # Returns
+///
+/// An `io::Result` that fails if a record cannot be serialized or written.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{write_jsonl, CurveState, LinearBondingCurve, OperationSide, Simulation};
+///
+/// let mut simulation = Simulation::new(CurveState::new(LinearBondingCurve::new(100, 1000), 0))
+///     .with_agent(|_step, _supply| Some((OperationSide::Add, 10)));
+/// let mut jsonl = Vec::new();
+/// write_jsonl(&mut jsonl, &simulation.run(2)).unwrap();
+/// assert_eq!(
+///     String::from_utf8(jsonl).unwrap(),
+///     "{\"step\":0,\"agent\":0,\"side\":\"add\",\"amount\":10,\"cost\":14500,\"supply\":10}\n\
+///      {\"step\":1,\"agent\":0,\"side\":\"add\",\"amount\":10,\"cost\":24500,\"supply\":20}\n"
+/// );
+/// ```
+#[cfg(feature = "jsonl")]
+pub fn write_jsonl<W, T>(mut writer: W, records: impl IntoIterator<Item = T>) -> std::io::Result<()>
+where
+    W: std::io::Write,
+    T: serde::Serialize,
+{
+    for record in records {
+        serde_json::to_writer(&mut writer, &record)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, CurveState, OperationSide, QuadraticBondingCurve, Simulation,
+        SimulationStep,
+    };
+
+    #[test]
+    pub fn test_simulation_records_every_trade() {
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let mut simulation = Simulation::new(CurveState::new(curve, 0))
+            .with_agent(|step, _supply| (step % 2 == 0).then_some((OperationSide::Add, 4)))
+            .with_agent(|_step, _supply| Some((OperationSide::Remove, 3)));
+        let steps = simulation.run(3);
+        let mut state = CurveState::new(curve, 0);
+        let buy = state.buy(4).unwrap();
+        let sell = state.sell(3).unwrap();
+        assert_eq!(
+            steps[..2],
+            [
+                SimulationStep {
+                    step: 0,
+                    agent: 0,
+                    side: OperationSide::Add,
+                    amount: 4,
+                    cost: buy.cost,
+                    supply: 4,
+                    error: None,
+                },
+                SimulationStep {
+                    step: 0,
+                    agent: 1,
+                    side: OperationSide::Remove,
+                    amount: 3,
+                    cost: sell.cost,
+                    supply: 1,
+                    error: None,
+                },
+            ]
+        );
+        // The first agent sits out step 1, so the sale of 3 from a supply of 1 is rejected.
+        assert_eq!(steps[2].step, 1);
+        assert_eq!(steps[2].error, Some(BondingCurveError::SupplyUnderflow));
+        assert_eq!((steps[2].cost, steps[2].supply), (0, 1));
+        assert_eq!(steps.len(), 5);
+        assert_eq!(simulation.current_step(), 3);
+        assert_eq!(simulation.state().supply(), 2);
+    }
+
+    #[cfg(feature = "jsonl")]
+    #[test]
+    pub fn test_write_jsonl() {
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let mut simulation = Simulation::new(CurveState::new(curve, 0))
+            .with_agent(|_step, _supply| Some((OperationSide::Remove, 1)));
+        let steps = simulation.run(2);
+        let mut jsonl = Vec::new();
+        crate::write_jsonl(&mut jsonl, &steps).unwrap();
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "{\"step\":0,\"agent\":0,\"side\":\"remove\",\"amount\":1,\"cost\":0,\"supply\":0,\
+             \"error\":\"SupplyUnderflow\"}"
+        );
+        let parsed: Vec<SimulationStep> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(parsed, steps);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    pub fn test_seeded_simulation_is_reproducible() {
+        use crate::RandomTrader;
+
+        let curve = QuadraticBondingCurve::new(1, 0, 500);
+        let run = |seed| {
+            Simulation::seeded(CurveState::new(curve, 1_000), seed)
+                .with_agent(RandomTrader::new(40))
+                .with_agent(RandomTrader::new(40))
+                .with_agent(|_step, _supply| Some((OperationSide::Add, 1)))
+                .run(200)
+        };
+        let first = run(42);
+        // Byte-identical, not just equal, so the run can be published and diffed.
+        assert_eq!(format!("{first:?}"), format!("{:?}", run(42)));
+        assert_ne!(first, run(43));
+        // The shuffled schedule changes who trades first, but every agent acts every step.
+        let agents: Vec<usize> = first
+            .iter()
+            .take_while(|s| s.step == 0)
+            .map(|s| s.agent)
+            .collect();
+        assert!(agents.contains(&2));
+        assert!((0..200).any(|step| first
+            .iter()
+            .find(|record| record.step == step)
+            .is_some_and(|record| record.agent != 0)));
+
+        #[cfg(feature = "jsonl")]
+        {
+            let jsonl = |steps: &[SimulationStep]| {
+                let mut bytes = Vec::new();
+                crate::write_jsonl(&mut bytes, steps).unwrap();
+                bytes
+            };
+            assert_eq!(jsonl(&first), jsonl(&run(42)));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    pub fn test_simulation_traces_steps() {
+        use std::sync::{Arc, Mutex};
+        use tracing::{span, Event, Metadata, Subscriber};
+
+        struct Names(Arc<Mutex<Vec<&'static str>>>);
+
+        impl Subscriber for Names {
+            fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+                metadata.target() == "magic_curves::core::sim"
+            }
+            fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+                self.0.lock().unwrap().push(attributes.metadata().name());
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event<'_>) {
+                self.0.lock().unwrap().push(event.metadata().name());
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let mut simulation =
+            Simulation::new(CurveState::new(QuadraticBondingCurve::new(1, 0, 500), 0))
+                .with_agent(|_step, _supply| Some((OperationSide::Add, 4)))
+                .with_agent(|_step, _supply| None)
+                .with_agent(|_step, _supply| Some((OperationSide::Remove, 100)));
+        tracing::subscriber::with_default(Names(names.clone()), || simulation.run(2));
+        // One span per step, and one event per attempted trade, rejected or not.
+        let names = names.lock().unwrap();
+        assert_eq!(names.len(), 6);
+        assert!(names
+            .iter()
+            .step_by(3)
+            .all(|name| *name == "simulation_step"));
+    }
+}
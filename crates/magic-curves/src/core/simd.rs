@@ -0,0 +1,250 @@
+#[cfg(feature = "simd")]
+use std::simd::u64x4;
+
+#[cfg(all(feature = "simd", feature = "float", not(feature = "soft-float")))]
+use std::simd::{cmp::SimdPartialEq, f64x4, num::SimdUint, Select, StdFloat};
+
+#[cfg(feature = "simd")]
+use super::BondingCurveWithCheckedOperations;
+use super::{BondingCurve, LinearBondingCurve, QuadraticBondingCurve};
+
+#[cfg(feature = "float")]
+use super::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+/// Prices many supplies at once, using SIMD lanes when the `simd` feature is enabled.
+///
+/// The `simd` feature relies on `std::simd` and needs a nightly toolchain. Without it, the
+/// prices are computed one by one, so the same code builds on stable. The float curves also
+/// price one by one under `soft-float`, since vector `exp` and `ln` are not bit-reproducible.
+pub trait SimdBondingCurve<T>: BondingCurve<T> {
+    /// Calculates the price at each of the given supplies.
+    ///
+    /// Integer curves return exactly what `calculate_price` does, including its overflow
+    /// behavior. Float curves may differ from `calculate_price` by floating point rounding.
+    ///
+    /// # Arguments
+    ///
+    /// * `supplies` - The supplies to price.
+    ///
+    /// # Returns
+    ///
+    /// The price at each supply, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{LinearBondingCurve, SimdBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// assert_eq!(curve.calculate_prices_simd(&[0, 1, 2]), vec![1000, 1100, 1200]);
+    /// ```
+    fn calculate_prices_simd(&self, supplies: &[u64]) -> Vec<T>;
+}
+
+#[cfg(feature = "simd")]
+const LANES: usize = 4;
+
+/// Prices full chunks with `vector` and the remainder with `scalar`. `vector` returns `None`
+/// when a chunk must be priced with `scalar` instead.
+#[cfg(feature = "simd")]
+fn price_lanes<T: Copy>(
+    supplies: &[u64],
+    scalar: impl Fn(u64) -> T,
+    vector: impl Fn(u64x4) -> Option<[T; LANES]>,
+) -> Vec<T> {
+    let mut prices = Vec::with_capacity(supplies.len());
+    let chunks = supplies.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        match vector(u64x4::from_slice(chunk)) {
+            Some(lanes) => prices.extend_from_slice(&lanes),
+            None => prices.extend(chunk.iter().map(|&supply| scalar(supply))),
+        }
+    }
+    prices.extend(remainder.iter().map(|&supply| scalar(supply)));
+    prices
+}
+
+/// Returns whether a curve whose every intermediate grows with the supply prices all of
+/// `supplies` without overflow, which holds when it prices the largest of them. Batches that
+/// do not fit are priced with `calculate_price`, to keep its overflow behavior.
+#[cfg(feature = "simd")]
+fn fits_up_to_max(curve: &impl BondingCurveWithCheckedOperations<u64>, supplies: &[u64]) -> bool {
+    supplies
+        .iter()
+        .max()
+        .is_none_or(|&max| curve.calculate_price_checked(max).is_ok())
+}
+
+impl SimdBondingCurve<u64> for LinearBondingCurve {
+    fn calculate_prices_simd(&self, supplies: &[u64]) -> Vec<u64> {
+        #[cfg(feature = "simd")]
+        {
+            let fits = fits_up_to_max(self, supplies);
+            let linear = u64x4::splat(self.linear);
+            let base = u64x4::splat(self.base);
+            price_lanes(
+                supplies,
+                |supply| self.calculate_price(supply),
+                |x| fits.then(|| (linear * x + base).to_array()),
+            )
+        }
+        #[cfg(not(feature = "simd"))]
+        supplies
+            .iter()
+            .map(|&supply| self.calculate_price(supply))
+            .collect()
+    }
+}
+
+impl SimdBondingCurve<u64> for QuadraticBondingCurve {
+    fn calculate_prices_simd(&self, supplies: &[u64]) -> Vec<u64> {
+        #[cfg(feature = "simd")]
+        {
+            let fits = fits_up_to_max(self, supplies);
+            let quadratic = u64x4::splat(self.quadratic);
+            let linear = u64x4::splat(self.linear);
+            let base = u64x4::splat(self.base);
+            price_lanes(
+                supplies,
+                |supply| self.calculate_price(supply),
+                |x| fits.then(|| (quadratic * x * x + linear * x + base).to_array()),
+            )
+        }
+        #[cfg(not(feature = "simd"))]
+        supplies
+            .iter()
+            .map(|&supply| self.calculate_price(supply))
+            .collect()
+    }
+}
+
+#[cfg(feature = "float")]
+impl SimdBondingCurve<f64> for ExponentialBondingCurve {
+    fn calculate_prices_simd(&self, supplies: &[u64]) -> Vec<f64> {
+        #[cfg(all(feature = "simd", not(feature = "soft-float")))]
+        {
+            let base = f64x4::splat(self.base);
+            let growth = f64x4::splat(self.growth);
+            price_lanes(
+                supplies,
+                |supply| self.calculate_price(supply),
+                |x| Some((base * (growth * x.cast::<f64>()).exp()).to_array()),
+            )
+        }
+        #[cfg(any(not(feature = "simd"), feature = "soft-float"))]
+        supplies
+            .iter()
+            .map(|&supply| self.calculate_price(supply))
+            .collect()
+    }
+}
+
+#[cfg(feature = "float")]
+impl SimdBondingCurve<f64> for LogarithmicBondingCurve {
+    fn calculate_prices_simd(&self, supplies: &[u64]) -> Vec<f64> {
+        #[cfg(all(feature = "simd", not(feature = "soft-float")))]
+        {
+            let base = f64x4::splat(self.base);
+            let growth = f64x4::splat(self.growth);
+            price_lanes(
+                supplies,
+                |supply| self.calculate_price(supply),
+                |x| {
+                    // The log of 0 is undefined, so those lanes take the base price.
+                    let x = x.cast::<f64>();
+                    let prices = growth * x.ln() + base;
+                    Some(x.simd_eq(f64x4::splat(0.0)).select(base, prices).to_array())
+                },
+            )
+        }
+        #[cfg(any(not(feature = "simd"), feature = "soft-float"))]
+        supplies
+            .iter()
+            .map(|&supply| self.calculate_price(supply))
+            .collect()
+    }
+}
+
+#[cfg(feature = "float")]
+impl SimdBondingCurve<f64> for SigmoidBondingCurve {
+    fn calculate_prices_simd(&self, supplies: &[u64]) -> Vec<f64> {
+        #[cfg(all(feature = "simd", not(feature = "soft-float")))]
+        {
+            let max_price = f64x4::splat(self.max_price);
+            let growth = f64x4::splat(self.growth);
+            let mid_supply = f64x4::splat(self.mid_supply as f64);
+            let one = f64x4::splat(1.0);
+            price_lanes(
+                supplies,
+                |supply| self.calculate_price(supply),
+                |x| {
+                    let exponent = -growth * (x.cast::<f64>() - mid_supply);
+                    Some((max_price / (one + exponent.exp())).to_array())
+                },
+            )
+        }
+        #[cfg(any(not(feature = "simd"), feature = "soft-float"))]
+        supplies
+            .iter()
+            .map(|&supply| self.calculate_price(supply))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BondingCurve, LinearBondingCurve, QuadraticBondingCurve, SimdBondingCurve};
+
+    #[test]
+    pub fn test_integer_prices_simd() {
+        let supplies: Vec<u64> = (0..11).map(|x| x * 1_000).collect();
+        let linear = LinearBondingCurve::new(100, 1000);
+        let quadratic = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        assert_eq!(
+            linear.calculate_prices_simd(&supplies),
+            supplies
+                .iter()
+                .map(|&x| linear.calculate_price(x))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            quadratic.calculate_prices_simd(&supplies),
+            supplies
+                .iter()
+                .map(|&x| quadratic.calculate_price(x))
+                .collect::<Vec<_>>()
+        );
+        assert!(linear.calculate_prices_simd(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic)]
+    pub fn test_integer_prices_simd_overflow() {
+        // Only one supply overflows, and it overflows just like `calculate_price`.
+        let curve = LinearBondingCurve::new(2, 0);
+        let supplies = [0, 1, 2, 3, u64::MAX / 2, u64::MAX / 2 + 1, 4, 5];
+        let expected: Vec<u64> = supplies.iter().map(|&x| x.wrapping_mul(2)).collect();
+        assert_eq!(curve.calculate_prices_simd(&supplies), expected);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_float_prices_simd() {
+        use crate::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+        fn assert_close<C: SimdBondingCurve<f64>>(curve: C, supplies: &[u64]) {
+            let prices = curve.calculate_prices_simd(supplies);
+            assert_eq!(prices.len(), supplies.len());
+            for (&supply, price) in supplies.iter().zip(prices) {
+                let expected = curve.calculate_price(supply);
+                crate::assert_price_eq!(price, expected, 1e-12);
+            }
+        }
+
+        let supplies: Vec<u64> = (0..11).map(|x| x * 100).collect();
+        assert_close(ExponentialBondingCurve::new(0.01, 0.002), &supplies);
+        assert_close(LogarithmicBondingCurve::new(0.02, 0.5), &supplies);
+        assert_close(SigmoidBondingCurve::new(1000.0, 0.01, 500), &supplies);
+    }
+}
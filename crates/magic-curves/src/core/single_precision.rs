@@ -0,0 +1,375 @@
+use std::{
+    cmp::Ordering,
+    f32::consts::E,
+    hash::{Hash, Hasher},
+};
+
+use super::{canonical_key, math, BondingCurve, OperationSide};
+
+/// Represents an exponential bonding curve over single precision floats.
+///
+/// This is the `f32` counterpart of `ExponentialBondingCurve`, for game engines and embedded
+/// targets that don't need double precision. Prices carry about 7 significant digits.
+///
+/// # Fields
+///
+/// * `base`: The base price, which is the initial price for the first token.
+/// * `growth`: The growth rate that determines how quickly the price increases.
+#[derive(Copy, Clone, Debug)]
+pub struct ExponentialBondingCurveF32 {
+    pub base: f32,
+    pub growth: f32,
+}
+
+/// Represents a logarithmic bonding curve over single precision floats.
+///
+/// This is the `f32` counterpart of `LogarithmicBondingCurve`.
+///
+/// # Fields
+///
+/// * `base`: The base price, which is the initial price for the first token.
+/// * `growth`: The growth rate that determines how quickly the price increases.
+#[derive(Copy, Clone, Debug)]
+pub struct LogarithmicBondingCurveF32 {
+    pub base: f32,
+    pub growth: f32,
+}
+
+/// Represents a sigmoid bonding curve over single precision floats.
+///
+/// This is the `f32` counterpart of `SigmoidBondingCurve`.
+///
+/// # Fields
+///
+/// * `max_price`: The maximum price that the curve approaches asymptotically.
+/// * `growth`: The growth rate that determines how quickly the price increases.
+/// * `mid_supply`: The supply at which the price is half of the maximum price.
+#[derive(Copy, Clone, Debug)]
+pub struct SigmoidBondingCurveF32 {
+    pub max_price: f32,
+    pub growth: f32,
+    pub mid_supply: u64,
+}
+
+impl ExponentialBondingCurveF32 {
+    /// Creates a new `ExponentialBondingCurveF32` with the specified base price and growth rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base price, which is the initial price for the first token.
+    /// * `growth` - The growth rate that determines how quickly the price increases.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `ExponentialBondingCurveF32`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, ExponentialBondingCurveF32};
+    ///
+    /// let curve = ExponentialBondingCurveF32::new(0.01, 0.02);
+    /// assert_eq!(curve.calculate_price(0), 0.01);
+    /// ```
+    pub fn new(base: f32, growth: f32) -> Self {
+        Self { base, growth }
+    }
+}
+
+impl LogarithmicBondingCurveF32 {
+    /// Creates a new `LogarithmicBondingCurveF32` with the specified base price and growth rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base price, which is the initial price for the first token.
+    /// * `growth` - The growth rate that determines how quickly the price increases.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `LogarithmicBondingCurveF32`.
+    pub fn new(base: f32, growth: f32) -> Self {
+        Self { base, growth }
+    }
+}
+
+impl SigmoidBondingCurveF32 {
+    /// Creates a new `SigmoidBondingCurveF32` with the specified parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_price` - The maximum price that the curve approaches asymptotically.
+    /// * `growth` - The growth rate that determines how quickly the price increases.
+    /// * `mid_supply` - The supply at which the price is half of the maximum price.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SigmoidBondingCurveF32`.
+    pub fn new(max_price: f32, growth: f32, mid_supply: u64) -> Self {
+        Self {
+            max_price,
+            growth,
+            mid_supply,
+        }
+    }
+}
+
+impl ExponentialBondingCurveF32 {
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64) {
+        (
+            canonical_key(self.base.into()),
+            canonical_key(self.growth.into()),
+        )
+    }
+}
+
+impl PartialEq for ExponentialBondingCurveF32 {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ExponentialBondingCurveF32 {}
+
+impl Hash for ExponentialBondingCurveF32 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for ExponentialBondingCurveF32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExponentialBondingCurveF32 {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl BondingCurve<f32> for ExponentialBondingCurveF32 {
+    /// Calculates the price based on the supply. See `ExponentialBondingCurve`.
+    fn calculate_price(&self, supply: u64) -> f32 {
+        self.base * math::powf_f32(E, self.growth * supply as f32)
+    }
+
+    /// Calculates the price for a given amount of tokens. See `ExponentialBondingCurve`.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f32 {
+        if amount == 0 {
+            return 0.0;
+        }
+
+        let start = starting_supply as f32;
+        let end = match side {
+            OperationSide::Add => (starting_supply + amount) as f32,
+            OperationSide::Remove => (starting_supply - amount) as f32,
+        };
+        let integral = self.base / self.growth
+            * (math::powf_f32(E, self.growth * end) - math::powf_f32(E, self.growth * start));
+        match side {
+            OperationSide::Add => integral,
+            OperationSide::Remove => -integral,
+        }
+    }
+}
+
+impl LogarithmicBondingCurveF32 {
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64) {
+        (
+            canonical_key(self.base.into()),
+            canonical_key(self.growth.into()),
+        )
+    }
+}
+
+impl PartialEq for LogarithmicBondingCurveF32 {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for LogarithmicBondingCurveF32 {}
+
+impl Hash for LogarithmicBondingCurveF32 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for LogarithmicBondingCurveF32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LogarithmicBondingCurveF32 {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl BondingCurve<f32> for LogarithmicBondingCurveF32 {
+    /// Calculates the price based on the supply. See `LogarithmicBondingCurve`.
+    fn calculate_price(&self, supply: u64) -> f32 {
+        if supply == 0 {
+            return self.base; // Avoid taking the log of 0
+        }
+        self.growth * math::ln_f32(supply as f32) + self.base
+    }
+
+    /// Calculates the price for a given amount of tokens. See `LogarithmicBondingCurve`.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f32 {
+        if amount == 0 {
+            return 0.0;
+        }
+
+        let start = starting_supply as f32;
+        let end = match side {
+            OperationSide::Add => (starting_supply + amount) as f32,
+            OperationSide::Remove => (starting_supply - amount) as f32,
+        };
+        let integral = |x: f32| {
+            if x == 0.0 {
+                return 0.0;
+            }
+            self.growth * x * math::ln_f32(x) - self.growth * x + self.base * x
+        };
+        let price = match side {
+            OperationSide::Add => integral(end) - integral(start),
+            OperationSide::Remove => integral(start) - integral(end),
+        };
+        if starting_supply == 0 && side == OperationSide::Add {
+            price + self.base // Add base price for the first token
+        } else {
+            price
+        }
+    }
+}
+
+impl SigmoidBondingCurveF32 {
+    /// Returns the canonical key the comparison and hashing impls are defined by.
+    fn key(&self) -> (u64, u64, u64) {
+        (
+            canonical_key(self.max_price.into()),
+            canonical_key(self.growth.into()),
+            self.mid_supply,
+        )
+    }
+}
+
+impl PartialEq for SigmoidBondingCurveF32 {
+    /// Compares the parameters by their canonical key, so `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SigmoidBondingCurveF32 {}
+
+impl Hash for SigmoidBondingCurveF32 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for SigmoidBondingCurveF32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SigmoidBondingCurveF32 {
+    /// Orders curves by their parameters in declaration order, with `NaN` last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl BondingCurve<f32> for SigmoidBondingCurveF32 {
+    /// Calculates the price based on the supply. See `SigmoidBondingCurve`.
+    fn calculate_price(&self, supply: u64) -> f32 {
+        let s = supply as f32;
+        self.max_price / (1.0 + math::exp_f32(-self.growth * (s - self.mid_supply as f32)))
+    }
+
+    /// Calculates the price for a given amount of tokens. See `SigmoidBondingCurve`.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> f32 {
+        if amount == 0 {
+            return 0.0;
+        }
+
+        let s = starting_supply as f32;
+        let n = amount as f32;
+        let mid_supply = self.mid_supply as f32;
+        let (start_supply, end_supply) = match side {
+            OperationSide::Add => (s, s + n),
+            OperationSide::Remove => (s - n, s),
+        };
+        let integral = |x: f32| math::ln_f32(1.0 + math::exp_f32(self.growth * (x - mid_supply)));
+        (self.max_price / self.growth) * (integral(end_supply) - integral(start_supply))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurve, ExponentialBondingCurve, ExponentialBondingCurveF32, LogarithmicBondingCurve,
+        LogarithmicBondingCurveF32, OperationSide, SigmoidBondingCurve, SigmoidBondingCurveF32,
+    };
+
+    fn assert_close<A: BondingCurve<f32>, B: BondingCurve<f64>>(single: A, double: B) {
+        let close = |a: f32, b: f64| crate::relative_eq(a as f64, b, 1e-4);
+        for supply in [0, 1, 10, 480, 1_000] {
+            let (a, b) = (
+                single.calculate_price(supply),
+                double.calculate_price(supply),
+            );
+            assert!(close(a, b), "supply {}: {} != {}", supply, a, b);
+        }
+        for (supply, amount, side) in [
+            (0, 10, OperationSide::Add),
+            (480, 10, OperationSide::Add),
+            (480, 10, OperationSide::Remove),
+            (10, 10, OperationSide::Remove),
+        ] {
+            let a = single.calculate_price_many(supply, amount, side);
+            let b = double.calculate_price_many(supply, amount, side);
+            assert!(
+                close(a, b),
+                "{} {} {:?}: {} != {}",
+                supply,
+                amount,
+                side,
+                a,
+                b
+            );
+        }
+        assert_eq!(single.calculate_price_many(5, 0, OperationSide::Add), 0.0);
+    }
+
+    #[test]
+    pub fn test_f32_curves_match_f64_curves() {
+        assert_close(
+            ExponentialBondingCurveF32::new(0.01, 0.002),
+            ExponentialBondingCurve::new(0.01, 0.002),
+        );
+        assert_close(
+            LogarithmicBondingCurveF32::new(0.02, 0.5),
+            LogarithmicBondingCurve::new(0.02, 0.5),
+        );
+        assert_close(
+            SigmoidBondingCurveF32::new(100.0, 0.01, 500),
+            SigmoidBondingCurve::new(100.0, 0.01, 500),
+        );
+    }
+}
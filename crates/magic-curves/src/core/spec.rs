@@ -0,0 +1,275 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use super::{AnyBondingCurve, CurveFamily, LinearBondingCurve, QuadraticBondingCurve};
+#[cfg(feature = "float")]
+use super::{ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve};
+
+/// Represents the possible errors that can occur while parsing a curve spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCurveSpecError {
+    /// Indicates that the spec is not of the form `family:param,param,...`.
+    InvalidFormat(String),
+    /// Indicates that the family name is unknown.
+    UnknownFamily(String),
+    /// Indicates that the family cannot be parsed into the requested curve type, or is
+    /// compiled out (float families without the `float` feature).
+    UnsupportedFamily(CurveFamily),
+    /// Indicates that the family takes a different number of parameters.
+    ParameterCount {
+        family: CurveFamily,
+        expected: usize,
+        found: usize,
+    },
+    /// Indicates that a parameter is not a valid number, or that the parameters, given in the
+    /// variant, are rejected by the float curve's `try_new`.
+    InvalidParameter(String),
+}
+
+impl Display for ParseCurveSpecError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseCurveSpecError::InvalidFormat(spec) => {
+                write!(f, "Expected `family:param,...`, found `{}`.", spec)
+            }
+            ParseCurveSpecError::UnknownFamily(name) => {
+                write!(f, "Unknown curve family `{}`.", name)
+            }
+            ParseCurveSpecError::UnsupportedFamily(family) => {
+                write!(f, "The {} family is not supported here.", family)
+            }
+            ParseCurveSpecError::ParameterCount {
+                family,
+                expected,
+                found,
+            } => write!(
+                f,
+                "The {} family takes {} parameters, found {}.",
+                family, expected, found
+            ),
+            ParseCurveSpecError::InvalidParameter(param) => {
+                write!(f, "Invalid curve parameter `{}`.", param)
+            }
+        }
+    }
+}
+
+impl Error for ParseCurveSpecError {}
+
+impl FromStr for AnyBondingCurve {
+    type Err = ParseCurveSpecError;
+
+    /// Parses a compact curve spec such as `quadratic:10000000,500000000,1000000000`.
+    ///
+    /// Parameters are given in declaration order of the curve's fields. Float curves are built
+    /// with their `try_new`, so a spec such as `exponential:NaN,0.02` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{AnyBondingCurve, LinearBondingCurve};
+    ///
+    /// let curve: AnyBondingCurve = "linear:100,1000".parse().unwrap();
+    /// assert_eq!(curve, AnyBondingCurve::Linear(LinearBondingCurve::new(100, 1000)));
+    /// assert_eq!(curve.to_string(), "linear:100,1000");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, params) = s
+            .split_once(':')
+            .ok_or_else(|| ParseCurveSpecError::InvalidFormat(s.to_string()))?;
+        let family: CurveFamily = name
+            .trim()
+            .parse()
+            .map_err(|_| ParseCurveSpecError::UnknownFamily(name.trim().to_string()))?;
+        #[cfg(feature = "float")]
+        let invalid_curve = || ParseCurveSpecError::InvalidParameter(params.trim().to_string());
+        let params: Vec<&str> = params.split(',').map(str::trim).collect();
+        let expected = match family {
+            CurveFamily::Quadratic | CurveFamily::Sigmoid => 3,
+            _ => 2,
+        };
+        if params.len() != expected {
+            return Err(ParseCurveSpecError::ParameterCount {
+                family,
+                expected,
+                found: params.len(),
+            });
+        }
+        match family {
+            CurveFamily::Linear => Ok(LinearBondingCurve::new(
+                parse_param(params[0])?,
+                parse_param(params[1])?,
+            )
+            .into()),
+            CurveFamily::Quadratic => Ok(QuadraticBondingCurve::new(
+                parse_param(params[0])?,
+                parse_param(params[1])?,
+                parse_param(params[2])?,
+            )
+            .into()),
+            #[cfg(feature = "float")]
+            CurveFamily::Exponential => Ok(ExponentialBondingCurve::try_new(
+                parse_param(params[0])?,
+                parse_param(params[1])?,
+            )
+            .map_err(|_| invalid_curve())?
+            .into()),
+            #[cfg(feature = "float")]
+            CurveFamily::Logarithmic => Ok(LogarithmicBondingCurve::try_new(
+                parse_param(params[0])?,
+                parse_param(params[1])?,
+            )
+            .map_err(|_| invalid_curve())?
+            .into()),
+            #[cfg(feature = "float")]
+            CurveFamily::Sigmoid => Ok(SigmoidBondingCurve::try_new(
+                parse_param(params[0])?,
+                parse_param(params[1])?,
+                parse_param(params[2])?,
+            )
+            .map_err(|_| invalid_curve())?
+            .into()),
+            #[cfg(not(feature = "float"))]
+            _ => Err(ParseCurveSpecError::UnsupportedFamily(family)),
+        }
+    }
+}
+
+impl Display for AnyBondingCurve {
+    /// Formats the curve as a compact spec that `FromStr` parses back to the same curve.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:", self.family())?;
+        match self {
+            AnyBondingCurve::Linear(curve) => write!(f, "{},{}", curve.linear, curve.base),
+            AnyBondingCurve::Quadratic(curve) => {
+                write!(f, "{},{},{}", curve.quadratic, curve.linear, curve.base)
+            }
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Exponential(curve) => write!(f, "{},{}", curve.base, curve.growth),
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Logarithmic(curve) => write!(f, "{},{}", curve.base, curve.growth),
+            #[cfg(feature = "float")]
+            AnyBondingCurve::Sigmoid(curve) => write!(
+                f,
+                "{},{},{}",
+                curve.max_price, curve.growth, curve.mid_supply
+            ),
+        }
+    }
+}
+
+macro_rules! impl_from_str_for_curve {
+    ($curve:ty, $variant:ident) => {
+        impl FromStr for $curve {
+            type Err = ParseCurveSpecError;
+
+            /// Parses a compact curve spec, which must be of this curve's family.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.parse::<AnyBondingCurve>()? {
+                    AnyBondingCurve::$variant(curve) => Ok(curve),
+                    other => Err(ParseCurveSpecError::UnsupportedFamily(other.family())),
+                }
+            }
+        }
+    };
+}
+
+impl_from_str_for_curve!(LinearBondingCurve, Linear);
+impl_from_str_for_curve!(QuadraticBondingCurve, Quadratic);
+#[cfg(feature = "float")]
+impl_from_str_for_curve!(ExponentialBondingCurve, Exponential);
+#[cfg(feature = "float")]
+impl_from_str_for_curve!(LogarithmicBondingCurve, Logarithmic);
+#[cfg(feature = "float")]
+impl_from_str_for_curve!(SigmoidBondingCurve, Sigmoid);
+
+fn parse_param<T: FromStr>(param: &str) -> Result<T, ParseCurveSpecError> {
+    param
+        .parse()
+        .map_err(|_| ParseCurveSpecError::InvalidParameter(param.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        AnyBondingCurve, CurveFamily, LinearBondingCurve, ParseCurveSpecError,
+        QuadraticBondingCurve,
+    };
+
+    #[test]
+    pub fn test_curve_spec_round_trip() {
+        let spec = "quadratic:10000000,500000000,1000000000";
+        let curve: AnyBondingCurve = spec.parse().unwrap();
+        assert_eq!(
+            curve,
+            AnyBondingCurve::Quadratic(QuadraticBondingCurve::new(
+                10_000_000,
+                500_000_000,
+                1_000_000_000
+            ))
+        );
+        assert_eq!(curve.to_string(), spec);
+        assert_eq!(
+            " Linear : 100 , 1000 ".parse::<LinearBondingCurve>(),
+            Ok(LinearBondingCurve::new(100, 1000))
+        );
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_curve_spec_float_round_trip() {
+        for spec in [
+            "exponential:0.01,0.02",
+            "logarithmic:0.02,0.5",
+            "sigmoid:1000,0.01,500",
+            "exponential:0.1,0.30000000000000004",
+        ] {
+            let curve: AnyBondingCurve = spec.parse().unwrap();
+            assert_eq!(curve.to_string(), spec);
+            assert_eq!(curve.to_string().parse::<AnyBondingCurve>(), Ok(curve));
+        }
+        for spec in [
+            "exponential:NaN,0.02",
+            "exponential:-1,0.02",
+            "logarithmic:0.02,-0.5",
+            "sigmoid:inf,0.01,500",
+        ] {
+            let params = spec.split_once(':').unwrap().1;
+            assert_eq!(
+                spec.parse::<AnyBondingCurve>(),
+                Err(ParseCurveSpecError::InvalidParameter(params.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_curve_spec_errors() {
+        assert_eq!(
+            "linear".parse::<AnyBondingCurve>(),
+            Err(ParseCurveSpecError::InvalidFormat("linear".to_string()))
+        );
+        assert_eq!(
+            "cubic:1,2".parse::<AnyBondingCurve>(),
+            Err(ParseCurveSpecError::UnknownFamily("cubic".to_string()))
+        );
+        assert_eq!(
+            "quadratic:1,2".parse::<AnyBondingCurve>(),
+            Err(ParseCurveSpecError::ParameterCount {
+                family: CurveFamily::Quadratic,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "linear:1,-2".parse::<AnyBondingCurve>(),
+            Err(ParseCurveSpecError::InvalidParameter("-2".to_string()))
+        );
+        assert_eq!(
+            "linear:1,2".parse::<QuadraticBondingCurve>(),
+            Err(ParseCurveSpecError::UnsupportedFamily(CurveFamily::Linear))
+        );
+    }
+}
@@ -0,0 +1,804 @@
+use super::{
+    charge_fees, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuoteRequest,
+    ReserveTranches, TrancheBalances, TranchePolicy,
+};
+
+/// Represents the outcome of a trade applied to a `CurveState`.
+///
+/// # Fields
+///
+/// * `side`: Whether tokens were added or removed.
+/// * `amount`: The number of tokens traded.
+/// * `cost`: The total price of the traded tokens, before fees.
+/// * `supply`: The supply after the trade.
+/// * `fee`: The fee paid on top of a purchase or taken from the proceeds of a sale, such as
+///   with `CurveState::apply_with_fees`, or 0 if the trade was not charged a fee.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeResult {
+    pub side: OperationSide,
+    pub amount: u64,
+    pub cost: u64,
+    pub supply: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fee: u64,
+}
+
+/// Represents the terminal state of a `CurveState` after `CurveState::graduate`, once the
+/// token has left the curve, such as for a DEX pool in a launchpad.
+///
+/// # Fields
+///
+/// * `final_price`: The spot price at graduation, which the curve stays frozen at.
+/// * `allow_sells`: Whether tokens can still be sold back, each at `final_price`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Graduation {
+    pub final_price: u64,
+    pub allow_sells: bool,
+}
+
+impl Graduation {
+    /// Prices a trade against a graduated supply: purchases fail, and sales fail unless
+    /// allowed, each token then selling at `final_price`.
+    pub(crate) fn quote(
+        &self,
+        supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        if side == OperationSide::Add || !self.allow_sells {
+            return Err(BondingCurveError::Graduated);
+        }
+        Ok(TradeResult {
+            side,
+            amount,
+            cost: self
+                .final_price
+                .checked_mul(amount)
+                .ok_or(BondingCurveError::Overflow)?,
+            supply: supply
+                .checked_sub(amount)
+                .ok_or(BondingCurveError::SupplyUnderflow)?,
+            fee: 0,
+        })
+    }
+}
+
+/// Represents a curve together with its current supply.
+///
+/// Trades are validated before they are applied: a failed trade leaves the state untouched,
+/// and removing more tokens than the supply holds fails with
+/// `BondingCurveError::SupplyUnderflow`. Every applied trade is reported to the state's
+/// `TradeObserver`, which is `()` unless one is set with `CurveState::with_observer`.
+///
+/// After `CurveState::graduate`, purchases fail with `BondingCurveError::Graduated`, and so do
+/// sales unless the graduation allows them at its frozen price.
+///
+/// With `CurveState::with_tranches`, the state also keeps the reserve split into
+/// `ReserveTranches`, settled with every applied trade.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{BondingCurveError, CurveState, LinearBondingCurve};
+///
+/// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+/// let trade = state.buy(10).unwrap();
+/// assert_eq!(trade.cost, 14_500);
+/// assert_eq!(state.supply(), 10);
+/// assert_eq!(state.sell(11), Err(BondingCurveError::SupplyUnderflow));
+/// assert_eq!(state.supply(), 10);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CurveState<C, O = ()> {
+    curve: C,
+    supply: u64,
+    graduation: Option<Graduation>,
+    tranches: Option<ReserveTranches>,
+    observer: O,
+}
+
+/// Represents a checkpoint of a `CurveState`, taken with `CurveState::snapshot`.
+///
+/// With the `serde` feature, a snapshot can be stored and later turned back into an identical
+/// state with `CurveState::restore`. Observers are not part of a snapshot.
+///
+/// # Fields
+///
+/// * `curve`: The curve trades are priced with.
+/// * `supply`: The supply when the snapshot was taken.
+/// * `graduation`: The graduation of the state, if it had graduated.
+/// * `tranches`: The reserve tranches of the state, if it kept them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveSnapshot<C> {
+    pub curve: C,
+    pub supply: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub graduation: Option<Graduation>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub tranches: Option<ReserveTranches>,
+}
+
+/// Receives every trade applied to a `CurveState`.
+///
+/// Implemented for `()`, which ignores trades, for `Vec<TradeResult>`, which records them, and
+/// for any `FnMut(&TradeResult)` closure.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{CurveState, LinearBondingCurve, TradeResult};
+///
+/// let mut state =
+///     CurveState::new(LinearBondingCurve::new(100, 1000), 0).with_observer(Vec::new());
+/// state.buy(10).unwrap();
+/// state.sell(4).unwrap();
+/// let supplies: Vec<u64> = state.observer().iter().map(|trade| trade.supply).collect();
+/// assert_eq!(supplies, vec![10, 6]);
+///
+/// let mut volume = 0;
+/// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0)
+///     .with_observer(|trade: &TradeResult| volume += trade.cost);
+/// state.buy(10).unwrap();
+/// assert_eq!(volume, 14_500);
+/// ```
+pub trait TradeObserver {
+    /// Called after a trade has been applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - The applied trade, including the new supply.
+    fn on_trade(&mut self, trade: &TradeResult);
+}
+
+impl TradeObserver for () {
+    fn on_trade(&mut self, _trade: &TradeResult) {}
+}
+
+impl TradeObserver for Vec<TradeResult> {
+    fn on_trade(&mut self, trade: &TradeResult) {
+        self.push(*trade);
+    }
+}
+
+impl<F: FnMut(&TradeResult)> TradeObserver for F {
+    fn on_trade(&mut self, trade: &TradeResult) {
+        self(trade)
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>> CurveState<C> {
+    /// Creates a new `CurveState`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The curve trades are priced with.
+    /// * `supply` - The current supply of tokens.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `CurveState`.
+    pub fn new(curve: C, supply: u64) -> Self {
+        Self {
+            curve,
+            supply,
+            graduation: None,
+            tranches: None,
+            observer: (),
+        }
+    }
+
+    /// Recreates the state captured by `CurveState::snapshot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The snapshot to restore.
+    ///
+    /// # Returns
+    ///
+    /// A `CurveState` with the snapshot's curve, supply, graduation, and tranches, and no
+    /// observer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{CurveState, LinearBondingCurve};
+    ///
+    /// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+    /// state.buy(10).unwrap();
+    /// let snapshot = state.snapshot();
+    /// let mut resumed = CurveState::restore(snapshot);
+    /// assert_eq!(resumed.buy(5), state.buy(5));
+    /// ```
+    pub fn restore(snapshot: CurveSnapshot<C>) -> Self {
+        Self {
+            graduation: snapshot.graduation,
+            tranches: snapshot.tranches,
+            ..Self::new(snapshot.curve, snapshot.supply)
+        }
+    }
+}
+
+impl<C: BondingCurveWithCheckedOperations<u64>, O: TradeObserver> CurveState<C, O> {
+    /// Replaces the observer notified of every applied trade.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The new `TradeObserver`.
+    ///
+    /// # Returns
+    ///
+    /// The same state, reporting trades to `observer`.
+    pub fn with_observer<P: TradeObserver>(self, observer: P) -> CurveState<C, P> {
+        CurveState {
+            curve: self.curve,
+            supply: self.supply,
+            graduation: self.graduation,
+            tranches: self.tranches,
+            observer,
+        }
+    }
+
+    /// Captures the curve, supply, graduation, and tranches, so the state can be resumed with
+    /// `CurveState::restore`.
+    pub fn snapshot(&self) -> CurveSnapshot<C>
+    where
+        C: Clone,
+    {
+        CurveSnapshot {
+            curve: self.curve.clone(),
+            supply: self.supply,
+            graduation: self.graduation,
+            tranches: self.tranches,
+        }
+    }
+
+    /// Returns the observer notified of every applied trade.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns the observer notified of every applied trade, mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Returns the curve trades are priced with.
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the current supply of tokens.
+    pub fn supply(&self) -> u64 {
+        self.supply
+    }
+
+    /// Splits the reserve into tranches, which every applied trade then settles according to
+    /// `policy`.
+    ///
+    /// A sale prices the supplies it leaves, so the liquidity collected from purchases falls
+    /// short of selling the whole supply by the `ReserveHealth::obligation` gap, and the last
+    /// sales fail with `BondingCurveError::InsufficientReserve` unless `liquidity` starts with
+    /// that gap.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The tax and royalty policy applied to every trade.
+    /// * `balances` - The starting balance of every tranche, such as the reserve at the current
+    ///   supply in `liquidity`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the same state, keeping the tranches, or
+    /// `BondingCurveError::InvalidParameter` if the policy is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{
+    ///     CurveState, LinearBondingCurve, TrancheBalances, TranchePolicy,
+    /// };
+    ///
+    /// let policy = TranchePolicy::new(100, 50);
+    /// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0)
+    ///     .with_tranches(policy, TrancheBalances::default())
+    ///     .unwrap();
+    /// state.buy(10).unwrap();
+    /// state.sell(4).unwrap();
+    /// let balances = state.tranches().unwrap().balances;
+    /// assert_eq!((balances.liquidity, balances.treasury, balances.creator), (7_100, 219, 109));
+    /// ```
+    pub fn with_tranches(
+        self,
+        policy: TranchePolicy,
+        balances: TrancheBalances,
+    ) -> Result<Self, BondingCurveError> {
+        policy.validate()?;
+        Ok(Self {
+            tranches: Some(ReserveTranches::new(policy, balances)),
+            ..self
+        })
+    }
+
+    /// Returns the reserve tranches, or `None` if the state does not keep them.
+    pub fn tranches(&self) -> Option<ReserveTranches> {
+        self.tranches
+    }
+
+    /// Returns the graduation of the state, or `None` if the curve is still trading.
+    pub fn graduation(&self) -> Option<Graduation> {
+        self.graduation
+    }
+
+    /// Graduates the curve, freezing it at the current spot price. From then on purchases
+    /// fail with `BondingCurveError::Graduated`, and sales either pay the frozen price per
+    /// token or fail the same way.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow_sells` - Whether tokens can still be sold back at the frozen price.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Graduation`, `BondingCurveError::Graduated` if the curve has
+    /// already graduated, or the `BondingCurveError` of the spot price.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveError, CurveState, LinearBondingCurve};
+    ///
+    /// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+    /// state.buy(10).unwrap();
+    /// let graduation = state.graduate(true).unwrap();
+    /// assert_eq!(graduation.final_price, 2_000);
+    /// assert_eq!(state.buy(1), Err(BondingCurveError::Graduated));
+    /// assert_eq!(state.sell(3).unwrap().cost, 6_000);
+    /// ```
+    pub fn graduate(&mut self, allow_sells: bool) -> Result<Graduation, BondingCurveError> {
+        if self.graduation.is_some() {
+            return Err(BondingCurveError::Graduated);
+        }
+        let graduation = Graduation {
+            final_price: self.curve.calculate_price_checked(self.supply)?,
+            allow_sells,
+        };
+        self.graduation = Some(graduation);
+        Ok(graduation)
+    }
+
+    /// Prices a trade without applying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TradeResult` the trade would produce, or a
+    /// `BondingCurveError` if it cannot be applied.
+    pub fn quote(
+        &self,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        if let Some(graduation) = self.graduation {
+            return graduation.quote(self.supply, amount, side);
+        }
+        let response = QuoteRequest::new(self.supply, amount, side).quote(&self.curve)?;
+        Ok(TradeResult {
+            side,
+            amount,
+            cost: response.price,
+            supply: response.ending_supply,
+            fee: 0,
+        })
+    }
+
+    /// Prices a trade charged a fee from a fee curve, without applying it. See
+    /// `quote_with_fees`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fees` - The curve that gives the fee rate at every supply, in basis points.
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TradeResult` the trade would produce, with its fee, or a
+    /// `BondingCurveError` if it cannot be applied.
+    pub fn quote_with_fees<F: BondingCurveWithCheckedOperations<u64>>(
+        &self,
+        fees: &F,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        let trade = self.quote(amount, side)?;
+        let quote = charge_fees(fees, self.supply, amount, side, trade.cost, trade.supply)?;
+        Ok(TradeResult {
+            fee: quote.fee,
+            ..trade
+        })
+    }
+
+    /// Applies a trade, updating the supply and any tranches, and notifying the observer.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the applied `TradeResult`, or a `BondingCurveError` if the
+    /// trade cannot be applied, such as `BondingCurveError::InsufficientReserve` if the
+    /// liquidity tranche cannot pay for a sale, in which case the state is unchanged.
+    pub fn apply(
+        &mut self,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        self.settle(self.quote(amount, side), amount, side)
+    }
+
+    /// Applies a trade charged a fee from a fee curve, like `CurveState::apply`. The fee is
+    /// reported in the `TradeResult` and left out of any tranches, which only settle the
+    /// price of the tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `fees` - The curve that gives the fee rate at every supply, in basis points.
+    /// * `amount` - The number of tokens to trade.
+    /// * `side` - Whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the applied `TradeResult`, with its fee, or a
+    /// `BondingCurveError` if the trade cannot be applied, in which case the state is
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{CurveState, LinearBondingCurve, OperationSide};
+    ///
+    /// let mut state = CurveState::new(LinearBondingCurve::new(100, 1000), 0);
+    /// // A flat 1% fee.
+    /// let fees = LinearBondingCurve::new(0, 100);
+    /// let trade = state.apply_with_fees(&fees, 10, OperationSide::Add).unwrap();
+    /// assert_eq!((trade.cost, trade.fee), (14_500, 145));
+    /// assert_eq!(state.buy(10).unwrap().fee, 0);
+    /// ```
+    pub fn apply_with_fees<F: BondingCurveWithCheckedOperations<u64>>(
+        &mut self,
+        fees: &F,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        self.settle(self.quote_with_fees(fees, amount, side), amount, side)
+    }
+
+    /// Applies a quoted trade, updating the supply and any tranches, and notifying the
+    /// observer.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn settle(
+        &mut self,
+        quote: Result<TradeResult, BondingCurveError>,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<TradeResult, BondingCurveError> {
+        let quote = quote.and_then(|trade| {
+            let tranches = self.tranches.map(|t| t.settle(&trade)).transpose()?;
+            Ok((trade, tranches))
+        });
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &quote {
+            tracing::debug!(supply = self.supply, amount, ?side, %error, "rejected trade");
+        }
+        let (trade, tranches) = quote?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            from = self.supply,
+            to = trade.supply,
+            amount,
+            ?side,
+            cost = trade.cost,
+            fee = trade.fee,
+            "applied trade"
+        );
+        self.supply = trade.supply;
+        self.tranches = tranches;
+        self.observer.on_trade(&trade);
+        Ok(trade)
+    }
+
+    /// Adds `amount` tokens to the supply. See `CurveState::apply`.
+    pub fn buy(&mut self, amount: u64) -> Result<TradeResult, BondingCurveError> {
+        self.apply(amount, OperationSide::Add)
+    }
+
+    /// Removes `amount` tokens from the supply. See `CurveState::apply`.
+    pub fn sell(&mut self, amount: u64) -> Result<TradeResult, BondingCurveError> {
+        self.apply(amount, OperationSide::Remove)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, CurveSnapshot, CurveState,
+        Graduation, OperationSide, QuadraticBondingCurve, TradeResult, TrancheBalances,
+        TranchePolicy,
+    };
+
+    #[test]
+    pub fn test_curve_state_graduation() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 100);
+        let final_price = curve.calculate_price_checked(100).unwrap();
+        assert_eq!(
+            state.graduate(true),
+            Ok(Graduation {
+                final_price,
+                allow_sells: true
+            })
+        );
+        assert_eq!(state.graduate(false), Err(BondingCurveError::Graduated));
+        assert_eq!(state.buy(1), Err(BondingCurveError::Graduated));
+        assert_eq!(state.supply(), 100);
+        let sale = state.sell(40).unwrap();
+        assert_eq!((sale.cost, sale.supply), (40 * final_price, 60));
+        assert_eq!(state.sell(61), Err(BondingCurveError::SupplyUnderflow));
+
+        // A snapshot keeps the state graduated.
+        let restored = CurveState::restore(state.snapshot());
+        assert_eq!(restored.graduation(), state.graduation());
+
+        let mut frozen = CurveState::new(curve, 100);
+        frozen.graduate(false).unwrap();
+        assert_eq!(frozen.sell(1), Err(BondingCurveError::Graduated));
+        assert_eq!(frozen.supply(), 100);
+    }
+
+    #[test]
+    pub fn test_curve_state_tranches() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let policy = TranchePolicy::new(300, 200);
+        assert_eq!(
+            CurveState::new(curve, 0)
+                .with_tranches(TranchePolicy::new(9_000, 1_001), TrancheBalances::default())
+                .map(|state| state.tranches()),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        let mut state = CurveState::new(curve, 0)
+            .with_tranches(policy, TrancheBalances::default())
+            .unwrap()
+            .with_observer(Vec::new());
+        let buy = state.buy(100).unwrap();
+        let sale = state.sell(30).unwrap();
+        let balances = state.tranches().unwrap().balances;
+        assert_eq!(balances.liquidity, buy.cost - sale.cost);
+        assert_eq!(balances.treasury, buy.cost * 3 / 100 + sale.cost * 3 / 100);
+        assert_eq!(balances.creator, buy.cost / 50 + sale.cost / 50);
+
+        // Selling everything owes more than the purchases paid in, and a failed sale leaves
+        // the state untouched.
+        assert_eq!(state.sell(70), Err(BondingCurveError::InsufficientReserve));
+        assert_eq!(state.supply(), 70);
+        assert_eq!(state.tranches().unwrap().balances, balances);
+        assert_eq!(state.observer().len(), 2);
+        assert_eq!(
+            CurveState::restore(state.snapshot()).tranches(),
+            state.tranches()
+        );
+    }
+
+    #[test]
+    pub fn test_curve_state_trades() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 100);
+        let trade = state.buy(10).unwrap();
+        assert_eq!(
+            trade,
+            TradeResult {
+                side: OperationSide::Add,
+                amount: 10,
+                cost: curve
+                    .calculate_price_many_checked(100, 10, OperationSide::Add)
+                    .unwrap(),
+                supply: 110,
+                fee: 0,
+            }
+        );
+        assert_eq!(state.quote(110, OperationSide::Remove).unwrap().supply, 0);
+        assert_eq!(state.supply(), 110);
+        state.sell(110).unwrap();
+        assert_eq!(state.supply(), 0);
+    }
+
+    #[test]
+    pub fn test_curve_state_fees() {
+        use crate::{quote_with_fees, GeometricBondingCurve, LinearBondingCurve};
+
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let fees = GeometricBondingCurve::new(500, 9_900);
+        let mut state = CurveState::new(curve, 0)
+            .with_tranches(TranchePolicy::new(100, 0), TrancheBalances::default())
+            .unwrap()
+            .with_observer(Vec::new());
+        let expected = quote_with_fees(&curve, &fees, 0, 10, OperationSide::Add).unwrap();
+        let bought = state
+            .apply_with_fees(&fees, 10, OperationSide::Add)
+            .unwrap();
+        assert_eq!((bought.cost, bought.fee), (expected.price, expected.fee));
+        assert!(bought.fee > 0);
+        assert_eq!(
+            state
+                .quote_with_fees(&fees, 4, OperationSide::Remove)
+                .unwrap()
+                .supply,
+            6
+        );
+        // The observer sees the fee, and the tranches only settle the price.
+        assert_eq!(state.observer(), &vec![bought]);
+        assert_eq!(state.tranches().unwrap().balances.liquidity, bought.cost);
+        assert_eq!(state.buy(1).unwrap().fee, 0);
+
+        // A fee rate above 100% is rejected, and leaves the state untouched.
+        let invalid = LinearBondingCurve::new(0, 10_001);
+        assert_eq!(
+            state.apply_with_fees(&invalid, 1, OperationSide::Add),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(state.supply(), 11);
+        assert_eq!(state.observer().len(), 2);
+    }
+
+    #[test]
+    pub fn test_curve_state_supply_underflow() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 5);
+        assert_eq!(state.sell(6), Err(BondingCurveError::SupplyUnderflow));
+        assert_eq!(state.supply(), 5);
+        let mut full = CurveState::new(curve, u64::MAX);
+        assert_eq!(full.buy(1), Err(BondingCurveError::Overflow));
+        assert_eq!(full.supply(), u64::MAX);
+    }
+
+    #[test]
+    pub fn test_curve_state_observers() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 0).with_observer(Vec::new());
+        let bought = state.buy(10).unwrap();
+        assert_eq!(state.sell(11), Err(BondingCurveError::SupplyUnderflow));
+        state.quote(5, OperationSide::Remove).unwrap();
+        let sold = state.sell(10).unwrap();
+        assert_eq!(state.observer(), &vec![bought, sold]);
+        state.observer_mut().clear();
+
+        let mut supplies = Vec::new();
+        let mut state = state.with_observer(|trade: &TradeResult| supplies.push(trade.supply));
+        state.buy(3).unwrap();
+        state.sell(1).unwrap();
+        assert_eq!(supplies, vec![3, 2]);
+    }
+
+    #[test]
+    pub fn test_curve_state_snapshot() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 0).with_observer(Vec::new());
+        state.buy(10).unwrap();
+        let snapshot = state.snapshot();
+        assert_eq!(
+            snapshot,
+            CurveSnapshot {
+                curve,
+                supply: 10,
+                graduation: None,
+                tranches: None
+            }
+        );
+        let mut resumed = CurveState::restore(snapshot);
+        assert_eq!(resumed.sell(4), state.sell(4));
+        assert_eq!(resumed.supply(), state.supply());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_trade_result_json() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let trade = CurveState::new(curve, 0).buy(1).unwrap();
+        let json = serde_json::to_string(&trade).unwrap();
+        assert_eq!(
+            json,
+            r#"{"side":"add","amount":1,"cost":1000000000,"supply":1,"fee":0}"#
+        );
+        assert_eq!(serde_json::from_str::<TradeResult>(&json).unwrap(), trade);
+        // Records written before trades carried a fee still parse, with no fee.
+        let legacy = r#"{"side":"add","amount":1,"cost":1000000000,"supply":1}"#;
+        assert_eq!(serde_json::from_str::<TradeResult>(legacy).unwrap(), trade);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_curve_state_snapshot_json() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut state = CurveState::new(curve, 0);
+        state.buy(10).unwrap();
+        let json = serde_json::to_string(&state.snapshot()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"curve":{"quadratic":10000000,"linear":500000000,"base":1000000000},"supply":10}"#
+        );
+        let restored = CurveState::restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored, state);
+    }
+
+    #[cfg(all(feature = "serde", feature = "float"))]
+    #[test]
+    pub fn test_curve_state_snapshot_validates_float_curves() {
+        use crate::SigmoidBondingCurve;
+
+        let json = r#"{"curve":{"max_price":1000.0,"growth":0.0,"mid_supply":500},"supply":10}"#;
+        assert!(serde_json::from_str::<CurveSnapshot<SigmoidBondingCurve>>(json).is_err());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    pub fn test_curve_state_traces_trades() {
+        use std::sync::{Arc, Mutex};
+        use tracing::{span, Event, Metadata, Subscriber};
+
+        struct Targets(Arc<Mutex<Vec<&'static str>>>);
+
+        impl Subscriber for Targets {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event<'_>) {
+                self.0.lock().unwrap().push(event.metadata().target());
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let targets = Arc::new(Mutex::new(Vec::new()));
+        let mut state = CurveState::new(QuadraticBondingCurve::new(1, 0, 500), 0);
+        tracing::subscriber::with_default(Targets(targets.clone()), || {
+            state.buy(10).unwrap();
+            state.sell(20).unwrap_err();
+        });
+        // Every quote returns or fails, and every trade is applied or rejected.
+        assert_eq!(
+            *targets.lock().unwrap(),
+            vec![
+                "magic_curves::core::quote",
+                "magic_curves::core::state",
+                "magic_curves::core::quote",
+                "magic_curves::core::state",
+            ]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use super::AsF64;
+
+/// Represents the summary statistics of a set of values, computed by `summarize`.
+///
+/// # Fields
+///
+/// * `count`: The number of values summarized.
+/// * `mean`: The arithmetic mean.
+/// * `std_dev`: The population standard deviation, `σ`.
+/// * `min`: The smallest value.
+/// * `max`: The largest value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Represents a histogram of equal-width bins, computed by `histogram`.
+///
+/// Bin `i` counts the values in `start + i * width..start + (i + 1) * width`, except that the
+/// last bin also counts the largest value.
+///
+/// # Fields
+///
+/// * `start`: The lower edge of the first bin, the smallest value.
+/// * `width`: The width of every bin.
+/// * `counts`: The number of values in each bin.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Histogram {
+    pub start: f64,
+    pub width: f64,
+    pub counts: Vec<u64>,
+}
+
+/// Returns the finite values of `values` as `f64`s.
+fn finite_values<T: AsF64>(values: &[T]) -> Vec<f64> {
+    values
+        .iter()
+        .map(|value| value.as_f64())
+        .filter(|value| value.is_finite())
+        .collect()
+}
+
+/// Computes the count, mean, standard deviation, and range of a set of values, such as the
+/// raises of many simulated sales.
+///
+/// Non-finite values are ignored, here and in the other statistics helpers.
+///
+/// # Arguments
+///
+/// * `values` - The values to summarize, of any price type.
+///
+/// # Returns
+///
+/// The `Summary`, or `None` if there are no finite values.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::summarize;
+///
+/// let summary = summarize(&[2u64, 4, 4, 4, 5, 5, 7, 9]).unwrap();
+/// assert_eq!((summary.mean, summary.std_dev), (5.0, 2.0));
+/// assert_eq!((summary.min, summary.max), (2.0, 9.0));
+/// ```
+pub fn summarize<T: AsF64>(values: &[T]) -> Option<Summary> {
+    let values = finite_values(values);
+    if values.is_empty() {
+        return None;
+    }
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean) * (value - mean))
+        .sum::<f64>()
+        / count;
+    Some(Summary {
+        count: values.len(),
+        mean,
+        std_dev: variance.sqrt(),
+        min: values.iter().copied().fold(f64::INFINITY, f64::min),
+        max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    })
+}
+
+/// Computes several percentiles of a set of values at once, sorting them only once.
+///
+/// Percentiles interpolate linearly between the two nearest ranks, so the 50th percentile of
+/// an even number of values is the mean of the middle two.
+///
+/// # Arguments
+///
+/// * `values` - The values, of any price type.
+/// * `percents` - The percentiles to compute, each from `0.0` to `100.0`.
+///
+/// # Returns
+///
+/// The percentiles in the order of `percents`, or `None` if there are no finite values or a
+/// percent is outside `0.0..=100.0`.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::percentiles;
+///
+/// let raises: Vec<u64> = (1..=101).collect();
+/// assert_eq!(percentiles(&raises, &[5.0, 50.0, 95.0]), Some(vec![6.0, 51.0, 96.0]));
+/// ```
+pub fn percentiles<T: AsF64>(values: &[T], percents: &[f64]) -> Option<Vec<f64>> {
+    let mut values = finite_values(values);
+    if values.is_empty() || !percents.iter().all(|p| (0.0..=100.0).contains(p)) {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let last = (values.len() - 1) as f64;
+    Some(
+        percents
+            .iter()
+            .map(|percent| {
+                let rank = percent / 100.0 * last;
+                let below = rank.floor();
+                let low = values[below as usize];
+                let high = values[rank.ceil() as usize];
+                low + (high - low) * (rank - below)
+            })
+            .collect(),
+    )
+}
+
+/// Computes one percentile of a set of values. See `percentiles`.
+///
+/// # Arguments
+///
+/// * `values` - The values, of any price type.
+/// * `percent` - The percentile to compute, from `0.0` to `100.0`.
+///
+/// # Returns
+///
+/// The percentile, or `None` if there are no finite values or `percent` is outside
+/// `0.0..=100.0`.
+pub fn percentile<T: AsF64>(values: &[T], percent: f64) -> Option<f64> {
+    percentiles(values, &[percent]).map(|percentiles| percentiles[0])
+}
+
+/// Counts a set of values into `bins` equal-width bins spanning their range.
+///
+/// When every value is the same, the bins have a width of zero and the last one counts them
+/// all.
+///
+/// # Arguments
+///
+/// * `values` - The values, of any price type.
+/// * `bins` - The number of bins.
+///
+/// # Returns
+///
+/// The `Histogram`, or `None` if there are no finite values or `bins` is zero.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::histogram;
+///
+/// let histogram = histogram(&[0.0, 1.0, 2.5, 3.0, 9.9, 10.0], 2).unwrap();
+/// assert_eq!((histogram.start, histogram.width), (0.0, 5.0));
+/// assert_eq!(histogram.counts, vec![4, 2]);
+/// ```
+pub fn histogram<T: AsF64>(values: &[T], bins: usize) -> Option<Histogram> {
+    let summary = summarize(values)?;
+    if bins == 0 {
+        return None;
+    }
+    let width = (summary.max - summary.min) / bins as f64;
+    let mut counts = vec![0; bins];
+    for value in finite_values(values) {
+        let bin = if width > 0.0 {
+            ((value - summary.min) / width) as usize
+        } else {
+            bins
+        };
+        counts[bin.min(bins - 1)] += 1;
+    }
+    Some(Histogram {
+        start: summary.min,
+        width,
+        counts,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{histogram, percentile, percentiles, summarize};
+
+    #[test]
+    pub fn test_summarize() {
+        let summary = summarize(&[1.0, f64::NAN, 3.0, f64::INFINITY]).unwrap();
+        assert_eq!(summary.count, 2);
+        assert_eq!((summary.mean, summary.std_dev), (2.0, 1.0));
+        assert_eq!(summarize::<u64>(&[]), None);
+        assert_eq!(summarize(&[f64::NAN]), None);
+        let single = summarize(&[u128::MAX]).unwrap();
+        assert_eq!((single.std_dev, single.min), (0.0, u128::MAX as f64));
+    }
+
+    #[test]
+    pub fn test_percentiles() {
+        let values = [4u64, 1, 3, 2];
+        assert_eq!(percentile(&values, 0.0), Some(1.0));
+        assert_eq!(percentile(&values, 50.0), Some(2.5));
+        assert_eq!(percentile(&values, 100.0), Some(4.0));
+        assert_eq!(percentiles(&values, &[25.0, 75.0]), Some(vec![1.75, 3.25]));
+        assert_eq!(percentile(&[7.0f32], 95.0), Some(7.0));
+        assert_eq!(percentile(&values, 100.5), None);
+        assert_eq!(percentile(&values, f64::NAN), None);
+        assert_eq!(percentile::<f64>(&[], 50.0), None);
+    }
+
+    #[test]
+    pub fn test_histogram() {
+        let values: Vec<u64> = (0..100).collect();
+        let histogram = histogram(&values, 4).unwrap();
+        assert_eq!(histogram.width, 24.75);
+        assert_eq!(histogram.counts, vec![25, 25, 25, 25]);
+        let flat = crate::histogram(&[5u64, 5, 5], 3).unwrap();
+        assert_eq!((flat.width, flat.counts), (0.0, vec![0, 0, 3]));
+        assert_eq!(crate::histogram(&values, 0), None);
+        assert_eq!(crate::histogram::<u64>(&[], 3), None);
+    }
+}
@@ -0,0 +1,98 @@
+use std::{iter::FusedIterator, marker::PhantomData};
+
+use super::BondingCurve;
+
+/// Represents a lazy stream of successive spot prices, created by `BondingCurve::price_stream`.
+///
+/// The stream yields the price at `start_supply`, then at each following supply, up to and
+/// including `u64::MAX`. It is effectively infinite, so bound it with `take`, `take_while`, or
+/// `step_by` before collecting.
+#[derive(Debug)]
+pub struct PriceStream<'a, C: ?Sized, T> {
+    curve: &'a C,
+    next: Option<u64>,
+    price: PhantomData<fn() -> T>,
+}
+
+impl<'a, C: ?Sized, T> PriceStream<'a, C, T> {
+    pub(crate) fn new(curve: &'a C, start_supply: u64) -> Self {
+        Self {
+            curve,
+            next: Some(start_supply),
+            price: PhantomData,
+        }
+    }
+}
+
+impl<C: BondingCurve<T> + ?Sized, T> Iterator for PriceStream<'_, C, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let supply = self.next?;
+        self.next = supply.checked_add(1);
+        Some(self.curve.calculate_price(supply))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            Some(supply) => {
+                let remaining = u64::MAX - supply;
+                match usize::try_from(remaining)
+                    .ok()
+                    .and_then(|n| n.checked_add(1))
+                {
+                    Some(n) => (n, Some(n)),
+                    None => (usize::MAX, None),
+                }
+            }
+            None => (0, Some(0)),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        // Skipping is free, which keeps `step_by` from pricing the supplies it steps over.
+        self.next = self.next.and_then(|supply| supply.checked_add(n as u64));
+        self.next()
+    }
+}
+
+impl<C: BondingCurve<T> + ?Sized, T> FusedIterator for PriceStream<'_, C, T> {}
+
+#[cfg(test)]
+mod test {
+    use crate::{BondingCurve, LinearBondingCurve, QuadraticBondingCurve};
+
+    #[test]
+    pub fn test_price_stream() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let prices: Vec<u64> = curve.price_stream(100).take(5).collect();
+        let expected: Vec<u64> = (100..105).map(|x| curve.calculate_price(x)).collect();
+        assert_eq!(prices, expected);
+
+        let stepped: Vec<u64> = curve.price_stream(0).step_by(10).take(3).collect();
+        assert_eq!(
+            stepped,
+            vec![
+                curve.calculate_price(0),
+                curve.calculate_price(10),
+                curve.calculate_price(20)
+            ]
+        );
+        assert_eq!(
+            curve.price_stream(1).take(4).sum::<u64>(),
+            curve.calculate_price_many(1, 4, crate::OperationSide::Add)
+        );
+    }
+
+    #[test]
+    pub fn test_price_stream_ends_at_max_supply() {
+        let curve = LinearBondingCurve::new(0, 7);
+        let mut stream = curve.price_stream(u64::MAX - 1);
+        assert_eq!(stream.size_hint(), (2, Some(2)));
+        assert_eq!(stream.next(), Some(7));
+        assert_eq!(stream.next(), Some(7));
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.next(), None);
+        assert_eq!(curve.price_stream(u64::MAX - 3).nth(5), None);
+    }
+}
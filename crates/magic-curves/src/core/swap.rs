@@ -0,0 +1,241 @@
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, OperationSide, QuoteRequest, TradeResult,
+    BASIS_POINTS,
+};
+
+/// Represents the fees of a swap through the reserve currency, in basis points.
+///
+/// # Fields
+///
+/// * `sell`: The fee on the reserve received for the sold tokens.
+/// * `buy`: The fee on the reserve spent on the bought tokens.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapFees {
+    pub sell: u64,
+    pub buy: u64,
+}
+
+impl SwapFees {
+    /// Creates a new `SwapFees`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sell` - The fee on the reserve received for the sold tokens, in basis points.
+    /// * `buy` - The fee on the reserve spent on the bought tokens, in basis points.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SwapFees`.
+    pub fn new(sell: u64, buy: u64) -> Self {
+        Self { sell, buy }
+    }
+}
+
+/// Represents the outcome of `quote_swap`.
+///
+/// # Fields
+///
+/// * `sold`: The sale of the input tokens into their curve, with the reserve it pays before
+///   fees.
+/// * `bought`: The purchase of the output tokens from their curve, with the reserve it costs
+///   before fees.
+/// * `sell_fee`: The fee on the sale's reserve, also the `fee` of `sold`.
+/// * `buy_fee`: The fee on the purchase's reserve, also the `fee` of `bought`.
+/// * `leftover`: The reserve left over after buying as many output tokens as it affords,
+///   returned to the trader.
+/// * `sell_impact`: How far below the spot price of the input tokens the sale fills on
+///   average, in basis points.
+/// * `buy_impact`: How far above the spot price of the output tokens the purchase fills on
+///   average, in basis points.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapQuote {
+    pub sold: TradeResult,
+    pub bought: TradeResult,
+    pub sell_fee: u64,
+    pub buy_fee: u64,
+    pub leftover: u64,
+    pub sell_impact: u64,
+    pub buy_impact: u64,
+}
+
+/// Quotes swapping tokens of one curve into tokens of another that share a reserve currency,
+/// by selling the input tokens into their curve and buying output tokens with the proceeds.
+///
+/// The sell fee is taken from the proceeds, and the rest buys the most output tokens whose
+/// cost plus the buy fee it covers. Fees are rounded up. Nothing is applied to either curve;
+/// the `TradeResult`s hold the supplies each curve would have after the swap.
+///
+/// # Arguments
+///
+/// * `from` - The curve of the input tokens.
+/// * `from_supply` - The supply of the input tokens.
+/// * `to` - The curve of the output tokens.
+/// * `to_supply` - The supply of the output tokens.
+/// * `amount` - The number of input tokens to swap.
+/// * `fees` - The fees of both legs.
+///
+/// # Returns
+///
+/// A `Result` containing the `SwapQuote`, `BondingCurveError::InvalidParameter` if a fee is
+/// above `BASIS_POINTS`, or the `BondingCurveError` the sale fails with.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{quote_swap, LinearBondingCurve, SwapFees};
+///
+/// let from = LinearBondingCurve::new(100, 1000);
+/// let to = LinearBondingCurve::new(10, 500);
+/// let swap = quote_swap(&from, 100, &to, 0, 10, SwapFees::new(30, 30)).unwrap();
+/// // Selling 10 tokens pays 105_500, less a 317 fee, which buys 103 tokens.
+/// assert_eq!((swap.sold.cost, swap.sell_fee), (105_500, 317));
+/// assert_eq!((swap.bought.amount, swap.bought.cost, swap.buy_fee), (103, 104_030, 313));
+/// assert_eq!(swap.leftover, 840);
+/// // The sale fills 4.09% below its spot price, and the purchase at about twice its own.
+/// assert_eq!((swap.sell_impact, swap.buy_impact), (409, 10_200));
+/// ```
+pub fn quote_swap<A, B>(
+    from: &A,
+    from_supply: u64,
+    to: &B,
+    to_supply: u64,
+    amount: u64,
+    fees: SwapFees,
+) -> Result<SwapQuote, BondingCurveError>
+where
+    A: BondingCurveWithCheckedOperations<u64>,
+    B: BondingCurveWithCheckedOperations<u64>,
+{
+    if fees.sell > BASIS_POINTS || fees.buy > BASIS_POINTS {
+        return Err(BondingCurveError::InvalidParameter);
+    }
+    let sale = QuoteRequest::new(from_supply, amount, OperationSide::Remove).quote(from)?;
+    let sell_fee = fee_of(sale.price, fees.sell);
+    let sold = TradeResult {
+        side: OperationSide::Remove,
+        amount,
+        cost: sale.price,
+        supply: sale.ending_supply,
+        fee: sell_fee,
+    };
+    let budget = sold.cost - sell_fee;
+
+    let total = |tokens: u64| -> Option<(u64, u64)> {
+        let cost = to
+            .calculate_price_many_checked(to_supply, tokens, OperationSide::Add)
+            .ok()?;
+        let fee = fee_of(cost, fees.buy);
+        (cost.checked_add(fee)? <= budget).then_some((cost, fee))
+    };
+    // The cost of a purchase only grows with its size, so bisect for the largest that fits.
+    let (mut low, mut high) = (0u64, u64::MAX - to_supply);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if total(mid).is_some() {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    let (cost, buy_fee) = total(low).unwrap_or((0, 0));
+    let bought = TradeResult {
+        side: OperationSide::Add,
+        amount: low,
+        cost,
+        supply: to_supply + low,
+        fee: buy_fee,
+    };
+
+    let spot = |curve: &dyn BondingCurveWithCheckedOperations<u64>, supply, tokens| {
+        curve
+            .calculate_price_checked(supply)
+            .map(|price| price as u128 * tokens as u128)
+    };
+    let sell_spot = spot(from, from_supply, amount)?;
+    let buy_spot = spot(to, to_supply, low)?;
+    Ok(SwapQuote {
+        sold,
+        bought,
+        sell_fee,
+        buy_fee,
+        leftover: budget - cost - buy_fee,
+        sell_impact: impact(sell_spot.saturating_sub(sold.cost as u128), sell_spot),
+        buy_impact: impact((bought.cost as u128).saturating_sub(buy_spot), buy_spot),
+    })
+}
+
+/// Returns `bps` basis points of `value`, rounded up.
+fn fee_of(value: u64, bps: u64) -> u64 {
+    (value as u128 * bps as u128).div_ceil(BASIS_POINTS as u128) as u64
+}
+
+/// Returns `excess` in basis points of the spot value `spot`, or zero if `spot` is zero.
+fn impact(excess: u128, spot: u128) -> u64 {
+    if spot == 0 {
+        return 0;
+    }
+    u64::try_from(excess * BASIS_POINTS as u128 / spot).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        quote_swap, BondingCurveError, BondingCurveWithCheckedOperations, CurveState,
+        LinearBondingCurve, OperationSide, QuadraticBondingCurve, SwapFees,
+    };
+
+    #[test]
+    pub fn test_quote_swap_buys_the_most_affordable() {
+        let from = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let to = LinearBondingCurve::new(1_000_000, 50_000_000);
+        let swap = quote_swap(&from, 5_000, &to, 200, 40, SwapFees::new(25, 100)).unwrap();
+        let budget = swap.sold.cost - swap.sell_fee;
+        assert_eq!(budget, swap.bought.cost + swap.buy_fee + swap.leftover);
+        // One more token would cost more than the proceeds cover.
+        let next = to
+            .calculate_price_many_checked(200, swap.bought.amount + 1, OperationSide::Add)
+            .unwrap();
+        assert!(next + next.div_ceil(100) > budget);
+        // The legs are the trades a `CurveState` would apply with flat fee curves.
+        let mut sold = CurveState::new(from, 5_000);
+        let sell_fees = LinearBondingCurve::new(0, 25);
+        assert_eq!(
+            sold.apply_with_fees(&sell_fees, 40, OperationSide::Remove),
+            Ok(swap.sold)
+        );
+        let mut bought = CurveState::new(to, 200);
+        let buy_fees = LinearBondingCurve::new(0, 100);
+        assert_eq!(
+            bought.apply_with_fees(&buy_fees, swap.bought.amount, OperationSide::Add),
+            Ok(swap.bought)
+        );
+        assert!(swap.sell_impact > 0 && swap.buy_impact > 0);
+    }
+
+    #[test]
+    pub fn test_quote_swap_edges() {
+        let from = LinearBondingCurve::new(0, 1_000);
+        let free = LinearBondingCurve::new(0, 0);
+        let swap = quote_swap(&from, 10, &from, 0, 0, SwapFees::default()).unwrap();
+        assert_eq!((swap.bought.amount, swap.leftover), (0, 0));
+        // A flat curve fills at its spot price, with no impact.
+        let swap = quote_swap(&from, 10, &from, 0, 10, SwapFees::default()).unwrap();
+        assert_eq!((swap.bought.amount, swap.leftover), (10, 0));
+        assert_eq!((swap.sell_impact, swap.buy_impact), (0, 0));
+        let swap = quote_swap(&from, 10, &from, 0, 10, SwapFees::new(10_000, 0)).unwrap();
+        assert_eq!((swap.sell_fee, swap.bought.amount), (10_000, 0));
+        // Free tokens are bought until the supply is full.
+        let swap = quote_swap(&from, 10, &free, 5, 1, SwapFees::default()).unwrap();
+        assert_eq!(swap.bought.supply, u64::MAX);
+        assert_eq!(
+            quote_swap(&from, 10, &from, 0, 11, SwapFees::default()),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            quote_swap(&from, 10, &from, 0, 1, SwapFees::new(0, 10_001)),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+}
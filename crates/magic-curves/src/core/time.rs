@@ -0,0 +1,345 @@
+use std::iter::Sum;
+
+use super::{supply_range, BondingCurve, BondingCurveError, OperationSide};
+
+/// Represents a bonding curve whose price depends on both the supply and time.
+///
+/// This is the interface for time-based mechanics such as Dutch auctions, gradual or
+/// variable-rate auctions, and price decay. `elapsed` is measured in whatever unit the curve
+/// is parameterized in, usually seconds or slots since the curve started. Ordinary curves
+/// can be used wherever a `TimeBondingCurve` is expected by wrapping them in `TimeInvariant`.
+///
+/// # Type Parameters
+///
+/// * `T` - The type used to represent prices, as in `BondingCurve`.
+pub trait TimeBondingCurve<T> {
+    /// Calculates the price for a single token at the given supply and time.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The current total supply of tokens.
+    /// * `elapsed` - The time elapsed since the curve started.
+    ///
+    /// # Returns
+    ///
+    /// The price of a single token at the given supply and time.
+    fn price_at(&self, supply: u64, elapsed: u64) -> T;
+
+    /// Calculates the total price for a given amount of tokens traded at a single point in
+    /// time, with the semantics of `BondingCurve::calculate_price_many`.
+    ///
+    /// The provided implementation sums `price_at` over every priced supply. It panics when
+    /// removing more than `starting_supply` or when adding past `u64::MAX`.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    /// * `elapsed` - The time elapsed since the curve started.
+    ///
+    /// # Returns
+    ///
+    /// The total price for the specified amount of tokens.
+    fn price_many_at(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        elapsed: u64,
+    ) -> T
+    where
+        T: Sum<T>,
+    {
+        match supply_range(starting_supply, amount, side) {
+            Ok(Some((first, last))) => (first..=last)
+                .map(|supply| self.price_at(supply, elapsed))
+                .sum(),
+            Ok(None) => std::iter::empty().sum(),
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+/// Adapts an ordinary `BondingCurve` into a `TimeBondingCurve` that ignores time.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{LinearBondingCurve, OperationSide, TimeBondingCurve, TimeInvariant};
+///
+/// let curve = TimeInvariant(LinearBondingCurve::new(100, 1000));
+/// assert_eq!(curve.price_at(10, 0), curve.price_at(10, 3_600));
+/// assert_eq!(curve.price_many_at(0, 2, OperationSide::Add, 60), 2100);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimeInvariant<C>(pub C);
+
+impl<T, C: BondingCurve<T>> TimeBondingCurve<T> for TimeInvariant<C> {
+    /// Returns `calculate_price` of the wrapped curve, whatever the time.
+    fn price_at(&self, supply: u64, _elapsed: u64) -> T {
+        self.0.calculate_price(supply)
+    }
+
+    /// Returns `calculate_price_many` of the wrapped curve, whatever the time.
+    fn price_many_at(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        _elapsed: u64,
+    ) -> T
+    where
+        T: Sum<T>,
+    {
+        self.0.calculate_price_many(starting_supply, amount, side)
+    }
+}
+
+/// Represents a descending-price (Dutch) auction layered on top of a bonding curve.
+///
+/// The curve's price is discounted by `decay` for every unit of elapsed time, and never
+/// drops below `floor`:
+///
+/// ```ignore
+/// f(x, t) = max(curve(x) - decay * t, floor)
+/// ```
+///
+/// # Fields
+///
+/// * `curve`: The bonding curve that sets the starting price at each supply.
+/// * `decay`: The price reduction per unit of elapsed time.
+/// * `floor`: The lowest price the auction can reach.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{DutchAuction, LinearBondingCurve, TimeBondingCurve};
+///
+/// let auction = DutchAuction::new(LinearBondingCurve::new(0, 1_000), 10, 200);
+/// assert_eq!(auction.price_at(0, 0), 1_000);
+/// assert_eq!(auction.price_at(0, 30), 700);
+/// assert_eq!(auction.price_at(0, 1_000), 200);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DutchAuction<C> {
+    pub curve: C,
+    pub decay: u64,
+    pub floor: u64,
+}
+
+impl<C: BondingCurve<u64>> DutchAuction<C> {
+    /// Creates a new `DutchAuction`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The bonding curve that sets the starting price at each supply.
+    /// * `decay` - The price reduction per unit of elapsed time.
+    /// * `floor` - The lowest price the auction can reach.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `DutchAuction`.
+    pub fn new(curve: C, decay: u64, floor: u64) -> Self {
+        Self {
+            curve,
+            decay,
+            floor,
+        }
+    }
+}
+
+impl<C: BondingCurve<u64>> TimeBondingCurve<u64> for DutchAuction<C> {
+    fn price_at(&self, supply: u64, elapsed: u64) -> u64 {
+        let discount = self.decay.saturating_mul(elapsed);
+        self.curve
+            .calculate_price(supply)
+            .saturating_sub(discount)
+            .max(self.floor)
+    }
+}
+
+/// Represents a curve whose price decays toward a floor while nobody buys.
+///
+/// This is a resource sink for game economies: the longer a resource goes unbought, the
+/// cheaper it gets, and every purchase resets the clock. The part of the price above `floor`
+/// halves every `half_life` units of time since the last purchase, interpolating linearly
+/// within each half-life:
+///
+/// ```ignore
+/// f(x, t) = floor + (curve(x) - floor) * 2^(-(t - last_purchase) / half_life)
+/// ```
+///
+/// Prices at or below `floor` do not decay. `TimeBondingCurve::price_at` takes the current
+/// time, measured from the same origin as `last_purchase`.
+///
+/// # Fields
+///
+/// * `curve`: The bonding curve that sets the undecayed price at each supply.
+/// * `floor`: The price the decay approaches.
+/// * `half_life`: The time it takes the price above `floor` to halve. Must not be zero.
+/// * `last_purchase`: The time of the last purchase.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{InactivityDecay, LinearBondingCurve, TimeBondingCurve};
+///
+/// let mut decay = InactivityDecay::new(LinearBondingCurve::new(0, 1_000), 200, 100, 0);
+/// assert_eq!(decay.price_at(0, 0), 1_000);
+/// assert_eq!(decay.price_at(0, 100), 600);
+/// assert_eq!(decay.price_at(0, 200), 400);
+/// assert_eq!(decay.buy(0, 1, 200), Ok(400));
+/// assert_eq!(decay.price_at(1, 200), 1_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InactivityDecay<C> {
+    pub curve: C,
+    pub floor: u64,
+    pub half_life: u64,
+    pub last_purchase: u64,
+}
+
+impl<C: BondingCurve<u64>> InactivityDecay<C> {
+    /// Creates a new `InactivityDecay`.
+    ///
+    /// # Arguments
+    ///
+    /// * `curve` - The bonding curve that sets the undecayed price at each supply.
+    /// * `floor` - The price the decay approaches.
+    /// * `half_life` - The time it takes the price above `floor` to halve. Must not be zero.
+    /// * `last_purchase` - The time of the last purchase, or of the launch.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `InactivityDecay`.
+    pub fn new(curve: C, floor: u64, half_life: u64, last_purchase: u64) -> Self {
+        assert!(half_life > 0, "half_life must not be zero");
+        Self {
+            curve,
+            floor,
+            half_life,
+            last_purchase,
+        }
+    }
+
+    /// Prices a purchase at the current decayed price and resets the decay clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `supply` - The supply before the purchase.
+    /// * `amount` - The number of tokens bought. Buying nothing does not reset the clock.
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total price, or `BondingCurveError::Overflow` if the supply
+    /// or the total price does not fit in a `u64`, in which case the clock is not reset.
+    pub fn buy(&mut self, supply: u64, amount: u64, now: u64) -> Result<u64, BondingCurveError> {
+        let Some((first, last)) = supply_range(supply, amount, OperationSide::Add)? else {
+            return Ok(0);
+        };
+        let cost = (first..=last).try_fold(0u64, |total, supply| {
+            total
+                .checked_add(self.price_at(supply, now))
+                .ok_or(BondingCurveError::Overflow)
+        })?;
+        self.last_purchase = now;
+        Ok(cost)
+    }
+}
+
+impl<C: BondingCurve<u64>> TimeBondingCurve<u64> for InactivityDecay<C> {
+    fn price_at(&self, supply: u64, now: u64) -> u64 {
+        let price = self.curve.calculate_price(supply);
+        let Some(excess) = price.checked_sub(self.floor) else {
+            return price;
+        };
+        let idle = now.saturating_sub(self.last_purchase);
+        let halvings = idle / self.half_life;
+        if halvings >= u64::BITS as u64 {
+            return self.floor;
+        }
+        let decayed = excess >> halvings;
+        let within = (idle % self.half_life) as u128;
+        let step = (decayed / 2) as u128 * within / self.half_life as u128;
+        self.floor + decayed - step as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurve, BondingCurveError, DutchAuction, InactivityDecay, LinearBondingCurve,
+        OperationSide, QuadraticBondingCurve, TimeBondingCurve, TimeInvariant,
+    };
+
+    #[test]
+    pub fn test_time_invariant() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let timed = TimeInvariant(curve);
+        for elapsed in [0, 1, u64::MAX] {
+            assert_eq!(timed.price_at(100, elapsed), curve.calculate_price(100));
+            assert_eq!(
+                timed.price_many_at(100, 10, OperationSide::Remove, elapsed),
+                curve.calculate_price_many(100, 10, OperationSide::Remove)
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_dutch_auction() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let auction = DutchAuction::new(curve, 1_000_000, 600_000_000);
+        assert_eq!(auction.price_at(10, 0), curve.calculate_price(10));
+        assert_eq!(
+            auction.price_at(10, 100),
+            curve.calculate_price(10) - 100_000_000
+        );
+        assert_eq!(auction.price_at(10, u64::MAX), 600_000_000);
+        let many = auction.price_many_at(10, 3, OperationSide::Add, 100);
+        let expected: u64 = (10..13).map(|x| auction.price_at(x, 100)).sum();
+        assert_eq!(many, expected);
+    }
+
+    #[test]
+    pub fn test_inactivity_decay() {
+        let curve = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let mut decay = InactivityDecay::new(curve, 900_000_000, 3_600, 1_000);
+        let price = curve.calculate_price(20);
+        let excess = price - 900_000_000;
+        assert_eq!(decay.price_at(20, 500), price);
+        assert_eq!(decay.price_at(20, 1_000 + 3_600), 900_000_000 + excess / 2);
+        assert_eq!(
+            decay.price_at(20, 1_000 + 5_400),
+            900_000_000 + excess / 2 - excess / 8
+        );
+        assert_eq!(decay.price_at(20, u64::MAX), 900_000_000);
+        let mut previous = price;
+        for now in (1_000..20_000).step_by(100) {
+            let decayed = decay.price_at(20, now);
+            assert!(decayed <= previous);
+            previous = decayed;
+        }
+
+        let expected = decay.price_many_at(20, 2, OperationSide::Add, 8_200);
+        assert_eq!(decay.buy(20, 2, 8_200), Ok(expected));
+        assert_eq!(decay.last_purchase, 8_200);
+        assert_eq!(decay.price_at(22, 8_200), curve.calculate_price(22));
+        assert_eq!(decay.buy(22, 0, 9_000), Ok(0));
+        assert_eq!(decay.last_purchase, 8_200);
+    }
+
+    #[test]
+    pub fn test_inactivity_decay_below_floor() {
+        let mut decay = InactivityDecay::new(LinearBondingCurve::new(1, u64::MAX - 1), 5, 10, 0);
+        assert_eq!(decay.price_at(0, 1_000), 5);
+        assert_eq!(
+            decay.buy(u64::MAX, 2, 1_000),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(decay.last_purchase, 0);
+        let flat = InactivityDecay::new(LinearBondingCurve::new(0, 3), 5, 10, 0);
+        assert_eq!(flat.price_at(0, 1_000), 3);
+    }
+}
@@ -44,7 +44,7 @@ pub fn float_to_fixed_point(value: f64, decimals: u8) -> u64 {
 ///
 /// ```
 /// use magic_curves::fixed_point_to_float;
-/// 
+///
 /// let floating = fixed_point_to_float(314, 2);
 /// assert_eq!(floating, 3.14);
 /// ```
@@ -52,7 +52,197 @@ pub fn fixed_point_to_float(value: u64, decimals: u8) -> f64 {
     value as f64 / 10u64.pow(decimals as u32) as f64
 }
 
+/// Checks whether two prices differ by at most `epsilon`.
+///
+/// # Arguments
+///
+/// * `a` - The first price.
+/// * `b` - The second price.
+/// * `epsilon` - The largest allowed absolute difference.
+///
+/// # Returns
+///
+/// `true` if the prices are equal within `epsilon`. `NaN` is never equal to anything.
+///
+/// # Examples
+///
+/// ```
+/// use magic_curves::abs_diff_eq;
+///
+/// assert!(abs_diff_eq(0.1 + 0.2, 0.3, 1e-12));
+/// assert!(!abs_diff_eq(1.0, 1.1, 1e-12));
+/// ```
+pub fn abs_diff_eq(a: f64, b: f64, epsilon: f64) -> bool {
+    a == b || (a - b).abs() <= epsilon
+}
+
+/// Checks whether two prices differ by at most `max_relative` times the larger of them.
+///
+/// This is the comparison to use for prices that span many orders of magnitude, where a
+/// fixed `epsilon` is either too loose for small prices or too strict for large ones.
+///
+/// # Arguments
+///
+/// * `a` - The first price.
+/// * `b` - The second price.
+/// * `max_relative` - The largest allowed difference, relative to the larger magnitude.
+///
+/// # Returns
+///
+/// `true` if the prices are equal within `max_relative`. `NaN` is never equal to anything.
+///
+/// # Examples
+///
+/// ```
+/// use magic_curves::relative_eq;
+///
+/// assert!(relative_eq(1_000_000.0, 1_000_000.5, 1e-6));
+/// assert!(!relative_eq(1.0, 1.5, 1e-6));
+/// ```
+pub fn relative_eq(a: f64, b: f64, max_relative: f64) -> bool {
+    a == b || (a - b).abs() <= a.abs().max(b.abs()) * max_relative
+}
+
+/// Checks whether a float price matches a fixed-point price within `epsilon` of the
+/// fixed-point unit, such as one lamport when `decimals` is 9.
+///
+/// # Arguments
+///
+/// * `price` - The floating-point price.
+/// * `fixed` - The fixed-point price.
+/// * `decimals` - The number of decimal places used in the fixed-point representation.
+/// * `epsilon` - The largest allowed difference, in fixed-point units.
+///
+/// # Returns
+///
+/// `true` if the prices are equal within `epsilon` units.
+///
+/// # Examples
+///
+/// ```
+/// use magic_curves::fixed_point_approx_eq;
+///
+/// assert!(fixed_point_approx_eq(3.1415, 3_141_500_001, 9, 1.0));
+/// assert!(!fixed_point_approx_eq(3.1415, 3_141_500_002, 9, 1.0));
+/// ```
+pub fn fixed_point_approx_eq(price: f64, fixed: u64, decimals: u8, epsilon: f64) -> bool {
+    let scale = 10u64.pow(decimals as u32) as f64;
+    abs_diff_eq(price * scale, fixed as f64, epsilon)
+}
+
+/// Represents how closely two float prices must agree to be considered equal, combining an
+/// absolute and a relative epsilon.
+///
+/// Two prices are equal when their difference is within `absolute`, or within `relative`
+/// times the larger of them, so `absolute` covers prices near zero and `relative` the rest.
+/// The default is a relative tolerance of `1e-9`, about a million times the precision of an
+/// `f64`.
+///
+/// # Fields
+///
+/// * `absolute`: The largest allowed absolute difference.
+/// * `relative`: The largest allowed difference, relative to the larger magnitude.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::Tolerance;
+///
+/// let tolerance = Tolerance::new(1e-6, 1e-3);
+/// assert!(tolerance.approx_eq(0.0, 5e-7));
+/// assert!(tolerance.approx_eq(1_000.0, 1_000.5));
+/// assert!(!tolerance.approx_eq(1.0, 1.01));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::relative(1e-9)
+    }
+}
+
+impl Tolerance {
+    /// A tolerance that only accepts equal prices.
+    pub const EXACT: Tolerance = Tolerance {
+        absolute: 0.0,
+        relative: 0.0,
+    };
+
+    /// Creates a new `Tolerance`.
+    ///
+    /// # Arguments
+    ///
+    /// * `absolute` - The largest allowed absolute difference.
+    /// * `relative` - The largest allowed difference, relative to the larger magnitude.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Tolerance`.
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Creates a `Tolerance` with only an absolute epsilon, as checked by `abs_diff_eq`.
+    pub fn absolute(epsilon: f64) -> Self {
+        Self::new(epsilon, 0.0)
+    }
+
+    /// Creates a `Tolerance` with only a relative epsilon, as checked by `relative_eq`.
+    pub fn relative(max_relative: f64) -> Self {
+        Self::new(0.0, max_relative)
+    }
+
+    /// Checks whether two prices are equal within the tolerance. `NaN` is never equal to
+    /// anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first price.
+    /// * `b` - The second price.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the prices are equal within `absolute` or `relative`.
+    pub fn approx_eq(&self, a: f64, b: f64) -> bool {
+        abs_diff_eq(a, b, self.absolute) || relative_eq(a, b, self.relative)
+    }
+}
+
+/// Asserts that two prices are equal within a relative tolerance, as checked by
+/// `relative_eq`.
+///
+/// On failure, the panic message shows both prices and their difference.
+///
+/// # Examples
+///
+/// ```
+/// use magic_curves::{assert_price_eq, BondingCurve, SigmoidBondingCurve};
+///
+/// let curve = SigmoidBondingCurve::new(1000.0, 0.01, 500);
+/// assert_price_eq!(curve.calculate_price(500), 500.0, 1e-12);
+/// ```
+#[macro_export]
+macro_rules! assert_price_eq {
+    ($a:expr, $b:expr, $max_relative:expr $(,)?) => {{
+        let (a, b, max_relative): (f64, f64, f64) = ($a, $b, $max_relative);
+        assert!(
+            $crate::relative_eq(a, b, max_relative),
+            "prices differ: {} != {} (difference {}, max relative {})",
+            a,
+            b,
+            (a - b).abs(),
+            max_relative
+        );
+    }};
+}
+
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
 
     #[test]
@@ -71,4 +261,34 @@ mod tests {
         assert_eq!(crate::fixed_point_to_float(12, 1), 1.2);
         assert_eq!(crate::fixed_point_to_float(12345, 5), 0.12345);
     }
+
+    #[test]
+    fn test_approximate_equality() {
+        assert!(crate::abs_diff_eq(1.0, 1.0 + 1e-10, 1e-9));
+        assert!(!crate::abs_diff_eq(f64::NAN, f64::NAN, 1.0));
+        assert!(crate::relative_eq(f64::INFINITY, f64::INFINITY, 0.0));
+        assert!(crate::relative_eq(1e12, 1e12 + 1.0, 1e-9));
+        assert!(!crate::relative_eq(1e-12, 2e-12, 1e-9));
+        assert!(crate::fixed_point_approx_eq(450.166, 45_016_600, 5, 0.5));
+        crate::assert_price_eq!(0.1 + 0.2, 0.3, 1e-15);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        use crate::Tolerance;
+
+        assert!(Tolerance::EXACT.approx_eq(2.5, 2.5));
+        assert!(!Tolerance::EXACT.approx_eq(0.1 + 0.2, 0.3));
+        assert!(Tolerance::default().approx_eq(0.1 + 0.2, 0.3));
+        assert!(!Tolerance::default().approx_eq(0.0, 1e-300));
+        assert!(Tolerance::absolute(1e-300).approx_eq(0.0, 1e-300));
+        assert!(Tolerance::relative(1e-9).approx_eq(1e12, 1e12 + 1.0));
+        assert!(!Tolerance::new(1.0, 1.0).approx_eq(f64::NAN, f64::NAN));
+    }
+
+    #[test]
+    #[should_panic(expected = "prices differ")]
+    fn test_assert_price_eq_fails() {
+        crate::assert_price_eq!(1.0, 1.001, 1e-6);
+    }
 }
@@ -0,0 +1,265 @@
+use super::{bps_of, BondingCurveError, OperationSide, TradeResult, BASIS_POINTS};
+
+/// Represents the tax and royalty policy that splits every trade on a `CurveState` across the
+/// tranches of its reserve.
+///
+/// The liquidity tranche backs the curve: it receives the price of every purchase and pays
+/// the price of every sale. On top of that, each trade pays `treasury_tax` basis points of its
+/// price to the treasury and `creator_royalty` basis points to the creator, both rounded down.
+/// A buyer pays them on top of the price, and a seller has them taken out of the proceeds.
+///
+/// # Fields
+///
+/// * `treasury_tax`: The tax paid to the treasury, in basis points of the price.
+/// * `creator_royalty`: The royalty paid to the creator, in basis points of the price.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranchePolicy {
+    pub treasury_tax: u64,
+    pub creator_royalty: u64,
+}
+
+/// Represents the balances of the tranches of a reserve.
+///
+/// # Fields
+///
+/// * `liquidity`: The part of the reserve that backs the curve.
+/// * `treasury`: The taxes collected for the treasury.
+/// * `creator`: The royalties collected for the creator.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrancheBalances {
+    pub liquidity: u64,
+    pub treasury: u64,
+    pub creator: u64,
+}
+
+/// Represents a reserve split into tranches, together with the policy that updates them.
+///
+/// # Fields
+///
+/// * `policy`: The tax and royalty policy applied to every trade.
+/// * `balances`: The current balance of every tranche.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     OperationSide, ReserveTranches, TradeResult, TrancheBalances, TranchePolicy,
+/// };
+///
+/// // A 1% treasury tax and a 0.5% creator royalty.
+/// let policy = TranchePolicy::new(100, 50);
+/// let tranches = ReserveTranches::new(policy, TrancheBalances::default());
+/// let trade = TradeResult { side: OperationSide::Add, amount: 10, cost: 14_500, supply: 10, fee: 0 };
+/// assert_eq!(policy.total(&trade), Ok(14_717));
+/// let balances = tranches.settle(&trade).unwrap().balances;
+/// assert_eq!((balances.liquidity, balances.treasury, balances.creator), (14_500, 145, 72));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReserveTranches {
+    pub policy: TranchePolicy,
+    pub balances: TrancheBalances,
+}
+
+impl TranchePolicy {
+    /// Creates a new `TranchePolicy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `treasury_tax` - The tax paid to the treasury, in basis points of the price.
+    /// * `creator_royalty` - The royalty paid to the creator, in basis points of the price.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `TranchePolicy`.
+    pub fn new(treasury_tax: u64, creator_royalty: u64) -> Self {
+        Self {
+            treasury_tax,
+            creator_royalty,
+        }
+    }
+
+    /// Checks that the tax and royalty together take at most the whole price.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or `BondingCurveError::InvalidParameter` if they add up to more than
+    /// `BASIS_POINTS`.
+    pub fn validate(&self) -> Result<(), BondingCurveError> {
+        match self.treasury_tax.checked_add(self.creator_royalty) {
+            Some(total) if total <= BASIS_POINTS => Ok(()),
+            _ => Err(BondingCurveError::InvalidParameter),
+        }
+    }
+
+    /// Returns the treasury tax and creator royalty on a price.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The price of a trade.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the tax and the royalty, or `BondingCurveError::InvalidParameter`
+    /// if the policy is not valid.
+    pub fn taxes(&self, price: u64) -> Result<(u64, u64), BondingCurveError> {
+        self.validate()?;
+        Ok((
+            bps_of(price, self.treasury_tax)?,
+            bps_of(price, self.creator_royalty)?,
+        ))
+    }
+
+    /// Returns what the trader pays for a purchase, or receives for a sale, taxes included.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - The trade to settle.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the total, `BondingCurveError::InvalidParameter` if the policy is
+    /// not valid, or `BondingCurveError::Overflow` if a purchase does not fit in a `u64`.
+    pub fn total(&self, trade: &TradeResult) -> Result<u64, BondingCurveError> {
+        let (tax, royalty) = self.taxes(trade.cost)?;
+        match trade.side {
+            OperationSide::Add => trade
+                .cost
+                .checked_add(tax)
+                .and_then(|total| total.checked_add(royalty))
+                .ok_or(BondingCurveError::Overflow),
+            OperationSide::Remove => Ok(trade.cost - tax - royalty),
+        }
+    }
+}
+
+impl ReserveTranches {
+    /// Creates a new `ReserveTranches`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The tax and royalty policy applied to every trade.
+    /// * `balances` - The starting balance of every tranche.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `ReserveTranches`.
+    pub fn new(policy: TranchePolicy, balances: TrancheBalances) -> Self {
+        Self { policy, balances }
+    }
+
+    /// Returns the tranches after a trade.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - The trade to settle.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the updated tranches, `BondingCurveError::InsufficientReserve` if
+    /// the liquidity tranche cannot pay for a sale, `BondingCurveError::Overflow` if a balance
+    /// does not fit in a `u64`, or `BondingCurveError::InvalidParameter` if the policy is not
+    /// valid.
+    pub fn settle(&self, trade: &TradeResult) -> Result<Self, BondingCurveError> {
+        let (tax, royalty) = self.policy.taxes(trade.cost)?;
+        let liquidity = match trade.side {
+            OperationSide::Add => self
+                .balances
+                .liquidity
+                .checked_add(trade.cost)
+                .ok_or(BondingCurveError::Overflow)?,
+            OperationSide::Remove => self
+                .balances
+                .liquidity
+                .checked_sub(trade.cost)
+                .ok_or(BondingCurveError::InsufficientReserve)?,
+        };
+        Ok(Self {
+            policy: self.policy,
+            balances: TrancheBalances {
+                liquidity,
+                treasury: self
+                    .balances
+                    .treasury
+                    .checked_add(tax)
+                    .ok_or(BondingCurveError::Overflow)?,
+                creator: self
+                    .balances
+                    .creator
+                    .checked_add(royalty)
+                    .ok_or(BondingCurveError::Overflow)?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveError, OperationSide, ReserveTranches, TradeResult, TrancheBalances,
+        TranchePolicy, BASIS_POINTS,
+    };
+
+    #[test]
+    pub fn test_reserve_tranches_settle() {
+        let policy = TranchePolicy::new(250, 100);
+        let tranches = ReserveTranches::new(policy, TrancheBalances::default());
+        let buy = TradeResult {
+            side: OperationSide::Add,
+            amount: 10,
+            cost: 10_000,
+            supply: 10,
+            fee: 0,
+        };
+        let bought = tranches.settle(&buy).unwrap();
+        assert_eq!(
+            bought.balances,
+            TrancheBalances {
+                liquidity: 10_000,
+                treasury: 250,
+                creator: 100
+            }
+        );
+        assert_eq!(policy.total(&buy), Ok(10_350));
+
+        let sell = TradeResult {
+            side: OperationSide::Remove,
+            amount: 4,
+            cost: 3_000,
+            supply: 6,
+            fee: 0,
+        };
+        let sold = bought.settle(&sell).unwrap();
+        assert_eq!(
+            sold.balances,
+            TrancheBalances {
+                liquidity: 7_000,
+                treasury: 325,
+                creator: 130
+            }
+        );
+        assert_eq!(policy.total(&sell), Ok(2_895));
+        assert_eq!(
+            tranches.settle(&sell),
+            Err(BondingCurveError::InsufficientReserve)
+        );
+    }
+
+    #[test]
+    pub fn test_tranche_policy_validate() {
+        assert_eq!(TranchePolicy::new(BASIS_POINTS, 0).validate(), Ok(()));
+        assert_eq!(
+            TranchePolicy::new(BASIS_POINTS, 1).validate(),
+            Err(BondingCurveError::InvalidParameter)
+        );
+        assert_eq!(
+            TranchePolicy::new(u64::MAX, 1).taxes(100),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+}
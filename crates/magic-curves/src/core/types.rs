@@ -1,4 +1,14 @@
-use super::BondingCurveError;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+#[cfg(not(feature = "panic-free"))]
+use std::{iter::Sum, ops::RangeInclusive};
+
+#[cfg(not(feature = "panic-free"))]
+use super::PriceStream;
+use super::{BondingCurveError, ItemizedQuote, QuoteItem};
 
 /// Represents the side of an operation in a bonding curve.
 ///
@@ -10,11 +20,116 @@ use super::BondingCurveError;
 /// * `Add` - Represents an operation that adds tokens to the supply.
 /// * `Remove` - Represents an operation that removes tokens from the supply.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum OperationSide {
     Add,
     Remove,
 }
 
+/// Represents the family (shape) of a bonding curve.
+///
+/// This enum is used to select which kind of curve an operation such as fitting
+/// should work with, and to tag curves in serialized form.
+///
+/// # Variants
+///
+/// * `Linear` - A `LinearBondingCurve`.
+/// * `Quadratic` - A `QuadraticBondingCurve`.
+/// * `Exponential` - An `ExponentialBondingCurve`.
+/// * `Logarithmic` - A `LogarithmicBondingCurve`.
+/// * `Sigmoid` - A `SigmoidBondingCurve`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CurveFamily {
+    Linear,
+    Quadratic,
+    Exponential,
+    Logarithmic,
+    Sigmoid,
+}
+
+impl CurveFamily {
+    /// Returns the byte used to tag this family in serialized curves.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::CurveFamily;
+    ///
+    /// assert_eq!(CurveFamily::from_tag(CurveFamily::Sigmoid.tag()), Some(CurveFamily::Sigmoid));
+    /// ```
+    pub fn tag(self) -> u8 {
+        match self {
+            CurveFamily::Linear => 0,
+            CurveFamily::Quadratic => 1,
+            CurveFamily::Exponential => 2,
+            CurveFamily::Logarithmic => 3,
+            CurveFamily::Sigmoid => 4,
+        }
+    }
+
+    /// Returns the family tagged with `tag`, if any.
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(CurveFamily::Linear),
+            1 => Some(CurveFamily::Quadratic),
+            2 => Some(CurveFamily::Exponential),
+            3 => Some(CurveFamily::Logarithmic),
+            4 => Some(CurveFamily::Sigmoid),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CurveFamily {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            CurveFamily::Linear => "linear",
+            CurveFamily::Quadratic => "quadratic",
+            CurveFamily::Exponential => "exponential",
+            CurveFamily::Logarithmic => "logarithmic",
+            CurveFamily::Sigmoid => "sigmoid",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for CurveFamily {
+    type Err = ParseCurveFamilyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "linear" => Ok(CurveFamily::Linear),
+            "quadratic" => Ok(CurveFamily::Quadratic),
+            "exponential" => Ok(CurveFamily::Exponential),
+            "logarithmic" => Ok(CurveFamily::Logarithmic),
+            "sigmoid" => Ok(CurveFamily::Sigmoid),
+            _ => Err(ParseCurveFamilyError(s.to_string())),
+        }
+    }
+}
+
+/// Represents an error parsing a `CurveFamily` from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCurveFamilyError(pub String);
+
+impl Display for ParseCurveFamilyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Unknown curve family `{}`.", self.0)
+    }
+}
+
+impl Error for ParseCurveFamilyError {}
+
 /// Represents a bonding curve for token pricing.
 ///
 /// This trait defines the interface for a bonding curve, which is used to calculate
@@ -37,6 +152,7 @@ pub enum OperationSide {
 /// * `ExponentialBondingCurve` - An exponential bonding curve.
 /// * `LogarithmicBondingCurve` - A logarithmic bonding curve.
 /// * `SigmoidBondingCurve` - A sigmoid bonding curve.
+#[cfg(not(feature = "panic-free"))]
 pub trait BondingCurve<T> {
     /// Calculates the price for a single token at the given supply.
     ///
@@ -51,6 +167,12 @@ pub trait BondingCurve<T> {
 
     /// Calculates the total price for a given amount of tokens starting from a specific supply.
     ///
+    /// # Semantics
+    ///
+    /// * An `amount` of zero costs nothing, on either side.
+    /// * `OperationSide::Remove` with `amount == starting_supply` is a full exit, which brings
+    ///   the supply down to zero.
+    ///
     /// # Arguments
     ///
     /// * `starting_supply` - The initial supply before the operation.
@@ -60,7 +182,153 @@ pub trait BondingCurve<T> {
     /// # Returns
     ///
     /// The total price for the specified amount of tokens.
-    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> T;
+    ///
+    /// # Default
+    ///
+    /// The provided implementation sums `calculate_price` over every priced supply, so a custom
+    /// curve only needs its spot price formula. It is correct but linear in `amount`; the
+    /// built-in curves override it with closed-form formulas. It panics when removing more
+    /// than `starting_supply` or when adding past `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, OperationSide};
+    ///
+    /// struct Doubling;
+    ///
+    /// impl BondingCurve<u64> for Doubling {
+    ///     fn calculate_price(&self, supply: u64) -> u64 {
+    ///         2 * supply
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Doubling.calculate_price_many(1, 3, OperationSide::Add), 2 + 4 + 6);
+    /// assert_eq!(Doubling.calculate_price_many(3, 3, OperationSide::Remove), 2 + 4 + 6);
+    /// ```
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> T
+    where
+        T: Sum<T>,
+    {
+        match supply_range(starting_supply, amount, side) {
+            Ok(Some((first, last))) => (first..=last)
+                .map(|supply| self.calculate_price(supply))
+                .sum(),
+            Ok(None) => std::iter::empty().sum(),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns a lazy stream of the spot prices at `start_supply` and every supply after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_supply` - The supply of the first price in the stream.
+    ///
+    /// # Returns
+    ///
+    /// A `PriceStream`, which ends after the price at `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, LinearBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let prices: Vec<u64> = curve.price_stream(0).step_by(10).take(3).collect();
+    /// assert_eq!(prices, vec![1000, 2000, 3000]);
+    /// ```
+    fn price_stream(&self, start_supply: u64) -> PriceStream<'_, Self, T> {
+        PriceStream::new(self, start_supply)
+    }
+
+    /// Prices every supply in a range, handing the prices to `callback` in chunks of at most
+    /// `chunk_size`, so exporters can stream huge ranges with bounded memory.
+    ///
+    /// One buffer of `chunk_size` prices is allocated and reused for every chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `supplies` - The supply range to price.
+    /// * `chunk_size` - The largest number of prices per chunk.
+    /// * `callback` - Called with the supply of the first price in the chunk, and the chunk.
+    ///
+    /// # Returns
+    ///
+    /// `BondingCurveError::InvalidParameter` if `chunk_size` is zero, before any price.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurve, LinearBondingCurve};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let mut chunks = Vec::new();
+    /// curve
+    ///     .for_each_price(0..=4, 2, |start, prices| chunks.push((start, prices.to_vec())))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     chunks,
+    ///     vec![(0, vec![1000, 1100]), (2, vec![1200, 1300]), (4, vec![1400])]
+    /// );
+    /// ```
+    fn for_each_price<F>(
+        &self,
+        supplies: RangeInclusive<u64>,
+        chunk_size: usize,
+        mut callback: F,
+    ) -> Result<(), BondingCurveError>
+    where
+        Self: Sized,
+        F: FnMut(u64, &[T]),
+    {
+        if chunk_size == 0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let (mut start, end) = supplies.into_inner();
+        if start > end {
+            return Ok(());
+        }
+        let mut prices = Vec::with_capacity(chunk_size);
+        loop {
+            // `chunk_size - 1` tokens past `start`, clamped to the end of the range.
+            let last = start.saturating_add(chunk_size as u64 - 1).min(end);
+            prices.clear();
+            prices.extend((start..=last).map(|supply| self.calculate_price(supply)));
+            callback(start, &prices);
+            if last == end {
+                return Ok(());
+            }
+            start = last + 1;
+        }
+    }
+}
+
+/// Expands to the given items unless the `panic-free` feature compiled the unchecked
+/// `BondingCurve` trait out, so the code generated by `polynomial_curve!` and the derive
+/// macro follows the features of this crate rather than those of the calling crate.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "panic-free"))]
+macro_rules! __unchecked {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+/// With the `panic-free` feature the unchecked trait does not exist, so no curve implements
+/// it:
+///
+/// ```compile_fail,E0432
+/// use magic_curves::{BondingCurve, LinearBondingCurve};
+///
+/// LinearBondingCurve::new(100, 1000).calculate_price(10);
+/// ```
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "panic-free")]
+macro_rules! __unchecked {
+    ($($item:item)*) => {};
 }
 
 /// Represents a bonding curve with checked operations for token pricing.
@@ -104,4 +372,361 @@ pub trait BondingCurveWithCheckedOperations<T> {
         amount: u64,
         side: OperationSide,
     ) -> Result<T, BondingCurveError>;
+
+    /// Quotes a batch of tokens along with the price of every token in it, such as for a
+    /// mint page that lists "token #101 costs X, token #102 costs Y".
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `ItemizedQuote` with one item per token, or a
+    /// `BondingCurveError` if any price fails. Use `quote_itemized_chunked` for large amounts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide};
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let quote = curve.quote_itemized(100, 2, OperationSide::Add).unwrap();
+    /// assert_eq!(quote.items[0].price, 11_000);
+    /// assert_eq!(quote.items[1].price, 11_100);
+    /// assert_eq!(quote.total, 22_100);
+    /// ```
+    fn quote_itemized(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<ItemizedQuote<T>, BondingCurveError> {
+        self.quote_itemized_chunked(starting_supply, amount, side, 1)
+    }
+
+    /// Quotes a batch of tokens along with the price of every `chunk` tokens in it.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    /// * `chunk` - The number of tokens per item. The last item holds the remainder.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `ItemizedQuote`, `BondingCurveError::InvalidParameter` if
+    /// `chunk` is zero, or the first `BondingCurveError` a price fails with.
+    fn quote_itemized_chunked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        chunk: u64,
+    ) -> Result<ItemizedQuote<T>, BondingCurveError> {
+        if chunk == 0 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let total = self.calculate_price_many_checked(starting_supply, amount, side)?;
+        let mut items = Vec::new();
+        let (mut supply, mut remaining) = (starting_supply, amount);
+        while remaining > 0 {
+            let size = remaining.min(chunk);
+            items.push(QuoteItem {
+                starting_supply: supply,
+                amount: size,
+                price: self.calculate_price_many_checked(supply, size, side)?,
+            });
+            // The total succeeded, so the supply stays in range.
+            supply = match side {
+                OperationSide::Add => supply + size,
+                OperationSide::Remove => supply - size,
+            };
+            remaining -= size;
+        }
+        Ok(ItemizedQuote { items, total })
+    }
+
+    /// Quotes a batch of tokens like `quote_itemized_chunked`, but writes the items into a
+    /// buffer instead of allocating, for contexts that forbid allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_supply` - The initial supply before the operation.
+    /// * `amount` - The number of tokens to add or remove.
+    /// * `side` - Specifies whether tokens are being added or removed.
+    /// * `chunk` - The number of tokens per item. The last item holds the remainder.
+    /// * `items` - The buffer the items are written to, from the start.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of items written and the total price,
+    /// `BondingCurveError::InvalidParameter` if `chunk` is zero or `items` cannot hold every
+    /// item, or the first `BondingCurveError` a price fails with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{
+    ///     BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide, QuoteItem,
+    /// };
+    ///
+    /// let curve = LinearBondingCurve::new(100, 1000);
+    /// let mut items = [QuoteItem { starting_supply: 0, amount: 0, price: 0 }; 4];
+    /// let (len, total) = curve
+    ///     .quote_itemized_into(100, 5, OperationSide::Add, 2, &mut items)
+    ///     .unwrap();
+    /// assert_eq!((len, total), (3, 56_000));
+    /// assert_eq!(items[2].amount, 1);
+    /// ```
+    fn quote_itemized_into(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+        chunk: u64,
+        items: &mut [QuoteItem<T>],
+    ) -> Result<(usize, T), BondingCurveError> {
+        if chunk == 0 || amount.div_ceil(chunk) > items.len() as u64 {
+            return Err(BondingCurveError::InvalidParameter);
+        }
+        let total = self.calculate_price_many_checked(starting_supply, amount, side)?;
+        let (mut supply, mut remaining, mut len) = (starting_supply, amount, 0);
+        while remaining > 0 {
+            let size = remaining.min(chunk);
+            items[len] = QuoteItem {
+                starting_supply: supply,
+                amount: size,
+                price: self.calculate_price_many_checked(supply, size, side)?,
+            };
+            // The total succeeded, so the supply stays in range.
+            supply = match side {
+                OperationSide::Add => supply + size,
+                OperationSide::Remove => supply - size,
+            };
+            remaining -= size;
+            len += 1;
+        }
+        Ok((len, total))
+    }
+}
+
+/// Lets a borrowed curve, including a `&dyn BondingCurveWithCheckedOperations<T>`, be used
+/// wherever an owned curve is expected.
+impl<T, C: BondingCurveWithCheckedOperations<T> + ?Sized> BondingCurveWithCheckedOperations<T>
+    for &C
+{
+    fn calculate_price_checked(&self, supply: u64) -> Result<T, BondingCurveError> {
+        (**self).calculate_price_checked(supply)
+    }
+
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<T, BondingCurveError> {
+        (**self).calculate_price_many_checked(starting_supply, amount, side)
+    }
+}
+
+/// Returns the first and last supply priced by an operation, or `None` if `amount` is zero.
+///
+/// Fails with `BondingCurveError::SupplyUnderflow` when removing more than `starting_supply`,
+/// and with `BondingCurveError::Overflow` when adding past `u64::MAX`.
+pub(crate) fn supply_range(
+    starting_supply: u64,
+    amount: u64,
+    side: OperationSide,
+) -> Result<Option<(u64, u64)>, BondingCurveError> {
+    if amount == 0 {
+        return Ok(None);
+    }
+    match side {
+        OperationSide::Add => starting_supply
+            .checked_add(amount - 1)
+            .map(|last| Some((starting_supply, last)))
+            .ok_or(BondingCurveError::Overflow),
+        OperationSide::Remove if amount > starting_supply => {
+            Err(BondingCurveError::SupplyUnderflow)
+        }
+        OperationSide::Remove => Ok(Some((starting_supply - amount + 1, starting_supply))),
+    }
+}
+
+/// The number of basis points in one, the scale of every fee and ratio given in basis points.
+pub const BASIS_POINTS: u64 = 10_000;
+
+/// Returns `bps` basis points of `value`, rounded down, or `BondingCurveError::InvalidParameter`
+/// if `bps` is above `BASIS_POINTS`.
+pub(crate) fn bps_of(value: u64, bps: u64) -> Result<u64, BondingCurveError> {
+    if bps > BASIS_POINTS {
+        return Err(BondingCurveError::InvalidParameter);
+    }
+    Ok((value as u128 * bps as u128 / BASIS_POINTS as u128) as u64)
+}
+
+/// The starting hash of `fnv1a`.
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continues a 64-bit FNV-1a hash over `bytes`. Start from `FNV_OFFSET_BASIS`.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes
+        .iter()
+        .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// Returns `value` if it is finite, or `BondingCurveError::Overflow`.
+#[cfg(feature = "float")]
+pub(crate) fn finite(value: f64) -> Result<f64, BondingCurveError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(BondingCurveError::Overflow)
+    }
+}
+
+/// Maps a float curve parameter to a key that is equal, ordered, and hashed consistently.
+///
+/// `-0.0` and `0.0` share a key, as do all `NaN`s, which sort after every other value. Other
+/// values keep the order of `f64::total_cmp`.
+#[cfg(feature = "float")]
+pub(crate) fn canonical_key(value: f64) -> u64 {
+    let value = if value == 0.0 {
+        0.0
+    } else if value.is_nan() {
+        f64::NAN
+    } else {
+        value
+    };
+    let bits = value.to_bits();
+    // Flipping the sign bit orders positives after negatives; flipping the rest of a
+    // negative orders larger magnitudes first.
+    if bits >> 63 == 0 {
+        bits | 1 << 63
+    } else {
+        !bits
+    }
+}
+
+/// Narrows a `u128` result of the unchecked integer formulas to `u64` the way native
+/// arithmetic overflows: panicking in debug builds and wrapping in release builds.
+#[cfg(not(feature = "panic-free"))]
+pub(crate) fn narrow(value: u128) -> u64 {
+    debug_assert!(value <= u64::MAX as u128, "price does not fit in a u64");
+    value as u64
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{BondingCurve, BondingCurveError, LinearBondingCurve, OperationSide};
+    use crate::{CurveFamily, ParseCurveFamilyError};
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_default_calculate_price_many() {
+        struct SpotOnly(LinearBondingCurve);
+
+        impl BondingCurve<u64> for SpotOnly {
+            fn calculate_price(&self, supply: u64) -> u64 {
+                self.0.calculate_price(supply)
+            }
+        }
+
+        let linear = LinearBondingCurve::new(100, 1000);
+        let curve = SpotOnly(linear);
+        for side in [OperationSide::Add, OperationSide::Remove] {
+            for amount in 0..=20 {
+                assert_eq!(
+                    curve.calculate_price_many(20, amount, side),
+                    linear.calculate_price_many(20, amount, side)
+                );
+            }
+        }
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    #[should_panic]
+    pub fn test_default_calculate_price_many_underflow() {
+        struct Flat;
+
+        impl BondingCurve<u64> for Flat {
+            fn calculate_price(&self, _supply: u64) -> u64 {
+                1
+            }
+        }
+
+        Flat.calculate_price_many(5, 6, OperationSide::Remove);
+    }
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_for_each_price() {
+        let curve = LinearBondingCurve::new(100, 1000);
+        let mut seen = Vec::new();
+        let mut largest = 0;
+        curve
+            .for_each_price(10..=109, 32, |start, prices| {
+                assert_eq!(start, 10 + seen.len() as u64);
+                largest = largest.max(prices.len());
+                seen.extend_from_slice(prices);
+            })
+            .unwrap();
+        assert_eq!(largest, 32);
+        assert_eq!(seen, curve.price_stream(10).take(100).collect::<Vec<_>>());
+
+        let flat = LinearBondingCurve::new(0, 1);
+        let mut chunks = Vec::new();
+        flat.for_each_price(u64::MAX - 2..=u64::MAX, 2, |start, prices| {
+            chunks.push((start, prices.len()))
+        })
+        .unwrap();
+        assert_eq!(chunks, vec![(u64::MAX - 2, 2), (u64::MAX, 1)]);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = curve.for_each_price(5..=4, 2, |_, _| panic!("empty range"));
+        assert_eq!(empty, Ok(()));
+        assert_eq!(
+            curve.for_each_price(0..=4, 0, |_, _| panic!("zero chunks")),
+            Err(BondingCurveError::InvalidParameter)
+        );
+    }
+
+    #[test]
+    pub fn test_curve_family_parsing() {
+        assert_eq!("Exponential".parse(), Ok(CurveFamily::Exponential));
+        assert_eq!(CurveFamily::Quadratic.to_string(), "quadratic");
+        assert_eq!(
+            "cubic".parse::<CurveFamily>(),
+            Err(ParseCurveFamilyError("cubic".to_string()))
+        );
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_canonical_key_order() {
+        use crate::canonical_key;
+
+        let values = [
+            f64::NEG_INFINITY,
+            -1.5,
+            -1e-300,
+            0.0,
+            1e-300,
+            2.0,
+            f64::INFINITY,
+        ];
+        for pair in values.windows(2) {
+            assert!(canonical_key(pair[0]) < canonical_key(pair[1]));
+        }
+        assert_eq!(canonical_key(-0.0), canonical_key(0.0));
+        assert_eq!(canonical_key(f64::NAN), canonical_key(-f64::NAN));
+        assert!(canonical_key(f64::NAN) > canonical_key(f64::INFINITY));
+    }
 }
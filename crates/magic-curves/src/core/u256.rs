@@ -0,0 +1,311 @@
+use ruint::aliases::U256;
+
+#[cfg(not(feature = "panic-free"))]
+use super::BondingCurve;
+use super::{
+    mul_div, supply_range, BondingCurveError, BondingCurveWithCheckedOperations, OperationSide,
+    Rounding,
+};
+
+/// Represents a linear bonding curve over 256-bit unsigned integers.
+///
+/// This is the `U256` counterpart of `LinearBondingCurve`, for wei-denominated curves.
+/// `U256` is `ruint`'s `Uint<256, 4>`, the same type alloy re-exports as `alloy_primitives::U256`.
+///
+/// # Fields
+///
+/// * `linear`: The linear coefficient that determines the rate of price increase.
+/// * `base`: The base price, which is the minimum price for the first token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LinearBondingCurveU256 {
+    pub linear: U256,
+    pub base: U256,
+}
+
+/// Represents a quadratic bonding curve over 256-bit unsigned integers.
+///
+/// This is the `U256` counterpart of `QuadraticBondingCurve`, for wei-denominated curves.
+///
+/// # Fields
+///
+/// * `quadratic`: The quadratic coefficient.
+/// * `linear`: The linear coefficient.
+/// * `base`: The base price, which is the minimum price for the first token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QuadraticBondingCurveU256 {
+    pub quadratic: U256,
+    pub linear: U256,
+    pub base: U256,
+}
+
+impl LinearBondingCurveU256 {
+    /// Creates a new `LinearBondingCurveU256` with the specified linear coefficient and base price.
+    ///
+    /// # Arguments
+    ///
+    /// * `linear` - The linear coefficient that determines the rate of price increase.
+    /// * `base` - The base price, which is the minimum price for the first token.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `LinearBondingCurveU256`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::LinearBondingCurveU256;
+    /// use ruint::aliases::U256;
+    ///
+    /// let curve = LinearBondingCurveU256::new(U256::from(100), U256::from(1000));
+    /// ```
+    pub fn new(linear: U256, base: U256) -> Self {
+        Self { linear, base }
+    }
+}
+
+impl QuadraticBondingCurveU256 {
+    /// Creates a new `QuadraticBondingCurveU256` with the specified coefficients.
+    ///
+    /// # Arguments
+    ///
+    /// * `quadratic` - The quadratic coefficient.
+    /// * `linear` - The linear coefficient.
+    /// * `base` - The base price, which is the minimum price for the first token.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `QuadraticBondingCurveU256`.
+    pub fn new(quadratic: U256, linear: U256, base: U256) -> Self {
+        Self {
+            quadratic,
+            linear,
+            base,
+        }
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<U256> for LinearBondingCurveU256 {
+    /// Calculates the price based on the supply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the price does not fit in a `U256`. Use `calculate_price_checked` to handle it.
+    fn calculate_price(&self, supply: u64) -> U256 {
+        self.calculate_price_checked(supply)
+            .expect("price does not fit in a U256")
+    }
+
+    /// Calculates the price for a given amount of tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the price does not fit in a `U256` or the supply range is invalid.
+    /// Use `calculate_price_many_checked` to handle it.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> U256 {
+        self.calculate_price_many_checked(starting_supply, amount, side)
+            .expect("price does not fit in a U256")
+    }
+}
+
+impl BondingCurveWithCheckedOperations<U256> for LinearBondingCurveU256 {
+    /// Calculates the price based on the supply, with overflow checks.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) = linear * x + base
+    /// ```
+    fn calculate_price_checked(&self, supply: u64) -> Result<U256, BondingCurveError> {
+        self.linear
+            .checked_mul(U256::from(supply))
+            .and_then(|x| x.checked_add(self.base))
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Calculates the price for a given amount of tokens, with overflow checks.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) = amount * base + linear * amount * (first + last) / 2
+    /// ```
+    ///
+    /// where `first..=last` is the supply range being priced. The product is taken at
+    /// full 512-bit width by `mul_div`, so only the final price has to fit in a `U256`.
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<U256, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(U256::ZERO);
+        };
+        let n = U256::from(amount);
+        let index_sum = n * (U256::from(first) + U256::from(last));
+        let linear_term = mul_div(self.linear, index_sum, U256::from(2), Rounding::Floor)?;
+        self.base
+            .checked_mul(n)
+            .and_then(|x| x.checked_add(linear_term))
+            .ok_or(BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(not(feature = "panic-free"))]
+impl BondingCurve<U256> for QuadraticBondingCurveU256 {
+    /// Calculates the price based on the supply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the price does not fit in a `U256`. Use `calculate_price_checked` to handle it.
+    fn calculate_price(&self, supply: u64) -> U256 {
+        self.calculate_price_checked(supply)
+            .expect("price does not fit in a U256")
+    }
+
+    /// Calculates the price for a given amount of tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the price does not fit in a `U256` or the supply range is invalid.
+    /// Use `calculate_price_many_checked` to handle it.
+    fn calculate_price_many(&self, starting_supply: u64, amount: u64, side: OperationSide) -> U256 {
+        self.calculate_price_many_checked(starting_supply, amount, side)
+            .expect("price does not fit in a U256")
+    }
+}
+
+impl BondingCurveWithCheckedOperations<U256> for QuadraticBondingCurveU256 {
+    /// Calculates the price based on the supply, with overflow checks.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) = quadratic * x^2 + linear * x + base
+    /// ```
+    fn calculate_price_checked(&self, supply: u64) -> Result<U256, BondingCurveError> {
+        let x = U256::from(supply);
+        self.quadratic
+            .checked_mul(x * x)
+            .and_then(|q| q.checked_add(self.linear.checked_mul(x)?))
+            .and_then(|p| p.checked_add(self.base))
+            .ok_or(BondingCurveError::Overflow)
+    }
+
+    /// Calculates the price for a given amount of tokens, with overflow checks.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) = amount * base + linear * Σi + quadratic * Σi²
+    /// ```
+    ///
+    /// where `i` ranges over the supply range being priced. Both products are taken at full
+    /// 512-bit width by `mul_div`, so only the final price has to fit in a `U256`.
+    fn calculate_price_many_checked(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> Result<U256, BondingCurveError> {
+        let Some((first, last)) = supply_range(starting_supply, amount, side)? else {
+            return Ok(U256::ZERO);
+        };
+        let n = U256::from(amount);
+        let index_sum = n * (U256::from(first) + U256::from(last));
+        // 6 * Σi² over first..=last, as a difference of k(k + 1)(2k + 1) prefix sums.
+        let six_squares = |k: u64| {
+            let k = U256::from(k);
+            k * (k + U256::from(1)) * (U256::from(2) * k + U256::from(1))
+        };
+        let square_sum = if first == 0 {
+            six_squares(last)
+        } else {
+            six_squares(last) - six_squares(first - 1)
+        };
+        let linear_term = mul_div(self.linear, index_sum, U256::from(2), Rounding::Floor)?;
+        let quadratic_term = mul_div(self.quadratic, square_sum, U256::from(6), Rounding::Floor)?;
+        self.base
+            .checked_mul(n)
+            .and_then(|x| x.checked_add(linear_term))
+            .and_then(|x| x.checked_add(quadratic_term))
+            .ok_or(BondingCurveError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ruint::aliases::U256;
+
+    #[cfg(not(feature = "panic-free"))]
+    use crate::{
+        BondingCurve, LinearBondingCurve, QuadraticBondingCurve, QuadraticBondingCurveU256,
+    };
+    use crate::{
+        BondingCurveError, BondingCurveWithCheckedOperations, LinearBondingCurveU256, OperationSide,
+    };
+
+    #[cfg(not(feature = "panic-free"))]
+    #[test]
+    pub fn test_u256_matches_u64_curves() {
+        let linear = LinearBondingCurve::new(100, 1000);
+        let linear_u256 = LinearBondingCurveU256::new(U256::from(100), U256::from(1000));
+        let quadratic = QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000);
+        let quadratic_u256 = QuadraticBondingCurveU256::new(
+            U256::from(10_000_000),
+            U256::from(500_000_000),
+            U256::from(1_000_000_000),
+        );
+        for (supply, amount) in [(0, 1), (0, 10), (5, 3), (1_000, 250)] {
+            for side in [OperationSide::Add, OperationSide::Remove] {
+                if side == OperationSide::Remove && amount > supply {
+                    continue;
+                }
+                assert_eq!(
+                    linear_u256.calculate_price_many(supply, amount, side),
+                    U256::from(
+                        linear
+                            .calculate_price_many_checked(supply, amount, side)
+                            .unwrap()
+                    )
+                );
+                assert_eq!(
+                    quadratic_u256.calculate_price_many(supply, amount, side),
+                    U256::from(
+                        quadratic
+                            .calculate_price_many_checked(supply, amount, side)
+                            .unwrap()
+                    )
+                );
+            }
+            assert_eq!(
+                quadratic_u256.calculate_price(supply),
+                U256::from(quadratic.calculate_price(supply))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_u256_full_width_intermediate() {
+        // linear * amount * (first + last) exceeds 256 bits, but the halved sum does not.
+        let half = U256::from(1) << 255;
+        let curve = LinearBondingCurveU256::new(half, U256::ZERO);
+        assert_eq!(
+            curve.calculate_price_many_checked(0, 2, OperationSide::Add),
+            Ok(half)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(1, 2, OperationSide::Add),
+            Err(BondingCurveError::Overflow)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(0, 2, OperationSide::Remove),
+            Err(BondingCurveError::SupplyUnderflow)
+        );
+        assert_eq!(
+            curve.calculate_price_many_checked(3, 0, OperationSide::Add),
+            Ok(U256::ZERO)
+        );
+    }
+}
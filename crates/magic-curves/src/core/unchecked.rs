@@ -0,0 +1,175 @@
+use super::{LinearBondingCurve, OperationSide, QuadraticBondingCurve};
+
+/// The inverse of 3 modulo `2^64`, which divides exact multiples of 3 in wrapping arithmetic.
+const INVERSE_OF_THREE: u64 = 0xaaaa_aaaa_aaaa_aaab;
+
+/// The no-validation fast path of an integer curve, for hot loops off-chain such as market
+/// makers repricing every tick.
+///
+/// Despite the name, these are safe functions that cannot cause undefined behavior. They skip
+/// every check instead: arithmetic wraps modulo `2^64` in every build, and a sale of more
+/// tokens than the supply wraps around zero rather than failing. Whenever the checked result
+/// fits in a `u64`, the wrapped one is the same, so validate the configuration once, for
+/// example with `max_safe_supply`, and price inside that range without paying for checks.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::{
+///     BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
+///     UncheckedBondingCurve,
+/// };
+///
+/// let curve = LinearBondingCurve::new(100, 1000);
+/// assert_eq!(curve.price_unchecked_unsafe(10), 2000);
+/// let total = curve.price_many_unchecked_unsafe(10, 5, OperationSide::Remove);
+/// assert_eq!(Ok(total), curve.calculate_price_many_checked(10, 5, OperationSide::Remove));
+/// // Out of range, the result wraps instead of failing.
+/// assert_eq!(LinearBondingCurve::new(u64::MAX, 1).price_unchecked_unsafe(2), u64::MAX);
+/// ```
+pub trait UncheckedBondingCurve {
+    /// Calculates the price at `supply`, wrapping on overflow.
+    fn price_unchecked_unsafe(&self, supply: u64) -> u64;
+
+    /// Calculates the total price of a trade, wrapping on overflow and without checking that
+    /// a sale fits in the supply.
+    fn price_many_unchecked_unsafe(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> u64;
+}
+
+/// Returns the first supply a trade prices, wrapping for sales larger than the supply.
+fn first_supply(starting_supply: u64, amount: u64, side: OperationSide) -> u64 {
+    match side {
+        OperationSide::Add => starting_supply,
+        OperationSide::Remove => starting_supply.wrapping_sub(amount).wrapping_add(1),
+    }
+}
+
+/// Returns `n * (n - 1) / 2` modulo `2^64`, the sum of `i` for `i` in `0..n`.
+fn sum_of_indices(n: u64) -> u64 {
+    // The product is even and fits in a `u128` where the division by 2 is exact.
+    ((n as u128 * n.wrapping_sub(1) as u128) >> 1) as u64
+}
+
+impl UncheckedBondingCurve for LinearBondingCurve {
+    fn price_unchecked_unsafe(&self, supply: u64) -> u64 {
+        self.linear.wrapping_mul(supply).wrapping_add(self.base)
+    }
+
+    fn price_many_unchecked_unsafe(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> u64 {
+        let first = first_supply(starting_supply, amount, side);
+        // Sum of supplies: first * n + sum_i
+        let supplies = first
+            .wrapping_mul(amount)
+            .wrapping_add(sum_of_indices(amount));
+        self.linear
+            .wrapping_mul(supplies)
+            .wrapping_add(self.base.wrapping_mul(amount))
+    }
+}
+
+impl UncheckedBondingCurve for QuadraticBondingCurve {
+    fn price_unchecked_unsafe(&self, supply: u64) -> u64 {
+        self.quadratic
+            .wrapping_mul(supply)
+            .wrapping_mul(supply)
+            .wrapping_add(self.linear.wrapping_mul(supply))
+            .wrapping_add(self.base)
+    }
+
+    fn price_many_unchecked_unsafe(
+        &self,
+        starting_supply: u64,
+        amount: u64,
+        side: OperationSide,
+    ) -> u64 {
+        let first = first_supply(starting_supply, amount, side);
+        let sum_i = sum_of_indices(amount);
+        // Sum of i^2 for i in 0..n: sum_i * (2n - 1) / 3, an exact multiple of 3, so the
+        // division is a multiplication by the inverse of 3.
+        let sum_i2 = sum_i
+            .wrapping_mul(amount.wrapping_mul(2).wrapping_sub(1))
+            .wrapping_mul(INVERSE_OF_THREE);
+        // Sum of squared supplies: first^2 * n + 2 * first * sum_i + sum_i2
+        let squares = first
+            .wrapping_mul(first)
+            .wrapping_mul(amount)
+            .wrapping_add(first.wrapping_mul(2).wrapping_mul(sum_i))
+            .wrapping_add(sum_i2);
+        // Sum of supplies: first * n + sum_i
+        let supplies = first.wrapping_mul(amount).wrapping_add(sum_i);
+        self.quadratic
+            .wrapping_mul(squares)
+            .wrapping_add(self.linear.wrapping_mul(supplies))
+            .wrapping_add(self.base.wrapping_mul(amount))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BondingCurveWithCheckedOperations, LinearBondingCurve, OperationSide,
+        QuadraticBondingCurve, UncheckedBondingCurve,
+    };
+
+    /// Asserts that the fast path matches every checked result that fits.
+    fn assert_matches_checked<C>(curve: &C)
+    where
+        C: BondingCurveWithCheckedOperations<u64> + UncheckedBondingCurve,
+    {
+        for supply in [0, 1, 2, 3, 10, 999, 65_536, 1 << 20, u32::MAX as u64] {
+            if let Ok(price) = curve.calculate_price_checked(supply) {
+                assert_eq!(curve.price_unchecked_unsafe(supply), price, "{supply}");
+            }
+            for amount in [0, 1, 2, 3, 7, 500, supply] {
+                for side in [OperationSide::Add, OperationSide::Remove] {
+                    if let Ok(total) = curve.calculate_price_many_checked(supply, amount, side) {
+                        assert_eq!(
+                            curve.price_many_unchecked_unsafe(supply, amount, side),
+                            total,
+                            "{supply} {amount} {side:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_unchecked_matches_checked() {
+        assert_matches_checked(&LinearBondingCurve::new(100, 1000));
+        assert_matches_checked(&LinearBondingCurve::new(1 << 30, 7));
+        assert_matches_checked(&QuadraticBondingCurve::new(
+            10_000_000,
+            500_000_000,
+            1_000_000_000,
+        ));
+        assert_matches_checked(&QuadraticBondingCurve::new(3, 0, 1 << 40));
+    }
+
+    #[test]
+    pub fn test_unchecked_wraps() {
+        let curve = QuadraticBondingCurve::new(1, 0, 0);
+        // The squares of the two supplies add up past `2^64`.
+        let supply = 1u64 << 32;
+        assert_eq!(
+            curve.price_many_unchecked_unsafe(supply, 2, OperationSide::Remove),
+            ((supply as u128 * supply as u128 + (supply as u128 - 1).pow(2)) as u64)
+        );
+        // Selling past the supply wraps around zero instead of failing.
+        let linear = LinearBondingCurve::new(0, 5);
+        assert_eq!(
+            linear.price_many_unchecked_unsafe(1, 3, OperationSide::Remove),
+            15
+        );
+    }
+}
@@ -0,0 +1,440 @@
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "float")]
+use super::{
+    fnv1a, ExponentialBondingCurve, LogarithmicBondingCurve, SigmoidBondingCurve, Tolerance,
+    FNV_OFFSET_BASIS,
+};
+use super::{
+    BondingCurveError, BondingCurveWithCheckedOperations, CurveFamily, LinearBondingCurve,
+    OperationSide, QuadraticBondingCurve,
+};
+
+/// The version of the test-vector format emitted by `test_vectors_json`.
+///
+/// It is bumped whenever the layout of the JSON document or the set of vectors changes.
+pub const TEST_VECTORS_VERSION: u32 = 1;
+
+/// Represents a parameter, input, or output value in a test vector.
+///
+/// # Variants
+///
+/// * `Integer` - An unsigned integer, written to JSON as a decimal string so it survives
+///   languages whose numbers are 64-bit floats.
+/// * `Float` - A finite `f64`, written to JSON as the shortest decimal that round-trips.
+/// * `Side` - An `OperationSide`, written to JSON as `"add"` or `"remove"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorValue {
+    Integer(u64),
+    #[cfg(feature = "float")]
+    Float(f64),
+    Side(OperationSide),
+}
+
+/// Represents a single conformance test vector: a curve, a function call, and its result.
+///
+/// # Fields
+///
+/// * `family`: The family of the curve.
+/// * `params`: The curve parameters, in declaration order.
+/// * `function`: The name of the function being called, such as `calculate_price_many_checked`.
+/// * `inputs`: The function arguments, in declaration order.
+/// * `expected`: The value the function returns, or the error a checked function fails with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestVector {
+    pub family: CurveFamily,
+    pub params: Vec<(&'static str, VectorValue)>,
+    pub function: &'static str,
+    pub inputs: Vec<(&'static str, VectorValue)>,
+    pub expected: Result<VectorValue, BondingCurveError>,
+}
+
+#[cfg(feature = "float")]
+impl TestVector {
+    /// Checks a port's result against the vector's expected value, for conformance tests.
+    ///
+    /// Floats are compared within `tolerance`, since ports rarely reproduce this crate's
+    /// `exp`, `ln`, and `pow` bit for bit; integers, sides, and errors must match exactly.
+    ///
+    /// # Arguments
+    ///
+    /// * `actual` - The value or error the port's function returned.
+    /// * `tolerance` - How closely float results must agree.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `actual` matches the expected value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use magic_curves::{test_vectors, Tolerance, VectorValue};
+    ///
+    /// let vector = test_vectors()
+    ///     .into_iter()
+    ///     .find(|vector| matches!(vector.expected, Ok(VectorValue::Float(_))))
+    ///     .unwrap();
+    /// let Ok(VectorValue::Float(price)) = vector.expected else { unreachable!() };
+    /// let ported = Ok(VectorValue::Float(price * (1.0 + 1e-12)));
+    /// assert!(vector.matches(&ported, Tolerance::default()));
+    /// assert!(!vector.matches(&ported, Tolerance::EXACT));
+    /// ```
+    pub fn matches(
+        &self,
+        actual: &Result<VectorValue, BondingCurveError>,
+        tolerance: Tolerance,
+    ) -> bool {
+        match (&self.expected, actual) {
+            (Ok(VectorValue::Float(expected)), Ok(VectorValue::Float(actual))) => {
+                tolerance.approx_eq(*expected, *actual)
+            }
+            (expected, actual) => expected == actual,
+        }
+    }
+}
+
+const SUPPLIES: [u64; 5] = [0, 1, 10, 1_000, 1_000_000];
+
+const MANY: [(u64, u64, OperationSide); 8] = [
+    (0, 1, OperationSide::Add),
+    (10, 0, OperationSide::Add),
+    (0, 10, OperationSide::Add),
+    (10, 5, OperationSide::Add),
+    (10, 5, OperationSide::Remove),
+    (10, 10, OperationSide::Remove),
+    (1_000, 100, OperationSide::Add),
+    (1_000_000, 1_000, OperationSide::Remove),
+];
+
+/// Inputs that only checked functions are exercised with, since they fail.
+const MANY_INVALID: [(u64, u64, OperationSide); 2] = [
+    (10, 12, OperationSide::Remove),
+    (u64::MAX, 2, OperationSide::Add),
+];
+
+/// Returns the canonical set of conformance test vectors.
+///
+/// The set is deterministic, so ports of this crate to other languages can check
+/// themselves against it. Float vectors are only emitted for finite outputs.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::test_vectors;
+///
+/// let vectors = test_vectors();
+/// assert!(vectors.iter().any(|v| v.function == "calculate_price_many_checked"));
+/// ```
+pub fn test_vectors() -> Vec<TestVector> {
+    let mut vectors = Vec::new();
+    integer_vectors(
+        &mut vectors,
+        CurveFamily::Linear,
+        vec![
+            ("linear", VectorValue::Integer(100)),
+            ("base", VectorValue::Integer(1_000)),
+        ],
+        &LinearBondingCurve::new(100, 1_000),
+    );
+    integer_vectors(
+        &mut vectors,
+        CurveFamily::Linear,
+        vec![
+            ("linear", VectorValue::Integer(u64::MAX / 2)),
+            ("base", VectorValue::Integer(1)),
+        ],
+        &LinearBondingCurve::new(u64::MAX / 2, 1),
+    );
+    integer_vectors(
+        &mut vectors,
+        CurveFamily::Quadratic,
+        vec![
+            ("quadratic", VectorValue::Integer(10_000_000)),
+            ("linear", VectorValue::Integer(500_000_000)),
+            ("base", VectorValue::Integer(1_000_000_000)),
+        ],
+        &QuadraticBondingCurve::new(10_000_000, 500_000_000, 1_000_000_000),
+    );
+    #[cfg(feature = "float")]
+    {
+        float_vectors(
+            &mut vectors,
+            CurveFamily::Exponential,
+            vec![
+                ("base", VectorValue::Float(0.01)),
+                ("growth", VectorValue::Float(0.02)),
+            ],
+            &ExponentialBondingCurve::new(0.01, 0.02),
+        );
+        float_vectors(
+            &mut vectors,
+            CurveFamily::Logarithmic,
+            vec![
+                ("base", VectorValue::Float(0.02)),
+                ("growth", VectorValue::Float(0.5)),
+            ],
+            &LogarithmicBondingCurve::new(0.02, 0.5),
+        );
+        float_vectors(
+            &mut vectors,
+            CurveFamily::Sigmoid,
+            vec![
+                ("max_price", VectorValue::Float(1_000.0)),
+                ("growth", VectorValue::Float(0.01)),
+                ("mid_supply", VectorValue::Integer(500)),
+            ],
+            &SigmoidBondingCurve::new(1_000.0, 0.01, 500),
+        );
+    }
+    vectors
+}
+
+/// Returns the canonical test vectors as a JSON document.
+///
+/// The document is `{"version": TEST_VECTORS_VERSION, "vectors": [...]}` with one vector per
+/// line, so diffs between versions stay readable.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::test_vectors_json;
+///
+/// assert!(test_vectors_json().starts_with("{\"version\":1,"));
+/// ```
+pub fn test_vectors_json() -> String {
+    let vectors: Vec<String> = test_vectors().iter().map(ToString::to_string).collect();
+    format!(
+        "{{\"version\":{},\"vectors\":[\n{}\n]}}\n",
+        TEST_VECTORS_VERSION,
+        vectors.join(",\n")
+    )
+}
+
+/// Returns a digest of the exact bits of every float test vector output.
+///
+/// This is the comparison harness for float determinism: run it on every target a float
+/// curve is deployed to and compare the results. With the `soft-float` feature the digest is
+/// the same everywhere; without it, it can differ wherever the platform's `exp`, `ln`, or
+/// `pow` round differently. The digest is FNV-1a over the outputs' `f64::to_bits`, in
+/// `test_vectors` order.
+///
+/// # Example
+///
+/// ```
+/// use magic_curves::float_vectors_digest;
+///
+/// assert_eq!(float_vectors_digest(), float_vectors_digest());
+/// ```
+#[cfg(feature = "float")]
+pub fn float_vectors_digest() -> u64 {
+    test_vectors()
+        .iter()
+        .filter_map(|vector| match vector.expected {
+            Ok(VectorValue::Float(price)) => Some(price.to_bits()),
+            _ => None,
+        })
+        .fold(FNV_OFFSET_BASIS, |hash, price| {
+            fnv1a(hash, &price.to_le_bytes())
+        })
+}
+
+impl Display for VectorValue {
+    /// Formats the value as JSON.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            VectorValue::Integer(value) => write!(f, "\"{}\"", value),
+            #[cfg(feature = "float")]
+            VectorValue::Float(value) => write!(f, "{:?}", value),
+            VectorValue::Side(OperationSide::Add) => write!(f, "\"add\""),
+            VectorValue::Side(OperationSide::Remove) => write!(f, "\"remove\""),
+        }
+    }
+}
+
+impl Display for TestVector {
+    /// Formats the vector as a single-line JSON object.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{{\"family\":\"{}\",\"params\":", self.family)?;
+        write_object(f, &self.params)?;
+        write!(f, ",\"function\":\"{}\",\"inputs\":", self.function)?;
+        write_object(f, &self.inputs)?;
+        match &self.expected {
+            Ok(value) => write!(f, ",\"expected\":{{\"ok\":{}}}}}", value),
+            Err(error) => write!(f, ",\"expected\":{{\"error\":\"{}\"}}}}", error.name()),
+        }
+    }
+}
+
+fn write_object(f: &mut Formatter, entries: &[(&'static str, VectorValue)]) -> fmt::Result {
+    write!(f, "{{")?;
+    for (index, (key, value)) in entries.iter().enumerate() {
+        if index > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "\"{}\":{}", key, value)?;
+    }
+    write!(f, "}}")
+}
+
+fn many_inputs(
+    starting_supply: u64,
+    amount: u64,
+    side: OperationSide,
+) -> Vec<(&'static str, VectorValue)> {
+    vec![
+        ("starting_supply", VectorValue::Integer(starting_supply)),
+        ("amount", VectorValue::Integer(amount)),
+        ("side", VectorValue::Side(side)),
+    ]
+}
+
+fn integer_vectors<C>(
+    vectors: &mut Vec<TestVector>,
+    family: CurveFamily,
+    params: Vec<(&'static str, VectorValue)>,
+    curve: &C,
+) where
+    C: BondingCurveWithCheckedOperations<u64>,
+{
+    let mut push = |function, inputs, expected: Result<u64, BondingCurveError>| {
+        vectors.push(TestVector {
+            family,
+            params: params.clone(),
+            function,
+            inputs,
+            expected: expected.map(VectorValue::Integer),
+        })
+    };
+    for supply in SUPPLIES {
+        let inputs = vec![("supply", VectorValue::Integer(supply))];
+        let checked = curve.calculate_price_checked(supply);
+        // Unchecked functions are only specified where they cannot overflow.
+        if let Ok(price) = checked {
+            push("calculate_price", inputs.clone(), Ok(price));
+        }
+        push("calculate_price_checked", inputs, checked);
+    }
+    for (starting_supply, amount, side) in MANY.into_iter().chain(MANY_INVALID) {
+        let inputs = many_inputs(starting_supply, amount, side);
+        let checked = curve.calculate_price_many_checked(starting_supply, amount, side);
+        if let Ok(price) = checked {
+            push("calculate_price_many", inputs.clone(), Ok(price));
+        }
+        push("calculate_price_many_checked", inputs, checked);
+    }
+}
+
+#[cfg(feature = "float")]
+fn float_vectors<C>(
+    vectors: &mut Vec<TestVector>,
+    family: CurveFamily,
+    params: Vec<(&'static str, VectorValue)>,
+    curve: &C,
+) where
+    C: BondingCurveWithCheckedOperations<f64>,
+{
+    // JSON cannot represent NaN or infinities, so those outputs are left unspecified.
+    let mut push = |function, inputs, price: Result<f64, BondingCurveError>| {
+        if let Ok(price) = price {
+            vectors.push(TestVector {
+                family,
+                params: params.clone(),
+                function,
+                inputs,
+                expected: Ok(VectorValue::Float(price)),
+            })
+        }
+    };
+    for supply in SUPPLIES {
+        let inputs = vec![("supply", VectorValue::Integer(supply))];
+        push(
+            "calculate_price",
+            inputs,
+            curve.calculate_price_checked(supply),
+        );
+    }
+    for (starting_supply, amount, side) in MANY {
+        let inputs = many_inputs(starting_supply, amount, side);
+        let price = curve.calculate_price_many_checked(starting_supply, amount, side);
+        push("calculate_price_many", inputs, price);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        test_vectors, test_vectors_json, BondingCurveError, CurveFamily, OperationSide, TestVector,
+        VectorValue,
+    };
+
+    #[test]
+    pub fn test_vector_json_format() {
+        let vector = TestVector {
+            family: CurveFamily::Linear,
+            params: vec![
+                ("linear", VectorValue::Integer(100)),
+                ("base", VectorValue::Integer(1_000)),
+            ],
+            function: "calculate_price_many_checked",
+            inputs: vec![
+                ("starting_supply", VectorValue::Integer(10)),
+                ("amount", VectorValue::Integer(2)),
+                ("side", VectorValue::Side(OperationSide::Add)),
+            ],
+            expected: Ok(VectorValue::Integer(4_100)),
+        };
+        assert_eq!(
+            vector.to_string(),
+            "{\"family\":\"linear\",\"params\":{\"linear\":\"100\",\"base\":\"1000\"},\
+             \"function\":\"calculate_price_many_checked\",\"inputs\":{\"starting_supply\":\"10\",\
+             \"amount\":\"2\",\"side\":\"add\"},\"expected\":{\"ok\":\"4100\"}}"
+        );
+        let failing = TestVector {
+            expected: Err(BondingCurveError::Overflow),
+            ..vector
+        };
+        assert!(failing
+            .to_string()
+            .ends_with(",\"expected\":{\"error\":\"Overflow\"}}"));
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    pub fn test_vector_matches() {
+        use crate::Tolerance;
+
+        for vector in test_vectors() {
+            assert!(vector.matches(&vector.expected, Tolerance::EXACT));
+            let swapped = match vector.expected {
+                Ok(_) => Err(BondingCurveError::Overflow),
+                Err(_) => Ok(VectorValue::Integer(0)),
+            };
+            assert!(!vector.matches(&swapped, Tolerance::new(f64::MAX, f64::MAX)));
+        }
+        let integer = TestVector {
+            family: CurveFamily::Linear,
+            params: vec![],
+            function: "calculate_price",
+            inputs: vec![],
+            expected: Ok(VectorValue::Integer(10)),
+        };
+        assert!(!integer.matches(&Ok(VectorValue::Integer(11)), Tolerance::absolute(5.0)));
+        assert!(!integer.matches(&Ok(VectorValue::Float(10.0)), Tolerance::absolute(5.0)));
+    }
+
+    #[test]
+    pub fn test_vectors_are_deterministic() {
+        assert_eq!(test_vectors(), test_vectors());
+        let json = test_vectors_json();
+        assert_eq!(json.lines().count(), test_vectors().len() + 2);
+        assert!(json.contains("\"expected\":{\"error\":\"Overflow\"}"));
+    }
+
+    #[cfg(feature = "soft-float")]
+    #[test]
+    pub fn test_soft_float_digest_is_pinned() {
+        // The same on every target. If a change to the float vectors is intended, bump
+        // `TEST_VECTORS_VERSION` and update the digest.
+        assert_eq!(crate::float_vectors_digest(), 0x86cd_38c3_ae83_83a1);
+    }
+}
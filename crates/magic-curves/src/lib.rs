@@ -1,3 +1,14 @@
+// Integer-only builds must not contain a single float operation. Every float item is gated on
+// `float`, so using one fails to compile; this lint, which only clippy checks, catches float
+// arithmetic on integers, and CI runs it on the integer-only build.
+#![cfg_attr(not(feature = "float"), deny(clippy::float_arithmetic))]
+// `std::simd` is only available on nightly.
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
 pub mod core;
 
 pub use core::*;
+
+/// Derives `BondingCurve` from a price formula. See `magic_curves_derive::BondingCurve`.
+#[cfg(feature = "derive")]
+pub use magic_curves_derive::BondingCurve;